@@ -208,6 +208,8 @@ impl AgentBridge {
             size_bytes: result.content.len() as u64,
             created_at: Utc::now(),
             content: String::from_utf8_lossy(&result.content).into_owned(),
+            metadata: result.metadata.clone(),
+            signature: String::new(),
         }
     }
 
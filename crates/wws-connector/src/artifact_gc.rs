@@ -0,0 +1,93 @@
+//! Retention-based garbage collection of completed-task artifacts.
+//!
+//! Result content is kept in [`wws_state::ContentStore`] so peers and agents
+//! can fetch it by CID, but nothing ever dropped it once its task was done.
+//! When `content.artifact_retention_secs` is set, [`ArtifactRetention`]
+//! notes when each task is first seen completed and reports the ones that
+//! have stayed completed for longer than the retention period. The
+//! connector then evicts their artifacts unless the content is still
+//! referenced: from a Merkle root of a task that is not being collected,
+//! or by the result of such a task.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+/// Default seconds between collection passes.
+pub const DEFAULT_ARTIFACT_GC_INTERVAL_SECS: u64 = 300;
+
+/// When each completed task was first seen completed.
+#[derive(Debug, Clone)]
+pub struct ArtifactRetention {
+    retention: Duration,
+    completed_since: HashMap<String, DateTime<Utc>>,
+}
+
+impl ArtifactRetention {
+    pub fn new(retention: Duration) -> Self {
+        Self {
+            retention,
+            completed_since: HashMap::new(),
+        }
+    }
+
+    /// Record the currently completed tasks as of `now`. Tasks no longer
+    /// reported completed (e.g. reopened or pruned) are forgotten.
+    pub fn observe<'a>(&mut self, completed: impl IntoIterator<Item = &'a str>, now: DateTime<Utc>) {
+        let mut still_completed = HashMap::with_capacity(self.completed_since.len());
+        for task_id in completed {
+            let since = self.completed_since.get(task_id).copied().unwrap_or(now);
+            still_completed.insert(task_id.to_string(), since);
+        }
+        self.completed_since = still_completed;
+    }
+
+    /// Tasks completed for longer than the retention period, sorted.
+    pub fn expired(&self, now: DateTime<Utc>) -> Vec<String> {
+        let mut expired: Vec<String> = self
+            .completed_since
+            .iter()
+            .filter(|(_, since)| (now - **since).to_std().is_ok_and(|age| age > self.retention))
+            .map(|(task_id, _)| task_id.clone())
+            .collect();
+        expired.sort();
+        expired
+    }
+}
+
+/// Outcome of one collection pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArtifactGcReport {
+    /// CIDs removed from the content store.
+    pub evicted: Vec<String>,
+    /// CIDs of expired tasks kept because live state still references them.
+    pub protected: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tasks_expire_after_retention_from_first_sighting() {
+        let mut retention = ArtifactRetention::new(Duration::from_secs(60));
+        let t0 = Utc::now();
+        retention.observe(["a"], t0);
+        retention.observe(["a", "b"], t0 + chrono::Duration::seconds(30));
+        assert!(retention.expired(t0 + chrono::Duration::seconds(60)).is_empty());
+        assert_eq!(retention.expired(t0 + chrono::Duration::seconds(61)), vec!["a"]);
+        assert_eq!(
+            retention.expired(t0 + chrono::Duration::seconds(91)),
+            vec!["a", "b"]
+        );
+
+        // A task that is no longer completed starts over.
+        retention.observe(["b"], t0 + chrono::Duration::seconds(92));
+        retention.observe(["a", "b"], t0 + chrono::Duration::seconds(93));
+        assert_eq!(
+            retention.expired(t0 + chrono::Duration::seconds(100)),
+            vec!["b"]
+        );
+    }
+}
@@ -6,10 +6,12 @@
 //!
 //! Environment variable prefix: WWS_
 
+use std::collections::{BTreeSet, HashMap};
 use std::net::SocketAddr;
 use std::path::Path;
 
 use serde::{Deserialize, Serialize};
+use wws_network::PeerId;
 
 /// Top-level connector configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,6 +40,225 @@ pub struct ConnectorConfig {
     /// Agent identity configuration.
     #[serde(default)]
     pub identity: IdentityConfig,
+    /// Content-addressed storage configuration.
+    #[serde(default)]
+    pub content: ContentConfig,
+    /// Consensus participation configuration.
+    #[serde(default)]
+    pub consensus: ConsensusConfig,
+    /// Hierarchy, consensus and topic hashing settings for individual
+    /// swarms, keyed by swarm ID. Unset fields use the global `[hierarchy]`,
+    /// `[consensus]` and `[network]` values.
+    #[serde(default)]
+    pub swarm_overrides: HashMap<String, SwarmOverrides>,
+}
+
+/// Settings that replace the global ones within one swarm.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SwarmOverrides {
+    /// Replaces `hierarchy.branching_factor`. The node only builds a pyramid
+    /// for the primary swarm, so this only shapes the hierarchy for
+    /// `swarm.swarm_id`; other swarms just report it in `swarm.list_swarms`.
+    #[serde(default)]
+    pub branching_factor: Option<u32>,
+    /// Replaces `hierarchy.epoch_duration_secs`. Epochs run on the primary
+    /// swarm's schedule, so this only takes effect for `swarm.swarm_id`.
+    #[serde(default)]
+    pub epoch_duration_secs: Option<u64>,
+    /// Replaces `consensus.min_peers_for_consensus` for proposals and votes
+    /// on the swarm's tasks.
+    #[serde(default)]
+    pub min_peers_for_consensus: Option<usize>,
+    /// Replaces `consensus.vote_collection_window_secs`.
+    #[serde(default)]
+    pub vote_collection_window_secs: Option<u64>,
+    /// Replaces `network.topic_hashing` for the swarm's scoped topics.
+    #[serde(default)]
+    pub topic_hashing: Option<wws_network::TopicHashing>,
+}
+
+impl SwarmOverrides {
+    fn apply_to_hierarchy(&self, hierarchy: &mut HierarchyConfig) {
+        if let Some(k) = self.branching_factor {
+            hierarchy.branching_factor = k;
+        }
+        if let Some(secs) = self.epoch_duration_secs {
+            hierarchy.epoch_duration_secs = secs;
+        }
+    }
+
+    fn apply_to_consensus(&self, consensus: &mut ConsensusConfig) {
+        if let Some(peers) = self.min_peers_for_consensus {
+            consensus.min_peers_for_consensus = peers;
+        }
+        if let Some(secs) = self.vote_collection_window_secs {
+            consensus.vote_collection_window_secs = secs;
+        }
+    }
+}
+
+/// Global hierarchy and consensus settings together with the per-swarm
+/// overrides, for resolving the settings of any swarm at runtime.
+#[derive(Debug, Clone, Default)]
+pub struct SwarmSettings {
+    hierarchy: HierarchyConfig,
+    consensus: ConsensusConfig,
+    overrides: HashMap<String, SwarmOverrides>,
+}
+
+impl SwarmSettings {
+    pub fn from_config(config: &ConnectorConfig) -> Self {
+        Self {
+            hierarchy: config.hierarchy.clone(),
+            consensus: config.consensus.clone(),
+            overrides: config.swarm_overrides.clone(),
+        }
+    }
+
+    /// Hierarchy settings in effect within `swarm_id`.
+    pub fn hierarchy(&self, swarm_id: &str) -> HierarchyConfig {
+        let mut hierarchy = self.hierarchy.clone();
+        if let Some(overrides) = self.overrides.get(swarm_id) {
+            overrides.apply_to_hierarchy(&mut hierarchy);
+        }
+        hierarchy
+    }
+
+    /// Consensus settings in effect within `swarm_id`.
+    pub fn consensus(&self, swarm_id: &str) -> ConsensusConfig {
+        let mut consensus = self.consensus.clone();
+        if let Some(overrides) = self.overrides.get(swarm_id) {
+            overrides.apply_to_consensus(&mut consensus);
+        }
+        consensus
+    }
+}
+
+/// Consensus participation configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsensusConfig {
+    /// Connected peers required before this node proposes plans, votes, or
+    /// triggers epoch elections. Until then it abstains, so a freshly
+    /// started node cannot decide anything from a partial view of the
+    /// swarm. Zero disables the check.
+    #[serde(default)]
+    pub min_peers_for_consensus: usize,
+    /// Reject proposed plans (local and remote) whose rationale is shorter
+    /// than `min_rationale_len`, so voters always have a justification to
+    /// evaluate.
+    #[serde(default)]
+    pub require_rationale: bool,
+    /// Minimum rationale length in bytes when `require_rationale` is set.
+    #[serde(default = "default_min_rationale_len")]
+    pub min_rationale_len: usize,
+    /// Expand `{parent_description}`, `{parent_task_id}` and
+    /// `{subtask_index}` in proposed subtask descriptions before the plan
+    /// limits are checked and the plan is hashed.
+    #[serde(default = "default_expand_subtask_templates")]
+    pub expand_subtask_templates: bool,
+    /// Seconds ballots are collected for a task once its proposals are
+    /// revealed. IRV runs when the window closes, or earlier once every
+    /// expected voter has voted; later ballots are rejected. Zero leaves
+    /// only the task-age voting timeout.
+    #[serde(default = "default_vote_collection_window")]
+    pub vote_collection_window_secs: u64,
+    /// How proposers may vote on their own task: `allow_non_first` lets
+    /// them rank their own plan anywhere but first, `fully_prohibit`
+    /// rejects any ballot from them.
+    #[serde(default)]
+    pub self_vote_policy: wws_consensus::SelfVotePolicy,
+    /// Executors each directly executed subtask is assigned to. Above one,
+    /// only those executors may submit, and a subtask only completes once a
+    /// majority agree on the result CID.
+    #[serde(default = "default_result_redundancy")]
+    pub result_redundancy: usize,
+    /// Fraction of `result_redundancy` executors that must submit the same
+    /// CID; a CID needs strictly more than this share to win.
+    #[serde(default = "default_result_majority")]
+    pub result_majority: f64,
+    /// Maximum number of subtasks accepted in a single plan.
+    #[serde(default = "default_max_subtasks_per_plan")]
+    pub max_subtasks_per_plan: usize,
+    /// Maximum length of a plan subtask description, in bytes.
+    #[serde(default = "default_max_subtask_description_len")]
+    pub max_subtask_description_len: usize,
+    /// Size each RFP's expected proposer count from the current swarm
+    /// membership instead of the board-size formula.
+    #[serde(default)]
+    pub adaptive_proposers: bool,
+    /// Upper bound on the adaptive expected proposer count.
+    #[serde(default = "default_max_expected_proposers")]
+    pub max_expected_proposers: usize,
+    /// Minimum seconds a commit phase stays open, even once every expected
+    /// proposer has committed. Only consulted in adaptive mode.
+    #[serde(default)]
+    pub min_commit_window_secs: u64,
+    /// Maximum length of an injected task description, in bytes.
+    #[serde(default = "default_max_task_description_len")]
+    pub max_task_description_len: usize,
+    /// Seconds a completed or rejected task stays in the task set (and so in
+    /// `swarm.receive_task` and the TUI task list). Zero keeps it forever.
+    #[serde(default)]
+    pub task_set_retention_secs: u64,
+    /// Seconds a finished task stays queryable via `swarm.get_task` and its
+    /// timeline. Never shorter than `task_set_retention_secs`; zero keeps
+    /// details forever.
+    #[serde(default)]
+    pub task_detail_retention_secs: u64,
+}
+
+impl ConsensusConfig {
+    /// Voting engine configuration for new tasks.
+    pub fn voting_config(&self) -> wws_consensus::voting::VotingConfig {
+        wws_consensus::voting::VotingConfig {
+            collection_window: (self.vote_collection_window_secs > 0)
+                .then(|| std::time::Duration::from_secs(self.vote_collection_window_secs)),
+            self_vote_policy: self.self_vote_policy,
+            ..Default::default()
+        }
+    }
+
+    /// Rationale length enforced on plans; zero when not required.
+    pub fn effective_min_rationale_len(&self) -> usize {
+        if self.require_rationale {
+            self.min_rationale_len.max(1)
+        } else {
+            0
+        }
+    }
+}
+
+/// Content-addressed storage configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentConfig {
+    /// Hash function for new content IDs (`"sha256"` or `"blake3"`).
+    /// Content addressed with either algorithm is always accepted.
+    #[serde(default)]
+    pub hash_algo: wws_state::HashAlgo,
+    /// Seconds a task stays completed before its artifacts are evicted
+    /// from the content store, unless still referenced. Zero keeps them
+    /// forever.
+    #[serde(default)]
+    pub artifact_retention_secs: u64,
+    /// Seconds between artifact garbage collection passes.
+    #[serde(default = "default_artifact_gc_interval")]
+    pub artifact_gc_interval_secs: u64,
+    /// Maximum total size of the content store in bytes. Once exceeded, the
+    /// oldest content is evicted. Unlimited when unset.
+    #[serde(default)]
+    pub capacity_bytes: Option<u64>,
+    /// Sign locally submitted result artifacts with the node identity key
+    /// when the submitter did not sign them itself.
+    #[serde(default = "default_true")]
+    pub sign_artifacts: bool,
+    /// Reject results whose artifact carries no producer signature.
+    /// Invalid signatures are always rejected.
+    #[serde(default)]
+    pub require_artifact_signatures: bool,
+    /// Sources `swarm.get_content` tries in order when fetching content
+    /// that is not stored locally.
+    #[serde(default = "default_fetch_chain")]
+    pub fetch_chain: Vec<crate::content_resolver::ContentSource>,
 }
 
 /// Agent identity configuration.
@@ -73,6 +294,10 @@ pub struct FileServerConfig {
     /// Address to bind the HTTP file server to.
     #[serde(default = "default_file_server_addr")]
     pub bind_addr: String,
+    /// Bearer token for the `POST /tasks` webhook. The endpoint is only
+    /// served when this is set.
+    #[serde(default)]
+    pub webhook_token: Option<String>,
 }
 
 /// Network layer configuration.
@@ -81,6 +306,10 @@ pub struct NetworkConfig {
     /// Address to listen on for P2P connections.
     #[serde(default = "default_listen_addr")]
     pub listen_addr: String,
+    /// Further P2P listen addresses, e.g. an IPv6 or QUIC address next to
+    /// the IPv4 `listen_addr`.
+    #[serde(default)]
+    pub extra_listen_addrs: Vec<String>,
     /// Bootstrap peer multiaddresses.
     #[serde(default)]
     pub bootstrap_peers: Vec<String>,
@@ -91,17 +320,91 @@ pub struct NetworkConfig {
     #[serde(default)]
     pub no_default_bootstrap: bool,
     /// Whether mDNS local discovery is enabled. Enabled by default for zero-conf LAN discovery.
+    /// Only consulted in the `auto` discovery mode.
     #[serde(default = "default_true")]
     pub mdns_enabled: bool,
+    /// Which discovery sources to run: `auto`, `mdns_only`, `bootstrap_only`
+    /// (no mDNS; startup fails without `bootstrap_peers`), or `static_only`
+    /// (dial `bootstrap_peers` and nothing else, for air-gapped networks).
+    #[serde(default)]
+    pub discovery_mode: wws_network::DiscoveryMode,
     /// Idle connection timeout in seconds.
     #[serde(default = "default_idle_timeout")]
     pub idle_connection_timeout_secs: u64,
+    /// Timeout in seconds for establishing an outbound connection.
+    #[serde(default = "default_dial_timeout")]
+    pub dial_timeout_secs: u64,
+    /// Window in milliseconds within which a peer's repeated connects and
+    /// disconnects are coalesced into one membership change (0 = off).
+    #[serde(default = "default_peer_debounce_ms")]
+    pub peer_debounce_ms: u64,
+    /// Window in milliseconds over which keepalives, announcements and
+    /// task-set updates for the same topic are coalesced into one
+    /// `swarm.state_batch` message (0 = publish each immediately, the
+    /// default, since older nodes drop batched updates).
+    #[serde(default = "default_state_batch_window_ms")]
+    pub state_batch_window_ms: u64,
+    /// Seconds between checks that re-subscribe to dropped GossipSub
+    /// topics (0 = off).
+    #[serde(default = "default_resubscribe_interval")]
+    pub resubscribe_interval_secs: u64,
+    /// Seconds GossipSub remembers message IDs to drop duplicates.
+    #[serde(default = "default_gossip_duplicate_cache")]
+    pub gossip_duplicate_cache_secs: u64,
+    /// Heartbeats of GossipSub message history kept for retransmission.
+    #[serde(default = "default_gossip_history_length")]
+    pub gossip_history_length: usize,
+    /// Topic categories (`consensus`, `tasks`, `results`, `keepalive`,
+    /// `hierarchy`, `discovery`, `other`) published ahead of other traffic
+    /// and given dedicated GossipSub mesh scoring. Empty disables both.
+    #[serde(default = "default_high_priority_topics")]
+    pub high_priority_topics: Vec<wws_network::TopicCategory>,
+    /// Times a publish that found no GossipSub mesh peers is retried
+    /// before the message is dropped (0 = off).
+    #[serde(default = "default_publish_retry_attempts")]
+    pub publish_retry_attempts: u32,
+    /// Milliseconds between publish retries.
+    #[serde(default = "default_publish_retry_interval_ms")]
+    pub publish_retry_interval_ms: u64,
+    /// Topic categories whose publishes are retried. Keepalives and
+    /// announcements are left out by default: the next one replaces them.
+    #[serde(default = "default_publish_retry_topics")]
+    pub publish_retry_topics: Vec<wws_network::TopicCategory>,
+    /// How topic names become GossipSub topic hashes: `ident` (the name
+    /// itself) or `sha256`. Every peer of a swarm must use the same value;
+    /// `swarm_overrides.<id>.topic_hashing` sets it for one swarm.
+    #[serde(default)]
+    pub topic_hashing: wws_network::TopicHashing,
+    /// Number of peers each Kademlia record, such as a swarm registry
+    /// entry, is replicated to.
+    #[serde(default = "default_kademlia_replication_factor")]
+    pub kademlia_replication_factor: usize,
+    /// Milliseconds between pings on each connection. Each ping is an RTT
+    /// sample for the Vivaldi coordinates; lower values sample more often
+    /// at the cost of more traffic.
+    #[serde(default = "default_ping_interval_ms")]
+    pub ping_interval_ms: u64,
+    /// Seconds a ping may wait for its reply before it counts as failed.
+    #[serde(default = "default_ping_timeout")]
+    pub ping_timeout_secs: u64,
     /// Run as a public bootstrap node (no agent bridge, high capacity).
     #[serde(default)]
     pub bootstrap_mode: bool,
     /// Enable QUIC transport alongside TCP.
     #[serde(default = "default_true")]
     pub enable_quic: bool,
+    /// Private-network pre-shared key as 64 hex characters. Only nodes
+    /// configured with the same key can connect; QUIC is disabled when set.
+    #[serde(default)]
+    pub psk: Option<String>,
+    /// Peer IDs (or `did:swarm:` agent IDs) allowed to connect. When
+    /// non-empty, connections from and to any other peer are refused.
+    #[serde(default)]
+    pub allowed_peers: Vec<String>,
+    /// Peer IDs (or `did:swarm:` agent IDs) never allowed to connect, even
+    /// if also listed in `allowed_peers`.
+    #[serde(default)]
+    pub denied_peers: Vec<String>,
     /// Enable circuit relay client (for NAT traversal).
     #[serde(default = "default_true")]
     pub enable_relay_client: bool,
@@ -128,6 +431,64 @@ pub struct HierarchyConfig {
     /// Keep-alive interval in seconds.
     #[serde(default = "default_keepalive_interval")]
     pub keepalive_interval_secs: u64,
+    /// Stay subscribed to the task topics of every tier up to
+    /// `MAX_HIERARCHY_DEPTH`, so the operator console can follow the whole
+    /// tree. On by default: with it off, a coordinator keeps only its own and
+    /// its parent tier's task topics, and injections, assignments and status
+    /// updates published on other tiers are no longer received, so the
+    /// console and `swarm.get_task` lose sight of those subtasks and
+    /// dependencies on them are never seen to complete.
+    #[serde(default = "default_true")]
+    pub monitor_all_tiers: bool,
+    /// Seconds a proposal reveal that arrived before its commit is held
+    /// waiting for the commit. Zero rejects such reveals immediately.
+    #[serde(default = "default_reveal_grace")]
+    pub reveal_grace_secs: u64,
+    /// Have the coordinator of a decomposed task combine its subtask
+    /// results into a manifest and submit it as the task's result once all
+    /// subtasks complete, instead of waiting for agent synthesis.
+    #[serde(default)]
+    pub aggregate_results: bool,
+    /// Shorten epochs while membership churns and lengthen them while it is
+    /// stable, between `min_epoch_duration_secs` and `max_epoch_duration_secs`.
+    /// `epoch_duration_secs` is the length of the first epoch.
+    #[serde(default)]
+    pub adaptive_epochs: bool,
+    /// Shortest epoch chosen in adaptive mode.
+    #[serde(default = "default_min_epoch_duration")]
+    pub min_epoch_duration_secs: u64,
+    /// Longest epoch chosen in adaptive mode.
+    #[serde(default = "default_max_epoch_duration")]
+    pub max_epoch_duration_secs: u64,
+    /// Peer ID or DID of the node that coordinates tasks until the first
+    /// Tier-1 election completes. Unset, or while that node is not a
+    /// member, the member with the lowest agent ID leads.
+    #[serde(default)]
+    pub genesis_leader: Option<String>,
+    /// Fraction by which the swarm size must pass a `k^d` boundary before
+    /// the hierarchy depth changes, so a swarm hovering around a boundary
+    /// keeps a stable depth. Zero recomputes the depth exactly.
+    #[serde(default = "default_depth_hysteresis")]
+    pub depth_hysteresis: f64,
+    /// Seconds an assignee has to acknowledge a subtask assignment before
+    /// it is reassigned (0 = don't wait for acknowledgements).
+    #[serde(default = "default_task_ack_timeout")]
+    pub task_ack_timeout_secs: u64,
+    /// Seconds of connect/disconnect history a peer's measured uptime
+    /// fraction covers.
+    #[serde(default = "default_uptime_window")]
+    pub uptime_window_secs: u64,
+    /// Keep measured peer uptime in this file across restarts. Not
+    /// persisted when unset.
+    #[serde(default)]
+    pub uptime_file: Option<std::path::PathBuf>,
+    /// How a failed leader's successor is chosen from its branch.
+    #[serde(default)]
+    pub succession_policy: wws_hierarchy::SuccessionPolicy,
+    /// Weights of proof of compute, reputation, uptime and stake in the
+    /// composite score that ranks election candidates and successors.
+    #[serde(default)]
+    pub score_weights: wws_protocol::ScoreWeights,
 }
 
 /// JSON-RPC server configuration.
@@ -156,6 +517,11 @@ pub struct AgentConfig {
     /// Whether MCP compatibility mode is enabled.
     #[serde(default)]
     pub mcp_compatible: bool,
+    /// Maximum in-progress subtasks an executor works on at once (0 = unlimited).
+    /// Coordinators apply the same limit when assigning to subordinates and
+    /// queue subtasks that no candidate has room for.
+    #[serde(default = "default_max_concurrent_tasks")]
+    pub max_concurrent_tasks: usize,
 }
 
 /// Logging configuration.
@@ -167,6 +533,16 @@ pub struct LoggingConfig {
     /// Whether to output JSON-formatted logs.
     #[serde(default)]
     pub json_format: bool,
+    /// Number of event log entries kept in memory for the TUI and console.
+    #[serde(default = "default_event_log_capacity")]
+    pub event_log_capacity: usize,
+    /// Append every event log entry to this JSON-lines file and reload the
+    /// newest entries from it on startup. Disabled when unset.
+    #[serde(default)]
+    pub event_log_path: Option<std::path::PathBuf>,
+    /// Size in bytes at which the event log file is rotated to `<path>.1`.
+    #[serde(default = "default_event_log_max_bytes")]
+    pub event_log_max_bytes: u64,
 }
 
 /// Swarm identity and multi-swarm configuration.
@@ -187,6 +563,13 @@ pub struct SwarmConfig {
     /// Interval in seconds between swarm announcements on the DHT.
     #[serde(default = "default_swarm_announce_interval")]
     pub announce_interval_secs: u64,
+    /// Stretch the announcement interval as the swarm grows and skip
+    /// re-announcing a record that has not changed.
+    #[serde(default = "default_true")]
+    pub adaptive_announce: bool,
+    /// Ceiling for the adaptive announcement interval, in seconds.
+    #[serde(default = "default_max_announce_interval")]
+    pub max_announce_interval_secs: u64,
 }
 
 // -- Defaults --
@@ -203,18 +586,111 @@ fn default_true() -> bool {
 fn default_idle_timeout() -> u64 {
     60
 }
+fn default_dial_timeout() -> u64 {
+    10
+}
+fn default_peer_debounce_ms() -> u64 {
+    crate::peer_flap::DEFAULT_PEER_DEBOUNCE_MS
+}
+fn default_state_batch_window_ms() -> u64 {
+    crate::state_batch::DEFAULT_STATE_BATCH_WINDOW_MS
+}
+fn default_resubscribe_interval() -> u64 {
+    30
+}
+fn default_gossip_duplicate_cache() -> u64 {
+    wws_network::behaviour::GossipConfig::default()
+        .duplicate_cache_time
+        .as_secs()
+}
+fn default_gossip_history_length() -> usize {
+    wws_network::behaviour::GossipConfig::default().history_length
+}
+fn default_high_priority_topics() -> Vec<wws_network::TopicCategory> {
+    wws_network::TopicPriorities::default().high.into_iter().collect()
+}
+fn default_publish_retry_attempts() -> u32 {
+    wws_network::PublishRetryConfig::default().max_attempts
+}
+fn default_publish_retry_interval_ms() -> u64 {
+    wws_network::PublishRetryConfig::default().interval.as_millis() as u64
+}
+fn default_publish_retry_topics() -> Vec<wws_network::TopicCategory> {
+    wws_network::PublishRetryConfig::default().categories.into_iter().collect()
+}
+fn default_kademlia_replication_factor() -> usize {
+    wws_network::libp2p::kad::K_VALUE.get()
+}
+fn default_ping_interval_ms() -> u64 {
+    wws_network::behaviour::BehaviourConfig::default().ping_interval.as_millis() as u64
+}
+fn default_ping_timeout() -> u64 {
+    wws_network::behaviour::BehaviourConfig::default().ping_timeout.as_secs()
+}
+fn default_artifact_gc_interval() -> u64 {
+    crate::artifact_gc::DEFAULT_ARTIFACT_GC_INTERVAL_SECS
+}
+fn default_fetch_chain() -> Vec<crate::content_resolver::ContentSource> {
+    crate::content_resolver::DEFAULT_FETCH_CHAIN.to_vec()
+}
+fn default_min_rationale_len() -> usize {
+    1
+}
+fn default_vote_collection_window() -> u64 {
+    45
+}
 fn default_branching_factor() -> u32 {
     wws_protocol::DEFAULT_BRANCHING_FACTOR
 }
 fn default_epoch_duration() -> u64 {
     wws_protocol::DEFAULT_EPOCH_DURATION_SECS
 }
+fn default_min_epoch_duration() -> u64 {
+    wws_hierarchy::epoch::AdaptiveEpochConfig::default().min_duration_secs
+}
+fn default_max_epoch_duration() -> u64 {
+    wws_hierarchy::epoch::AdaptiveEpochConfig::default().max_duration_secs
+}
+fn default_depth_hysteresis() -> f64 {
+    wws_hierarchy::pyramid::DEFAULT_DEPTH_HYSTERESIS
+}
+fn default_uptime_window() -> u64 {
+    crate::uptime::DEFAULT_UPTIME_WINDOW_SECS
+}
+fn default_task_ack_timeout() -> u64 {
+    crate::task_ack::DEFAULT_TASK_ACK_TIMEOUT_SECS
+}
 fn default_leader_timeout() -> u64 {
     wws_protocol::LEADER_TIMEOUT_SECS
 }
 fn default_keepalive_interval() -> u64 {
     wws_protocol::KEEPALIVE_INTERVAL_SECS
 }
+fn default_expand_subtask_templates() -> bool {
+    true
+}
+fn default_reveal_grace() -> u64 {
+    wws_protocol::DEFAULT_REVEAL_GRACE_SECS
+}
+fn default_max_expected_proposers() -> usize {
+    10
+}
+fn default_result_redundancy() -> usize {
+    1
+}
+fn default_result_majority() -> f64 {
+    wws_consensus::result_tally::DEFAULT_RESULT_MAJORITY
+}
+fn default_max_subtasks_per_plan() -> usize {
+    wws_protocol::DEFAULT_MAX_SUBTASKS_PER_PLAN
+}
+fn default_max_task_description_len() -> usize {
+    wws_protocol::DEFAULT_MAX_TASK_DESCRIPTION_LEN
+}
+
+fn default_max_subtask_description_len() -> usize {
+    wws_protocol::DEFAULT_MAX_SUBTASK_DESCRIPTION_LEN
+}
 fn default_rpc_addr() -> String {
     "127.0.0.1:9370".to_string()
 }
@@ -227,6 +703,9 @@ fn default_rpc_timeout() -> u64 {
 fn default_agent_name() -> String {
     "wws-agent".to_string()
 }
+fn default_max_concurrent_tasks() -> usize {
+    8
+}
 pub fn default_identity_dir() -> std::path::PathBuf {
     dirs::home_dir()
         .unwrap_or_else(|| std::path::PathBuf::from("."))
@@ -235,6 +714,12 @@ pub fn default_identity_dir() -> std::path::PathBuf {
 fn default_log_level() -> String {
     "info".to_string()
 }
+fn default_event_log_capacity() -> usize {
+    crate::event_log::DEFAULT_EVENT_LOG_CAPACITY
+}
+fn default_event_log_max_bytes() -> u64 {
+    crate::event_log::DEFAULT_EVENT_LOG_MAX_BYTES
+}
 fn default_swarm_id() -> String {
     wws_protocol::DEFAULT_SWARM_ID.to_string()
 }
@@ -244,6 +729,9 @@ fn default_swarm_name() -> String {
 fn default_swarm_announce_interval() -> u64 {
     wws_protocol::SWARM_ANNOUNCE_INTERVAL_SECS
 }
+fn default_max_announce_interval() -> u64 {
+    wws_protocol::MAX_SWARM_ANNOUNCE_INTERVAL_SECS
+}
 fn default_file_server_addr() -> String {
     "127.0.0.1:9371".to_string()
 }
@@ -258,6 +746,45 @@ impl Default for SwarmConfig {
             name: default_swarm_name(),
             is_public: true,
             announce_interval_secs: default_swarm_announce_interval(),
+            adaptive_announce: true,
+            max_announce_interval_secs: default_max_announce_interval(),
+        }
+    }
+}
+
+impl Default for ContentConfig {
+    fn default() -> Self {
+        Self {
+            hash_algo: wws_state::HashAlgo::default(),
+            artifact_retention_secs: 0,
+            artifact_gc_interval_secs: default_artifact_gc_interval(),
+            capacity_bytes: None,
+            sign_artifacts: true,
+            require_artifact_signatures: false,
+            fetch_chain: default_fetch_chain(),
+        }
+    }
+}
+
+impl Default for ConsensusConfig {
+    fn default() -> Self {
+        Self {
+            min_peers_for_consensus: 0,
+            require_rationale: false,
+            min_rationale_len: default_min_rationale_len(),
+            expand_subtask_templates: default_expand_subtask_templates(),
+            vote_collection_window_secs: default_vote_collection_window(),
+            self_vote_policy: wws_consensus::SelfVotePolicy::default(),
+            result_redundancy: default_result_redundancy(),
+            result_majority: default_result_majority(),
+            max_subtasks_per_plan: default_max_subtasks_per_plan(),
+            max_subtask_description_len: default_max_subtask_description_len(),
+            adaptive_proposers: false,
+            max_expected_proposers: default_max_expected_proposers(),
+            min_commit_window_secs: 0,
+            max_task_description_len: default_max_task_description_len(),
+            task_set_retention_secs: 0,
+            task_detail_retention_secs: 0,
         }
     }
 }
@@ -267,6 +794,7 @@ impl Default for FileServerConfig {
         Self {
             enabled: true,
             bind_addr: default_file_server_addr(),
+            webhook_token: None,
         }
     }
 }
@@ -282,6 +810,9 @@ impl Default for ConnectorConfig {
             swarm: SwarmConfig::default(),
             file_server: FileServerConfig::default(),
             identity: IdentityConfig::default(),
+            content: ContentConfig::default(),
+            consensus: ConsensusConfig::default(),
+            swarm_overrides: HashMap::new(),
         }
     }
 }
@@ -290,13 +821,32 @@ impl Default for NetworkConfig {
     fn default() -> Self {
         Self {
             listen_addr: default_listen_addr(),
+            extra_listen_addrs: Vec::new(),
             bootstrap_peers: Vec::new(),
             bootstrap_domain: default_bootstrap_domain(),
             no_default_bootstrap: false,
             mdns_enabled: true,
+            discovery_mode: wws_network::DiscoveryMode::default(),
             idle_connection_timeout_secs: default_idle_timeout(),
+            dial_timeout_secs: default_dial_timeout(),
+            peer_debounce_ms: default_peer_debounce_ms(),
+            state_batch_window_ms: default_state_batch_window_ms(),
+            resubscribe_interval_secs: default_resubscribe_interval(),
+            gossip_duplicate_cache_secs: default_gossip_duplicate_cache(),
+            gossip_history_length: default_gossip_history_length(),
+            high_priority_topics: default_high_priority_topics(),
+            publish_retry_attempts: default_publish_retry_attempts(),
+            publish_retry_interval_ms: default_publish_retry_interval_ms(),
+            publish_retry_topics: default_publish_retry_topics(),
+            topic_hashing: wws_network::TopicHashing::default(),
+            kademlia_replication_factor: default_kademlia_replication_factor(),
+            ping_interval_ms: default_ping_interval_ms(),
+            ping_timeout_secs: default_ping_timeout(),
             bootstrap_mode: false,
             enable_quic: true,
+            psk: None,
+            allowed_peers: Vec::new(),
+            denied_peers: Vec::new(),
             enable_relay_client: true,
             enable_relay_server: false,
             enable_dcutr: true,
@@ -311,6 +861,19 @@ impl Default for HierarchyConfig {
             epoch_duration_secs: default_epoch_duration(),
             leader_timeout_secs: default_leader_timeout(),
             keepalive_interval_secs: default_keepalive_interval(),
+            monitor_all_tiers: true,
+            reveal_grace_secs: default_reveal_grace(),
+            aggregate_results: false,
+            adaptive_epochs: false,
+            min_epoch_duration_secs: default_min_epoch_duration(),
+            max_epoch_duration_secs: default_max_epoch_duration(),
+            genesis_leader: None,
+            depth_hysteresis: default_depth_hysteresis(),
+            task_ack_timeout_secs: default_task_ack_timeout(),
+            uptime_window_secs: default_uptime_window(),
+            uptime_file: None,
+            succession_policy: wws_hierarchy::SuccessionPolicy::default(),
+            score_weights: wws_protocol::ScoreWeights::default(),
         }
     }
 }
@@ -331,6 +894,7 @@ impl Default for AgentConfig {
             name: default_agent_name(),
             capabilities: Vec::new(),
             mcp_compatible: false,
+            max_concurrent_tasks: default_max_concurrent_tasks(),
         }
     }
 }
@@ -340,10 +904,21 @@ impl Default for LoggingConfig {
         Self {
             level: default_log_level(),
             json_format: false,
+            event_log_capacity: default_event_log_capacity(),
+            event_log_path: None,
+            event_log_max_bytes: default_event_log_max_bytes(),
         }
     }
 }
 
+impl NetworkConfig {
+    /// `listen_addr` followed by `extra_listen_addrs`.
+    pub fn all_listen_addrs(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.listen_addr.as_str())
+            .chain(self.extra_listen_addrs.iter().map(String::as_str))
+    }
+}
+
 impl ConnectorConfig {
     /// Load configuration from a TOML file.
     pub fn from_file(path: &Path) -> Result<Self, anyhow::Error> {
@@ -382,6 +957,9 @@ impl ConnectorConfig {
         if let Ok(val) = std::env::var("WWS_LISTEN_ADDR") {
             self.network.listen_addr = val;
         }
+        if let Ok(val) = std::env::var("WWS_PSK") {
+            self.network.psk = Some(val);
+        }
         if let Ok(val) = std::env::var("WWS_RPC_BIND_ADDR") {
             self.rpc.bind_addr = val;
         }
@@ -398,15 +976,104 @@ impl ConnectorConfig {
                 self.hierarchy.epoch_duration_secs = d;
             }
         }
+        if let Ok(val) = std::env::var("WWS_ADAPTIVE_EPOCHS") {
+            self.hierarchy.adaptive_epochs = val == "true" || val == "1";
+        }
+        if let Ok(val) = std::env::var("WWS_GENESIS_LEADER") {
+            self.hierarchy.genesis_leader = Some(val).filter(|v| !v.trim().is_empty());
+        }
+        if let Ok(val) = std::env::var("WWS_TASK_SET_RETENTION_SECS") {
+            if let Ok(secs) = val.parse() {
+                self.consensus.task_set_retention_secs = secs;
+            }
+        }
+        if let Ok(val) = std::env::var("WWS_TASK_ACK_TIMEOUT_SECS") {
+            if let Ok(secs) = val.parse() {
+                self.hierarchy.task_ack_timeout_secs = secs;
+            }
+        }
+        if let Ok(val) = std::env::var("WWS_TASK_DETAIL_RETENTION_SECS") {
+            if let Ok(secs) = val.parse() {
+                self.consensus.task_detail_retention_secs = secs;
+            }
+        }
+        if let Ok(val) = std::env::var("WWS_UPTIME_FILE") {
+            self.hierarchy.uptime_file = Some(std::path::PathBuf::from(val));
+        }
+        if let Ok(val) = std::env::var("WWS_SUCCESSION_POLICY") {
+            if let Ok(policy) = val.parse() {
+                self.hierarchy.succession_policy = policy;
+            }
+        }
+        if let Ok(val) = std::env::var("WWS_DEPTH_HYSTERESIS") {
+            if let Ok(margin) = val.parse() {
+                self.hierarchy.depth_hysteresis = margin;
+            }
+        }
+        if let Ok(val) = std::env::var("WWS_AGGREGATE_RESULTS") {
+            self.hierarchy.aggregate_results = val == "true" || val == "1";
+        }
+        if let Ok(val) = std::env::var("WWS_MONITOR_ALL_TIERS") {
+            self.hierarchy.monitor_all_tiers = val == "true" || val == "1";
+        }
         if let Ok(val) = std::env::var("WWS_AGENT_NAME") {
             self.agent.name = val;
         }
+        if let Ok(val) = std::env::var("WWS_MAX_CONCURRENT_TASKS") {
+            if let Ok(n) = val.parse() {
+                self.agent.max_concurrent_tasks = n;
+            }
+        }
         if let Ok(val) = std::env::var("WWS_BOOTSTRAP_PEERS") {
             self.network.bootstrap_peers = val.split(',').map(|s| s.trim().to_string()).collect();
         }
         if let Ok(val) = std::env::var("WWS_BOOTSTRAP_DOMAIN") {
             self.network.bootstrap_domain = val;
         }
+        if let Ok(val) = std::env::var("WWS_DISCOVERY_MODE") {
+            if let Ok(mode) = val.parse() {
+                self.network.discovery_mode = mode;
+            }
+        }
+        if let Ok(val) = std::env::var("WWS_HIGH_PRIORITY_TOPICS") {
+            let categories: Result<Vec<_>, _> = val
+                .split(',')
+                .filter(|s| !s.trim().is_empty())
+                .map(str::parse)
+                .collect();
+            if let Ok(categories) = categories {
+                self.network.high_priority_topics = categories;
+            }
+        }
+        if let Ok(val) = std::env::var("WWS_PUBLISH_RETRY_ATTEMPTS") {
+            if let Ok(attempts) = val.parse() {
+                self.network.publish_retry_attempts = attempts;
+            }
+        }
+        if let Ok(val) = std::env::var("WWS_PING_INTERVAL_MS") {
+            if let Ok(ms) = val.parse() {
+                self.network.ping_interval_ms = ms;
+            }
+        }
+        if let Ok(val) = std::env::var("WWS_TOPIC_HASHING") {
+            if let Ok(hashing) = val.parse() {
+                self.network.topic_hashing = hashing;
+            }
+        }
+        if let Ok(val) = std::env::var("WWS_ALLOWED_PEERS") {
+            self.network.allowed_peers = val
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect();
+        }
+        if let Ok(val) = std::env::var("WWS_DENIED_PEERS") {
+            self.network.denied_peers = val
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect();
+        }
         if std::env::var("WWS_NO_DEFAULT_BOOTSTRAP").is_ok() {
             self.network.no_default_bootstrap = true;
         }
@@ -428,12 +1095,130 @@ impl ConnectorConfig {
         if let Ok(val) = std::env::var("WWS_FILE_SERVER_ENABLED") {
             self.file_server.enabled = val == "true" || val == "1";
         }
+        if let Ok(val) = std::env::var("WWS_WEBHOOK_TOKEN") {
+            self.file_server.webhook_token = Some(val).filter(|t| !t.trim().is_empty());
+        }
         if let Ok(val) = std::env::var("WWS_IDENTITY_PATH") {
             self.identity.path = std::path::PathBuf::from(val);
         }
         if let Ok(val) = std::env::var("WWS_NAME") {
             self.identity.wws_name = Some(val);
         }
+        if let Ok(val) = std::env::var("WWS_MIN_PEERS_FOR_CONSENSUS") {
+            if let Ok(n) = val.parse() {
+                self.consensus.min_peers_for_consensus = n;
+            }
+        }
+        if let Ok(val) = std::env::var("WWS_VOTE_COLLECTION_WINDOW_SECS") {
+            if let Ok(secs) = val.parse() {
+                self.consensus.vote_collection_window_secs = secs;
+            }
+        }
+        if let Ok(val) = std::env::var("WWS_REQUIRE_RATIONALE") {
+            self.consensus.require_rationale = val == "true" || val == "1";
+        }
+        if let Ok(val) = std::env::var("WWS_SELF_VOTE_POLICY") {
+            if let Ok(policy) = val.parse() {
+                self.consensus.self_vote_policy = policy;
+            }
+        }
+        if let Ok(val) = std::env::var("WWS_ARTIFACT_RETENTION_SECS") {
+            if let Ok(secs) = val.parse() {
+                self.content.artifact_retention_secs = secs;
+            }
+        }
+        if let Ok(val) = std::env::var("WWS_CONTENT_CAPACITY_BYTES") {
+            if let Ok(bytes) = val.parse() {
+                self.content.capacity_bytes = Some(bytes);
+            }
+        }
+        if let Ok(val) = std::env::var("WWS_REQUIRE_ARTIFACT_SIGNATURES") {
+            self.content.require_artifact_signatures = val == "true" || val == "1";
+        }
+        if let Ok(val) = std::env::var("WWS_CONTENT_HASH_ALGO") {
+            if let Ok(algo) = val.parse() {
+                self.content.hash_algo = algo;
+            }
+        }
+        if let Ok(val) = std::env::var("WWS_CONTENT_FETCH_CHAIN") {
+            if let Ok(chain) = val.split(',').map(str::parse).collect() {
+                self.content.fetch_chain = chain;
+            }
+        }
+    }
+
+    /// Hierarchy settings in effect within `swarm_id`.
+    pub fn hierarchy_for(&self, swarm_id: &str) -> HierarchyConfig {
+        SwarmSettings::from_config(self).hierarchy(swarm_id)
+    }
+
+    /// Consensus settings in effect within `swarm_id`.
+    pub fn consensus_for(&self, swarm_id: &str) -> ConsensusConfig {
+        SwarmSettings::from_config(self).consensus(swarm_id)
+    }
+
+    /// GossipSub topic hashing: `network.topic_hashing`, replaced per swarm
+    /// by `swarm_overrides.<id>.topic_hashing`.
+    pub fn topic_hashing(&self) -> wws_network::TopicHashingConfig {
+        wws_network::TopicHashingConfig {
+            default: self.network.topic_hashing,
+            swarms: self
+                .swarm_overrides
+                .iter()
+                .filter_map(|(swarm_id, o)| o.topic_hashing.map(|h| (swarm_id.clone(), h)))
+                .collect(),
+        }
+    }
+
+    /// Peer allow and deny lists from `network.allowed_peers` and
+    /// `network.denied_peers`. Fails on an entry that is not a peer ID.
+    pub fn peer_filter(&self) -> Result<wws_network::PeerFilter, anyhow::Error> {
+        fn parse(key: &str, entries: &[String]) -> Result<BTreeSet<PeerId>, anyhow::Error> {
+            entries
+                .iter()
+                .map(|entry| {
+                    entry
+                        .strip_prefix("did:swarm:")
+                        .unwrap_or(entry)
+                        .parse::<PeerId>()
+                        .map_err(|e| anyhow::anyhow!("network.{}: invalid peer ID {:?}: {}", key, entry, e))
+                })
+                .collect()
+        }
+        Ok(wws_network::PeerFilter {
+            allowed: parse("allowed_peers", &self.network.allowed_peers)?,
+            denied: parse("denied_peers", &self.network.denied_peers)?,
+        })
+    }
+
+    /// Agent ID of `hierarchy.genesis_leader`, given as a peer ID or
+    /// `did:swarm:` DID. Fails on a value that is not a peer ID.
+    pub fn genesis_leader(&self) -> Result<Option<wws_protocol::AgentId>, anyhow::Error> {
+        let Some(entry) = &self.hierarchy.genesis_leader else {
+            return Ok(None);
+        };
+        let peer = entry
+            .strip_prefix("did:swarm:")
+            .unwrap_or(entry)
+            .parse::<PeerId>()
+            .map_err(|e| anyhow::anyhow!("hierarchy.genesis_leader: invalid peer ID {:?}: {}", entry, e))?;
+        Ok(Some(wws_protocol::AgentId::new(format!("did:swarm:{}", peer))))
+    }
+
+    /// `hierarchy.score_weights`, rejecting negative or non-finite weights.
+    pub fn score_weights(&self) -> Result<wws_protocol::ScoreWeights, anyhow::Error> {
+        let weights = self.hierarchy.score_weights;
+        for (name, weight) in [
+            ("proof_of_compute", weights.proof_of_compute),
+            ("reputation", weights.reputation),
+            ("uptime", weights.uptime),
+            ("stake", weights.stake),
+        ] {
+            if !weight.is_finite() || weight < 0.0 {
+                anyhow::bail!("hierarchy.score_weights.{name}: weight must be a non-negative number, got {weight}");
+            }
+        }
+        Ok(weights)
     }
 
     /// Parse the RPC bind address into a SocketAddr.
@@ -452,9 +1237,451 @@ mod config_tests {
         assert!(config.mdns_enabled, "mDNS should be enabled by default for zero-conf discovery");
     }
 
+    #[test]
+    fn network_config_reads_discovery_mode() {
+        assert_eq!(NetworkConfig::default().discovery_mode, wws_network::DiscoveryMode::Auto);
+        let config: NetworkConfig = toml::from_str("discovery_mode = \"bootstrap_only\"\n").unwrap();
+        assert_eq!(config.discovery_mode, wws_network::DiscoveryMode::BootstrapOnly);
+    }
+
+    #[test]
+    fn network_config_selects_static_only_discovery() {
+        let config: ConnectorConfig = toml::from_str(
+            "[network]\ndiscovery_mode = \"static_only\"\nno_default_bootstrap = true\n",
+        )
+        .unwrap();
+        assert_eq!(config.network.discovery_mode, wws_network::DiscoveryMode::StaticOnly);
+    }
+
+    #[test]
+    fn network_config_lists_extra_listen_addrs_after_primary() {
+        let config: NetworkConfig = toml::from_str(
+            "listen_addr = \"/ip4/0.0.0.0/tcp/9000\"\nextra_listen_addrs = [\"/ip6/::/tcp/9000\"]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.all_listen_addrs().collect::<Vec<_>>(),
+            vec!["/ip4/0.0.0.0/tcp/9000", "/ip6/::/tcp/9000"]
+        );
+
+        let defaults = NetworkConfig::default();
+        assert_eq!(defaults.all_listen_addrs().count(), 1);
+    }
+
     #[test]
     fn network_config_default_bootstrap_domain() {
         let config = NetworkConfig::default();
         assert_eq!(config.bootstrap_domain, "worldwideswarm.net");
     }
+
+    #[test]
+    fn swarm_config_defaults_to_adaptive_announce() {
+        let config: SwarmConfig = toml::from_str("").unwrap();
+        assert!(config.adaptive_announce);
+        assert_eq!(config.max_announce_interval_secs, wws_protocol::MAX_SWARM_ANNOUNCE_INTERVAL_SECS);
+
+        let config: SwarmConfig =
+            toml::from_str("adaptive_announce = false\nmax_announce_interval_secs = 120\n").unwrap();
+        assert!(!config.adaptive_announce);
+        assert_eq!(config.max_announce_interval_secs, 120);
+    }
+
+    #[test]
+    fn consensus_config_reads_min_peers() {
+        let defaults: ConnectorConfig = toml::from_str("").unwrap();
+        assert_eq!(defaults.consensus.min_peers_for_consensus, 0);
+        let config: ConnectorConfig =
+            toml::from_str("[consensus]\nmin_peers_for_consensus = 3\n").unwrap();
+        assert_eq!(config.consensus.min_peers_for_consensus, 3);
+    }
+
+    #[test]
+    fn consensus_config_reads_rationale_requirement() {
+        let defaults: ConnectorConfig = toml::from_str("").unwrap();
+        assert!(!defaults.consensus.require_rationale);
+        assert_eq!(defaults.consensus.effective_min_rationale_len(), 0);
+        let config: ConnectorConfig =
+            toml::from_str("[consensus]\nrequire_rationale = true\nmin_rationale_len = 40\n").unwrap();
+        assert_eq!(config.consensus.effective_min_rationale_len(), 40);
+    }
+
+    #[test]
+    fn consensus_config_reads_vote_collection_window() {
+        let defaults: ConnectorConfig = toml::from_str("").unwrap();
+        assert_eq!(
+            defaults.consensus.voting_config().collection_window,
+            Some(std::time::Duration::from_secs(45))
+        );
+        let config: ConnectorConfig =
+            toml::from_str("[consensus]\nvote_collection_window_secs = 0\n").unwrap();
+        assert_eq!(config.consensus.voting_config().collection_window, None);
+    }
+
+    #[test]
+    fn consensus_config_reads_self_vote_policy() {
+        use wws_consensus::SelfVotePolicy;
+        let defaults: ConnectorConfig = toml::from_str("").unwrap();
+        assert_eq!(
+            defaults.consensus.voting_config().self_vote_policy,
+            SelfVotePolicy::AllowNonFirst
+        );
+        let config: ConnectorConfig =
+            toml::from_str("[consensus]\nself_vote_policy = \"fully_prohibit\"\n").unwrap();
+        assert_eq!(
+            config.consensus.voting_config().self_vote_policy,
+            SelfVotePolicy::FullyProhibit
+        );
+    }
+
+    #[test]
+    fn swarm_overrides_replace_global_settings_for_that_swarm_only() {
+        let config: ConnectorConfig = toml::from_str(
+            r#"
+[hierarchy]
+branching_factor = 10
+
+[consensus]
+vote_collection_window_secs = 45
+
+[swarm_overrides.research]
+branching_factor = 4
+vote_collection_window_secs = 20
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.hierarchy_for("research").branching_factor, 4);
+        assert_eq!(config.hierarchy_for("public").branching_factor, 10);
+        assert_eq!(config.consensus_for("research").vote_collection_window_secs, 20);
+        assert_eq!(config.consensus_for("public").vote_collection_window_secs, 45);
+        // Fields the override leaves unset fall back to the global value.
+        assert_eq!(
+            config.hierarchy_for("research").epoch_duration_secs,
+            config.hierarchy.epoch_duration_secs
+        );
+    }
+
+    #[test]
+    fn topic_hashing_can_be_set_per_swarm() {
+        use wws_network::TopicHashing;
+        let config: ConnectorConfig = toml::from_str(
+            r#"
+[swarm_overrides.research]
+topic_hashing = "sha256"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.network.topic_hashing, TopicHashing::Ident);
+        let hashing = config.topic_hashing();
+        assert_eq!(hashing.hashing_for("/wws/1.0.0/s/research/keepalive"), TopicHashing::Sha256);
+        assert_eq!(hashing.hashing_for("/wws/1.0.0/s/public/keepalive"), TopicHashing::Ident);
+        assert_eq!(hashing.hashing_for("/wws/1.0.0/swarm/discovery"), TopicHashing::Ident);
+    }
+
+    #[test]
+    fn peer_lists_parse_into_peer_filter() {
+        let (allowed, denied) = (PeerId::random(), PeerId::random());
+        let config: ConnectorConfig = toml::from_str(&format!(
+            "[network]\nallowed_peers = [\"{allowed}\"]\ndenied_peers = [\"did:swarm:{denied}\"]\n"
+        ))
+        .unwrap();
+        let filter = config.peer_filter().unwrap();
+        assert_eq!(filter.allowed, BTreeSet::from([allowed]));
+        assert_eq!(filter.denied, BTreeSet::from([denied]));
+
+        let defaults: ConnectorConfig = toml::from_str("").unwrap();
+        assert!(defaults.peer_filter().unwrap().is_open());
+        let invalid: ConnectorConfig =
+            toml::from_str("[network]\ndenied_peers = [\"not-a-peer\"]\n").unwrap();
+        let err = invalid.peer_filter().unwrap_err().to_string();
+        assert!(err.contains("network.denied_peers"), "{err}");
+    }
+
+    #[test]
+    fn content_config_defaults_keep_artifacts() {
+        let defaults: ContentConfig = toml::from_str("").unwrap();
+        assert_eq!(defaults.artifact_retention_secs, 0);
+        assert_eq!(defaults.artifact_gc_interval_secs, 300);
+        assert_eq!(defaults.capacity_bytes, None);
+        let config: ContentConfig = toml::from_str(
+            "artifact_retention_secs = 86400\nartifact_gc_interval_secs = 60\ncapacity_bytes = 1048576\n",
+        )
+        .unwrap();
+        assert_eq!(config.artifact_retention_secs, 86400);
+        assert_eq!(config.artifact_gc_interval_secs, 60);
+        assert_eq!(config.capacity_bytes, Some(1048576));
+    }
+
+    #[test]
+    fn content_config_artifact_signing() {
+        let defaults: ContentConfig = toml::from_str("").unwrap();
+        assert!(defaults.sign_artifacts);
+        assert!(!defaults.require_artifact_signatures);
+        let config: ContentConfig =
+            toml::from_str("sign_artifacts = false\nrequire_artifact_signatures = true\n").unwrap();
+        assert!(!config.sign_artifacts);
+        assert!(config.require_artifact_signatures);
+    }
+
+    #[test]
+    fn content_config_reads_fetch_chain() {
+        use crate::content_resolver::{ContentSource, DEFAULT_FETCH_CHAIN};
+        let defaults: ContentConfig = toml::from_str("").unwrap();
+        assert_eq!(defaults.fetch_chain, DEFAULT_FETCH_CHAIN);
+        let config: ContentConfig = toml::from_str("fetch_chain = [\"dht\", \"local\"]\n").unwrap();
+        assert_eq!(config.fetch_chain, [ContentSource::Dht, ContentSource::Local]);
+        assert!(toml::from_str::<ContentConfig>("fetch_chain = [\"cache\"]\n").is_err());
+    }
+
+    #[test]
+    fn network_config_reads_gossip_dedup_settings() {
+        let defaults: NetworkConfig = toml::from_str("").unwrap();
+        assert_eq!(defaults.gossip_duplicate_cache_secs, 60);
+        assert_eq!(defaults.gossip_history_length, 5);
+        let config: NetworkConfig =
+            toml::from_str("gossip_duplicate_cache_secs = 300\ngossip_history_length = 10\n").unwrap();
+        assert_eq!(config.gossip_duplicate_cache_secs, 300);
+        assert_eq!(config.gossip_history_length, 10);
+    }
+
+    #[test]
+    fn network_config_reads_high_priority_topics() {
+        use wws_network::TopicCategory;
+        let defaults: NetworkConfig = toml::from_str("").unwrap();
+        assert_eq!(defaults.high_priority_topics, vec![TopicCategory::Consensus]);
+        let config: NetworkConfig =
+            toml::from_str("high_priority_topics = [\"consensus\", \"results\"]\n").unwrap();
+        assert_eq!(
+            config.high_priority_topics,
+            vec![TopicCategory::Consensus, TopicCategory::Results]
+        );
+        let config: NetworkConfig = toml::from_str("high_priority_topics = []\n").unwrap();
+        assert!(config.high_priority_topics.is_empty());
+    }
+
+    #[test]
+    fn network_config_reads_publish_retry() {
+        use wws_network::TopicCategory;
+        let defaults: NetworkConfig = toml::from_str("").unwrap();
+        assert_eq!(defaults.publish_retry_attempts, 5);
+        assert_eq!(defaults.publish_retry_interval_ms, 500);
+        assert!(!defaults.publish_retry_topics.contains(&TopicCategory::Keepalive));
+        let config: NetworkConfig = toml::from_str(
+            "publish_retry_attempts = 0\npublish_retry_topics = [\"consensus\"]\n",
+        )
+        .unwrap();
+        assert_eq!(config.publish_retry_attempts, 0);
+        assert_eq!(config.publish_retry_topics, vec![TopicCategory::Consensus]);
+    }
+
+    #[test]
+    fn network_config_reads_kademlia_replication_factor() {
+        let defaults: NetworkConfig = toml::from_str("").unwrap();
+        assert_eq!(defaults.kademlia_replication_factor, 20);
+        let config: NetworkConfig = toml::from_str("kademlia_replication_factor = 32\n").unwrap();
+        assert_eq!(config.kademlia_replication_factor, 32);
+    }
+
+    #[test]
+    fn network_config_reads_ping_settings() {
+        let defaults: NetworkConfig = toml::from_str("").unwrap();
+        assert_eq!(defaults.ping_interval_ms, 1000);
+        assert_eq!(defaults.ping_timeout_secs, 20);
+        let config: NetworkConfig =
+            toml::from_str("ping_interval_ms = 250\nping_timeout_secs = 5\n").unwrap();
+        assert_eq!(config.ping_interval_ms, 250);
+        assert_eq!(config.ping_timeout_secs, 5);
+    }
+
+    #[test]
+    fn network_config_reads_psk_hex() {
+        let defaults: NetworkConfig = toml::from_str("").unwrap();
+        assert_eq!(defaults.psk, None);
+
+        let hex = "ab".repeat(32);
+        let config: NetworkConfig = toml::from_str(&format!("psk = \"{hex}\"\n")).unwrap();
+        assert_eq!(
+            wws_network::pnet::parse_psk_hex(config.psk.as_deref().unwrap()).unwrap(),
+            [0xab; 32]
+        );
+    }
+
+    #[test]
+    fn network_config_parses_transport_timeouts() {
+        let config: NetworkConfig = toml::from_str(
+            "dial_timeout_secs = 3\nidle_connection_timeout_secs = 120\n",
+        )
+        .unwrap();
+        assert_eq!(config.dial_timeout_secs, 3);
+        assert_eq!(config.idle_connection_timeout_secs, 120);
+
+        let defaults: NetworkConfig = toml::from_str("").unwrap();
+        assert_eq!(defaults.dial_timeout_secs, 10);
+        assert_eq!(defaults.idle_connection_timeout_secs, 60);
+    }
+
+    #[test]
+    fn content_config_parses_hash_algo() {
+        let config: ConnectorConfig = toml::from_str("[content]\nhash_algo = \"blake3\"\n").unwrap();
+        assert_eq!(config.content.hash_algo, wws_state::HashAlgo::Blake3);
+
+        let defaults: ConnectorConfig = toml::from_str("").unwrap();
+        assert_eq!(defaults.content.hash_algo, wws_state::HashAlgo::Sha256);
+    }
+
+    #[test]
+    fn hierarchy_config_parses_monitor_all_tiers() {
+        let config: HierarchyConfig = toml::from_str("monitor_all_tiers = false\n").unwrap();
+        assert!(!config.monitor_all_tiers);
+
+        let defaults: HierarchyConfig = toml::from_str("").unwrap();
+        assert!(defaults.monitor_all_tiers);
+        assert!(HierarchyConfig::default().monitor_all_tiers);
+    }
+
+    #[test]
+    fn consensus_config_parses_plan_limits() {
+        let config: ConnectorConfig = toml::from_str(
+            "[consensus]\nmax_subtasks_per_plan = 5\nmax_subtask_description_len = 256\n",
+        )
+        .unwrap();
+        assert_eq!(config.consensus.max_subtasks_per_plan, 5);
+        assert_eq!(config.consensus.max_subtask_description_len, 256);
+
+        let defaults: ConnectorConfig = toml::from_str("").unwrap();
+        assert_eq!(
+            defaults.consensus.max_subtasks_per_plan,
+            wws_protocol::DEFAULT_MAX_SUBTASKS_PER_PLAN
+        );
+        assert_eq!(defaults.hierarchy.reveal_grace_secs, wws_protocol::DEFAULT_REVEAL_GRACE_SECS);
+    }
+
+    #[test]
+    fn consensus_config_parses_adaptive_proposers() {
+        let config: ConsensusConfig = toml::from_str(
+            "adaptive_proposers = true\nmax_expected_proposers = 4\nmin_commit_window_secs = 15\n",
+        )
+        .unwrap();
+        assert!(config.adaptive_proposers);
+        assert_eq!(config.max_expected_proposers, 4);
+        assert_eq!(config.min_commit_window_secs, 15);
+
+        let defaults: ConsensusConfig = toml::from_str("").unwrap();
+        assert!(!defaults.adaptive_proposers);
+        assert_eq!(defaults.max_expected_proposers, 10);
+        assert_eq!(defaults.min_commit_window_secs, 0);
+    }
+
+    #[test]
+    fn genesis_leader_accepts_peer_id_or_did() {
+        let peer = PeerId::random();
+        let mut config = ConnectorConfig::default();
+        assert_eq!(config.genesis_leader().unwrap(), None);
+        for entry in [peer.to_string(), format!("did:swarm:{}", peer)] {
+            config.hierarchy.genesis_leader = Some(entry);
+            let leader = config.genesis_leader().unwrap().unwrap();
+            assert_eq!(leader.as_str(), format!("did:swarm:{}", peer));
+        }
+        config.hierarchy.genesis_leader = Some("not-a-peer".into());
+        assert!(config.genesis_leader().is_err());
+    }
+
+    #[test]
+    fn hierarchy_config_parses_adaptive_epochs() {
+        let config: HierarchyConfig = toml::from_str(
+            "adaptive_epochs = true\nmin_epoch_duration_secs = 120\nmax_epoch_duration_secs = 7200\n",
+        )
+        .unwrap();
+        assert!(config.adaptive_epochs);
+        assert_eq!(config.min_epoch_duration_secs, 120);
+        assert_eq!(config.max_epoch_duration_secs, 7200);
+
+        let defaults: HierarchyConfig = toml::from_str("").unwrap();
+        assert!(!defaults.adaptive_epochs);
+        assert_eq!(defaults.min_epoch_duration_secs, 300);
+        assert_eq!(defaults.max_epoch_duration_secs, 14400);
+    }
+
+    #[test]
+    fn hierarchy_config_parses_depth_hysteresis() {
+        let defaults: HierarchyConfig = toml::from_str("").unwrap();
+        assert_eq!(defaults.depth_hysteresis, 0.1);
+        let config: HierarchyConfig = toml::from_str("depth_hysteresis = 0.25\n").unwrap();
+        assert_eq!(config.depth_hysteresis, 0.25);
+    }
+
+    #[test]
+    fn consensus_config_parses_task_retention() {
+        let defaults: ConsensusConfig = toml::from_str("").unwrap();
+        assert_eq!(defaults.task_set_retention_secs, 0);
+        assert_eq!(defaults.task_detail_retention_secs, 0);
+        let config: ConsensusConfig =
+            toml::from_str("task_set_retention_secs = 600\ntask_detail_retention_secs = 86400\n").unwrap();
+        assert_eq!(config.task_set_retention_secs, 600);
+        assert_eq!(config.task_detail_retention_secs, 86400);
+    }
+
+    #[test]
+    fn hierarchy_config_parses_task_ack_timeout() {
+        let defaults: HierarchyConfig = toml::from_str("").unwrap();
+        assert_eq!(defaults.task_ack_timeout_secs, crate::task_ack::DEFAULT_TASK_ACK_TIMEOUT_SECS);
+        let config: HierarchyConfig = toml::from_str("task_ack_timeout_secs = 45\n").unwrap();
+        assert_eq!(config.task_ack_timeout_secs, 45);
+    }
+
+    #[test]
+    fn hierarchy_config_reads_uptime_settings() {
+        let defaults: HierarchyConfig = toml::from_str("").unwrap();
+        assert_eq!(defaults.uptime_window_secs, 3600);
+        assert!(defaults.uptime_file.is_none());
+        let config: HierarchyConfig =
+            toml::from_str("uptime_window_secs = 600\nuptime_file = \"/var/lib/wws/uptime.json\"\n").unwrap();
+        assert_eq!(config.uptime_window_secs, 600);
+        assert_eq!(config.uptime_file, Some(std::path::PathBuf::from("/var/lib/wws/uptime.json")));
+    }
+
+    #[test]
+    fn hierarchy_config_reads_succession_policy() {
+        let defaults: HierarchyConfig = toml::from_str("").unwrap();
+        assert_eq!(defaults.succession_policy, wws_hierarchy::SuccessionPolicy::HighestScore);
+        let config: HierarchyConfig = toml::from_str("succession_policy = \"lowest_latency\"\n").unwrap();
+        assert_eq!(config.succession_policy, wws_hierarchy::SuccessionPolicy::LowestLatency);
+    }
+
+    #[test]
+    fn hierarchy_config_reads_score_weights() {
+        let defaults = ConnectorConfig::default();
+        assert_eq!(defaults.score_weights().unwrap(), wws_protocol::ScoreWeights::default());
+
+        let mut config: ConnectorConfig =
+            toml::from_str("[hierarchy.score_weights]\nproof_of_compute = 0.5\nreputation = 0.3\n").unwrap();
+        let weights = config.score_weights().unwrap();
+        assert_eq!(weights.proof_of_compute, 0.5);
+        assert_eq!(weights.reputation, 0.3);
+        // Unset weights keep their defaults.
+        assert_eq!(weights.uptime, wws_protocol::ScoreWeights::default().uptime);
+
+        config.hierarchy.score_weights.stake = -0.1;
+        assert!(config.score_weights().is_err());
+    }
+
+    #[test]
+    fn consensus_config_parses_result_majority() {
+        let config: ConsensusConfig =
+            toml::from_str("result_redundancy = 3\nresult_majority = 0.66\n").unwrap();
+        assert_eq!(config.result_redundancy, 3);
+        assert_eq!(config.result_majority, 0.66);
+
+        let defaults: ConsensusConfig = toml::from_str("").unwrap();
+        assert_eq!(defaults.result_redundancy, 1);
+        assert_eq!(defaults.result_majority, 0.5);
+    }
+
+    #[test]
+    fn agent_config_parses_max_concurrent_tasks() {
+        let config: AgentConfig = toml::from_str("max_concurrent_tasks = 2\n").unwrap();
+        assert_eq!(config.max_concurrent_tasks, 2);
+
+        let defaults: AgentConfig = toml::from_str("").unwrap();
+        assert_eq!(defaults.max_concurrent_tasks, 8);
+    }
 }
@@ -0,0 +1,326 @@
+//! Resolution of content by CID through a fallback chain of sources.
+//!
+//! `swarm.get_content` with `fetch` set resolves a CID through a
+//! [`ContentResolver`], which tries each source of `content.fetch_chain` in
+//! order and returns the first hit:
+//!
+//! | Source | Where it looks |
+//! |--------|----------------|
+//! | `local` | The local content store |
+//! | `submitter` | A direct request to the peer that submitted a result with this CID |
+//! | `providers` | A direct request to each provider the DHT lists for the CID |
+//! | `dht` | Chunked content records stored in the DHT |
+//!
+//! Content received from a peer or the DHT is used only if it matches its
+//! CID, so a source answering with the wrong bytes just counts as a miss.
+//! Nothing publishes chunk manifests under content store CIDs yet, so `dht`
+//! is left out of the default chain.
+
+use std::fmt;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use wws_network::{NetworkError, PeerId};
+
+use crate::connector::ConnectorState;
+
+/// One step of the content fetch chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentSource {
+    Local,
+    Submitter,
+    Providers,
+    Dht,
+}
+
+/// Chain used when `content.fetch_chain` is not configured.
+pub const DEFAULT_FETCH_CHAIN: [ContentSource; 3] = [
+    ContentSource::Local,
+    ContentSource::Submitter,
+    ContentSource::Providers,
+];
+
+impl ContentSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Local => "local",
+            Self::Submitter => "submitter",
+            Self::Providers => "providers",
+            Self::Dht => "dht",
+        }
+    }
+}
+
+impl fmt::Display for ContentSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ContentSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "local" => Ok(Self::Local),
+            "submitter" => Ok(Self::Submitter),
+            "providers" => Ok(Self::Providers),
+            "dht" => Ok(Self::Dht),
+            other => Err(format!(
+                "unknown content source {other:?} (expected local, submitter, providers or dht)"
+            )),
+        }
+    }
+}
+
+/// Network operations the resolver needs, implemented by
+/// [`wws_network::SwarmHandle`].
+pub trait ContentNetwork: Sync {
+    fn request_content(
+        &self,
+        peer: PeerId,
+        cid: &str,
+    ) -> impl Future<Output = Result<Option<Vec<u8>>, NetworkError>> + Send;
+
+    fn get_providers(&self, cid: &str) -> impl Future<Output = Result<Vec<PeerId>, NetworkError>> + Send;
+
+    fn fetch_content(&self, cid: &str) -> impl Future<Output = Result<Vec<u8>, NetworkError>> + Send;
+}
+
+impl ContentNetwork for wws_network::SwarmHandle {
+    fn request_content(
+        &self,
+        peer: PeerId,
+        cid: &str,
+    ) -> impl Future<Output = Result<Option<Vec<u8>>, NetworkError>> + Send {
+        wws_network::SwarmHandle::request_content(self, peer, cid)
+    }
+
+    fn get_providers(&self, cid: &str) -> impl Future<Output = Result<Vec<PeerId>, NetworkError>> + Send {
+        wws_network::SwarmHandle::get_providers(self, cid)
+    }
+
+    fn fetch_content(&self, cid: &str) -> impl Future<Output = Result<Vec<u8>, NetworkError>> + Send {
+        wws_network::SwarmHandle::fetch_content(self, cid)
+    }
+}
+
+/// Walks the configured fetch chain for a CID.
+pub struct ContentResolver<'a, N> {
+    state: &'a Arc<RwLock<ConnectorState>>,
+    network: &'a N,
+}
+
+impl<'a, N: ContentNetwork> ContentResolver<'a, N> {
+    pub fn new(state: &'a Arc<RwLock<ConnectorState>>, network: &'a N) -> Self {
+        Self { state, network }
+    }
+
+    /// Content stored under `cid` and the source it came from, or `None`
+    /// once every source in the chain missed. Content found remotely is
+    /// kept in the local store.
+    pub async fn fetch(&self, cid: &str) -> Option<(Vec<u8>, ContentSource)> {
+        let (chain, local_peer) = {
+            let state = self.state.read().await;
+            (state.content_fetch_chain.clone(), state.agent_id.as_str().to_string())
+        };
+        for source in chain {
+            let found = match source {
+                ContentSource::Local => self.state.read().await.content_store.get(cid),
+                ContentSource::Submitter => {
+                    let peers = self.state.read().await.submitters_of(cid);
+                    self.request_from(&peers, &local_peer, cid).await
+                }
+                ContentSource::Providers => match self.network.get_providers(cid).await {
+                    Ok(peers) => self.request_from(&peers, &local_peer, cid).await,
+                    Err(e) => {
+                        tracing::debug!(cid, error = %e, "Provider lookup failed");
+                        None
+                    }
+                },
+                ContentSource::Dht => match self.network.fetch_content(cid).await {
+                    // Assembly only checks the manifest's own root CID,
+                    // which need not be the content store's CID.
+                    Ok(data) => {
+                        if self.state.write().await.content_store.put_verified(cid, &data).is_ok() {
+                            Some(data)
+                        } else {
+                            tracing::debug!(cid, "DHT content does not match its CID");
+                            None
+                        }
+                    }
+                    Err(e) => {
+                        tracing::debug!(cid, error = %e, "DHT content fetch failed");
+                        None
+                    }
+                },
+            };
+            if let Some(data) = found {
+                return Some((data, source));
+            }
+        }
+        None
+    }
+
+    /// Ask each of `peers` in turn, skipping this node, and keep the first
+    /// answer that matches `cid`.
+    async fn request_from(&self, peers: &[PeerId], local_peer: &str, cid: &str) -> Option<Vec<u8>> {
+        for peer in peers {
+            if local_peer.ends_with(&peer.to_string()) {
+                continue;
+            }
+            match self.network.request_content(*peer, cid).await {
+                Ok(Some(data)) => {
+                    if self.state.write().await.content_store.put_verified(cid, &data).is_ok() {
+                        return Some(data);
+                    }
+                    tracing::debug!(cid, peer = %peer, "Peer returned content not matching its CID");
+                }
+                Ok(None) => {}
+                Err(e) => tracing::debug!(cid, peer = %peer, error = %e, "Content request failed"),
+            }
+        }
+        None
+    }
+}
+
+impl ConnectorState {
+    /// Peers that submitted a result whose content is `cid`.
+    pub(crate) fn submitters_of(&self, cid: &str) -> Vec<PeerId> {
+        let mut peers: Vec<PeerId> = self
+            .task_results
+            .values()
+            .filter(|artifact| artifact.content_cid == cid)
+            .filter_map(|artifact| {
+                let agent = artifact.producer.as_str();
+                agent.strip_prefix("did:swarm:").unwrap_or(agent).parse().ok()
+            })
+            .collect();
+        peers.sort();
+        peers.dedup();
+        peers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use wws_protocol::{AgentId, Artifact};
+
+    /// Network double that records calls and answers from fixed data.
+    #[derive(Default)]
+    struct MockNetwork {
+        calls: Mutex<Vec<&'static str>>,
+        peer_content: Option<Vec<u8>>,
+        dht_content: Option<Vec<u8>>,
+    }
+
+    impl ContentNetwork for MockNetwork {
+        async fn request_content(&self, _peer: PeerId, _cid: &str) -> Result<Option<Vec<u8>>, NetworkError> {
+            self.calls.lock().unwrap().push("request_content");
+            Ok(self.peer_content.clone())
+        }
+
+        async fn get_providers(&self, _cid: &str) -> Result<Vec<PeerId>, NetworkError> {
+            self.calls.lock().unwrap().push("get_providers");
+            Ok(Vec::new())
+        }
+
+        async fn fetch_content(&self, cid: &str) -> Result<Vec<u8>, NetworkError> {
+            self.calls.lock().unwrap().push("fetch_content");
+            self.dht_content
+                .clone()
+                .ok_or_else(|| NetworkError::DhtError(format!("no record for {cid}")))
+        }
+    }
+
+    fn state_with_result(cid: &str, producer: PeerId) -> Arc<RwLock<ConnectorState>> {
+        let mut state = ConnectorState::new_for_test();
+        let artifact: Artifact = serde_json::from_value(serde_json::json!({
+            "task_id": "task-1",
+            "producer": AgentId::new(format!("did:swarm:{producer}")),
+            "content_cid": cid,
+        }))
+        .unwrap();
+        state.task_results.insert("task-1".into(), artifact);
+        Arc::new(RwLock::new(state))
+    }
+
+    #[tokio::test]
+    async fn local_hit_skips_the_network() {
+        let state = Arc::new(RwLock::new(ConnectorState::new_for_test()));
+        let cid = state.write().await.content_store.store(b"local bytes");
+        let network = MockNetwork::default();
+
+        let found = ContentResolver::new(&state, &network).fetch(&cid).await;
+        assert_eq!(found, Some((b"local bytes".to_vec(), ContentSource::Local)));
+        assert!(network.calls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn local_miss_falls_through_to_the_submitter() {
+        let data = b"remote bytes".to_vec();
+        let cid = ConnectorState::new_for_test().content_store.hash_algo().cid(&data);
+        let state = state_with_result(&cid, PeerId::random());
+        let network = MockNetwork {
+            peer_content: Some(data.clone()),
+            ..Default::default()
+        };
+
+        let found = ContentResolver::new(&state, &network).fetch(&cid).await;
+        assert_eq!(found, Some((data.clone(), ContentSource::Submitter)));
+        assert_eq!(*network.calls.lock().unwrap(), ["request_content"]);
+        assert_eq!(state.read().await.content_store.get(&cid), Some(data));
+    }
+
+    #[tokio::test]
+    async fn mismatching_peer_content_continues_down_the_chain() {
+        let cid = ConnectorState::new_for_test().content_store.hash_algo().cid(b"expected");
+        let state = state_with_result(&cid, PeerId::random());
+        let network = MockNetwork {
+            peer_content: Some(b"forged".to_vec()),
+            ..Default::default()
+        };
+
+        assert_eq!(ContentResolver::new(&state, &network).fetch(&cid).await, None);
+        assert_eq!(*network.calls.lock().unwrap(), ["request_content", "get_providers"]);
+        assert!(state.read().await.content_store.get(&cid).is_none());
+    }
+
+    #[tokio::test]
+    async fn mismatching_dht_content_is_a_miss() {
+        let state = Arc::new(RwLock::new(ConnectorState::new_for_test()));
+        let cid = {
+            let mut state = state.write().await;
+            state.content_fetch_chain = vec![ContentSource::Dht];
+            state.content_store.hash_algo().cid(b"expected")
+        };
+        let network = MockNetwork {
+            dht_content: Some(b"forged".to_vec()),
+            ..Default::default()
+        };
+
+        assert_eq!(ContentResolver::new(&state, &network).fetch(&cid).await, None);
+        assert!(state.read().await.content_store.get(&cid).is_none());
+    }
+
+    #[tokio::test]
+    async fn chain_order_is_configurable() {
+        let state = Arc::new(RwLock::new(ConnectorState::new_for_test()));
+        let cid = {
+            let mut state = state.write().await;
+            state.content_fetch_chain = vec![ContentSource::Dht, ContentSource::Local];
+            state.content_store.store(b"local bytes")
+        };
+        let network = MockNetwork::default();
+
+        let found = ContentResolver::new(&state, &network).fetch(&cid).await;
+        assert_eq!(found.map(|(_, source)| source), Some(ContentSource::Local));
+        assert_eq!(*network.calls.lock().unwrap(), ["fetch_content"]);
+    }
+}
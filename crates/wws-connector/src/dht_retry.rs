@@ -0,0 +1,213 @@
+//! Retry queue for DHT record puts that fail to reach the network.
+//!
+//! A Kademlia put can fail locally (no routing table entries) or once its
+//! query finishes without meeting the quorum. Either way the record, such as
+//! a swarm registry entry from `announce_swarm`, would otherwise never be
+//! published. [`DhtPutRetryQueue`] remembers every put until the network
+//! confirms it and re-issues failed ones with jittered exponential backoff,
+//! giving up after a fixed number of attempts.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+/// Delay before the first retry of a failed put.
+pub const DEFAULT_DHT_RETRY_BASE: Duration = Duration::from_secs(2);
+
+/// Upper bound on the delay between retries.
+pub const DEFAULT_DHT_RETRY_MAX_DELAY: Duration = Duration::from_secs(120);
+
+/// Failed attempts after which a put is dropped.
+pub const DEFAULT_DHT_RETRY_MAX_ATTEMPTS: u32 = 8;
+
+/// Delay before retry number `attempt` (1-based): `base * 2^(attempt-1)`
+/// capped at `max`, of which the upper half is scaled by `jitter` in
+/// `[0, 1]`. Keeping half fixed guarantees retries still back off, while
+/// the jitter spreads out nodes that failed at the same moment.
+pub fn jittered_backoff(base: Duration, max: Duration, attempt: u32, jitter: f64) -> Duration {
+    let exp = base
+        .saturating_mul(1u32.checked_shl(attempt.saturating_sub(1)).unwrap_or(u32::MAX))
+        .min(max);
+    exp / 2 + (exp / 2).mul_f64(jitter.clamp(0.0, 1.0))
+}
+
+#[derive(Debug, Clone)]
+struct PendingPut {
+    value: Vec<u8>,
+    /// Failed attempts so far.
+    attempts: u32,
+    /// When to retry; `None` while a put is in flight.
+    next_attempt: Option<Instant>,
+    last_error: Option<String>,
+}
+
+/// Status of one tracked put, as reported over RPC.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DhtPutStatus {
+    pub key: String,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    /// Seconds until the next retry, or `None` while awaiting the outcome
+    /// of the current attempt.
+    pub retry_in_secs: Option<u64>,
+}
+
+/// DHT puts awaiting confirmation, keyed by record key.
+#[derive(Debug, Clone)]
+pub struct DhtPutRetryQueue {
+    base: Duration,
+    max_delay: Duration,
+    max_attempts: u32,
+    puts: BTreeMap<Vec<u8>, PendingPut>,
+}
+
+impl DhtPutRetryQueue {
+    pub fn new(base: Duration, max_delay: Duration, max_attempts: u32) -> Self {
+        Self {
+            base,
+            max_delay,
+            max_attempts: max_attempts.max(1),
+            puts: BTreeMap::new(),
+        }
+    }
+
+    /// Track a put that was handed to the DHT. A newer value for the same
+    /// key replaces the old one and resets its attempt count.
+    pub fn sent(&mut self, key: Vec<u8>, value: Vec<u8>) {
+        self.puts.insert(
+            key,
+            PendingPut {
+                value,
+                attempts: 0,
+                next_attempt: None,
+                last_error: None,
+            },
+        );
+    }
+
+    /// The network confirmed the put for `key`.
+    pub fn confirmed(&mut self, key: &[u8]) {
+        self.puts.remove(key);
+    }
+
+    /// The put for `key` failed. Schedules a retry using `jitter` in
+    /// `[0, 1]`, or drops the put and returns `false` once it has failed
+    /// `max_attempts` times. Untracked keys are ignored.
+    pub fn failed(&mut self, key: &[u8], error: impl Into<String>, now: Instant, jitter: f64) -> bool {
+        let Some(put) = self.puts.get_mut(key) else {
+            return true;
+        };
+        put.attempts += 1;
+        put.last_error = Some(error.into());
+        if put.attempts >= self.max_attempts {
+            self.puts.remove(key);
+            return false;
+        }
+        put.next_attempt =
+            Some(now + jittered_backoff(self.base, self.max_delay, put.attempts, jitter));
+        true
+    }
+
+    /// Puts whose retry is due, marked in flight again.
+    pub fn take_due(&mut self, now: Instant) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let mut due = Vec::new();
+        for (key, put) in self.puts.iter_mut() {
+            if put.next_attempt.is_some_and(|at| at <= now) {
+                put.next_attempt = None;
+                due.push((key.clone(), put.value.clone()));
+            }
+        }
+        due
+    }
+
+    pub fn status(&self, now: Instant) -> Vec<DhtPutStatus> {
+        self.puts
+            .iter()
+            .map(|(key, put)| DhtPutStatus {
+                key: String::from_utf8_lossy(key).into_owned(),
+                attempts: put.attempts,
+                last_error: put.last_error.clone(),
+                retry_in_secs: put
+                    .next_attempt
+                    .map(|at| at.saturating_duration_since(now).as_secs()),
+            })
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.puts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.puts.is_empty()
+    }
+}
+
+impl Default for DhtPutRetryQueue {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_DHT_RETRY_BASE,
+            DEFAULT_DHT_RETRY_MAX_DELAY,
+            DEFAULT_DHT_RETRY_MAX_ATTEMPTS,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: Duration = Duration::from_secs(2);
+    const MAX: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn backoff_doubles_within_jitter_bounds_and_caps() {
+        for attempt in 1..=10u32 {
+            let exp = (BASE * 2u32.pow(attempt - 1)).min(MAX);
+            assert_eq!(jittered_backoff(BASE, MAX, attempt, 0.0), exp / 2);
+            assert_eq!(jittered_backoff(BASE, MAX, attempt, 1.0), exp);
+            let mid = jittered_backoff(BASE, MAX, attempt, 0.5);
+            assert!(mid > exp / 2 && mid < exp);
+        }
+        assert_eq!(jittered_backoff(BASE, MAX, 40, 1.0), MAX);
+    }
+
+    #[test]
+    fn failed_put_is_retried_after_backoff() {
+        let mut queue = DhtPutRetryQueue::new(BASE, MAX, 3);
+        let t0 = Instant::now();
+        queue.sent(b"swarm:abc".to_vec(), b"v1".to_vec());
+        // In flight: nothing to retry yet.
+        assert!(queue.take_due(t0 + MAX).is_empty());
+
+        assert!(queue.failed(b"swarm:abc", "quorum failed", t0, 1.0));
+        assert!(queue.take_due(t0 + BASE / 2).is_empty());
+        assert_eq!(
+            queue.take_due(t0 + BASE),
+            vec![(b"swarm:abc".to_vec(), b"v1".to_vec())]
+        );
+        // Back in flight after being taken.
+        assert!(queue.take_due(t0 + MAX).is_empty());
+
+        let status = queue.status(t0);
+        assert_eq!(status[0].key, "swarm:abc");
+        assert_eq!(status[0].attempts, 1);
+        assert_eq!(status[0].last_error.as_deref(), Some("quorum failed"));
+
+        queue.confirmed(b"swarm:abc");
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn put_is_dropped_after_max_attempts() {
+        let mut queue = DhtPutRetryQueue::new(BASE, MAX, 2);
+        let t0 = Instant::now();
+        queue.sent(b"k".to_vec(), b"v".to_vec());
+        assert!(queue.failed(b"k", "no peers", t0, 0.0));
+        assert!(!queue.failed(b"k", "no peers", t0, 0.0));
+        assert!(queue.is_empty());
+        // Failures for untracked keys are ignored.
+        assert!(queue.failed(b"other", "no peers", t0, 0.0));
+    }
+}
@@ -0,0 +1,289 @@
+//! Append-only on-disk copy of the connector event log.
+//!
+//! The TUI and operator console read the bounded in-memory window in
+//! [`crate::connector::ConnectorState::event_log`]. When
+//! `logging.event_log_path` is set, every entry is also appended to that
+//! file as one JSON line, so the history survives a crash or restart. Once
+//! the file reaches `logging.event_log_max_bytes` it is renamed to
+//! `<path>.1` (replacing any previous rotation) and a fresh file is started.
+//!
+//! Entries are logged while the connector state is locked, so the file is
+//! written by [`EventLogWriter`] on its own thread rather than inline.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+
+use crate::tui::LogEntry;
+
+/// Default number of entries kept in memory.
+pub const DEFAULT_EVENT_LOG_CAPACITY: usize = 1000;
+
+/// Default size at which the on-disk log is rotated (10 MiB).
+pub const DEFAULT_EVENT_LOG_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Entries buffered per `swarm.tail_logs` subscriber before the slowest
+/// one starts missing entries.
+pub const EVENT_LOG_STREAM_CAPACITY: usize = 256;
+
+/// JSON-lines event log file with single-generation size rotation.
+#[derive(Debug)]
+pub struct EventLogFile {
+    path: PathBuf,
+    max_bytes: u64,
+    file: File,
+    len: u64,
+}
+
+impl EventLogFile {
+    /// Open (or create) the log at `path` for appending.
+    pub fn open(path: impl Into<PathBuf>, max_bytes: u64) -> std::io::Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let mut len = file.metadata()?.len();
+        // Terminate a line left half-written by a crash so the next entry
+        // starts on its own line.
+        if len > 0 && !ends_with_newline(&path)? {
+            file.write_all(b"\n")?;
+            len += 1;
+        }
+        Ok(Self {
+            path,
+            max_bytes: max_bytes.max(1),
+            file,
+            len,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Path the log is rotated to.
+    pub fn rotated_path(path: &Path) -> PathBuf {
+        let mut rotated = path.as_os_str().to_owned();
+        rotated.push(".1");
+        PathBuf::from(rotated)
+    }
+
+    /// Append one entry as a JSON line, rotating first if the file is full.
+    pub fn append(&mut self, entry: &LogEntry) -> std::io::Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        if self.len > 0 && self.len + line.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        self.file.write_all(&line)?;
+        self.len += line.len() as u64;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        std::fs::rename(&self.path, Self::rotated_path(&self.path))?;
+        self.file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        self.len = 0;
+        Ok(())
+    }
+
+    /// Read the newest `capacity` entries from the rotated and current
+    /// files, oldest first. Unreadable files and malformed lines (e.g. a
+    /// line cut short by a crash) are skipped.
+    pub fn load_recent(path: &Path, capacity: usize) -> Vec<LogEntry> {
+        let mut entries = Vec::new();
+        for source in [Self::rotated_path(path), path.to_path_buf()] {
+            let Ok(file) = File::open(&source) else {
+                continue;
+            };
+            for line in BufReader::new(file).lines().map_while(Result::ok) {
+                if let Ok(entry) = serde_json::from_str::<LogEntry>(&line) {
+                    entries.push(entry);
+                }
+            }
+        }
+        let excess = entries.len().saturating_sub(capacity);
+        entries.drain(..excess);
+        entries
+    }
+}
+
+/// Work queued for the writer thread.
+#[derive(Debug)]
+enum WriterCommand {
+    Append(LogEntry),
+    Flush(mpsc::Sender<()>),
+}
+
+/// [`EventLogFile`] appended to from a dedicated thread, so logging never
+/// blocks on disk I/O. Dropping the writer waits for queued entries.
+#[derive(Debug)]
+pub struct EventLogWriter {
+    path: PathBuf,
+    tx: Option<mpsc::Sender<WriterCommand>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl EventLogWriter {
+    /// Move `file` onto a writer thread.
+    pub fn spawn(mut file: EventLogFile) -> std::io::Result<Self> {
+        let path = file.path().to_path_buf();
+        let (tx, rx) = mpsc::channel();
+        let thread = std::thread::Builder::new()
+            .name("event-log".into())
+            .spawn(move || {
+                for command in rx {
+                    match command {
+                        WriterCommand::Append(entry) => {
+                            if let Err(e) = file.append(&entry) {
+                                tracing::warn!(path = %file.path().display(), error = %e, "Failed to persist event log entry");
+                            }
+                        }
+                        WriterCommand::Flush(done) => {
+                            let _ = done.send(());
+                        }
+                    }
+                }
+            })?;
+        Ok(Self {
+            path,
+            tx: Some(tx),
+            thread: Some(thread),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Queue `entry` for appending.
+    pub fn append(&self, entry: LogEntry) {
+        if let Some(tx) = &self.tx {
+            let _ = tx.send(WriterCommand::Append(entry));
+        }
+    }
+
+    /// Block until every entry queued so far has been written.
+    pub fn flush(&self) {
+        let (done_tx, done_rx) = mpsc::channel();
+        if let Some(tx) = &self.tx {
+            if tx.send(WriterCommand::Flush(done_tx)).is_ok() {
+                let _ = done_rx.recv();
+            }
+        }
+    }
+}
+
+impl Drop for EventLogWriter {
+    fn drop(&mut self) {
+        self.tx.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn ends_with_newline(path: &Path) -> std::io::Result<bool> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::End(-1))?;
+    let mut last = [0u8; 1];
+    file.read_exact(&mut last)?;
+    Ok(last[0] == b'\n')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::LogCategory;
+
+    fn entry(message: &str) -> LogEntry {
+        LogEntry {
+            timestamp: chrono::Utc::now(),
+            category: LogCategory::System,
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn appended_entries_reload_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs").join("events.jsonl");
+        let mut log = EventLogFile::open(&path, DEFAULT_EVENT_LOG_MAX_BYTES).unwrap();
+        for i in 0..5 {
+            log.append(&entry(&format!("event {i}"))).unwrap();
+        }
+        drop(log);
+
+        let loaded = EventLogFile::load_recent(&path, 3);
+        let messages: Vec<_> = loaded.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, vec!["event 2", "event 3", "event 4"]);
+    }
+
+    #[test]
+    fn rotation_keeps_one_previous_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs").join("events.jsonl");
+        let line_len = serde_json::to_vec(&entry("event 0")).unwrap().len() as u64 + 1;
+        // Room for two lines per file.
+        let mut log = EventLogFile::open(&path, line_len * 2).unwrap();
+        for i in 0..5 {
+            log.append(&entry(&format!("event {i}"))).unwrap();
+        }
+        drop(log);
+
+        let rotated = std::fs::read_to_string(EventLogFile::rotated_path(&path)).unwrap();
+        assert_eq!(rotated.lines().count(), 2);
+        let current = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(current.lines().count(), 1);
+
+        // Entries from the rotated file are still reloaded, oldest first.
+        let loaded = EventLogFile::load_recent(&path, 10);
+        let messages: Vec<_> = loaded.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, vec!["event 2", "event 3", "event 4"]);
+    }
+
+    #[test]
+    fn truncated_line_is_skipped_on_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs").join("events.jsonl");
+        let mut log = EventLogFile::open(&path, DEFAULT_EVENT_LOG_MAX_BYTES).unwrap();
+        log.append(&entry("complete")).unwrap();
+        drop(log);
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"timestamp\":\"2026-").unwrap();
+        drop(file);
+
+        // Reopening after the crash keeps later entries readable.
+        let mut log = EventLogFile::open(&path, DEFAULT_EVENT_LOG_MAX_BYTES).unwrap();
+        log.append(&entry("after restart")).unwrap();
+        let loaded = EventLogFile::load_recent(&path, 10);
+        let messages: Vec<_> = loaded.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, vec!["complete", "after restart"]);
+    }
+
+    #[test]
+    fn writer_thread_appends_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+        let writer =
+            EventLogWriter::spawn(EventLogFile::open(&path, DEFAULT_EVENT_LOG_MAX_BYTES).unwrap())
+                .unwrap();
+        writer.append(entry("first"));
+        writer.append(entry("second"));
+        writer.flush();
+        let loaded = EventLogFile::load_recent(&path, 10);
+        assert_eq!(loaded.len(), 2);
+
+        // Dropping waits for entries still queued.
+        writer.append(entry("third"));
+        drop(writer);
+        let loaded = EventLogFile::load_recent(&path, 10);
+        let messages: Vec<_> = loaded.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, vec!["first", "second", "third"]);
+    }
+}
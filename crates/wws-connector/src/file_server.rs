@@ -17,7 +17,7 @@ use serde::Deserialize;
 use tokio::sync::RwLock;
 use tower_http::services::ServeDir;
 
-use wws_protocol::Tier;
+use wws_protocol::{RpcErrorCode, Tier};
 
 use crate::connector::{ConnectorState, MessageTraceEvent};
 
@@ -40,6 +40,7 @@ struct WebState {
     state: Arc<RwLock<ConnectorState>>,
     network_handle: wws_network::SwarmHandle,
     web_root: PathBuf,
+    webhook_token: Option<String>,
 }
 
 pub struct FileServer {
@@ -47,6 +48,7 @@ pub struct FileServer {
     state: Arc<RwLock<ConnectorState>>,
     network_handle: wws_network::SwarmHandle,
     web_root: PathBuf,
+    webhook_token: Option<String>,
 }
 
 impl FileServer {
@@ -60,9 +62,18 @@ impl FileServer {
             state,
             network_handle,
             web_root: detect_web_root(),
+            webhook_token: None,
         }
     }
 
+    /// Serve `POST /tasks` for CI systems and issue trackers, authenticated
+    /// with `Authorization: Bearer <token>`. Without a token the route is
+    /// not mounted.
+    pub fn with_webhook_token(mut self, token: Option<String>) -> Self {
+        self.webhook_token = token.filter(|t| !t.trim().is_empty());
+        self
+    }
+
     pub async fn run(self) -> Result<(), anyhow::Error> {
         let web_root = self.web_root.clone();
 
@@ -70,7 +81,9 @@ impl FileServer {
             state: self.state,
             network_handle: self.network_handle,
             web_root: web_root.clone(),
+            webhook_token: self.webhook_token,
         };
+        let webhook_enabled = web_state.webhook_token.is_some();
 
         // Serve static assets from /assets/* directly; everything else falls
         // through to the SPA index handler which returns index.html with 200.
@@ -78,12 +91,13 @@ impl FileServer {
         // not_found_service serves the file but still sets status 404.
         let assets_service = ServeDir::new(web_root.join("assets"));
 
-        let app = Router::new()
+        let mut app = Router::new()
             .route("/SKILL.md", get(skill_md))
             .route("/HEARTBEAT.md", get(heartbeat_md))
             .route("/MESSAGING.md", get(messaging_md))
             .route("/agent-onboarding.json", get(onboarding))
             .route("/api/health", get(api_health))
+            .route("/healthz", get(healthz))
             .route("/api/auth-status", get(api_auth_status))
             .route("/api/hierarchy", get(api_hierarchy))
             .route("/api/voting", get(api_voting))
@@ -117,7 +131,11 @@ impl FileServer {
             .route("/api/receipts/:receipt_id", get(api_receipt_detail))
             .route("/api/clarifications", get(api_clarifications))
             .route("/api/events", get(api_events))
-            .route("/api/peers/announce", post(api_peers_announce))
+            .route("/api/peers/announce", post(api_peers_announce));
+        if webhook_enabled {
+            app = app.route("/tasks", post(webhook_submit_task));
+        }
+        let app = app
             .nest_service("/assets", assets_service)
             .fallback(spa_index)
             .with_state(web_state);
@@ -208,9 +226,12 @@ async fn onboarding() -> Json<serde_json::Value> {
         "dashboard": "/",
         "methods": [
             "swarm.get_status",
+            "swarm.ping",
+            "swarm.export_state",
             "swarm.register_agent",
             "swarm.receive_task",
             "swarm.get_task",
+            "swarm.get_content",
             "swarm.get_task_timeline",
             "swarm.propose_plan",
             "swarm.submit_vote",
@@ -240,6 +261,17 @@ async fn api_health() -> Json<serde_json::Value> {
     Json(serde_json::json!({"ok": true, "service": "wws-connector", "version": env!("CARGO_PKG_VERSION")}))
 }
 
+/// Readiness probe for container orchestrators: 200 once ready, 503 before.
+async fn healthz(State(web): State<WebState>) -> impl IntoResponse {
+    let s = web.state.read().await;
+    let code = if s.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (code, Json(s.health_report()))
+}
+
 async fn api_auth_status() -> Json<serde_json::Value> {
     let token_required = std::env::var("OPENSWARM_WEB_TOKEN")
         .ok()
@@ -620,6 +652,89 @@ async fn api_submit_task(
     (StatusCode::OK, Json(result))
 }
 
+#[derive(Deserialize)]
+struct WebhookTaskRequest {
+    description: String,
+    /// Fan-out tier, as for `swarm.inject_task`.
+    #[serde(default)]
+    tier: Option<u32>,
+    /// `low`, `normal` or `high`, as for `swarm.inject_task`.
+    #[serde(default)]
+    priority: Option<String>,
+}
+
+/// Compare two secrets in time that depends only on their lengths, so a
+/// caller cannot guess a token byte by byte from response times.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// `POST /tasks`: inject a task from an external system. The task enters
+/// through `swarm.inject_task` with this node as injector.
+async fn webhook_submit_task(
+    State(web): State<WebState>,
+    headers: HeaderMap,
+    Json(req): Json<WebhookTaskRequest>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let Some(expected) = web.webhook_token.as_deref() else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"ok": false, "error": "webhook_disabled"})),
+        );
+    };
+    let provided = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim);
+    if !provided.is_some_and(|token| constant_time_eq(token.as_bytes(), expected.as_bytes())) {
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({"ok": false, "error": "invalid_webhook_token"})),
+        );
+    }
+
+    let injector_agent_id = {
+        let s = web.state.read().await;
+        if let Err(failure) = crate::rpc_server::check_ready(&s, "POST /tasks") {
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"ok": false, "error": failure.message})),
+            );
+        }
+        s.agent_id.to_string()
+    };
+    let params = serde_json::json!({
+        "description": req.description,
+        "tier": req.tier,
+        "priority": req.priority,
+        "injector_agent_id": injector_agent_id,
+    });
+    let response = crate::rpc_server::handle_inject_task(
+        Some("webhook-task".to_string()),
+        &params,
+        &web.state,
+        &web.network_handle,
+    )
+    .await;
+
+    if let Some(err) = response.error {
+        let status = match RpcErrorCode::from_code(err.code) {
+            Some(RpcErrorCode::RateLimited) => StatusCode::TOO_MANY_REQUESTS,
+            _ => StatusCode::BAD_REQUEST,
+        };
+        return (status, Json(serde_json::json!({"ok": false, "error": err.message})));
+    }
+
+    let task_id = response
+        .result
+        .as_ref()
+        .and_then(|r| r.get("task_id"))
+        .cloned()
+        .unwrap_or(serde_json::Value::Null);
+    (StatusCode::OK, Json(serde_json::json!({"ok": true, "task_id": task_id})))
+}
+
 async fn api_task_timeline(
     State(web): State<WebState>,
     AxumPath(task_id): AxumPath<String>,
@@ -1472,3 +1587,88 @@ async fn api_events(
     });
     Sse::new(stream).keep_alive(KeepAlive::default())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::header::AUTHORIZATION;
+
+    fn webhook_state(token: &str) -> WebState {
+        use wws_network::{SwarmHost, SwarmHostConfig};
+        let config = SwarmHostConfig {
+            listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+            ..Default::default()
+        };
+        let (_host, network_handle, _rx) = SwarmHost::new(config).expect("test SwarmHost");
+        WebState {
+            state: Arc::new(RwLock::new(ConnectorState::new_for_test())),
+            network_handle,
+            web_root: PathBuf::new(),
+            webhook_token: Some(token.to_string()),
+        }
+    }
+
+    fn webhook_request(description: &str) -> Json<WebhookTaskRequest> {
+        Json(WebhookTaskRequest {
+            description: description.to_string(),
+            tier: Some(2),
+            priority: Some("high".to_string()),
+        })
+    }
+
+    #[tokio::test]
+    async fn webhook_post_with_bearer_token_injects_task() {
+        let web = webhook_state("ci-secret");
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, "Bearer ci-secret".parse().unwrap());
+
+        let (status, Json(body)) = webhook_submit_task(
+            State(web.clone()),
+            headers,
+            webhook_request("Triage issue #42"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        let task_id = body["task_id"].as_str().expect("task id returned");
+
+        let s = web.state.read().await;
+        let task = s.task_details.get(task_id).expect("task stored");
+        assert_eq!(task.description, "Triage issue #42");
+        assert_eq!(task.tier_level, 2);
+        assert_eq!(task.priority, wws_protocol::TaskPriority::High);
+    }
+
+    #[tokio::test]
+    async fn webhook_post_waits_for_readiness() {
+        let web = webhook_state("ci-secret");
+        web.state.write().await.status = crate::connector::ConnectorStatus::Initializing;
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, "Bearer ci-secret".parse().unwrap());
+
+        let (status, _) =
+            webhook_submit_task(State(web.clone()), headers, webhook_request("Too early")).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(web.state.read().await.task_details.is_empty());
+    }
+
+    #[tokio::test]
+    async fn webhook_post_without_valid_token_is_rejected() {
+        let web = webhook_state("ci-secret");
+        let mut wrong = HeaderMap::new();
+        wrong.insert(AUTHORIZATION, "Bearer guess".parse().unwrap());
+        let mut prefix = HeaderMap::new();
+        prefix.insert(AUTHORIZATION, "Bearer ci-secre".parse().unwrap());
+
+        for headers in [HeaderMap::new(), wrong, prefix] {
+            let (status, Json(body)) = webhook_submit_task(
+                State(web.clone()),
+                headers,
+                webhook_request("Should not run"),
+            )
+            .await;
+            assert_eq!(status, StatusCode::UNAUTHORIZED);
+            assert_eq!(body["error"], "invalid_webhook_token");
+        }
+        assert!(web.state.read().await.task_details.is_empty());
+    }
+}
@@ -11,15 +11,30 @@
 //! to interact with the swarm.
 
 pub mod agent_bridge;
+pub mod artifact_gc;
 pub mod auth;
 pub mod config;
 pub mod connector;
+pub mod content_resolver;
+pub mod dht_retry;
+pub mod event_log;
 pub mod file_server;
 pub mod identity_store;
+pub mod membership;
 pub mod operator_console;
+pub mod peer_flap;
+pub mod peer_signature;
 pub mod reputation;
+pub mod result_aggregation;
 pub mod rpc_server;
+pub mod state_batch;
+pub mod swarm_crypto;
+pub mod task_ack;
+pub mod task_gc;
+pub mod task_template;
+pub mod topology;
 pub mod tui;
+pub mod uptime;
 
 pub use config::ConnectorConfig;
 pub use connector::WwsConnector;
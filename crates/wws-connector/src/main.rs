@@ -5,7 +5,7 @@
 //!
 //! Options:
 //!   -c, --config <FILE>    Path to configuration TOML file
-//!   -l, --listen <ADDR>    P2P listen address (overrides config)
+//!   -l, --listen <ADDR>    P2P listen address (repeatable, overrides config)
 //!   -r, --rpc <ADDR>       RPC bind address (overrides config)
 //!   -b, --bootstrap <ADDR> Bootstrap peer multiaddress (repeatable)
 //!   -v, --verbose          Increase logging verbosity
@@ -18,7 +18,7 @@ use std::path::PathBuf;
 use clap::Parser;
 
 use wws_connector::config::ConnectorConfig;
-use wws_connector::connector::WwsConnector;
+use wws_connector::connector::{WwsConnector, DEFAULT_SHUTDOWN_TIMEOUT};
 use wws_connector::file_server::FileServer;
 use wws_connector::rpc_server::RpcServer;
 
@@ -32,9 +32,10 @@ struct Cli {
     #[arg(short, long, value_name = "FILE")]
     config: Option<PathBuf>,
 
-    /// P2P listen address (e.g., /ip4/0.0.0.0/tcp/9000).
+    /// P2P listen address (e.g., /ip4/0.0.0.0/tcp/9000). Repeat to listen
+    /// on several addresses, e.g. IPv4 and IPv6.
     #[arg(short, long, value_name = "MULTIADDR")]
-    listen: Option<String>,
+    listen: Vec<String>,
 
     /// JSON-RPC server bind address (e.g., 127.0.0.1:9370).
     #[arg(short, long, value_name = "ADDR")]
@@ -101,8 +102,10 @@ async fn main() -> anyhow::Result<()> {
     let mut config = ConnectorConfig::load(cli.config.as_deref())?;
 
     // Apply CLI overrides.
-    if let Some(listen) = cli.listen {
-        config.network.listen_addr = listen;
+    let mut listen = cli.listen.into_iter();
+    if let Some(first) = listen.next() {
+        config.network.listen_addr = first;
+        config.network.extra_listen_addrs = listen.collect();
     }
     if let Some(rpc) = cli.rpc {
         config.rpc.bind_addr = rpc;
@@ -180,7 +183,7 @@ async fn main() -> anyhow::Result<()> {
 
     tracing::info!(
         agent = %config.agent.name,
-        listen = %config.network.listen_addr,
+        listen = ?config.network.all_listen_addrs().collect::<Vec<_>>(),
         rpc = %config.rpc.bind_addr,
         swarm_id = %config.swarm.swarm_id,
         swarm_name = %config.swarm.name,
@@ -214,6 +217,7 @@ async fn main() -> anyhow::Result<()> {
 
     // Create the connector with persistent identity.
     let connector = WwsConnector::new(config.clone(), Some(libp2p_keypair))?;
+    let shutdown = connector.shutdown_handle();
 
     // Get handles for the RPC server.
     let state = connector.shared_state();
@@ -227,9 +231,15 @@ async fn main() -> anyhow::Result<()> {
         config.rpc.max_connections,
     );
 
+    let rpc_token = shutdown.token();
     tokio::spawn(async move {
-        if let Err(e) = rpc_server.run().await {
-            tracing::error!(error = %e, "RPC server error");
+        tokio::select! {
+            result = rpc_server.run() => {
+                if let Err(e) = result {
+                    tracing::error!(error = %e, "RPC server error");
+                }
+            }
+            _ = rpc_token.cancelled() => {}
         }
     });
 
@@ -239,19 +249,26 @@ async fn main() -> anyhow::Result<()> {
             config.file_server.bind_addr.clone(),
             state.clone(),
             connector.network_handle(),
-        );
+        )
+        .with_webhook_token(config.file_server.webhook_token.clone());
+        let file_server_token = shutdown.token();
         tokio::spawn(async move {
-            if let Err(e) = file_server.run().await {
-                tracing::error!(error = %e, "HTTP file server error");
+            tokio::select! {
+                result = file_server.run() => {
+                    if let Err(e) = result {
+                        tracing::error!(error = %e, "HTTP file server error");
+                    }
+                }
+                _ = file_server_token.cancelled() => {}
             }
         });
     }
 
-    if cli.console {
+    let ui_handle = if cli.console {
         // Launch the operator console.
         let console_state = state.clone();
         let console_network_handle = connector.network_handle();
-        let console_handle = tokio::spawn(async move {
+        Some(tokio::spawn(async move {
             if let Err(e) =
                 wws_connector::operator_console::run_operator_console(
                     console_state,
@@ -265,24 +282,17 @@ async fn main() -> anyhow::Result<()> {
                         "Console mode disabled: {}. Continuing in headless mode.",
                         err_msg
                     );
+                    // Keep running headless until a signal arrives.
+                    std::future::pending::<()>().await;
                 } else {
                     tracing::error!(error = %e, "Operator console error");
                 }
             }
-        });
-
-        tokio::select! {
-            result = connector.run() => {
-                result?;
-            }
-            _ = console_handle => {
-                // Console exited, shutting down.
-            }
-        }
+        }))
     } else if cli.tui {
         // Spawn the TUI in a separate task.
         let tui_state = state.clone();
-        let tui_handle = tokio::spawn(async move {
+        Some(tokio::spawn(async move {
             if let Err(e) = wws_connector::tui::run_tui(tui_state).await {
                 let err_msg = e.to_string();
                 if err_msg.contains("TTY") || err_msg.contains("terminal") {
@@ -290,24 +300,73 @@ async fn main() -> anyhow::Result<()> {
                         "TUI mode disabled: {}. Continuing in non-TUI mode.",
                         err_msg
                     );
+                    std::future::pending::<()>().await;
                 } else {
                     tracing::error!(error = %e, "TUI error");
                 }
             }
-        });
-
-        tokio::select! {
-            result = connector.run() => {
-                result?;
-            }
-            _ = tui_handle => {
-                // TUI exited (user pressed 'q'), shutting down.
+        }))
+    } else {
+        None
+    };
+    let ui_exited = async {
+        match ui_handle {
+            // Console or TUI exited (e.g. the user pressed 'q').
+            Some(handle) => {
+                let _ = handle.await;
             }
+            None => std::future::pending().await,
         }
-    } else {
-        // Run the connector (this blocks until shutdown).
-        connector.run().await?;
+    };
+
+    // Run the connector until it fails, a signal arrives or the UI exits,
+    // then shut every subsystem down in order.
+    let run = connector.run();
+    tokio::pin!(run);
+    tokio::select! {
+        result = &mut run => return result,
+        _ = shutdown_signal() => tracing::info!("Shutdown signal received"),
+        _ = ui_exited => {}
+    }
+    let (stopped, result) = tokio::join!(
+        shutdown.shutdown(DEFAULT_SHUTDOWN_TIMEOUT),
+        tokio::time::timeout(DEFAULT_SHUTDOWN_TIMEOUT, &mut run),
+    );
+    if !stopped {
+        tracing::warn!("Connector did not stop within the shutdown timeout");
+    }
+    if let Ok(result) = result {
+        result?;
     }
 
     Ok(())
 }
+
+/// Resolve on SIGINT (Ctrl-C) or, on Unix, SIGTERM.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::warn!(error = %e, "Failed to listen for Ctrl-C");
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
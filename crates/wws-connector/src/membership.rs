@@ -0,0 +1,130 @@
+//! Membership in additional swarms alongside the primary one.
+//!
+//! A connector always belongs to its primary swarm
+//! ([`crate::connector::ConnectorState::current_swarm_id`]), whose tasks
+//! live in `ConnectorState::task_set`. [`SwarmMemberships`] tracks any
+//! further swarms it has joined, e.g. a private work swarm next to the
+//! public monitoring swarm. Each keeps its own task set, so a task injected
+//! into one swarm never shows up in another's.
+
+use std::collections::BTreeMap;
+
+use wws_protocol::{SwarmId, SwarmToken};
+use wws_state::OrSet;
+
+/// One additional swarm this connector participates in.
+#[derive(Debug, Clone)]
+pub struct SwarmMembership {
+    pub swarm_id: SwarmId,
+    /// Token the swarm was joined with; keys its payload encryption when
+    /// the swarm is private.
+    pub token: Option<SwarmToken>,
+    /// Nonce of our latest `swarm.join` for this swarm, which a member
+    /// rejecting it must echo.
+    pub join_nonce: Option<String>,
+    /// Tasks injected into or received on this swarm's topics.
+    pub task_set: OrSet<String>,
+    pub joined_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Additional swarm memberships, keyed by swarm ID.
+#[derive(Debug, Clone)]
+pub struct SwarmMemberships {
+    /// Node ID used to tag entries in each membership's task set.
+    node_id: String,
+    swarms: BTreeMap<String, SwarmMembership>,
+}
+
+impl SwarmMemberships {
+    pub fn new(node_id: impl Into<String>) -> Self {
+        Self {
+            node_id: node_id.into(),
+            swarms: BTreeMap::new(),
+        }
+    }
+
+    /// Start participating in `swarm_id`, joined with `token`. Returns
+    /// `false` if already a member.
+    pub fn join(&mut self, swarm_id: SwarmId, token: Option<SwarmToken>) -> bool {
+        if self.swarms.contains_key(swarm_id.as_str()) {
+            return false;
+        }
+        self.swarms.insert(
+            swarm_id.as_str().to_string(),
+            SwarmMembership {
+                swarm_id,
+                token,
+                join_nonce: None,
+                task_set: OrSet::new(self.node_id.clone()),
+                joined_at: chrono::Utc::now(),
+            },
+        );
+        true
+    }
+
+    /// Stop participating in `swarm_id`, returning its membership.
+    pub fn leave(&mut self, swarm_id: &str) -> Option<SwarmMembership> {
+        self.swarms.remove(swarm_id)
+    }
+
+    pub fn contains(&self, swarm_id: &str) -> bool {
+        self.swarms.contains_key(swarm_id)
+    }
+
+    pub fn get(&self, swarm_id: &str) -> Option<&SwarmMembership> {
+        self.swarms.get(swarm_id)
+    }
+
+    pub fn get_mut(&mut self, swarm_id: &str) -> Option<&mut SwarmMembership> {
+        self.swarms.get_mut(swarm_id)
+    }
+
+    /// Memberships in swarm ID order.
+    pub fn iter(&self) -> impl Iterator<Item = &SwarmMembership> {
+        self.swarms.values()
+    }
+
+    /// Joined swarm IDs in sorted order.
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.swarms.keys().map(String::as_str)
+    }
+
+    /// The additional swarm whose task set holds `task_id`, if any.
+    pub fn swarm_of_task(&self, task_id: &str) -> Option<&str> {
+        self.swarms
+            .iter()
+            .find(|(_, m)| m.task_set.contains(task_id))
+            .map(|(id, _)| id.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.swarms.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.swarms.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joined_swarms_keep_separate_task_sets() {
+        let mut memberships = SwarmMemberships::new("did:swarm:me");
+        assert!(memberships.join(SwarmId::new("work".into()), None));
+        assert!(memberships.join(SwarmId::new("monitor".into()), None));
+        assert!(!memberships.join(SwarmId::new("work".into()), None));
+
+        memberships.get_mut("work").unwrap().task_set.add("t1".into());
+        assert!(!memberships.get("monitor").unwrap().task_set.contains("t1"));
+        assert_eq!(memberships.swarm_of_task("t1"), Some("work"));
+        assert_eq!(memberships.swarm_of_task("t2"), None);
+        assert_eq!(memberships.ids().collect::<Vec<_>>(), vec!["monitor", "work"]);
+
+        assert!(memberships.leave("work").is_some());
+        assert_eq!(memberships.swarm_of_task("t1"), None);
+        assert_eq!(memberships.len(), 1);
+    }
+}
@@ -207,7 +207,7 @@ impl OperatorConsole {
             self.process_command(&input).await;
         } else {
             // Treat as a task description to inject.
-            self.inject_task(&input).await;
+            self.inject_task(&input, 1).await;
         }
 
         self.input.clear();
@@ -230,6 +230,10 @@ impl OperatorConsole {
                     "  <text>       - Inject a task with the given description",
                     Color::White,
                 );
+                self.add_message(
+                    "  /inject [--tier N] <text> - Inject a task at tier N (default 1)",
+                    Color::White,
+                );
                 self.add_message(
                     "  /status      - Show detailed connector status",
                     Color::White,
@@ -465,6 +469,10 @@ impl OperatorConsole {
                     Color::Cyan,
                 );
             }
+            "/inject" => match parse_inject_args(parts.get(1).copied().unwrap_or("")) {
+                Ok((tier, description)) => self.inject_task(description, tier).await,
+                Err(e) => self.add_message(&e, Color::Yellow),
+            },
             "/quit" | "/exit" | "/q" => {
                 // Handled in the event loop.
             }
@@ -477,11 +485,22 @@ impl OperatorConsole {
         }
     }
 
-    /// Inject a task into the swarm.
-    async fn inject_task(&mut self, description: &str) {
+    /// Inject a task into the swarm at the given tier.
+    async fn inject_task(&mut self, description: &str, tier: u32) {
         let mut state = self.state.write().await;
+        let description = match wws_protocol::sanitize_task_description(
+            description,
+            state.max_task_description_len,
+        ) {
+            Ok(d) => d,
+            Err(e) => {
+                drop(state);
+                self.add_message(&e.to_string(), Color::Yellow);
+                return;
+            }
+        };
         let epoch = state.epoch_manager.current_epoch();
-        let task = Task::new(description.to_string(), 1, epoch);
+        let task = Task::new(description.clone(), tier, epoch);
         let task_id = task.task_id.clone();
         let originator = state.agent_id.clone();
         let swarm_id = state.current_swarm_id.as_str().to_string();
@@ -518,13 +537,13 @@ impl OperatorConsole {
         );
 
         if let Ok(data) = serde_json::to_vec(&msg) {
-            let topic = SwarmTopics::tasks_for(&swarm_id, 1);
+            let topic = SwarmTopics::tasks_for(&swarm_id, tier);
             if let Err(e) = self.network_handle.publish(&topic, data).await {
                 tracing::debug!(error = %e, "Failed to publish console task injection");
             }
 
-            let proposals_topic = SwarmTopics::proposals_for(&swarm_id, &task_id);
-            let voting_topic = SwarmTopics::voting_for(&swarm_id, &task_id);
+            let proposals_topic = SwarmTopics::proposals_for(&swarm_id, &task_id, epoch);
+            let voting_topic = SwarmTopics::voting_for(&swarm_id, &task_id, epoch);
             let results_topic = SwarmTopics::results_for(&swarm_id, &task_id);
 
             let _ = self.network_handle.subscribe(&proposals_topic).await;
@@ -956,10 +975,14 @@ impl OperatorConsole {
 }
 
 /// Build a hierarchy tree from the connector state.
+///
+/// Roots and every node's children are ordered by agent ID (then last-seen)
+/// so the tree does not reshuffle between refreshes.
 fn build_hierarchy_tree(state: &ConnectorState) -> Vec<HierarchyNode> {
+    let members = state.active_member_ids(Duration::from_secs(180));
     let mut nodes: std::collections::HashMap<String, HierarchyNode> = std::collections::HashMap::new();
-    for agent_id in state.active_member_ids(Duration::from_secs(180)) {
-        let last_seen_secs = state.member_last_seen.get(&agent_id).map(|ts| {
+    for agent_id in &members {
+        let last_seen_secs = state.member_last_seen.get(agent_id).map(|ts| {
             chrono::Utc::now()
                 .signed_duration_since(*ts)
                 .num_seconds()
@@ -967,10 +990,10 @@ fn build_hierarchy_tree(state: &ConnectorState) -> Vec<HierarchyNode> {
         });
         let tier = state
             .agent_tiers
-            .get(&agent_id)
+            .get(agent_id)
             .map(format_tier)
             .unwrap_or_else(|| {
-                if agent_id == state.agent_id.to_string() {
+                if *agent_id == state.agent_id.to_string() {
                     format_tier(&state.my_tier)
                 } else {
                     "Peer".to_string()
@@ -979,16 +1002,16 @@ fn build_hierarchy_tree(state: &ConnectorState) -> Vec<HierarchyNode> {
         let task_count = state
             .task_details
             .values()
-            .filter(|task| task.assigned_to.as_ref().map(|a| a.to_string()) == Some(agent_id.clone()))
+            .filter(|task| task.assigned_to.as_ref().map(|a| a.to_string()).as_ref() == Some(agent_id))
             .count();
 
         nodes.insert(
             agent_id.clone(),
             HierarchyNode {
-                display_name: truncate_agent_id(&agent_id),
+                display_name: truncate_agent_id(agent_id),
                 agent_id: agent_id.clone(),
                 tier,
-                is_self: agent_id == state.agent_id.to_string(),
+                is_self: *agent_id == state.agent_id.to_string(),
                 children: Vec::new(),
                 task_count,
                 last_seen_secs,
@@ -996,28 +1019,55 @@ fn build_hierarchy_tree(state: &ConnectorState) -> Vec<HierarchyNode> {
         );
     }
 
+    let mut ordered = members;
+    ordered.sort_by(|a, b| {
+        a.cmp(b).then_with(|| {
+            state
+                .member_last_seen
+                .get(a)
+                .cmp(&state.member_last_seen.get(b))
+        })
+    });
+
     let mut roots: Vec<String> = Vec::new();
-    let keys: Vec<String> = nodes.keys().cloned().collect();
-    for agent_id in keys {
-        if let Some(parent) = state.agent_parents.get(&agent_id) {
-            if nodes.contains_key(parent) {
-                let child = nodes.get(&agent_id).cloned();
-                if let (Some(parent_node), Some(child_node)) = (nodes.get_mut(parent), child) {
-                    parent_node.children.push(child_node);
-                }
-                continue;
+    let mut children: std::collections::HashMap<String, Vec<String>> = std::collections::HashMap::new();
+    for agent_id in ordered {
+        match state.agent_parents.get(&agent_id) {
+            Some(parent) if parent != &agent_id && nodes.contains_key(parent) => {
+                children.entry(parent.clone()).or_default().push(agent_id);
             }
+            _ => roots.push(agent_id),
         }
-        roots.push(agent_id);
     }
 
-    roots.sort();
+    let mut visited = std::collections::HashSet::new();
     roots
-        .into_iter()
-        .filter_map(|id| nodes.get(&id).cloned())
+        .iter()
+        .filter_map(|id| assemble_hierarchy_node(id, &nodes, &children, &mut visited))
         .collect()
 }
 
+/// Attach `id`'s children (already in display order) recursively, skipping
+/// nodes reached twice through a parent cycle.
+fn assemble_hierarchy_node(
+    id: &str,
+    nodes: &std::collections::HashMap<String, HierarchyNode>,
+    children: &std::collections::HashMap<String, Vec<String>>,
+    visited: &mut std::collections::HashSet<String>,
+) -> Option<HierarchyNode> {
+    if !visited.insert(id.to_string()) {
+        return None;
+    }
+    let mut node = nodes.get(id)?.clone();
+    node.children = children
+        .get(id)
+        .into_iter()
+        .flatten()
+        .filter_map(|child| assemble_hierarchy_node(child, nodes, children, visited))
+        .collect();
+    Some(node)
+}
+
 fn summarize_flow_snapshot(state: &ConnectorState) -> FlowSnapshot {
     let mut flow = FlowSnapshot::default();
 
@@ -1164,6 +1214,32 @@ fn print_hierarchy_node(
     }
 }
 
+/// Split `/inject` arguments into a tier (from an optional leading
+/// `--tier N`, default 1) and the task description.
+fn parse_inject_args(args: &str) -> Result<(u32, &str), String> {
+    let usage = "Usage: /inject [--tier N] <description>".to_string();
+    let args = args.trim();
+    let (tier, description) = match args.strip_prefix("--tier") {
+        Some(rest) => {
+            let rest = rest.trim_start();
+            let (value, description) = rest.split_once(' ').unwrap_or((rest, ""));
+            let tier = value
+                .parse::<u32>()
+                .ok()
+                .filter(|t| (1..=wws_protocol::MAX_HIERARCHY_DEPTH).contains(t))
+                .ok_or_else(|| {
+                    format!("--tier must be between 1 and {}", wws_protocol::MAX_HIERARCHY_DEPTH)
+                })?;
+            (tier, description.trim())
+        }
+        None => (1, args),
+    };
+    if description.is_empty() {
+        return Err(usage);
+    }
+    Ok((tier, description))
+}
+
 fn truncate_agent_id(id: &str) -> String {
     if id.len() > 28 {
         format!("{}...{}", &id[..16], &id[id.len() - 8..])
@@ -1192,6 +1268,7 @@ fn format_task_status(status: TaskStatus) -> &'static str {
         TaskStatus::Failed => "Failed",
         TaskStatus::Rejected => "Rejected",
         TaskStatus::PendingReview => "Pending Review",
+        TaskStatus::Blocked => "Blocked",
     }
 }
 
@@ -1315,6 +1392,9 @@ mod tests {
 
         let mut state = ConnectorState {
             agent_id: AgentId::new("did:swarm:connector-self".to_string()),
+            identity_keypair: wws_network::libp2p::identity::Keypair::generate_ed25519(),
+            sign_artifacts: true,
+            require_artifact_signatures: false,
             status: ConnectorStatus::Running,
             epoch_manager: EpochManager::default(),
             pyramid: PyramidAllocator::default(),
@@ -1333,6 +1413,7 @@ mod tests {
             agent_names: std::collections::HashMap::new(),
             agent_activity: std::collections::HashMap::new(),
             task_vote_requirements: std::collections::HashMap::new(),
+            task_hop_budgets: std::collections::HashMap::new(),
             member_last_task_poll: std::collections::HashMap::new(),
             member_last_result: std::collections::HashMap::new(),
             task_result_text: std::collections::HashMap::new(),
@@ -1345,6 +1426,7 @@ mod tests {
             agent_tiers: std::collections::HashMap::new(),
             agent_parents: std::collections::HashMap::new(),
             current_layout: None,
+            announced_assignments: None,
             subordinates: std::collections::HashMap::new(),
             task_results: std::collections::HashMap::new(),
             network_stats: wws_protocol::NetworkStats {
@@ -1355,17 +1437,26 @@ mod tests {
                 my_tier: Tier::Executor,
                 subordinate_count: 0,
                 parent_id: None,
+                growth_rate_per_min: 0.0,
+                trend: wws_protocol::SwarmTrend::Stable,
             },
+            estimated_swarm_size: 1,
             event_log: Vec::new(),
+            event_log_capacity: crate::event_log::DEFAULT_EVENT_LOG_CAPACITY,
+            event_log_file: None,
+            event_log_seq: 0,
+            event_log_tx: tokio::sync::broadcast::channel(crate::event_log::EVENT_LOG_STREAM_CAPACITY).0,
             message_trace: Vec::new(),
             start_time: chrono::Utc::now(),
             current_swarm_id: SwarmId::new("public".to_string()),
             known_swarms: std::collections::HashMap::new(),
+            memberships: crate::membership::SwarmMemberships::new("seed"),
             swarm_token: None,
             active_holons: std::collections::HashMap::new(),
             deliberation_messages: std::collections::HashMap::new(),
             ballot_records: std::collections::HashMap::new(),
             irv_rounds: std::collections::HashMap::new(),
+            voting_results: std::collections::HashMap::new(),
             board_acceptances: std::collections::HashMap::new(),
             name_registry: std::collections::HashMap::new(),
             inbox: Vec::new(),
@@ -1379,6 +1470,37 @@ mod tests {
             guardian_votes: std::collections::HashMap::new(),
             receipts: std::collections::HashMap::new(),
             clarifications: std::collections::HashMap::new(),
+            plan_limits: wws_consensus::PlanLimits::default(),
+            expand_subtask_templates: true,
+            monitor_all_tiers: true,
+            reveal_grace: std::time::Duration::from_secs(wws_protocol::DEFAULT_REVEAL_GRACE_SECS),
+            adaptive_proposer_cap: None,
+            min_commit_window: std::time::Duration::ZERO,
+            result_redundancy: 1,
+            result_majority: wws_consensus::result_tally::DEFAULT_RESULT_MAJORITY,
+            result_aggregation: Default::default(),
+            content_fetch_chain: crate::content_resolver::DEFAULT_FETCH_CHAIN.to_vec(),
+            min_peers_for_consensus: 0,
+            swarm_settings: crate::config::SwarmSettings::default(),
+            consensus_gated: false,
+            result_tallies: std::collections::HashMap::new(),
+            bootstrap_status: std::collections::BTreeMap::new(),
+            max_concurrent_tasks: 0,
+            max_task_description_len: wws_protocol::DEFAULT_MAX_TASK_DESCRIPTION_LEN,
+            assignment_queue: std::collections::VecDeque::new(),
+            listen_addrs: Vec::new(),
+            transport_features: TransportConfig::default().features(),
+            peer_debounce: Default::default(),
+            state_batcher: Default::default(),
+            task_acks: Default::default(),
+            score_weights: Default::default(),
+            dht_put_retries: Default::default(),
+            artifact_retention: None,
+            task_retention: None,
+            last_swarm_announce: None,
+            peer_agent_versions: std::collections::HashMap::new(),
+            peer_rtts: std::collections::HashMap::new(),
+            uptime: crate::uptime::UptimeTracker::default(),
         };
 
         state.mark_member_seen("did:swarm:agent-1");
@@ -1393,6 +1515,43 @@ mod tests {
             .all(|n| n.last_seen_secs.is_some()));
     }
 
+    #[test]
+    fn hierarchy_tree_order_is_stable_across_builds() {
+        let mut state = ConnectorState::new_for_test();
+        for id in ["did:swarm:d", "did:swarm:b", "did:swarm:e", "did:swarm:a", "did:swarm:c"] {
+            state.mark_member_seen(id);
+        }
+        for child in ["did:swarm:e", "did:swarm:c", "did:swarm:d"] {
+            state.agent_parents.insert(child.into(), "did:swarm:b".into());
+        }
+        state.agent_parents.insert("did:swarm:a".into(), "did:swarm:d".into());
+
+        fn flatten(nodes: &[HierarchyNode], out: &mut Vec<String>) {
+            for n in nodes {
+                out.push(n.agent_id.clone());
+                flatten(&n.children, out);
+            }
+        }
+        let order = |state: &ConnectorState| {
+            let mut out = Vec::new();
+            flatten(&build_hierarchy_tree(state), &mut out);
+            out
+        };
+
+        let first = order(&state);
+        for _ in 0..10 {
+            assert_eq!(order(&state), first);
+        }
+        let b = build_hierarchy_tree(&state)
+            .into_iter()
+            .find(|n| n.agent_id == "did:swarm:b")
+            .expect("b is a root");
+        let child_ids: Vec<_> = b.children.iter().map(|c| c.agent_id.as_str()).collect();
+        assert_eq!(child_ids, ["did:swarm:c", "did:swarm:d", "did:swarm:e"]);
+        // Grandchildren survive regardless of attach order.
+        assert_eq!(b.children[1].children[0].agent_id, "did:swarm:a");
+    }
+
     #[test]
     fn flow_summary_counts_votes_decomposition_results() {
         use wws_hierarchy::{EpochManager, GeoCluster, PyramidAllocator, SuccessionManager};
@@ -1401,6 +1560,9 @@ mod tests {
 
         let mut state = ConnectorState {
             agent_id: AgentId::new("did:swarm:flow-test".to_string()),
+            identity_keypair: wws_network::libp2p::identity::Keypair::generate_ed25519(),
+            sign_artifacts: true,
+            require_artifact_signatures: false,
             status: ConnectorStatus::Running,
             epoch_manager: EpochManager::default(),
             pyramid: PyramidAllocator::default(),
@@ -1419,6 +1581,7 @@ mod tests {
             agent_names: std::collections::HashMap::new(),
             agent_activity: std::collections::HashMap::new(),
             task_vote_requirements: std::collections::HashMap::new(),
+            task_hop_budgets: std::collections::HashMap::new(),
             member_last_task_poll: std::collections::HashMap::new(),
             member_last_result: std::collections::HashMap::new(),
             task_result_text: std::collections::HashMap::new(),
@@ -1431,6 +1594,7 @@ mod tests {
             agent_tiers: std::collections::HashMap::new(),
             agent_parents: std::collections::HashMap::new(),
             current_layout: None,
+            announced_assignments: None,
             subordinates: std::collections::HashMap::new(),
             task_results: std::collections::HashMap::new(),
             network_stats: wws_protocol::NetworkStats {
@@ -1441,17 +1605,26 @@ mod tests {
                 my_tier: Tier::Tier0,
                 subordinate_count: 0,
                 parent_id: None,
+                growth_rate_per_min: 0.0,
+                trend: wws_protocol::SwarmTrend::Stable,
             },
+            estimated_swarm_size: 1,
             event_log: Vec::new(),
+            event_log_capacity: crate::event_log::DEFAULT_EVENT_LOG_CAPACITY,
+            event_log_file: None,
+            event_log_seq: 0,
+            event_log_tx: tokio::sync::broadcast::channel(crate::event_log::EVENT_LOG_STREAM_CAPACITY).0,
             message_trace: Vec::new(),
             start_time: chrono::Utc::now(),
             current_swarm_id: SwarmId::new("public".to_string()),
             known_swarms: std::collections::HashMap::new(),
+            memberships: crate::membership::SwarmMemberships::new("seed"),
             swarm_token: None,
             active_holons: std::collections::HashMap::new(),
             deliberation_messages: std::collections::HashMap::new(),
             ballot_records: std::collections::HashMap::new(),
             irv_rounds: std::collections::HashMap::new(),
+            voting_results: std::collections::HashMap::new(),
             board_acceptances: std::collections::HashMap::new(),
             name_registry: std::collections::HashMap::new(),
             inbox: Vec::new(),
@@ -1465,6 +1638,37 @@ mod tests {
             guardian_votes: std::collections::HashMap::new(),
             receipts: std::collections::HashMap::new(),
             clarifications: std::collections::HashMap::new(),
+            plan_limits: wws_consensus::PlanLimits::default(),
+            expand_subtask_templates: true,
+            monitor_all_tiers: true,
+            reveal_grace: std::time::Duration::from_secs(wws_protocol::DEFAULT_REVEAL_GRACE_SECS),
+            adaptive_proposer_cap: None,
+            min_commit_window: std::time::Duration::ZERO,
+            result_redundancy: 1,
+            result_majority: wws_consensus::result_tally::DEFAULT_RESULT_MAJORITY,
+            result_aggregation: Default::default(),
+            content_fetch_chain: crate::content_resolver::DEFAULT_FETCH_CHAIN.to_vec(),
+            min_peers_for_consensus: 0,
+            swarm_settings: crate::config::SwarmSettings::default(),
+            consensus_gated: false,
+            result_tallies: std::collections::HashMap::new(),
+            bootstrap_status: std::collections::BTreeMap::new(),
+            max_concurrent_tasks: 0,
+            max_task_description_len: wws_protocol::DEFAULT_MAX_TASK_DESCRIPTION_LEN,
+            assignment_queue: std::collections::VecDeque::new(),
+            listen_addrs: Vec::new(),
+            transport_features: TransportConfig::default().features(),
+            peer_debounce: Default::default(),
+            state_batcher: Default::default(),
+            task_acks: Default::default(),
+            score_weights: Default::default(),
+            dht_put_retries: Default::default(),
+            artifact_retention: None,
+            task_retention: None,
+            last_swarm_announce: None,
+            peer_agent_versions: std::collections::HashMap::new(),
+            peer_rtts: std::collections::HashMap::new(),
+            uptime: crate::uptime::UptimeTracker::default(),
         };
 
         state.push_task_timeline_event("t1", "injected", "", None);
@@ -1493,15 +1697,22 @@ mod tests {
         assert_eq!(flow.peer_events, 1);
     }
 
+    #[test]
+    fn inject_args_accept_optional_tier() {
+        assert_eq!(parse_inject_args("summarize logs"), Ok((1, "summarize logs")));
+        assert_eq!(parse_inject_args("--tier 3 summarize logs"), Ok((3, "summarize logs")));
+        assert!(parse_inject_args("--tier 0 summarize").is_err());
+        assert!(parse_inject_args("--tier 99 summarize").is_err());
+        assert!(parse_inject_args("--tier 2").is_err());
+        assert!(parse_inject_args("").is_err());
+    }
+
     #[tokio::test]
     async fn console_inject_task_publishes_to_swarm() {
         let cfg = SwarmHostConfig {
-            listen_addr: "/ip4/127.0.0.1/tcp/0".parse().expect("valid listen addr"),
+            listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().expect("valid listen addr")],
             transport: TransportConfig::default(),
-            discovery: DiscoveryConfig {
-                mdns_enabled: false,
-                ..Default::default()
-            },
+            discovery: DiscoveryConfig::standard(Vec::new(), false),
             ..Default::default()
         };
 
@@ -1550,6 +1761,9 @@ mod tests {
 
         let state = ConnectorState {
             agent_id: AgentId::new("did:swarm:console-test".to_string()),
+            identity_keypair: wws_network::libp2p::identity::Keypair::generate_ed25519(),
+            sign_artifacts: true,
+            require_artifact_signatures: false,
             status: ConnectorStatus::Running,
             epoch_manager: EpochManager::default(),
             pyramid: PyramidAllocator::default(),
@@ -1568,6 +1782,7 @@ mod tests {
             agent_names: std::collections::HashMap::new(),
             agent_activity: std::collections::HashMap::new(),
             task_vote_requirements: std::collections::HashMap::new(),
+            task_hop_budgets: std::collections::HashMap::new(),
             member_last_task_poll: std::collections::HashMap::new(),
             member_last_result: std::collections::HashMap::new(),
             task_result_text: std::collections::HashMap::new(),
@@ -1580,6 +1795,7 @@ mod tests {
             agent_tiers: std::collections::HashMap::new(),
             agent_parents: std::collections::HashMap::new(),
             current_layout: None,
+            announced_assignments: None,
             subordinates: std::collections::HashMap::new(),
             task_results: std::collections::HashMap::new(),
             network_stats: wws_protocol::NetworkStats {
@@ -1590,17 +1806,26 @@ mod tests {
                 my_tier: Tier::Tier0,
                 subordinate_count: 0,
                 parent_id: None,
+                growth_rate_per_min: 0.0,
+                trend: wws_protocol::SwarmTrend::Stable,
             },
+            estimated_swarm_size: 1,
             event_log: Vec::new(),
+            event_log_capacity: crate::event_log::DEFAULT_EVENT_LOG_CAPACITY,
+            event_log_file: None,
+            event_log_seq: 0,
+            event_log_tx: tokio::sync::broadcast::channel(crate::event_log::EVENT_LOG_STREAM_CAPACITY).0,
             message_trace: Vec::new(),
             start_time: chrono::Utc::now(),
             current_swarm_id: SwarmId::new("public".to_string()),
             known_swarms: std::collections::HashMap::new(),
+            memberships: crate::membership::SwarmMemberships::new("seed"),
             swarm_token: None,
             active_holons: std::collections::HashMap::new(),
             deliberation_messages: std::collections::HashMap::new(),
             ballot_records: std::collections::HashMap::new(),
             irv_rounds: std::collections::HashMap::new(),
+            voting_results: std::collections::HashMap::new(),
             board_acceptances: std::collections::HashMap::new(),
             name_registry: std::collections::HashMap::new(),
             inbox: Vec::new(),
@@ -1614,10 +1839,41 @@ mod tests {
             guardian_votes: std::collections::HashMap::new(),
             receipts: std::collections::HashMap::new(),
             clarifications: std::collections::HashMap::new(),
+            plan_limits: wws_consensus::PlanLimits::default(),
+            expand_subtask_templates: true,
+            monitor_all_tiers: true,
+            reveal_grace: std::time::Duration::from_secs(wws_protocol::DEFAULT_REVEAL_GRACE_SECS),
+            adaptive_proposer_cap: None,
+            min_commit_window: std::time::Duration::ZERO,
+            result_redundancy: 1,
+            result_majority: wws_consensus::result_tally::DEFAULT_RESULT_MAJORITY,
+            result_aggregation: Default::default(),
+            content_fetch_chain: crate::content_resolver::DEFAULT_FETCH_CHAIN.to_vec(),
+            min_peers_for_consensus: 0,
+            swarm_settings: crate::config::SwarmSettings::default(),
+            consensus_gated: false,
+            result_tallies: std::collections::HashMap::new(),
+            bootstrap_status: std::collections::BTreeMap::new(),
+            max_concurrent_tasks: 0,
+            max_task_description_len: wws_protocol::DEFAULT_MAX_TASK_DESCRIPTION_LEN,
+            assignment_queue: std::collections::VecDeque::new(),
+            listen_addrs: Vec::new(),
+            transport_features: TransportConfig::default().features(),
+            peer_debounce: Default::default(),
+            state_batcher: Default::default(),
+            task_acks: Default::default(),
+            score_weights: Default::default(),
+            dht_put_retries: Default::default(),
+            artifact_retention: None,
+            task_retention: None,
+            last_swarm_announce: None,
+            peer_agent_versions: std::collections::HashMap::new(),
+            peer_rtts: std::collections::HashMap::new(),
+            uptime: crate::uptime::UptimeTracker::default(),
         };
 
         let mut console = OperatorConsole::new(Arc::new(RwLock::new(state)), handle_a.clone());
-        console.inject_task("console injected task").await;
+        console.inject_task("console injected task", 1).await;
 
         let received = timeout(Duration::from_secs(10), async {
             loop {
@@ -0,0 +1,215 @@
+//! Debouncing of connect/disconnect events from flapping peers.
+//!
+//! A peer on an unstable link can connect and disconnect many times a
+//! second. [`PeerFlapDebouncer`] applies a peer's first transition at once,
+//! then holds further transitions until the peer has been quiet for the
+//! debounce window and applies only the net result, so a burst of flaps
+//! costs at most one membership update and log line. Every transition that
+//! arrives inside the window is counted as a flap.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Default window in milliseconds within which transitions are coalesced.
+pub const DEFAULT_PEER_DEBOUNCE_MS: u64 = 2000;
+
+/// How long a disconnected peer stays tracked once quiet (or the window,
+/// if longer), so its flap count remains available for a while.
+const DISCONNECTED_RETENTION: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone)]
+struct PeerLink {
+    /// Connection state last reflected in connector state.
+    applied: bool,
+    /// Connection state most recently reported by the network layer.
+    observed: bool,
+    /// When the network layer last reported a transition for this peer.
+    last_event: Instant,
+    /// Transitions that arrived within the window of the previous one.
+    flaps: u64,
+}
+
+/// Per-peer coalescing of connection transitions.
+#[derive(Debug, Clone)]
+pub struct PeerFlapDebouncer {
+    window: Duration,
+    peers: HashMap<String, PeerLink>,
+}
+
+impl PeerFlapDebouncer {
+    /// Debouncer coalescing transitions closer together than `window`.
+    /// A zero window applies every transition immediately.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            peers: HashMap::new(),
+        }
+    }
+
+    /// Record that `peer` connected (`true`) or disconnected (`false`).
+    ///
+    /// Returns the state to apply now, or `None` when the transition is
+    /// held back (or is a no-op) and will be resolved by [`Self::settle`].
+    pub fn observe(&mut self, peer: &str, connected: bool, now: Instant) -> Option<bool> {
+        let Some(link) = self.peers.get_mut(peer) else {
+            self.peers.insert(
+                peer.to_string(),
+                PeerLink {
+                    applied: connected,
+                    observed: connected,
+                    last_event: now,
+                    flaps: 0,
+                },
+            );
+            return Some(connected);
+        };
+        if link.observed == connected {
+            return None;
+        }
+        if now.saturating_duration_since(link.last_event) < self.window {
+            link.flaps += 1;
+            link.observed = connected;
+            link.last_event = now;
+            return None;
+        }
+        link.observed = connected;
+        link.last_event = now;
+        (link.applied != connected).then(|| {
+            link.applied = connected;
+            connected
+        })
+    }
+
+    /// Resolve peers that have been quiet for the window, returning each
+    /// peer whose net state differs from what was last applied.
+    ///
+    /// Peers settled as disconnected are forgotten once they have been
+    /// quiet for a minute (or the window, if longer).
+    pub fn settle(&mut self, now: Instant) -> Vec<(String, bool)> {
+        let retention = self.window.max(DISCONNECTED_RETENTION);
+        self.peers.retain(|_, link| {
+            link.applied
+                || link.observed
+                || now.saturating_duration_since(link.last_event) < retention
+        });
+        let mut changes = Vec::new();
+        for (peer, link) in self.peers.iter_mut() {
+            if link.applied != link.observed
+                && now.saturating_duration_since(link.last_event) >= self.window
+            {
+                link.applied = link.observed;
+                changes.push((peer.clone(), link.observed));
+            }
+        }
+        changes.sort();
+        changes
+    }
+
+    /// Number of rapid transitions seen from `peer`.
+    pub fn flap_count(&self, peer: &str) -> u64 {
+        self.peers.get(peer).map(|link| link.flaps).unwrap_or(0)
+    }
+
+    /// Number of peers currently tracked.
+    pub fn len(&self) -> usize {
+        self.peers.len()
+    }
+
+    /// Whether no peer is tracked.
+    pub fn is_empty(&self) -> bool {
+        self.peers.is_empty()
+    }
+}
+
+impl Default for PeerFlapDebouncer {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(DEFAULT_PEER_DEBOUNCE_MS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_secs(2);
+
+    #[test]
+    fn first_transition_applies_immediately() {
+        let mut debouncer = PeerFlapDebouncer::new(WINDOW);
+        let t0 = Instant::now();
+        assert_eq!(debouncer.observe("p1", true, t0), Some(true));
+        assert_eq!(debouncer.flap_count("p1"), 0);
+        // A disconnect after a quiet period applies immediately too.
+        assert_eq!(debouncer.observe("p1", false, t0 + WINDOW), Some(false));
+        assert_eq!(debouncer.flap_count("p1"), 0);
+    }
+
+    #[test]
+    fn rapid_flaps_produce_single_net_change() {
+        let mut debouncer = PeerFlapDebouncer::new(WINDOW);
+        let t0 = Instant::now();
+        let step = Duration::from_millis(100);
+
+        let mut applied = vec![debouncer.observe("p1", true, t0)];
+        for i in 1..=6u32 {
+            applied.push(debouncer.observe("p1", i % 2 == 0, t0 + step * i));
+        }
+        // Last report is connected, matching the state applied up front.
+        assert_eq!(applied.into_iter().flatten().collect::<Vec<_>>(), vec![true]);
+        assert!(debouncer.settle(t0 + step * 6 + WINDOW).is_empty());
+        assert_eq!(debouncer.flap_count("p1"), 6);
+    }
+
+    #[test]
+    fn burst_ending_disconnected_settles_after_window() {
+        let mut debouncer = PeerFlapDebouncer::new(WINDOW);
+        let t0 = Instant::now();
+        let step = Duration::from_millis(100);
+        assert_eq!(debouncer.observe("p1", true, t0), Some(true));
+        assert_eq!(debouncer.observe("p1", false, t0 + step), None);
+        assert_eq!(debouncer.observe("p1", true, t0 + step * 2), None);
+        assert_eq!(debouncer.observe("p1", false, t0 + step * 3), None);
+
+        assert!(debouncer.settle(t0 + step * 3 + WINDOW / 2).is_empty());
+        assert_eq!(
+            debouncer.settle(t0 + step * 3 + WINDOW),
+            vec![("p1".to_string(), false)]
+        );
+        assert!(debouncer.settle(t0 + step * 3 + WINDOW * 2).is_empty());
+        assert_eq!(debouncer.flap_count("p1"), 3);
+    }
+
+    #[test]
+    fn settled_disconnected_peers_are_forgotten() {
+        let mut debouncer = PeerFlapDebouncer::new(WINDOW);
+        let t0 = Instant::now();
+        let step = Duration::from_millis(100);
+        debouncer.observe("p1", true, t0);
+        debouncer.observe("p1", false, t0 + step);
+        debouncer.observe("p2", true, t0);
+
+        assert_eq!(debouncer.settle(t0 + step + WINDOW), vec![("p1".to_string(), false)]);
+        assert_eq!(debouncer.flap_count("p1"), 1);
+
+        // Once quiet past the retention, p1 is dropped; the connected peer stays.
+        let later = t0 + step + DISCONNECTED_RETENTION;
+        assert!(debouncer.settle(later).is_empty());
+        assert_eq!(debouncer.flap_count("p1"), 0);
+        assert_eq!(debouncer.len(), 1);
+
+        // A peer disconnected without flapping is dropped the same way.
+        assert_eq!(debouncer.observe("p2", false, later), Some(false));
+        debouncer.settle(later + DISCONNECTED_RETENTION);
+        assert!(debouncer.is_empty());
+    }
+
+    #[test]
+    fn zero_window_applies_every_transition() {
+        let mut debouncer = PeerFlapDebouncer::new(Duration::ZERO);
+        let t0 = Instant::now();
+        assert_eq!(debouncer.observe("p1", true, t0), Some(true));
+        assert_eq!(debouncer.observe("p1", false, t0), Some(false));
+        assert_eq!(debouncer.observe("p1", true, t0), Some(true));
+        assert_eq!(debouncer.flap_count("p1"), 0);
+    }
+}
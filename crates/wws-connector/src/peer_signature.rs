@@ -0,0 +1,66 @@
+//! Signatures tied to a node's libp2p identity.
+//!
+//! Agent IDs are `did:swarm:<peer id>`, and an Ed25519 peer ID embeds its
+//! public key, so a signature by the node's identity keypair can be checked
+//! against the agent ID alone, without a key exchange.
+
+use wws_network::libp2p::identity::{Keypair, PublicKey};
+use wws_network::PeerId;
+
+/// Multihash code of the identity hash, used for keys short enough to be
+/// embedded in the peer ID.
+const IDENTITY_MULTIHASH_CODE: u8 = 0x00;
+
+/// Hex-encoded signature of `payload` by `keypair`.
+pub fn sign_payload(keypair: &Keypair, payload: &[u8]) -> String {
+    keypair.sign(payload).map(hex::encode).unwrap_or_default()
+}
+
+/// Public key embedded in `agent_id`, if it is a `did:swarm:` ID over a
+/// peer ID that inlines its key.
+pub fn agent_public_key(agent_id: &str) -> Option<PublicKey> {
+    let peer_id: PeerId = agent_id.strip_prefix("did:swarm:")?.parse().ok()?;
+    let multihash = peer_id.to_bytes();
+    let (&code, rest) = multihash.split_first()?;
+    let (&len, key) = rest.split_first()?;
+    if code != IDENTITY_MULTIHASH_CODE || len as usize != key.len() {
+        return None;
+    }
+    PublicKey::try_decode_protobuf(key).ok()
+}
+
+/// Whether `signature_hex` is a valid signature of `payload` by the key
+/// behind `agent_id`.
+pub fn verify_agent_signature(agent_id: &str, payload: &[u8], signature_hex: &str) -> bool {
+    let Some(key) = agent_public_key(agent_id) else {
+        return false;
+    };
+    let Ok(signature) = hex::decode(signature_hex) else {
+        return false;
+    };
+    key.verify(payload, &signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent_id_of(keypair: &Keypair) -> String {
+        format!("did:swarm:{}", keypair.public().to_peer_id())
+    }
+
+    #[test]
+    fn signature_verifies_against_agent_id() {
+        let keypair = Keypair::generate_ed25519();
+        let agent_id = agent_id_of(&keypair);
+        let signature = sign_payload(&keypair, b"payload");
+
+        assert!(verify_agent_signature(&agent_id, b"payload", &signature));
+        assert!(!verify_agent_signature(&agent_id, b"tampered", &signature));
+
+        let other = agent_id_of(&Keypair::generate_ed25519());
+        assert!(!verify_agent_signature(&other, b"payload", &signature));
+        assert!(!verify_agent_signature("did:swarm:not-a-peer", b"payload", &signature));
+        assert!(!verify_agent_signature(&agent_id, b"payload", "zz"));
+    }
+}
@@ -0,0 +1,312 @@
+//! Automatic aggregation of subtask results at the parent tier.
+//!
+//! A decomposed task normally waits for its agent to synthesize the
+//! subtask results. With `hierarchy.aggregate_results` enabled, the
+//! coordinator that assigned the subtasks does it itself: it already
+//! listens on each child's results topic, and once every child has
+//! completed it assembles a [`ResultManifest`] over the children's content
+//! CIDs, adds the matching branch to the Merkle DAG, stores the manifest in
+//! the content store, and submits it as the parent's result. The parent's
+//! own results topic then carries the manifest CID one tier up, where the
+//! next coordinator may aggregate it in turn.
+//!
+//! The manifest lists children in plan order and carries no timestamps, so
+//! every node aggregating the same results derives the same CID.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use wws_protocol::{Artifact, ResultSubmissionParams, Task, TaskStatus};
+use wws_state::MerkleDag;
+
+use crate::connector::ConnectorState;
+use crate::connector::ResultSubmissionOutcome;
+use crate::tui::LogCategory;
+
+/// Content type of an aggregated parent result.
+pub const MANIFEST_CONTENT_TYPE: &str = "application/vnd.wws.result-manifest+json";
+
+/// One completed child in a [`ResultManifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub task_id: String,
+    pub content_cid: String,
+}
+
+/// Combined result of a parent task: its children's result CIDs and the
+/// Merkle root over them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResultManifest {
+    pub parent_task_id: String,
+    /// Branch hash over the children's leaf hashes, in plan order.
+    pub dag_root: String,
+    pub children: Vec<ManifestEntry>,
+}
+
+impl ResultManifest {
+    /// Build the manifest for `parent` once every subtask has completed
+    /// with a stored result; `None` while any is outstanding.
+    pub fn build(
+        parent: &Task,
+        tasks: &HashMap<String, Task>,
+        results: &HashMap<String, Artifact>,
+    ) -> Option<Self> {
+        if parent.subtasks.is_empty() {
+            return None;
+        }
+        let children = parent
+            .subtasks
+            .iter()
+            .map(|sub_id| {
+                let done = tasks.get(sub_id).is_some_and(|t| {
+                    matches!(t.status, TaskStatus::Completed | TaskStatus::PendingReview)
+                });
+                let cid = results.get(sub_id).map(|a| a.content_cid.as_str())?;
+                (done && !cid.is_empty()).then(|| ManifestEntry {
+                    task_id: sub_id.clone(),
+                    content_cid: cid.to_string(),
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let dag_root = MerkleDag::branch_hash(&Self::leaf_hashes_of(&children));
+        Some(Self {
+            parent_task_id: parent.task_id.clone(),
+            dag_root,
+            children,
+        })
+    }
+
+    /// DAG leaf hashes of the children, in manifest order.
+    pub fn leaf_hashes(&self) -> Vec<String> {
+        Self::leaf_hashes_of(&self.children)
+    }
+
+    fn leaf_hashes_of(children: &[ManifestEntry]) -> Vec<String> {
+        children
+            .iter()
+            .map(|c| MerkleDag::leaf_hash(c.content_cid.as_bytes()))
+            .collect()
+    }
+
+    /// Canonical JSON content stored under the manifest CID.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+/// Parent tasks this node aggregates once their subtasks complete.
+#[derive(Debug, Clone, Default)]
+pub struct ResultAggregator {
+    enabled: bool,
+    pending: HashSet<String>,
+}
+
+impl ResultAggregator {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            pending: HashSet::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Aggregate `parent_id` when its subtasks complete. Ignored while
+    /// aggregation is disabled.
+    pub fn track(&mut self, parent_id: &str) {
+        if self.enabled {
+            self.pending.insert(parent_id.to_string());
+        }
+    }
+
+    pub fn is_pending(&self, parent_id: &str) -> bool {
+        self.pending.contains(parent_id)
+    }
+
+    fn finish(&mut self, parent_id: &str) {
+        self.pending.remove(parent_id);
+    }
+}
+
+impl ConnectorState {
+    /// Submit the aggregated result of `child_id`'s parent if this node
+    /// aggregates it and the child was the last one outstanding.
+    pub(crate) fn aggregate_parent_of(
+        &mut self,
+        child_id: &str,
+    ) -> Option<(ResultSubmissionParams, ResultSubmissionOutcome)> {
+        let parent_id = self.task_details.get(child_id)?.parent_task_id.clone()?;
+        if !self.result_aggregation.is_pending(&parent_id) {
+            return None;
+        }
+        let parent = self.task_details.get(&parent_id)?;
+        if parent.status == TaskStatus::Completed {
+            self.result_aggregation.finish(&parent_id);
+            return None;
+        }
+        let manifest = ResultManifest::build(parent, &self.task_details, &self.task_results)?;
+        self.result_aggregation.finish(&parent_id);
+        self.merkle_dag.add_branch(parent_id.clone(), manifest.leaf_hashes());
+
+        let content = manifest.to_json();
+        let mut submission = ResultSubmissionParams {
+            task_id: parent_id.clone(),
+            agent_id: self.agent_id.clone(),
+            artifact: Artifact {
+                artifact_id: format!("{}-manifest", parent_id),
+                task_id: parent_id.clone(),
+                producer: self.agent_id.clone(),
+                content_cid: String::new(),
+                merkle_hash: manifest.dag_root.clone(),
+                content_type: MANIFEST_CONTENT_TYPE.to_string(),
+                size_bytes: 0,
+                created_at: chrono::Utc::now(),
+                content: content.clone(),
+                metadata: serde_json::Value::Null,
+                signature: String::new(),
+            },
+            merkle_proof: manifest.leaf_hashes(),
+            is_synthesis: true,
+        };
+        let params = serde_json::json!({ "is_synthesis": true, "content": content });
+        match self.apply_result_submission(&mut submission, &params) {
+            Ok(outcome) => {
+                self.push_log(
+                    LogCategory::Task,
+                    format!(
+                        "Aggregated {} subtask results of {} into {}",
+                        manifest.children.len(),
+                        parent_id,
+                        submission.artifact.content_cid
+                    ),
+                );
+                Some((submission, outcome))
+            }
+            Err(failure) => {
+                tracing::warn!(
+                    task_id = %parent_id,
+                    error = %failure.message,
+                    "Failed to submit aggregated result"
+                );
+                None
+            }
+        }
+    }
+}
+
+/// Aggregate and publish the result of `child_id`'s parent, then of its
+/// parent in turn, for as long as this node aggregates each level.
+pub(crate) async fn submit_aggregated_results(
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+    child_id: &str,
+) {
+    let mut child_id = child_id.to_string();
+    loop {
+        let aggregated = state.write().await.aggregate_parent_of(&child_id);
+        let Some((submission, outcome)) = aggregated else {
+            return;
+        };
+        crate::rpc_server::publish_result(network_handle, &submission, &outcome).await;
+        if !outcome.settled {
+            return;
+        }
+        child_id = submission.task_id;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str, parent: Option<&str>, status: TaskStatus) -> Task {
+        let mut task = Task::new(format!("task {id}"), 1, 1);
+        task.task_id = id.to_string();
+        task.parent_task_id = parent.map(str::to_string);
+        task.status = status;
+        task
+    }
+
+    fn result(task_id: &str, content: &str) -> Artifact {
+        serde_json::from_value(serde_json::json!({
+            "task_id": task_id,
+            "content_cid": MerkleDag::leaf_hash(content.as_bytes()),
+            "content": content,
+        }))
+        .unwrap()
+    }
+
+    fn state_with_parent() -> ConnectorState {
+        let mut state = ConnectorState::new_for_test();
+        state.result_aggregation = ResultAggregator::new(true);
+        let mut parent = task("root", None, TaskStatus::InProgress);
+        parent.subtasks = vec!["root-st-1".into(), "root-st-2".into()];
+        state.task_details.insert("root".into(), parent);
+        for id in ["root-st-1", "root-st-2"] {
+            state
+                .task_details
+                .insert(id.into(), task(id, Some("root"), TaskStatus::InProgress));
+        }
+        state.result_aggregation.track("root");
+        state
+    }
+
+    fn complete(state: &mut ConnectorState, id: &str, content: &str) {
+        state.task_details.get_mut(id).unwrap().status = TaskStatus::Completed;
+        state.task_results.insert(id.into(), result(id, content));
+    }
+
+    #[test]
+    fn manifest_waits_for_every_child() {
+        let mut state = state_with_parent();
+        complete(&mut state, "root-st-1", "first");
+        assert!(state.aggregate_parent_of("root-st-1").is_none());
+        assert!(state.result_aggregation.is_pending("root"));
+
+        complete(&mut state, "root-st-2", "second");
+        let (submission, outcome) = state.aggregate_parent_of("root-st-2").expect("parent aggregated");
+        assert!(outcome.settled);
+        assert_eq!(submission.task_id, "root");
+        assert_eq!(state.task_details["root"].status, TaskStatus::Completed);
+        assert!(!state.result_aggregation.is_pending("root"));
+
+        let manifest: ResultManifest =
+            serde_json::from_str(&submission.artifact.content).unwrap();
+        let children: Vec<&str> = manifest.children.iter().map(|c| c.task_id.as_str()).collect();
+        assert_eq!(children, ["root-st-1", "root-st-2"]);
+        assert!(state.merkle_dag.get_node(&manifest.dag_root).is_some());
+        assert_eq!(submission.artifact.merkle_hash, manifest.dag_root);
+    }
+
+    #[test]
+    fn manifest_cid_does_not_depend_on_completion_order() {
+        let mut a = state_with_parent();
+        complete(&mut a, "root-st-1", "first");
+        complete(&mut a, "root-st-2", "second");
+        let (from_a, _) = a.aggregate_parent_of("root-st-2").unwrap();
+
+        let mut b = state_with_parent();
+        complete(&mut b, "root-st-2", "second");
+        complete(&mut b, "root-st-1", "first");
+        let (from_b, _) = b.aggregate_parent_of("root-st-1").unwrap();
+
+        assert!(!from_a.artifact.content_cid.is_empty());
+        assert_eq!(from_a.artifact.content_cid, from_b.artifact.content_cid);
+        assert!(a.content_store.get(&from_a.artifact.content_cid).is_some());
+    }
+
+    #[test]
+    fn untracked_parent_is_left_for_the_agent() {
+        let mut state = state_with_parent();
+        state.result_aggregation = ResultAggregator::new(false);
+        state.result_aggregation.track("root");
+        complete(&mut state, "root-st-1", "first");
+        complete(&mut state, "root-st-2", "second");
+        assert!(state.aggregate_parent_of("root-st-2").is_none());
+        assert_eq!(state.task_details["root"].status, TaskStatus::InProgress);
+    }
+}
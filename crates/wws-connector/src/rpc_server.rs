@@ -3,16 +3,34 @@
 //! Provides the following methods for the local AI agent:
 //! - `swarm.connect()` - Connect to a peer by multiaddress
 //! - `swarm.get_network_stats()` - Get current network statistics
+//! - `swarm.get_cascade_progress()` - Get per-root-task cascade completion
+//! - `swarm.get_succession_history()` - Get recorded leader handovers
+//! - `swarm.get_election()` - Get the running Tier-1 election's candidates and live tally
+//! - `swarm.get_bootstrap_status()` - Get reachability of configured bootstrap peers
+//! - `swarm.get_peer_versions()` - Get a histogram of connected peers' software versions
+//! - `swarm.get_topic_stats()` - Get messages sent and received per GossipSub topic
+//! - `swarm.get_connection_metrics()` - Get per-peer connection counts, addresses and dial outcomes
+//! - `swarm.refresh_routing()` - Run a Kademlia random walk now instead of on the interval
+//! - `swarm.export_topology()` - Export members, tiers and parent links as JSON or Graphviz DOT
 //! - `swarm.propose_plan()` - Submit a task decomposition plan
 //! - `swarm.submit_result()` - Submit a task execution result
 //! - `swarm.receive_task()` - Poll for assigned tasks
+//! - `swarm.delegate_task()` - Hand an in-progress subtask off to another agent
+//! - `swarm.report_progress()` - Report intermediate progress on an assigned task
 //! - `swarm.get_task()` - Get full details for a task by ID
 //! - `swarm.get_task_timeline()` - Get lifecycle timeline for a task
+//! - `swarm.get_content()` - Fetch stored content with its type and metadata
+//! - `swarm.get_content_stats()` - Size and utilization of the content store
 //! - `swarm.get_status()` - Get connector and agent status
+//! - `swarm.ping()` - Cheap liveness/readiness probe
+//! - `swarm.tail_logs()` - Return recent event log entries, then stream new ones
+//! - `swarm.export_state()` - Dump tasks, members, swarms and epoch state
 //! - `swarm.register_agent()` - Register an execution agent identity
 //! - `swarm.list_swarms()` - List all known swarms with their info
+//! - `swarm.get_swarm_history()` - Recent join/leave/announce events of a swarm
 //! - `swarm.create_swarm()` - Create a new private swarm
 //! - `swarm.join_swarm()` - Join an existing swarm
+//! - `swarm.get_swarm_token()` - Re-derive the token for a swarm this node created
 //! - `swarm.register_name()` - Bind a human-readable name to a DID
 //! - `swarm.resolve_name()` - Resolve a name to a DID
 //! - `swarm.send_message()` - Send a direct message to another agent
@@ -29,20 +47,62 @@
 //! The server listens on localhost TCP and speaks JSON-RPC 2.0.
 //! Each line received is a JSON-RPC request; each line sent is a response.
 
+use std::collections::VecDeque;
+use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 
-use wws_consensus::rfp::RfpPhase;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::TcpListener;
 use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
 
 use wws_protocol::*;
 
-use crate::connector::{ConnectorState, SwarmRecord, TaskTimelineEvent, TaskVoteRequirement, WwsConnector};
-
-const ACTIVE_MEMBER_STALENESS_SECS: u64 = 45;
-const PARTICIPATION_POLL_STALENESS_SECS: u64 = 180;
+use crate::connector::{ConnectorState, ConnectorStatus, PlanProposalOutcome, ResultSubmissionOutcome, SwarmRecord, TaskTimelineEvent, TaskVoteRequirement, TierAssignments, WwsConnector};
+use crate::content_resolver::{ContentResolver, ContentSource};
+use crate::tui::{LogCategory, LogEntry};
+
+const ACTIVE_MEMBER_STALENESS_SECS: u64 = crate::connector::RPC_ACTIVE_MEMBER_STALENESS_SECS;
+
+/// Backlog entries `swarm.tail_logs` returns when `backlog` is omitted.
+const TAIL_LOGS_DEFAULT_BACKLOG: usize = 100;
+/// Upper bound on the `swarm.tail_logs` backlog.
+const TAIL_LOGS_MAX_BACKLOG: usize = 500;
+/// Upper bound on entries per second streamed to one `swarm.tail_logs` client.
+const TAIL_LOGS_MAX_PER_SEC: u32 = 50;
+
+/// Methods that are refused unless `OPENSWARM_RPC_TOKEN` is configured.
+const AUTH_REQUIRED_METHODS: &[&str] = &["swarm.get_swarm_token", "swarm.export_state"];
+
+/// Methods that publish to the swarm and are refused with `NotReady` until
+/// the connector is running. Read-only methods are always served.
+const NETWORK_METHODS: &[&str] = &[
+    "swarm.inject_task",
+    "swarm.propose_plan",
+    "swarm.submit_vote",
+    "swarm.submit_critique",
+    "swarm.submit_result",
+    "swarm.delegate_task",
+    "swarm.report_progress",
+    "swarm.register_agent",
+    "swarm.create_swarm",
+    "swarm.join_swarm",
+    "swarm.leave_swarm",
+    "swarm.send_message",
+];
+
+/// Refuse `method` with `NotReady` unless the connector is running. Entry
+/// points besides the RPC server that publish to the swarm use it too.
+pub(crate) fn check_ready(state: &ConnectorState, method: &str) -> Result<(), RpcFailure> {
+    if matches!(state.status, ConnectorStatus::Running | ConnectorStatus::InElection) {
+        return Ok(());
+    }
+    Err(RpcFailure::new(
+        RpcErrorCode::NotReady,
+        format!("Connector is not ready ({:?}); retry {} shortly", state.status, method),
+    ))
+}
 
 /// The JSON-RPC 2.0 server.
 pub struct RpcServer {
@@ -108,6 +168,11 @@ impl RpcServer {
 /// Handle a single RPC client connection.
 ///
 /// Reads newline-delimited JSON-RPC requests and sends back responses.
+/// Notifications are executed but never answered; their errors are logged.
+/// A client that half-closes its side, e.g. `echo '{...}' | nc`, still gets
+/// responses to every request it sent. The connection's cancellation token
+/// fires only when the connection is reset, aborting cancellable work of
+/// the request in flight.
 async fn handle_connection(
     stream: tokio::net::TcpStream,
     state: Arc<RwLock<ConnectorState>>,
@@ -115,31 +180,243 @@ async fn handle_connection(
 ) -> Result<(), anyhow::Error> {
     let (reader, mut writer) = stream.into_split();
     let mut lines = BufReader::new(reader).lines();
-
-    while let Some(line) = lines.next_line().await? {
-        let response = process_request(&line, &state, &network_handle).await;
+    let cancel = CancellationToken::new();
+    // Requests the client pipelined while an earlier one was in flight.
+    let mut pending = VecDeque::new();
+    // Set once the client has finished sending.
+    let mut read_closed = false;
+
+    loop {
+        let line = match pending.pop_front() {
+            Some(line) => line,
+            None if read_closed => break,
+            None => match lines.next_line().await? {
+                Some(line) => line,
+                None => break,
+            },
+        };
+        let notification = is_notification(&line);
+        // Subscribe before the backlog is read so no entry slips in between;
+        // the reply's `cursor` filters out entries already in the backlog.
+        let log_tail = match tail_logs_params(&line) {
+            Some(params) if !notification => {
+                Some((params, state.read().await.event_log_tx.subscribe()))
+            }
+            _ => None,
+        };
+        let request = process_request(&line, &state, &network_handle, &cancel);
+        let response =
+            watch_disconnect(&mut lines, &mut pending, &mut read_closed, &cancel, request).await;
+        if cancel.is_cancelled() {
+            tracing::debug!("RPC client connection reset mid-request; dropping response");
+            return Ok(());
+        }
+        if notification {
+            if let Some(error) = &response.error {
+                tracing::warn!(
+                    code = error.code,
+                    message = %error.message,
+                    "JSON-RPC notification failed"
+                );
+            }
+            continue;
+        }
         let response_json = serde_json::to_string(&response)?;
         writer.write_all(response_json.as_bytes()).await?;
         writer.write_all(b"\n").await?;
         writer.flush().await?;
+
+        if let (Some((params, receiver)), Some(result)) = (log_tail, &response.result) {
+            let cursor = result["cursor"].as_u64().unwrap_or(0);
+            return stream_log_tail(lines, writer, receiver, params, cursor).await;
+        }
     }
 
     Ok(())
 }
 
+/// Run `request` while watching the client's side of the connection.
+///
+/// Lines the client sends meanwhile are queued in `pending`. End of input
+/// only sets `read_closed`: the client may have just shut down its write
+/// side and still be waiting for the response. If reading fails, e.g. the
+/// connection was reset, `cancel` is fired so cancellable operations inside
+/// `request` abort early; the request still runs to completion.
+async fn watch_disconnect<R, T>(
+    lines: &mut tokio::io::Lines<R>,
+    pending: &mut VecDeque<String>,
+    read_closed: &mut bool,
+    cancel: &CancellationToken,
+    request: impl Future<Output = T>,
+) -> T
+where
+    R: AsyncBufRead + Unpin,
+{
+    tokio::pin!(request);
+    loop {
+        tokio::select! {
+            biased;
+            output = &mut request => return output,
+            next = lines.next_line(), if !*read_closed && !cancel.is_cancelled() => match next {
+                Ok(Some(line)) => pending.push_back(line),
+                Ok(None) => *read_closed = true,
+                Err(_) => cancel.cancel(),
+            },
+        }
+    }
+}
+
+/// Filter and rate limit of a `swarm.tail_logs` subscription.
+struct LogTailParams {
+    /// Categories to include; `None` includes every category.
+    categories: Option<Vec<LogCategory>>,
+    /// Number of existing entries returned with the reply.
+    backlog: usize,
+    /// Entries streamed per second before further ones are dropped.
+    max_per_sec: u32,
+}
+
+impl LogTailParams {
+    fn from_params(params: &serde_json::Value) -> Result<Self, String> {
+        let categories = match params.get("categories") {
+            None | Some(serde_json::Value::Null) => None,
+            Some(serde_json::Value::Array(names)) => Some(
+                names
+                    .iter()
+                    .map(|name| {
+                        name.as_str()
+                            .and_then(LogCategory::parse)
+                            .ok_or_else(|| format!("Unknown log category: {}", name))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            Some(_) => return Err("'categories' must be an array of strings".into()),
+        };
+        let backlog = params
+            .get("backlog")
+            .and_then(|v| v.as_u64())
+            .map_or(TAIL_LOGS_DEFAULT_BACKLOG, |n| n as usize)
+            .min(TAIL_LOGS_MAX_BACKLOG);
+        let max_per_sec = params
+            .get("max_per_sec")
+            .and_then(|v| v.as_u64())
+            .map_or(TAIL_LOGS_MAX_PER_SEC, |n| n.clamp(1, TAIL_LOGS_MAX_PER_SEC as u64) as u32);
+        Ok(Self {
+            categories,
+            backlog,
+            max_per_sec,
+        })
+    }
+
+    fn matches(&self, entry: &LogEntry) -> bool {
+        self.categories
+            .as_ref()
+            .is_none_or(|categories| categories.contains(&entry.category))
+    }
+}
+
+/// Parsed parameters if `request_str` is a valid `swarm.tail_logs` request.
+fn tail_logs_params(request_str: &str) -> Option<LogTailParams> {
+    let request: SwarmMessage = serde_json::from_str(request_str).ok()?;
+    if request.method != "swarm.tail_logs" {
+        return None;
+    }
+    LogTailParams::from_params(&request.params).ok()
+}
+
+/// A log entry as sent to `swarm.tail_logs` clients, tagged with its sequence number.
+fn log_entry_json(seq: u64, entry: &LogEntry) -> serde_json::Value {
+    let mut value = serde_json::to_value(entry).unwrap_or_default();
+    value["seq"] = seq.into();
+    value
+}
+
+/// Push entries newer than `cursor` to the client as `swarm.log`
+/// notifications until it disconnects. Entries over the rate limit, or
+/// missed because the client fell behind, are dropped and counted in the
+/// next notification's `dropped` field. Further requests on the connection
+/// are ignored.
+async fn stream_log_tail(
+    mut lines: tokio::io::Lines<BufReader<tokio::net::tcp::OwnedReadHalf>>,
+    mut writer: tokio::net::tcp::OwnedWriteHalf,
+    mut receiver: tokio::sync::broadcast::Receiver<(u64, LogEntry)>,
+    params: LogTailParams,
+    cursor: u64,
+) -> Result<(), anyhow::Error> {
+    use tokio::sync::broadcast::error::RecvError;
+
+    let mut window_start = tokio::time::Instant::now();
+    let mut sent_in_window = 0u32;
+    let mut dropped = 0u64;
+    loop {
+        tokio::select! {
+            line = lines.next_line() => {
+                if line?.is_none() {
+                    return Ok(());
+                }
+            }
+            received = receiver.recv() => {
+                let (seq, entry) = match received {
+                    Ok(item) => item,
+                    Err(RecvError::Lagged(missed)) => {
+                        dropped += missed;
+                        continue;
+                    }
+                    Err(RecvError::Closed) => return Ok(()),
+                };
+                if seq <= cursor || !params.matches(&entry) {
+                    continue;
+                }
+                if window_start.elapsed() >= Duration::from_secs(1) {
+                    window_start = tokio::time::Instant::now();
+                    sent_in_window = 0;
+                }
+                if sent_in_window >= params.max_per_sec {
+                    dropped += 1;
+                    continue;
+                }
+                sent_in_window += 1;
+
+                let mut log_params = log_entry_json(seq, &entry);
+                log_params["dropped"] = dropped.into();
+                dropped = 0;
+                let notification = serde_json::json!({
+                    "jsonrpc": JSONRPC_VERSION,
+                    "method": "swarm.log",
+                    "params": log_params,
+                });
+                writer.write_all(notification.to_string().as_bytes()).await?;
+                writer.write_all(b"\n").await?;
+                writer.flush().await?;
+            }
+        }
+    }
+}
+
+/// Whether `request_str` is a JSON-RPC 2.0 notification: a well-formed
+/// request object with no `id` member. An explicit `"id": null` is still a
+/// request, and unparseable input is answered with a parse error.
+fn is_notification(request_str: &str) -> bool {
+    matches!(
+        serde_json::from_str::<serde_json::Value>(request_str),
+        Ok(serde_json::Value::Object(request)) if !request.contains_key("id")
+    )
+}
+
 /// Process a single JSON-RPC request and return a response.
 async fn process_request(
     request_str: &str,
     state: &Arc<RwLock<ConnectorState>>,
     network_handle: &wws_network::SwarmHandle,
+    cancel: &CancellationToken,
 ) -> SwarmResponse {
     // Parse the request.
     let request: SwarmMessage = match serde_json::from_str(request_str) {
         Ok(r) => r,
         Err(e) => {
-            return SwarmResponse::error(
+            return SwarmResponse::rpc_error(
                 None,
-                -32700, // Parse error
+                RpcErrorCode::ParseError,
                 format!("Invalid JSON: {}", e),
             );
         }
@@ -148,22 +425,49 @@ async fn process_request(
     let request_id = request.id.clone();
 
     // Optional per-session RPC token check.
-    if let Ok(required_token) = std::env::var("OPENSWARM_RPC_TOKEN") {
-        if !required_token.trim().is_empty() {
-            let provided = request.params.get("rpc_token").and_then(|v| v.as_str()).unwrap_or("");
-            if provided != required_token.trim() {
-                return SwarmResponse::error(
-                    request_id.clone(),
-                    -32001,
-                    "Unauthorized: invalid or missing rpc_token".into(),
-                );
-            }
+    let required_token = std::env::var("OPENSWARM_RPC_TOKEN")
+        .ok()
+        .filter(|t| !t.trim().is_empty());
+    if let Some(required_token) = &required_token {
+        let provided = request.params.get("rpc_token").and_then(|v| v.as_str()).unwrap_or("");
+        if provided != required_token.trim() {
+            return SwarmResponse::rpc_error(
+                request_id.clone(),
+                RpcErrorCode::Unauthorized,
+                "Unauthorized: invalid or missing rpc_token",
+            );
+        }
+    }
+
+    // Methods that disclose swarm credentials are only served when RPC auth is enabled.
+    if required_token.is_none() && AUTH_REQUIRED_METHODS.contains(&request.method.as_str()) {
+        return SwarmResponse::rpc_error(
+            request_id,
+            RpcErrorCode::Unauthorized,
+            format!("Unauthorized: {} requires OPENSWARM_RPC_TOKEN to be set", request.method),
+        );
+    }
+
+    if NETWORK_METHODS.contains(&request.method.as_str()) {
+        if let Err(failure) = check_ready(&*state.read().await, &request.method) {
+            return failure.into_response(request_id);
         }
     }
 
     match request.method.as_str() {
         "swarm.connect" => handle_connect(request_id, &request.params, network_handle).await,
         "swarm.get_network_stats" => handle_get_network_stats(request_id, state).await,
+        "swarm.get_cascade_progress" => handle_get_cascade_progress(request_id, state).await,
+        "swarm.get_succession_history" => handle_get_succession_history(request_id, state).await,
+        "swarm.get_election" => handle_get_election(request_id, state).await,
+        "swarm.get_bootstrap_status" => handle_get_bootstrap_status(request_id, state).await,
+        "swarm.get_peer_versions" => handle_get_peer_versions(request_id, state).await,
+        "swarm.get_routing_table" => handle_get_routing_table(request_id, network_handle).await,
+        "swarm.refresh_routing" => handle_refresh_routing(request_id, network_handle).await,
+        "swarm.get_connection_metrics" => {
+            handle_get_connection_metrics(request_id, network_handle).await
+        }
+        "swarm.get_topic_stats" => handle_get_topic_stats(request_id, network_handle).await,
         "swarm.propose_plan" => {
             handle_propose_plan(request_id, &request.params, state, network_handle).await
         }
@@ -178,25 +482,48 @@ async fn process_request(
             handle_submit_result(request_id, &request.params, state, network_handle).await
         }
         "swarm.receive_task" => handle_receive_task(request_id, state).await,
+        "swarm.delegate_task" => {
+            handle_delegate_task(request_id, &request.params, state, network_handle).await
+        }
+        "swarm.report_progress" => {
+            handle_report_progress(request_id, &request.params, state, network_handle).await
+        }
         "swarm.get_task" => handle_get_task(request_id, &request.params, state).await,
+        "swarm.get_content" => {
+            handle_get_content(request_id, &request.params, state, network_handle, cancel).await
+        }
+        "swarm.get_content_stats" => handle_get_content_stats(request_id, state).await,
         "swarm.get_task_timeline" => {
             handle_get_task_timeline(request_id, &request.params, state).await
         }
         "swarm.get_status" => handle_get_status(request_id, state).await,
+        "swarm.ping" => handle_ping(request_id, state).await,
+        "swarm.tail_logs" => handle_tail_logs(request_id, &request.params, state).await,
+        "swarm.export_state" => handle_export_state(request_id, state).await,
         "swarm.register_agent" => {
             handle_register_agent(request_id, &request.params, state, network_handle).await
         }
         "swarm.list_swarms" => handle_list_swarms(request_id, state).await,
+        "swarm.get_swarm_history" => {
+            handle_get_swarm_history(request_id, &request.params, state).await
+        }
         "swarm.create_swarm" => {
-            handle_create_swarm(request_id, &request.params, state).await
+            handle_create_swarm(request_id, &request.params, state, network_handle).await
         }
         "swarm.join_swarm" => {
-            handle_join_swarm(request_id, &request.params, state).await
+            handle_join_swarm(request_id, &request.params, state, network_handle).await
+        }
+        "swarm.leave_swarm" => {
+            handle_leave_swarm(request_id, &request.params, state, network_handle).await
+        }
+        "swarm.get_swarm_token" => {
+            handle_get_swarm_token(request_id, &request.params, state).await
         }
         "swarm.inject_task" => {
             handle_inject_task(request_id, &request.params, state, network_handle).await
         }
         "swarm.get_hierarchy" => handle_get_hierarchy(request_id, state).await,
+        "swarm.export_topology" => handle_export_topology(request_id, &request.params, state).await,
         "swarm.get_board_status" => handle_get_board_status(request_id, state).await,
         "swarm.get_deliberation" => {
             handle_get_deliberation(request_id, &request.params, state).await
@@ -207,6 +534,9 @@ async fn process_request(
         "swarm.get_irv_rounds" => {
             handle_get_irv_rounds(request_id, &request.params, state).await
         }
+        "swarm.get_voting_result" => {
+            handle_get_voting_result(request_id, &request.params, state).await
+        }
         "swarm.register_name" => {
             handle_register_name(request_id, &request.params, state).await
         }
@@ -248,9 +578,9 @@ async fn process_request(
         "swarm.verify_receipt" => handle_verify_receipt(request_id, &request.params, state).await,
         "swarm.request_clarification" => handle_request_clarification(request_id, &request.params, state).await,
         "swarm.resolve_clarification" => handle_resolve_clarification(request_id, &request.params, state).await,
-        _ => SwarmResponse::error(
+        _ => SwarmResponse::rpc_error(
             request_id,
-            -32601, // Method not found
+            RpcErrorCode::MethodNotFound,
             format!("Unknown method: {}", request.method),
         ),
     }
@@ -266,7 +596,7 @@ async fn handle_submit_vote(
     let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
         Some(v) if !v.is_empty() => v.to_string(),
         _ => {
-            return SwarmResponse::error(id, -32602, "Missing 'task_id' parameter".to_string());
+            return SwarmResponse::invalid_params(id, "Missing 'task_id' parameter");
         }
     };
 
@@ -277,10 +607,9 @@ async fn handle_submit_vote(
             .filter_map(|v| v.as_str().map(|s| s.to_string()))
             .collect(),
         _ => {
-            return SwarmResponse::error(
+            return SwarmResponse::invalid_params(
                 id,
-                -32602,
-                "Missing or empty 'rankings' or 'ranked_plan_ids' parameter".to_string(),
+                "Missing or empty 'rankings' or 'ranked_plan_ids' parameter",
             );
         }
     };
@@ -290,11 +619,15 @@ async fn handle_submit_vote(
         params
             .get("epoch")
             .and_then(|v| v.as_u64())
-            .unwrap_or_else(|| state.epoch_manager.current_epoch())
+            .unwrap_or_else(|| state.task_epoch(&task_id))
     };
 
     let (voter, swarm_id, ballot_count, proposal_count, accepted_rankings) = {
         let mut state = state.write().await;
+        let task_swarm = state.swarm_of_task(&task_id);
+        if let Err(failure) = check_consensus_peers(&mut state, &task_swarm) {
+            return failure.into_response(id);
+        }
         let voter = state.agent_id.clone();
 
         let proposals: std::collections::HashMap<String, AgentId> = rankings
@@ -308,9 +641,10 @@ async fn handle_submit_vote(
             .collect();
 
         let (ballot_count, proposal_count, accepted_rankings) = {
+            let voting_config = state.voting_config_for_task(&task_id);
             let voting = state.voting_engines.entry(task_id.clone()).or_insert_with(|| {
                 let engine = wws_consensus::VotingEngine::new(
-                    wws_consensus::voting::VotingConfig::default(),
+                    voting_config,
                     task_id.clone(),
                     epoch,
                 );
@@ -338,9 +672,8 @@ async fn handle_submit_vote(
                         attempts_left -= 1;
                     }
                     Err(e) => {
-                        return SwarmResponse::error(
+                        return SwarmResponse::operation_failed(
                             id,
-                            -32000,
                             format!("Failed to record vote: {}", e),
                         );
                     }
@@ -382,7 +715,7 @@ async fn handle_submit_vote(
 
         (
             voter,
-            state.current_swarm_id.as_str().to_string(),
+            state.swarm_of_task(&task_id),
             ballot_count,
             proposal_count,
             accepted_rankings,
@@ -402,7 +735,7 @@ async fn handle_submit_vote(
     );
 
     if let Ok(data) = serde_json::to_vec(&vote_msg) {
-        let topic = SwarmTopics::voting_for(&swarm_id, &task_id);
+        let topic = SwarmTopics::voting_for(&swarm_id, &task_id, epoch);
         let _ = network_handle.publish(&topic, data).await;
     }
 
@@ -431,7 +764,7 @@ async fn handle_submit_critique(
     let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
         Some(v) if !v.is_empty() => v.to_string(),
         _ => {
-            return SwarmResponse::error(id, -32602, "Missing 'task_id' parameter".to_string());
+            return SwarmResponse::invalid_params(id, "Missing 'task_id' parameter");
         }
     };
 
@@ -452,15 +785,14 @@ async fn handle_submit_critique(
     {
         Some(scores) => scores,
         None => {
-            return SwarmResponse::error(
+            return SwarmResponse::invalid_params(
                 id,
-                -32602,
-                "Missing or invalid 'plan_scores' parameter".to_string(),
+                "Missing or invalid 'plan_scores' parameter",
             );
         }
     };
 
-    let (voter, swarm_id) = {
+    let (voter, swarm_id, epoch) = {
         let mut state = state.write().await;
         let voter = state.agent_id.clone();
 
@@ -523,7 +855,11 @@ async fn handle_submit_critique(
             ),
         );
 
-        (voter, state.current_swarm_id.as_str().to_string())
+        (
+            voter,
+            state.swarm_of_task(&task_id),
+            state.task_epoch(&task_id),
+        )
     };
 
     // Broadcast discussion.critique P2P message so all board members receive it
@@ -540,7 +876,7 @@ async fn handle_submit_critique(
         String::new(),
     );
     if let Ok(data) = serde_json::to_vec(&msg) {
-        let topic = SwarmTopics::voting_for(&swarm_id, &task_id);
+        let topic = SwarmTopics::voting_for(&swarm_id, &task_id, epoch);
         let _ = network_handle.publish(&topic, data).await;
     }
 
@@ -604,6 +940,10 @@ async fn handle_get_voting_state(
 }
 
 /// Handle `swarm.connect` - connect to a peer by multiaddress.
+///
+/// An address that does not parse, or names no transport to dial over, is
+/// rejected as invalid params (-32602); -32000 is reserved for dials that
+/// were attempted and failed.
 async fn handle_connect(
     id: Option<String>,
     params: &serde_json::Value,
@@ -612,10 +952,9 @@ async fn handle_connect(
     let addr_str = match params.get("addr").and_then(|v| v.as_str()) {
         Some(a) => a,
         None => {
-            return SwarmResponse::error(
+            return SwarmResponse::invalid_params(
                 id,
-                -32602,
-                "Missing 'addr' parameter".into(),
+                "Missing 'addr' parameter",
             );
         }
     };
@@ -623,29 +962,57 @@ async fn handle_connect(
     let addr: wws_network::Multiaddr = match addr_str.parse() {
         Ok(a) => a,
         Err(e) => {
-            return SwarmResponse::error(
+            return SwarmResponse::invalid_params(
                 id,
-                -32602,
-                format!("Invalid multiaddress: {}", e),
+                format!("Invalid multiaddress '{}': {}", addr_str, e),
             );
         }
     };
+    if !has_transport(&addr) {
+        return SwarmResponse::invalid_params(
+            id,
+            format!(
+                "Multiaddress '{}' has no transport component; \
+                 expected e.g. /tcp/<port> or /udp/<port>/quic-v1",
+                addr
+            ),
+        );
+    }
 
     match network_handle.dial(addr).await {
         Ok(()) => SwarmResponse::success(id, serde_json::json!({"connected": true})),
-        Err(e) => SwarmResponse::error(id, -32000, format!("Dial failed: {}", e)),
+        Err(e) => SwarmResponse::operation_failed(id, format!("Dial failed: {}", e)),
     }
 }
 
+/// Whether `addr` names a transport to dial over. `/dnsaddr` resolves to
+/// full addresses and a relay circuit carries its own, so both count.
+fn has_transport(addr: &wws_network::Multiaddr) -> bool {
+    use wws_network::libp2p::multiaddr::Protocol;
+    addr.iter().any(|p| {
+        matches!(
+            p,
+            Protocol::Tcp(_)
+                | Protocol::QuicV1
+                | Protocol::Quic
+                | Protocol::Ws(_)
+                | Protocol::Wss(_)
+                | Protocol::Memory(_)
+                | Protocol::P2pCircuit
+                | Protocol::Dnsaddr(_)
+        )
+    })
+}
+
 /// Handle `swarm.get_network_stats` - return current network statistics.
 async fn handle_get_network_stats(
     id: Option<String>,
     state: &Arc<RwLock<ConnectorState>>,
 ) -> SwarmResponse {
-    let state = state.read().await;
-    let mut stats = state.network_stats.clone();
+    let snapshot = state.read().await.snapshot();
+    let mut stats = snapshot.network_stats.clone();
     stats.total_agents =
-        state.active_member_count(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS)) as u64;
+        snapshot.active_member_count(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS)) as u64;
 
     SwarmResponse::success(
         id,
@@ -653,334 +1020,398 @@ async fn handle_get_network_stats(
     )
 }
 
-/// Handle `swarm.propose_plan` - submit a task decomposition plan.
-pub(crate) async fn handle_propose_plan(
+/// Handle `swarm.get_cascade_progress` - per-root-task cascade completion.
+///
+/// Each root task reports the aggregate ratio over every cascade level in its
+/// branch, a per-level breakdown, and the longest-outstanding pending subtask.
+async fn handle_get_cascade_progress(
     id: Option<String>,
-    params: &serde_json::Value,
     state: &Arc<RwLock<ConnectorState>>,
-    network_handle: &wws_network::SwarmHandle,
 ) -> SwarmResponse {
-    let mut plan: Plan = match serde_json::from_value(params.clone()) {
-        Ok(p) => p,
-        Err(e) => {
-            return SwarmResponse::error(
-                id,
-                -32602,
-                format!("Invalid plan: {}", e),
-            );
-        }
-    };
-
-    {
-        let state = state.read().await;
-        plan.proposer = state.agent_id.clone();
-    }
+    let state = state.read().await;
+    let cascade = &state.cascade;
+    let now = chrono::Utc::now();
 
-    if plan.subtasks.is_empty() {
-        return SwarmResponse::error(
-            id,
-            -32013,
-            "Plan must include at least one subtask".to_string(),
-        );
+    let mut roots: std::collections::BTreeMap<String, Vec<(String, usize, usize)>> =
+        std::collections::BTreeMap::new();
+    for (task_id, (completed, total)) in cascade.progress_by_task() {
+        roots
+            .entry(cascade.root_of(&task_id))
+            .or_default()
+            .push((task_id, completed, total));
     }
 
-    let plan_hash = match wws_consensus::RfpCoordinator::compute_plan_hash(&plan) {
-        Ok(h) => h,
-        Err(e) => {
-            return SwarmResponse::error(
-                id,
-                -32000,
-                format!("Hash computation failed: {}", e),
-            );
+    let ratio = |completed: usize, total: usize| {
+        if total == 0 {
+            0.0
+        } else {
+            completed as f64 / total as f64
         }
     };
 
-    let (swarm_id, has_task, subtask_count, reveals_to_publish) = {
-        let mut state = state.write().await;
-
-        let task = state.task_details.get(&plan.task_id).cloned().unwrap_or_else(|| Task {
-            task_id: plan.task_id.clone(),
-            parent_task_id: None,
-            epoch: plan.epoch,
-            status: TaskStatus::ProposalPhase,
-            description: "Task proposed for decomposition".to_string(),
-            assigned_to: Some(plan.proposer.clone()),
-            tier_level: 1,
-            subtasks: plan
-                .subtasks
+    let pending = cascade.pending_assignments();
+    let progress: Vec<serde_json::Value> = roots
+        .into_iter()
+        .map(|(root_task_id, mut levels)| {
+            levels.sort_by(|a, b| a.0.cmp(&b.0));
+            let completed: usize = levels.iter().map(|l| l.1).sum();
+            let total: usize = levels.iter().map(|l| l.2).sum();
+            let slowest = pending
                 .iter()
-                .map(|s| format!("{}:{}", s.index, s.description))
-                .collect(),
-            created_at: chrono::Utc::now(),
-            deadline: None,
-            ..Default::default()
-        });
-
-        // If a plan is proposed for a task that was previously assigned for
-        // direct execution (low-complexity path), transition it to ProposalPhase
-        // and clear the direct assignment.  The agent is re-decomposing the task
-        // instead of executing it directly, so the task becomes a coordination
-        // task that will get subtasks after voting completes.
-        if let Some(existing) = state.task_details.get_mut(&plan.task_id) {
-            if existing.status == TaskStatus::InProgress && existing.assigned_to.is_some() {
-                tracing::info!(
-                    task_id = %plan.task_id,
-                    previous_assignee = ?existing.assigned_to,
-                    "Task re-decomposed via propose_plan: clearing direct assignment, transitioning to ProposalPhase"
-                );
-                existing.status = TaskStatus::ProposalPhase;
-                existing.assigned_to = None;
-            }
-        }
-
-        let task_tier_level = state
-            .task_details
-            .get(&plan.task_id)
-            .map(|t| t.tier_level)
-            .unwrap_or(1);
-        // Expected proposers = board size (Tier1 members).
-        // At proposal time the hierarchy hasn't formed yet, so compute
-        // the board size from the same formula used in recompute_hierarchy:
-        //   pool <= 2  → no board
-        //   pool 3..12 → board = 3
-        //   pool 13..30 → board = round(pool/3), clamped 3-10
-        //   pool > 30  → board = round(sqrt(pool)), clamped 5-10
-        let total_members = state.member_last_seen.len() + 1; // +1 for self
-        let pool = total_members.saturating_sub(1); // minus injector
-        let expected_proposers = if pool <= 2 {
-            pool.max(1)
-        } else if pool <= 12 {
-            3
-        } else if pool <= 30 {
-            ((pool as f64) / 3.0).round().max(3.0).min(10.0) as usize
-        } else {
-            ((pool as f64).sqrt()).round().max(5.0).min(10.0) as usize
-        };
-
-        let commit = ProposalCommitParams {
-            task_id: plan.task_id.clone(),
-            proposer: plan.proposer.clone(),
-            epoch: plan.epoch,
-            plan_hash: plan_hash.clone(),
-        };
-
-        let reveal_phase_ready = {
-            let coordinator = state
-                .rfp_coordinators
-                .entry(plan.task_id.clone())
-                .or_insert_with(|| {
-                    wws_consensus::RfpCoordinator::new(
-                        plan.task_id.clone(),
-                        plan.epoch,
-                        expected_proposers,
-                    )
+                .filter(|a| cascade.root_of(&a.parent_task_id) == root_task_id)
+                .min_by_key(|a| a.task.created_at)
+                .map(|a| {
+                    serde_json::json!({
+                        "task_id": a.task.task_id,
+                        "parent_task_id": a.parent_task_id,
+                        "assignee": a.assignee.to_string(),
+                        "pending_secs": (now - a.task.created_at).num_seconds().max(0),
+                    })
                 });
+            serde_json::json!({
+                "root_task_id": root_task_id,
+                "completed": completed,
+                "total": total,
+                "ratio": ratio(completed, total),
+                "tasks": levels
+                    .iter()
+                    .map(|(task_id, completed, total)| serde_json::json!({
+                        "task_id": task_id,
+                        "completed": completed,
+                        "total": total,
+                        "ratio": ratio(*completed, *total),
+                    }))
+                    .collect::<Vec<_>>(),
+                "slowest_pending": slowest,
+            })
+        })
+        .collect();
 
-            if matches!(coordinator.phase(), RfpPhase::Idle) {
-                if let Err(e) = coordinator.inject_task(&task) {
-                    return SwarmResponse::error(
-                        id,
-                        -32000,
-                        format!("Failed to initialize RFP: {}", e),
-                    );
-                }
-            }
-
-            if let Err(e) = coordinator.record_commit(&commit) {
-                return SwarmResponse::error(
-                    id,
-                    -32000,
-                    format!("Failed to record proposal commit: {}", e),
-                );
-            }
+    SwarmResponse::success(id, serde_json::json!({ "roots": progress }))
+}
 
-            matches!(coordinator.phase(), RfpPhase::RevealPhase)
-        };
+/// Handle `swarm.get_succession_history` - list confirmed leader handovers, oldest first.
+async fn handle_get_succession_history(
+    id: Option<String>,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let state = state.read().await;
+    let events: Vec<serde_json::Value> = state
+        .succession
+        .history()
+        .iter()
+        .map(|event| {
+            serde_json::json!({
+                "failed": event.failed.to_string(),
+                "successor": event.successor.to_string(),
+                "epoch": event.epoch,
+                "at": event.at,
+            })
+        })
+        .collect();
 
-        state
-            .pending_plan_reveals
-            .entry(plan.task_id.clone())
-            .or_default()
-            .insert(plan.proposer.to_string(), plan.clone());
-
-        let mut reveals_to_publish = Vec::new();
-        if reveal_phase_ready {
-            let mut pending_items = state
-                .pending_plan_reveals
-                .remove(&plan.task_id)
-                .unwrap_or_default()
-                .into_iter()
-                .collect::<Vec<(String, Plan)>>();
-            pending_items.sort_by(|a, b| a.0.cmp(&b.0));
-
-            let mut reveal_errors = Vec::new();
-            if let Some(coordinator) = state.rfp_coordinators.get_mut(&plan.task_id) {
-                for (_, pending_plan) in pending_items {
-                    let reveal = ProposalRevealParams {
-                        task_id: plan.task_id.clone(),
-                        plan: pending_plan,
-                    };
-                    if let Err(e) = coordinator.record_reveal(&reveal) {
-                        reveal_errors.push(format!(
-                            "Failed to record deferred proposal reveal for task {}: {}",
-                            plan.task_id, e
-                        ));
-                    } else {
-                        reveals_to_publish.push(reveal);
-                    }
-                }
-            }
-            for err in reveal_errors {
-                state.push_log(crate::tui::LogCategory::Error, err);
-            }
-        }
+    SwarmResponse::success(id, serde_json::json!({ "events": events }))
+}
 
-        state.push_log(
-            crate::tui::LogCategory::Task,
-            format!(
-                "Plan proposed for task {}: {} subtasks (plan {}) -> {}",
-                plan.task_id,
-                plan.subtasks.len(),
-                plan.plan_id,
-                plan
-                    .subtasks
-                    .iter()
-                    .map(|s| format!("{}:{}", s.index, s.description))
-                    .collect::<Vec<_>>()
-                    .join(" | ")
-            ),
-        );
-        state.push_task_timeline_event(
-            &plan.task_id,
-            "proposed",
-            format!("Plan {} proposed with {} subtasks", plan.plan_id, plan.subtasks.len()),
-            Some(plan.proposer.to_string()),
+/// Handle `swarm.get_election` - report the Tier-1 election of the epoch
+/// transition in progress: candidates with their running tallies, the
+/// number of seats and the time until it is finalized. Outside an election
+/// `in_election` is false and `candidates` is empty.
+async fn handle_get_election(
+    id: Option<String>,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let state = state.read().await;
+    let election = state
+        .election
+        .as_ref()
+        .filter(|e| matches!(state.status, ConnectorStatus::InElection) && !e.is_finalized());
+    let Some(election) = election else {
+        return SwarmResponse::success(
+            id,
+            serde_json::json!({
+                "in_election": false,
+                "epoch": state.epoch_manager.current_epoch(),
+                "candidates": [],
+                "senate_size": 0,
+                "votes_cast": 0,
+                "time_remaining_secs": 0,
+            }),
         );
+    };
 
-        // Record proposal as a deliberation message
-        let subtask_descs = plan
-            .subtasks
-            .iter()
-            .map(|s| format!("{}. {}", s.index, s.description))
-            .collect::<Vec<_>>()
-            .join("\n");
-        state
-            .deliberation_messages
-            .entry(plan.task_id.clone())
-            .or_default()
-            .push(DeliberationMessage {
-                id: uuid::Uuid::new_v4().to_string(),
-                task_id: plan.task_id.clone(),
-                timestamp: chrono::Utc::now(),
-                speaker: plan.proposer.clone(),
-                round: 1,
-                message_type: DeliberationType::ProposalSubmission,
-                content: format!(
-                    "Plan: {}\nSubtasks:\n{}",
-                    plan.rationale, subtask_descs
-                ),
-                referenced_plan_id: Some(plan.plan_id.clone()),
-                critic_scores: None,
-            });
+    let candidates: Vec<serde_json::Value> = election
+        .live_tally()
+        .into_iter()
+        .enumerate()
+        .map(|(rank, c)| {
+            serde_json::json!({
+                "agent_id": c.agent_id.to_string(),
+                "rank": rank + 1,
+                "tally": c.tally,
+                "composite_score": c.composite,
+            })
+        })
+        .collect();
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "in_election": true,
+            "epoch": election.epoch(),
+            "candidates": candidates,
+            "senate_size": election.tier1_slots(),
+            "votes_cast": election.vote_count(),
+            "time_remaining_secs": state.epoch_manager.election_remaining_time().num_seconds(),
+        }),
+    )
+}
 
-        state.push_log(
-            crate::tui::LogCategory::System,
-            format!(
-                "AUDIT plan.propose actor={} task_id={} plan_id={} subtasks={}",
-                plan.proposer,
-                plan.task_id,
-                plan.plan_id,
-                plan.subtasks.len()
-            ),
-        );
+/// Handle `swarm.get_bootstrap_status` - report reachability of each configured
+/// bootstrap peer and DHT puts (e.g. the swarm registry entry) still awaiting
+/// confirmation.
+async fn handle_get_bootstrap_status(
+    id: Option<String>,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let state = state.read().await;
+    let peers: Vec<&crate::connector::BootstrapPeerStatus> =
+        state.bootstrap_status.values().collect();
+    let pending_dht_puts = state.dht_put_retries.status(std::time::Instant::now());
+    SwarmResponse::success(
+        id,
+        serde_json::json!({ "peers": peers, "pending_dht_puts": pending_dht_puts }),
+    )
+}
 
-        (
-            state.current_swarm_id.as_str().to_string(),
-            state.task_details.contains_key(&plan.task_id),
-            plan.subtasks.len(),
-            reveals_to_publish,
-        )
+/// Handle `swarm.get_peer_versions` - report which software versions the
+/// connected peers run and which of them this node cannot interoperate with.
+async fn handle_get_peer_versions(
+    id: Option<String>,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let report = state.read().await.peer_version_report();
+    match serde_json::to_value(report) {
+        Ok(result) => SwarmResponse::success(id, result),
+        Err(e) => SwarmResponse::rpc_error(id, RpcErrorCode::InternalError, e.to_string()),
+    }
+}
+
+/// Handle `swarm.get_topic_stats` - messages sent and received per GossipSub
+/// topic, keyed by topic name.
+async fn handle_get_topic_stats(
+    id: Option<String>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let topics = match network_handle.topic_stats().await {
+        Ok(topics) => topics,
+        Err(e) => return SwarmResponse::operation_failed(id, format!("Topic stats unavailable: {}", e)),
     };
+    let (sent, received) = topics
+        .values()
+        .fold((0u64, 0u64), |(s, r), c| (s + c.sent, r + c.received));
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "topics": topics,
+            "total_sent": sent,
+            "total_received": received,
+        }),
+    )
+}
 
-    if !has_task {
-        let mut state = state.write().await;
-        state.task_details.insert(
-            plan.task_id.clone(),
-            Task {
-                task_id: plan.task_id.clone(),
-                parent_task_id: None,
-                epoch: plan.epoch,
-                status: TaskStatus::ProposalPhase,
-                description: "Task proposed for decomposition".to_string(),
-                assigned_to: Some(plan.proposer.clone()),
-                tier_level: 1,
-                subtasks: plan
-                    .subtasks
-                    .iter()
-                    .map(|s| format!("{}:{}", s.index, s.description))
-                    .collect(),
-                created_at: chrono::Utc::now(),
-                deadline: None,
-                ..Default::default()
-            },
-        );
+/// Handle `swarm.refresh_routing` - start a Kademlia random walk immediately
+/// rather than waiting for the discovery interval.
+async fn handle_refresh_routing(
+    id: Option<String>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    match network_handle.trigger_random_walk().await {
+        Ok(started) => SwarmResponse::success(id, serde_json::json!({ "started": started })),
+        Err(e) => SwarmResponse::operation_failed(id, format!("Random walk unavailable: {}", e)),
     }
+}
 
-    {
-        let mut state = state.write().await;
-        let task_tier_level = state
-            .task_details
-            .get(&plan.task_id)
-            .map(|t| t.tier_level)
-            .unwrap_or(1);
-        let tier = tier_from_level(task_tier_level);
-        let tier_members = active_members_in_tier(&state, tier, Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS));
-        let expected = if !tier_members.is_empty() {
-            tier_members.len()
-        } else {
-            state.active_member_ids(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS)).len()
-        }.max(1);
-        state.task_vote_requirements.insert(
-            plan.task_id.clone(),
-            crate::connector::TaskVoteRequirement {
-                expected_proposers: expected,
-                expected_voters: expected,
-                tier_level: task_tier_level,
-            },
-        );
-
-        let proposal_owners: std::collections::HashMap<String, AgentId> = state
-            .rfp_coordinators
-            .get(&plan.task_id)
-            .map(|rfp| {
-                rfp.reveals
-                    .values()
-                    .map(|r| (r.plan.plan_id.clone(), r.plan.proposer.clone()))
-                    .collect()
+/// Handle `swarm.get_routing_table` - dump the Kademlia routing table with the
+/// swarm size estimate derived from it.
+async fn handle_get_routing_table(
+    id: Option<String>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let table = match network_handle.routing_table().await {
+        Ok(table) => table,
+        Err(e) => {
+            return SwarmResponse::operation_failed(id, format!("Routing table unavailable: {}", e))
+        }
+    };
+    let estimated_swarm_size = network_handle.estimated_swarm_size().await.unwrap_or(0);
+    let peers: Vec<serde_json::Value> = table
+        .iter()
+        .map(|entry| {
+            serde_json::json!({
+                "peer_id": entry.peer_id.to_string(),
+                "addrs": entry.addrs.iter().map(|a| a.to_string()).collect::<Vec<_>>(),
+                "bucket": entry.bucket,
             })
-            .unwrap_or_default();
-
-        let has_proposals = !proposal_owners.is_empty();
-        let voting = state.voting_engines.entry(plan.task_id.clone()).or_insert_with(|| {
-            wws_consensus::VotingEngine::new(
-                wws_consensus::voting::VotingConfig::default(),
-                plan.task_id.clone(),
-                plan.epoch,
-            )
-        });
-        voting.set_proposals(proposal_owners);
+        })
+        .collect();
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "peer_count": peers.len(),
+            "estimated_swarm_size": estimated_swarm_size,
+            "peers": peers,
+        }),
+    )
+}
 
-        if let Some(task) = state.task_details.get_mut(&plan.task_id) {
-            task.status = if has_proposals {
-                TaskStatus::VotingPhase
-            } else {
-                TaskStatus::ProposalPhase
-            };
+/// Handle `swarm.get_connection_metrics` - per-peer connection counts, open
+/// addresses and dial outcomes since startup, for diagnosing flaky links.
+async fn handle_get_connection_metrics(
+    id: Option<String>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let metrics = match network_handle.connection_metrics().await {
+        Ok(metrics) => metrics,
+        Err(e) => {
+            return SwarmResponse::operation_failed(id, format!("Connection metrics unavailable: {}", e))
+        }
+    };
+    let peers: Vec<serde_json::Value> = metrics
+        .peers
+        .iter()
+        .map(|(peer_id, m)| {
+            serde_json::json!({
+                "peer_id": peer_id.to_string(),
+                "open_connections": m.open_connections,
+                "addrs": m.addrs.iter().map(|a| a.to_string()).collect::<Vec<_>>(),
+                "established": m.established,
+                "dials_succeeded": m.dials_succeeded,
+                "dials_failed": m.dials_failed,
+                "closed": m.closed,
+                "last_dial_error": m.last_dial_error,
+            })
+        })
+        .collect();
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "peers": peers,
+            "unattributed_dial_failures": metrics.unattributed_dial_failures,
+        }),
+    )
+}
+
+/// An RPC failure produced by a state transition, mapped to a JSON-RPC error.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RpcFailure {
+    pub code: RpcErrorCode,
+    pub message: String,
+}
+
+impl RpcFailure {
+    pub(crate) fn new(code: RpcErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
         }
     }
 
-    let proposals_topic = SwarmTopics::proposals_for(&swarm_id, &plan.task_id);
-    let voting_topic = SwarmTopics::voting_for(&swarm_id, &plan.task_id);
+    fn into_response(self, id: Option<String>) -> SwarmResponse {
+        SwarmResponse::rpc_error(id, self.code, self.message)
+    }
+}
+
+
+/// Read the optional `swarm_id` parameter naming the swarm a request targets.
+///
+/// Returns `Ok(None)` when absent, or a failure when the connector is not a
+/// member of the named swarm.
+fn target_swarm_param(
+    params: &serde_json::Value,
+    state: &ConnectorState,
+) -> Result<Option<String>, RpcFailure> {
+    match params.get("swarm_id") {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(v) => match v.as_str() {
+            Some(swarm_id) if state.is_swarm_member(swarm_id) => Ok(Some(swarm_id.to_string())),
+            Some(swarm_id) => Err(RpcFailure::new(
+                RpcErrorCode::InvalidParams,
+                format!("Not a member of swarm '{}'", swarm_id),
+            )),
+            None => Err(RpcFailure::new(
+                RpcErrorCode::InvalidParams,
+                "'swarm_id' must be a string",
+            )),
+        },
+    }
+}
+
+/// Refuse a proposal or vote within `swarm_id` while fewer than that
+/// swarm's `consensus.min_peers_for_consensus` peers are connected.
+fn check_consensus_peers(state: &mut ConnectorState, swarm_id: &str) -> Result<(), RpcFailure> {
+    if state.consensus_participation_allowed_in(swarm_id) {
+        return Ok(());
+    }
+    Err(RpcFailure::new(
+        RpcErrorCode::InvalidState,
+        format!(
+            "Abstaining from consensus: {} peers connected, {} required",
+            state.agent_set.len(),
+            state.min_peers_for_consensus_in(swarm_id)
+        ),
+    ))
+}
+
+/// Handle `swarm.propose_plan` - submit a task decomposition plan.
+///
+/// Commits are published on the task's swarm, or on the optional
+/// `swarm_id` the connector is a member of.
+pub(crate) async fn handle_propose_plan(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let plan: Plan = match serde_json::from_value(params.clone()) {
+        Ok(p) => p,
+        Err(e) => {
+            return SwarmResponse::invalid_params(
+                id,
+                format!("Invalid plan: {}", e),
+            );
+        }
+    };
+
+    let outcome = {
+        let mut state = state.write().await;
+        let target_swarm = match target_swarm_param(params, &state) {
+            Ok(target) => target,
+            Err(failure) => return failure.into_response(id),
+        };
+        let plan_swarm = target_swarm.clone().unwrap_or_else(|| state.swarm_of_task(&plan.task_id));
+        if let Err(failure) = check_consensus_peers(&mut state, &plan_swarm) {
+            return failure.into_response(id);
+        }
+        match state.apply_plan_proposal(plan) {
+            Ok(mut outcome) => {
+                if let Some(swarm_id) = target_swarm {
+                    outcome.swarm_id = swarm_id;
+                }
+                outcome
+            }
+            Err(failure) => return failure.into_response(id),
+        }
+    };
+    let PlanProposalOutcome {
+        plan,
+        plan_hash,
+        swarm_id,
+        reveals_to_publish,
+    } = outcome;
+    let subtask_count = plan.subtasks.len();
+
+    let proposals_topic = SwarmTopics::proposals_for(&swarm_id, &plan.task_id, plan.epoch);
+    let voting_topic = SwarmTopics::voting_for(&swarm_id, &plan.task_id, plan.epoch);
     let results_topic = SwarmTopics::results_for(&swarm_id, &plan.task_id);
 
     if let Err(e) = network_handle.subscribe(&proposals_topic).await {
@@ -1007,9 +1438,8 @@ pub(crate) async fn handle_propose_plan(
     let commit_data = match serde_json::to_vec(&commit_msg) {
         Ok(data) => data,
         Err(e) => {
-            return SwarmResponse::error(
+            return SwarmResponse::operation_failed(
                 id,
-                -32000,
                 format!("Failed to serialize proposal commit: {}", e),
             );
         }
@@ -1034,9 +1464,8 @@ pub(crate) async fn handle_propose_plan(
     let current_reveal_data = match serde_json::to_vec(&current_reveal_msg) {
         Ok(data) => data,
         Err(e) => {
-            return SwarmResponse::error(
+            return SwarmResponse::operation_failed(
                 id,
-                -32000,
                 format!("Failed to serialize proposal reveal: {}", e),
             );
         }
@@ -1059,9 +1488,8 @@ pub(crate) async fn handle_propose_plan(
         let reveal_data = match serde_json::to_vec(&reveal_msg) {
             Ok(data) => data,
             Err(e) => {
-                return SwarmResponse::error(
+                return SwarmResponse::operation_failed(
                     id,
-                    -32000,
                     format!("Failed to serialize proposal reveal: {}", e),
                 );
             }
@@ -1112,7 +1540,6 @@ pub(crate) async fn handle_propose_plan(
             "commit_published": commit_published,
             "reveal_published": reveal_published,
             "subtasks_created": subtask_count,
-            "assignments_published": 0,
         }),
     )
 }
@@ -1127,273 +1554,232 @@ pub(crate) async fn handle_submit_result(
     let mut submission: ResultSubmissionParams = match serde_json::from_value(params.clone()) {
         Ok(s) => s,
         Err(e) => {
-            return SwarmResponse::error(
+            return SwarmResponse::invalid_params(
                 id,
-                -32602,
                 format!("Invalid result submission: {}", e),
             );
         }
     };
 
-    {
-        let state = state.read().await;
-        submission.agent_id = state.agent_id.clone();
-        submission.artifact.producer = state.agent_id.clone();
+    // Add to Merkle DAG and update task state.
+    let outcome = match state.write().await.apply_result_submission(&mut submission, params) {
+        Ok(outcome) => outcome,
+        Err(failure) => return failure.into_response(id),
+    };
+
+    publish_result(network_handle, &submission, &outcome).await;
+    if outcome.settled {
+        crate::result_aggregation::submit_aggregated_results(state, network_handle, &submission.task_id)
+            .await;
     }
 
-    // Add to Merkle DAG and update task state.
-    let dag_nodes = {
-        let mut state = state.write().await;
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "task_id": submission.task_id,
+            "artifact_id": submission.artifact.artifact_id,
+            "content_cid": outcome.content_cid,
+            "accepted": true,
+            "signed": !submission.artifact.signature.is_empty(),
+            "settled": outcome.settled,
+            "dag_nodes": outcome.dag_nodes,
+        }),
+    )
+}
 
-        if let Some(task) = state.task_details.get(&submission.task_id) {
-            // Allow submission when:
-            //  (a) task is assigned to this agent, OR
-            //  (b) task has no assignee (root/coordinator task) — connector synthesizes on behalf
-            //  (c) is_synthesis=true — coordinator synthesizing subtask results (any agent allowed)
-            let is_synthesis = params.get("is_synthesis").and_then(|v| v.as_bool()).unwrap_or(false);
-            let assignee_ok = is_synthesis
-                || task.assigned_to.is_none()
-                || task.assigned_to.as_ref() == Some(&submission.agent_id);
-            if !assignee_ok {
-                return SwarmResponse::error(
-                    id,
-                    -32012,
-                    format!(
-                        "Result submission ignored for {}: assignee {} is no longer current",
-                        submission.task_id, submission.agent_id
-                    ),
-                );
+/// Publish a submitted result to both the task-specific results topic and
+/// the tier-based task topic so all nodes (not just coordinator + assignee)
+/// learn about subtask completion.
+pub(crate) async fn publish_result(
+    network_handle: &wws_network::SwarmHandle,
+    submission: &ResultSubmissionParams,
+    outcome: &ResultSubmissionOutcome,
+) {
+    let msg = SwarmMessage::new(
+        ProtocolMethod::ResultSubmission.as_str(),
+        serde_json::to_value(submission).unwrap_or_default(),
+        String::new(),
+    );
+    if let Ok(data) = serde_json::to_vec(&msg) {
+        // Task-specific results topic (coordinator + assignee subscribe)
+        let topic = SwarmTopics::results_for(&outcome.swarm_id, &submission.task_id);
+        if let Err(e) = network_handle.publish(&topic, data.clone()).await {
+            if e.is_retryable() {
+                tracing::debug!(error = %e, "Result not published to results topic yet");
+            } else {
+                tracing::warn!(error = %e, "Failed to publish result to results topic");
+            }
+        }
+        // Tier-based topic (all nodes subscribe) — enables swarm-wide status sync
+        let tier_topic = SwarmTopics::tasks_for(&outcome.swarm_id, outcome.task_tier);
+        if let Err(e) = network_handle.publish(&tier_topic, data).await {
+            if e.is_retryable() {
+                tracing::debug!(error = %e, "Result not published to tier topic yet");
+            } else {
+                tracing::warn!(error = %e, "Failed to publish result to tier topic");
             }
-            if task.parent_task_id.is_none() && task.subtasks.is_empty() {
-                return SwarmResponse::error(
+        }
+    }
+    // Let peers outside the results topic find this node as a source of
+    // the result content.
+    let cid = &submission.artifact.content_cid;
+    if !cid.is_empty() {
+        if let Err(e) = network_handle.start_providing(cid).await {
+            tracing::debug!(cid = %cid, error = %e, "Result content not announced as provided");
+        }
+    }
+}
+
+/// Handle `swarm.delegate_task` - hand an in-progress subtask to a peer.
+///
+/// Params: `task_id`, `to` (the delegate's DID) and optionally `from`,
+/// which must be this agent: only a task's own assignee can hand it off.
+/// The delegate must be a known swarm member. The delegation is signed and
+/// published on the task's tier topic, where both the previous and the new
+/// assignee pick it up.
+async fn handle_delegate_task(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
+        Some(v) if !v.trim().is_empty() => v.trim().to_string(),
+        _ => return SwarmResponse::invalid_params(id, "Missing 'task_id' parameter"),
+    };
+    let to = match params.get("to").and_then(|v| v.as_str()) {
+        Some(v) if !v.trim().is_empty() => AgentId::new(v.trim().to_string()),
+        _ => return SwarmResponse::invalid_params(id, "Missing 'to' parameter"),
+    };
+
+    let (delegation, outcome) = {
+        let mut s = state.write().await;
+        if let Some(from) = params
+            .get("from")
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.trim().is_empty())
+        {
+            if from.trim() != s.agent_id.as_str() {
+                return SwarmResponse::invalid_params(
                     id,
-                    -32011,
                     format!(
-                        "Root result submission blocked for {}: no decomposed subtasks",
-                        submission.task_id
+                        "Cannot delegate on behalf of {}: only this agent's tasks can be delegated",
+                        from.trim()
                     ),
                 );
             }
-
-            if !task.subtasks.is_empty() {
-                let all_subtasks_done = task.subtasks.iter().all(|sub_id| {
-                    state
-                        .task_details
-                        .get(sub_id)
-                        // PendingReview is terminal for aggregation — result was submitted, awaiting human review
-                        .map(|t| t.status == TaskStatus::Completed || t.status == TaskStatus::PendingReview)
-                        .unwrap_or(false)
-                });
-                if !all_subtasks_done {
-                    return SwarmResponse::error(
-                        id,
-                        -32010,
-                        format!(
-                            "Cannot submit aggregated result for {} before all subtasks are completed",
-                            submission.task_id
-                        ),
-                    );
-                }
-            }
         }
-
-        let parent_task_id = state
-            .task_details
-            .get(&submission.task_id)
-            .and_then(|t| t.parent_task_id.clone());
-
-        if let Some(task) = state.task_details.get_mut(&submission.task_id) {
-            task.status = TaskStatus::Completed;
-            task.assigned_to = Some(submission.agent_id.clone());
-        }
-        state.task_set.remove(&submission.task_id);
-        state.bump_tasks_processed(submission.agent_id.as_str());
-        state.mark_member_submitted_result(submission.agent_id.as_str());
-        state.mark_member_seen(submission.agent_id.as_str());
-        state.merkle_dag.add_leaf(
-            submission.task_id.clone(),
-            submission.artifact.content_cid.as_bytes(),
-        );
-        let nodes = state.merkle_dag.node_count();
-        state.push_task_timeline_event(
-            &submission.task_id,
-            "result_submitted",
-            format!("Artifact {} (dag_nodes={})", submission.artifact.artifact_id, nodes),
-            Some(submission.agent_id.to_string()),
-        );
-        state.push_log(
-            crate::tui::LogCategory::Task,
-            format!(
-                "Result submitted for task {} by {} (artifact {}, dag_nodes={})",
-                submission.task_id,
-                submission.agent_id,
-                submission.artifact.artifact_id,
-                nodes
-            ),
-        );
-        state.push_log(
-            crate::tui::LogCategory::System,
-            format!(
-                "AUDIT result.submit actor={} task_id={} artifact={}",
-                submission.agent_id, submission.task_id, submission.artifact.artifact_id
-            ),
-        );
-
-        let confidence_delta = params.get("confidence_delta").and_then(|v| v.as_f64()).unwrap_or(0.0);
-        let _task_outcome_str = params.get("task_outcome").and_then(|v| v.as_str()).unwrap_or("succeeded_fully").to_string();
-
-        if confidence_delta > 0.2 {
-            state.push_log(
-                crate::tui::LogCategory::Swarm,
-                format!("Agent confidence dropped {:.2} during task — review suggested", confidence_delta),
+        if to != s.agent_id && !s.member_set.contains(to.as_str()) {
+            return SwarmResponse::invalid_params(
+                id,
+                format!("Unknown delegate: {} is not a known swarm member", to),
             );
         }
-
-        // PendingReview: flag task if confidence delta exceeds per-task threshold (Moltbook insight #8).
-        {
-            let threshold = state.task_details
-                .get(&submission.task_id)
-                .map(|t| t.confidence_review_threshold)
-                .unwrap_or(1.0);
-            if confidence_delta > threshold as f64 {
-                if let Some(t) = state.task_details.get_mut(&submission.task_id) {
-                    t.status = wws_protocol::TaskStatus::PendingReview;
-                }
-            }
-        }
-
-        // Track silent failure rate (Moltbook insight #16).
-        // total_outcomes_reported increments on every submit_result call.
-        // silent_failure_count only increments when outcome is FailedSilently.
-        {
-            let is_silent = params.get("outcome").map(|outcome_val| {
-                outcome_val.get("FailedSilently").is_some()
-                    || outcome_val.as_str() == Some("FailedSilently")
-            }).unwrap_or(false);
-            let activity = state.agent_activity.entry(submission.agent_id.to_string()).or_default();
-            activity.total_outcomes_reported += 1;
-            if is_silent {
-                activity.silent_failure_count += 1;
-            }
+        let mut delegation = TaskDelegationParams {
+            task_id,
+            from: s.agent_id.clone(),
+            to,
+            signature: String::new(),
+        };
+        s.sign_task_delegation(&mut delegation);
+        match s.apply_task_delegation(&delegation) {
+            Ok(outcome) => (delegation, outcome),
+            Err(failure) => return failure.into_response(id),
         }
+    };
 
-        // Store the result for potential aggregation
-        state.task_results.insert(submission.task_id.clone(), submission.artifact.clone());
-        let content_text = {
-            let from_params = params
-                .get("content")
-                .and_then(|v| v.as_str())
-                .or_else(|| params.get("artifact").and_then(|a| a.get("content")).and_then(|v| v.as_str()))
-                .unwrap_or("")
-                .to_string();
-            if from_params.trim().is_empty() {
-                submission.artifact.content.clone()
+    let msg = SwarmMessage::new(
+        ProtocolMethod::TaskDelegation.as_str(),
+        serde_json::to_value(&delegation).unwrap_or_default(),
+        String::new(),
+    );
+    if let Ok(data) = serde_json::to_vec(&msg) {
+        let topic = SwarmTopics::tasks_for(&outcome.swarm_id, outcome.task_tier);
+        if let Err(e) = network_handle.publish(&topic, data).await {
+            if e.is_retryable() {
+                tracing::debug!(error = %e, "Task delegation not published yet");
             } else {
-                from_params
-            }
-        };
-        if !content_text.trim().is_empty() {
-            state
-                .task_result_text
-                .insert(submission.task_id.clone(), content_text.clone());
-        }
-
-        // If is_synthesis flag is set, record a SynthesisResult deliberation message
-        // so it appears in the deliberation panel alongside critiques and proposals.
-        if params
-            .get("is_synthesis")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false)
-        {
-            let synth_msg = DeliberationMessage {
-                id: uuid::Uuid::new_v4().to_string(),
-                task_id: submission.task_id.clone(),
-                timestamp: chrono::Utc::now(),
-                speaker: submission.agent_id.clone(),
-                round: 3,
-                message_type: DeliberationType::SynthesisResult,
-                content: content_text.clone(),
-                referenced_plan_id: None,
-                critic_scores: None,
-            };
-            state
-                .deliberation_messages
-                .entry(submission.task_id.clone())
-                .or_default()
-                .push(synth_msg);
-        }
-
-        // Log when all subtasks of a parent are done (agent must synthesize).
-        if let Some(parent_id) = parent_task_id {
-            let all_subtasks_done = state
-                .task_details
-                .get(&parent_id)
-                .map(|parent| {
-                    !parent.subtasks.is_empty()
-                        && parent.subtasks.iter().all(|sub_id| {
-                            state
-                                .task_details
-                                .get(sub_id)
-                                .map(|t| t.status == TaskStatus::Completed || t.status == TaskStatus::PendingReview)
-                                .unwrap_or(false)
-                        })
-                })
-                .unwrap_or(false);
-
-            if all_subtasks_done {
-                state.push_task_timeline_event(
-                    &parent_id,
-                    "subtasks_complete",
-                    format!("All subtasks completed for parent {}; awaiting agent synthesis", parent_id),
-                    Some(submission.agent_id.to_string()),
-                );
-                state.push_log(
-                    crate::tui::LogCategory::Task,
-                    format!("All subtasks of {} completed — parent awaits agent synthesis", parent_id),
-                );
+                tracing::warn!(error = %e, "Failed to publish task delegation");
             }
         }
+    }
 
-        nodes
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "task_id": delegation.task_id,
+            "from": delegation.from,
+            "to": delegation.to,
+            "delegated": true,
+        }),
+    )
+}
+
+/// Handle `swarm.report_progress` - record how far this agent is with an
+/// assigned task.
+///
+/// Params: `task_id`, `percent` (clamped to 0-100) and an optional
+/// `message`. The report is published on the task's tier topic so the
+/// coordinator and dashboards see it too.
+async fn handle_report_progress(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
+        Some(v) if !v.trim().is_empty() => v.trim().to_string(),
+        _ => return SwarmResponse::invalid_params(id, "Missing 'task_id' parameter"),
+    };
+    let Some(percent) = params.get("percent").and_then(|v| v.as_f64()) else {
+        return SwarmResponse::invalid_params(id, "Missing or non-numeric 'percent' parameter");
     };
+    let message = params
+        .get("message")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
 
-    // Publish result to both the task-specific results topic and the tier-based
-    // task topic so all nodes (not just coordinator + assignee) learn about
-    // subtask completion.
-    let (swarm_id, task_tier) = {
-        let state = state.read().await;
-        let tier = state
-            .task_details
-            .get(&submission.task_id)
-            .map(|t| t.tier_level)
-            .unwrap_or(1);
-        (state.current_swarm_id.as_str().to_string(), tier)
+    let (report, outcome) = {
+        let mut s = state.write().await;
+        let mut report = TaskProgressParams {
+            task_id,
+            reporter: s.agent_id.clone(),
+            percent,
+            message,
+            signature: String::new(),
+        };
+        let outcome = match s.apply_task_progress(&report) {
+            Ok(outcome) => outcome,
+            Err(failure) => return failure.into_response(id),
+        };
+        // Publish the message as sanitized and recorded here.
+        report.message = outcome.progress.message.clone();
+        s.sign_task_progress(&mut report);
+        (report, outcome)
     };
+
     let msg = SwarmMessage::new(
-        ProtocolMethod::ResultSubmission.as_str(),
-        serde_json::to_value(&submission).unwrap_or_default(),
+        ProtocolMethod::TaskProgress.as_str(),
+        serde_json::to_value(&report).unwrap_or_default(),
         String::new(),
     );
     if let Ok(data) = serde_json::to_vec(&msg) {
-        // Task-specific results topic (coordinator + assignee subscribe)
-        let topic = SwarmTopics::results_for(&swarm_id, &submission.task_id);
-        if let Err(e) = network_handle.publish(&topic, data.clone()).await {
-            tracing::warn!(error = %e, "Failed to publish result to results topic");
-        }
-        // Tier-based topic (all nodes subscribe) — enables swarm-wide status sync
-        let tier_topic = SwarmTopics::tasks_for(&swarm_id, task_tier);
-        if let Err(e) = network_handle.publish(&tier_topic, data).await {
-            tracing::warn!(error = %e, "Failed to publish result to tier topic");
+        let topic = SwarmTopics::tasks_for(&outcome.swarm_id, outcome.task_tier);
+        if let Err(e) = network_handle.publish(&topic, data).await {
+            if e.is_retryable() {
+                tracing::debug!(error = %e, "Task progress not published yet");
+            } else {
+                tracing::warn!(error = %e, "Failed to publish task progress");
+            }
         }
     }
 
     SwarmResponse::success(
         id,
         serde_json::json!({
-            "task_id": submission.task_id,
-            "artifact_id": submission.artifact.artifact_id,
-            "accepted": true,
-            "dag_nodes": dag_nodes,
+            "task_id": report.task_id,
+            "progress": outcome.progress,
         }),
     )
 }
@@ -1436,11 +1822,19 @@ async fn handle_receive_task(
 
             if matches!(
                 task.status,
-                TaskStatus::Completed | TaskStatus::Failed | TaskStatus::Rejected
+                TaskStatus::Completed
+                    | TaskStatus::Failed
+                    | TaskStatus::Rejected
+                    | TaskStatus::Blocked
             ) {
                 return false;
             }
 
+            // Tasks this node executes directly reach its agent at any tier.
+            if task.assigned_to.as_ref() == Some(&my_id) && state.hop_budget_exhausted(&task.task_id) {
+                return true;
+            }
+
             match my_tier {
                 Tier::Executor => task.assigned_to.as_ref() == Some(&my_id),
                 _ => {
@@ -1450,13 +1844,25 @@ async fn handle_receive_task(
             }
         })
         .collect();
-    tasks.sort_by(|a, b| a.created_at.cmp(&b.created_at));
-    let tasks: Vec<String> = tasks.into_iter().map(|t| t.task_id.clone()).collect();
+    // Most urgent first, oldest first within a priority.
+    tasks.sort_by(|a, b| b.priority.cmp(&a.priority).then(a.created_at.cmp(&b.created_at)));
+    let mut tasks: Vec<String> = tasks.into_iter().map(|t| t.task_id.clone()).collect();
+
+    // Hand out at most `max_concurrent_tasks` at once; the rest stay queued
+    // locally and surface, oldest first, as earlier tasks finish.
+    let max_concurrent = state.max_concurrent_tasks;
+    let queued = if max_concurrent > 0 && tasks.len() > max_concurrent {
+        tasks.split_off(max_concurrent).len()
+    } else {
+        0
+    };
 
     SwarmResponse::success(
         id,
         serde_json::json!({
             "pending_tasks": tasks,
+            "queued_tasks": queued,
+            "max_concurrent_tasks": max_concurrent,
             "agent_id": state.agent_id.to_string(),
             "tier": format!("{:?}", state.my_tier),
         }),
@@ -1472,10 +1878,9 @@ async fn handle_get_task(
     let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
         Some(t) if !t.trim().is_empty() => t,
         _ => {
-            return SwarmResponse::error(
+            return SwarmResponse::invalid_params(
                 id,
-                -32602,
-                "Missing 'task_id' parameter".into(),
+                "Missing 'task_id' parameter",
             );
         }
     };
@@ -1484,9 +1889,8 @@ async fn handle_get_task(
     let task = match state.task_details.get(task_id) {
         Some(task) => task,
         None => {
-            return SwarmResponse::error(
+            return SwarmResponse::not_found(
                 id,
-                -32004,
                 format!("Task not found: {}", task_id),
             );
         }
@@ -1501,73 +1905,219 @@ async fn handle_get_task(
     )
 }
 
-/// Handle `swarm.get_task_timeline` - fetch lifecycle events for a task.
-async fn handle_get_task_timeline(
+/// Handle `swarm.get_content` - fetch stored content by CID together with
+/// the content type and metadata recorded when it was submitted.
+///
+/// With `fetch: true`, the content is resolved through `content.fetch_chain`
+/// (local store, submitter, DHT providers by default) and the
+/// response names the `source` that had it; the fetch is abandoned if the
+/// client resets the connection first. Otherwise only the local store is read.
+async fn handle_get_content(
     id: Option<String>,
     params: &serde_json::Value,
     state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+    cancel: &CancellationToken,
 ) -> SwarmResponse {
-    let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
-        Some(t) if !t.trim().is_empty() => t,
+    let cid = match params.get("cid").and_then(|v| v.as_str()) {
+        Some(c) if !c.trim().is_empty() => c,
         _ => {
-            return SwarmResponse::error(
-                id,
-                -32602,
-                "Missing 'task_id' parameter".into(),
-            );
+            return SwarmResponse::invalid_params(id, "Missing 'cid' parameter");
+        }
+    };
+    let fetch = params.get("fetch").and_then(|v| v.as_bool()).unwrap_or(false);
+
+    let (data, source) = if fetch {
+        let resolver = ContentResolver::new(state, network_handle);
+        match cancel.run_until_cancelled(resolver.fetch(cid)).await {
+            Some(Some(found)) => found,
+            Some(None) => {
+                return SwarmResponse::not_found(id, format!("Content not found: {}", cid));
+            }
+            None => {
+                if let Err(e) = network_handle.cancel_fetch(cid).await {
+                    tracing::debug!(error = %e, "Content fetch not cancelled");
+                }
+                return SwarmResponse::operation_failed(id, "Request cancelled: client disconnected");
+            }
+        }
+    } else {
+        match state.read().await.content_store.get(cid) {
+            Some(data) => (data, ContentSource::Local),
+            None => return SwarmResponse::not_found(id, format!("Content not found: {}", cid)),
         }
     };
-
-    let limit = params
-        .get("limit")
-        .and_then(|v| v.as_u64())
-        .map(|v| v as usize)
-        .unwrap_or(200)
-        .min(1000);
 
     let state = state.read().await;
-    let timeline: Vec<TaskTimelineEvent> = state
-        .task_timelines
-        .get(task_id)
-        .cloned()
-        .unwrap_or_default();
-    let total = timeline.len();
-    let start = total.saturating_sub(limit);
-    let events = timeline.into_iter().skip(start).collect::<Vec<_>>();
+    let (content_type, metadata, provenance) = match state.content_store.get_info(cid) {
+        Some(info) => (info.content_type.clone(), info.metadata.clone(), info.provenance.clone()),
+        None => ("application/octet-stream".to_string(), serde_json::Value::Null, None),
+    };
 
     SwarmResponse::success(
         id,
         serde_json::json!({
-            "task_id": task_id,
-            "events": events,
-            "event_count": total,
+            "cid": cid,
+            "content": String::from_utf8_lossy(&data),
+            "size_bytes": data.len(),
+            "content_type": content_type,
+            "metadata": metadata,
+            "provenance": provenance,
+            "providers": state.content_store.get_providers(cid),
+            "source": source,
         }),
     )
 }
 
-/// Handle `swarm.get_status` - get connector and agent status.
-async fn handle_get_status(
+/// Handle `swarm.get_content_stats` - size and utilization of the local
+/// content store.
+async fn handle_get_content_stats(
     id: Option<String>,
     state: &Arc<RwLock<ConnectorState>>,
 ) -> SwarmResponse {
-    let state = state.read().await;
-    let known_agents = state.active_member_count(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS));
+    let stats = state.read().await.content_store.stats();
+    SwarmResponse::success(id, serde_json::to_value(stats).unwrap_or_default())
+}
 
-    SwarmResponse::success(
+/// Handle `swarm.get_task_timeline` - fetch lifecycle events for a task.
+///
+/// Returns up to `limit` events with a `seq` below `before` (the tail when
+/// omitted). `next_cursor` is the `seq` of the first returned event, to pass
+/// as `before` for the previous page, or null at the start.
+async fn handle_get_task_timeline(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
+        Some(t) if !t.trim().is_empty() => t,
+        _ => {
+            return SwarmResponse::invalid_params(
+                id,
+                "Missing 'task_id' parameter",
+            );
+        }
+    };
+
+    let limit = params
+        .get("limit")
+        .and_then(|v| v.as_u64())
+        .map(|v| v as usize)
+        .unwrap_or(200)
+        .min(1000);
+
+    let before = match params.get("before") {
+        None | Some(serde_json::Value::Null) => None,
+        Some(v) => match v.as_u64() {
+            Some(b) => Some(b),
+            None => {
+                return SwarmResponse::invalid_params(
+                    id,
+                    "'before' must be a non-negative event sequence number",
+                );
+            }
+        },
+    };
+
+    let state = state.read().await;
+    let timeline: &[TaskTimelineEvent] = state
+        .task_timelines
+        .get(task_id)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let total = timeline.len();
+    let end = before.map_or(total, |before| timeline.partition_point(|e| e.seq < before));
+    let start = end.saturating_sub(limit);
+    let events = timeline[start..end].to_vec();
+    let next_cursor = (start > 0).then(|| timeline[start].seq);
+
+    SwarmResponse::success(
         id,
         serde_json::json!({
-            "agent_id": state.agent_id.to_string(),
-            "status": format!("{:?}", state.status),
-            "tier": format!("{:?}", state.my_tier),
-            "epoch": state.epoch_manager.current_epoch(),
-            "parent_id": state.parent_id.as_ref().map(|p| p.to_string()),
-            "active_tasks": state.task_set.len(),
+            "task_id": task_id,
+            "events": events,
+            "event_count": total,
+            "next_cursor": next_cursor,
+        }),
+    )
+}
+
+/// Handle `swarm.get_status` - get connector and agent status.
+async fn handle_get_status(
+    id: Option<String>,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let (snapshot, transport) = {
+        let state = state.read().await;
+        (state.snapshot(), state.transport_features)
+    };
+    let known_agents = snapshot.active_member_count(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS));
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "agent_id": snapshot.agent_id.to_string(),
+            "status": format!("{:?}", snapshot.status),
+            "tier": format!("{:?}", snapshot.my_tier),
+            "epoch": snapshot.epoch,
+            "parent_id": snapshot.parent_id.as_ref().map(|p| p.to_string()),
+            "active_tasks": snapshot.active_tasks,
             "known_agents": known_agents,
-            "content_items": state.content_store.item_count(),
+            "content_items": snapshot.content_items,
+            "transport": transport,
+            "protocol_version": wws_protocol::constants::PROTOCOL_VERSION,
+            "wire_format": format!("json-rpc/{}", wws_protocol::constants::JSONRPC_VERSION),
+        }),
+    )
+}
+
+/// Handle `swarm.ping` - report readiness without building a full status snapshot.
+async fn handle_ping(id: Option<String>, state: &Arc<RwLock<ConnectorState>>) -> SwarmResponse {
+    SwarmResponse::success(id, state.read().await.health_report())
+}
+
+/// Handle `swarm.tail_logs` - return the newest matching event log entries.
+///
+/// Over a TCP connection the reply is followed by a `swarm.log` notification
+/// for every later entry (see [`stream_log_tail`]).
+async fn handle_tail_logs(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let tail = match LogTailParams::from_params(params) {
+        Ok(tail) => tail,
+        Err(message) => return SwarmResponse::invalid_params(id, message),
+    };
+    let s = state.read().await;
+    let first_seq = s.event_log_seq + 1 - s.event_log.len() as u64;
+    let mut entries: Vec<serde_json::Value> = s
+        .event_log
+        .iter()
+        .zip(first_seq..)
+        .filter(|(entry, _)| tail.matches(entry))
+        .map(|(entry, seq)| log_entry_json(seq, entry))
+        .collect();
+    let excess = entries.len().saturating_sub(tail.backlog);
+    entries.drain(..excess);
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "entries": entries,
+            "cursor": s.event_log_seq,
         }),
     )
 }
 
+/// Handle `swarm.export_state` - return a full [`crate::connector::StateSnapshotFile`].
+async fn handle_export_state(id: Option<String>, state: &Arc<RwLock<ConnectorState>>) -> SwarmResponse {
+    let snapshot = state.read().await.export_snapshot();
+    match serde_json::to_value(&snapshot) {
+        Ok(value) => SwarmResponse::success(id, value),
+        Err(e) => SwarmResponse::rpc_error(id, RpcErrorCode::InternalError, format!("Failed to serialize state: {}", e)),
+    }
+}
+
 /// Handle `swarm.register_agent` - register an execution agent identity.
 async fn handle_register_agent(
     id: Option<String>,
@@ -1578,10 +2128,9 @@ async fn handle_register_agent(
     let requested_agent_id = match params.get("agent_id").and_then(|v| v.as_str()) {
         Some(v) if !v.trim().is_empty() => v.trim().to_string(),
         _ => {
-            return SwarmResponse::error(
+            return SwarmResponse::invalid_params(
                 id,
-                -32602,
-                "Missing 'agent_id' parameter".into(),
+                "Missing 'agent_id' parameter",
             );
         }
     };
@@ -1616,9 +2165,16 @@ async fn handle_register_agent(
             state.agent_parents.clear();
             state.subordinates.clear();
 
-            let k = dynamic_branching_factor(swarm_size) as usize;
-            let distribution = wws_hierarchy::PyramidAllocator::distribute(swarm_size, k as u64);
-            let tier_sizes: Vec<usize> = distribution.tiers.iter().map(|n| *n as usize).collect();
+            // The persistent allocator keeps the depth stable while the
+            // swarm size hovers around a k^d boundary.
+            let k = state.pyramid.branching_factor() as usize;
+            let layout = match state.pyramid.recompute(swarm_size) {
+                Ok(layout) => layout.clone(),
+                Err(e) => {
+                    return SwarmResponse::rpc_error(id, RpcErrorCode::InternalError, e.to_string());
+                }
+            };
+            let tier_sizes: Vec<usize> = layout.agents_per_tier.iter().map(|n| *n as usize).collect();
             let levels = tier_sizes.len().max(1);
 
             let mut offsets = Vec::with_capacity(levels + 1);
@@ -1702,12 +2258,7 @@ async fn handle_register_agent(
                     .unwrap_or(0) as u32;
             }
             state.network_stats.hierarchy_depth = levels as u32;
-            state.current_layout = wws_hierarchy::PyramidAllocator::new(wws_hierarchy::pyramid::PyramidConfig {
-                branching_factor: k as u32,
-                max_depth: wws_protocol::MAX_HIERARCHY_DEPTH,
-            })
-            .compute_layout(swarm_size)
-            .ok();
+            state.current_layout = Some(layout);
         }
 
         let announced_name = if let Some(existing) = state.agent_names.get(&canonical_agent_id) {
@@ -1751,17 +2302,26 @@ async fn handle_register_agent(
         let _ = network_handle.publish(&topic, data).await;
     }
 
-    // Broadcast tier assignments if hierarchy was recomputed
+    // Broadcast tier assignments if hierarchy was recomputed. Peers only
+    // accept assignments signed by a leader, so only leaders send them.
+    // Within an epoch, only members whose tier or parent moved since this
+    // leader's last broadcast get a new TierAssignment; the first broadcast
+    // of each epoch, and so of each new leader, carries the full set.
+    let hierarchy_assignments =
+        assignments_to_broadcast(&mut *state.write().await, epoch, hierarchy_assignments);
     if !hierarchy_assignments.is_empty() {
         let branch_size = dynamic_branching_factor(known_agents as u64);
         for (member_id, tier, parent) in hierarchy_assignments {
-            let params = TierAssignmentParams {
+            let mut params = TierAssignmentParams {
                 assigned_agent: AgentId::new(member_id),
                 tier,
                 parent_id: parent.map(|p| AgentId::new(p)).unwrap_or_else(|| AgentId::new("root".to_string())),
                 epoch,
                 branch_size,
+                issuer: AgentId::new(String::new()),
+                signature: String::new(),
             };
+            state.read().await.sign_tier_assignment(&mut params);
 
             let msg = SwarmMessage::new(
                 ProtocolMethod::TierAssignment.as_str(),
@@ -1787,42 +2347,56 @@ async fn handle_register_agent(
     )
 }
 
-fn dynamic_branching_factor(swarm_size: u64) -> u64 {
-    let approx = (swarm_size as f64).sqrt().round() as u64;
-    approx.clamp(3, 10)
-}
-
-fn tier_from_level(level: u32) -> Tier {
-    match level {
-        0 => Tier::Tier0,
-        1 => Tier::Tier1,
-        2 => Tier::Tier2,
-        n => Tier::TierN(n),
+/// The recomputed `assignments` this node should broadcast in `epoch`:
+/// none unless it is the epoch leader, only the changed ones if it already
+/// broadcast as leader in this epoch, and all of them otherwise.
+fn assignments_to_broadcast(
+    state: &mut ConnectorState,
+    epoch: u64,
+    assignments: Vec<(String, Tier, Option<String>)>,
+) -> Vec<(String, Tier, Option<String>)> {
+    if !state.is_epoch_leader(state.agent_id.as_str()) {
+        state.announced_assignments = None;
+        return Vec::new();
+    }
+    if assignments.is_empty() {
+        return assignments;
     }
+    let current: TierAssignments = assignments
+        .iter()
+        .map(|(member_id, tier, parent)| (member_id.clone(), (*tier, parent.clone())))
+        .collect();
+    let to_send = match &state.announced_assignments {
+        Some((announced_epoch, announced)) if *announced_epoch == epoch => {
+            changed_assignments(announced, assignments)
+        }
+        _ => assignments,
+    };
+    state.announced_assignments = Some((epoch, current));
+    to_send
 }
 
-fn active_members_in_tier(
-    state: &ConnectorState,
-    tier: Tier,
-    staleness: Duration,
-) -> Vec<String> {
-    let now = chrono::Utc::now();
-    let poll_staleness = Duration::from_secs(PARTICIPATION_POLL_STALENESS_SECS);
-    state
-        .active_member_ids(staleness)
+/// Keep the `(member, tier, parent)` assignments that differ from the
+/// `previous` broadcast.
+fn changed_assignments(
+    previous: &TierAssignments,
+    current: Vec<(String, Tier, Option<String>)>,
+) -> Vec<(String, Tier, Option<String>)> {
+    current
         .into_iter()
-        .filter(|id| state.agent_tiers.get(id).copied().unwrap_or(Tier::Executor) == tier)
-        .filter(|id| {
-            state
-                .member_last_task_poll
-                .get(id)
-                .and_then(|ts| now.signed_duration_since(*ts).to_std().ok())
-                .map(|age| age <= poll_staleness)
-                .unwrap_or(false)
+        .filter(|(member_id, tier, parent)| {
+            previous
+                .get(member_id)
+                .is_none_or(|(old_tier, old_parent)| old_tier != tier || old_parent != parent)
         })
         .collect()
 }
 
+fn dynamic_branching_factor(swarm_size: u64) -> u64 {
+    let approx = (swarm_size as f64).sqrt().round() as u64;
+    approx.clamp(3, 10)
+}
+
 /// Handle `swarm.list_swarms` - list all known swarms with their info.
 async fn handle_list_swarms(
     id: Option<String>,
@@ -1840,6 +2414,7 @@ async fn handle_list_swarms(
                 "is_public": record.is_public,
                 "agent_count": record.agent_count,
                 "joined": record.joined,
+                "branching_factor": state.branching_factor_for(record.swarm_id.as_str()),
             })
         })
         .collect();
@@ -1849,23 +2424,53 @@ async fn handle_list_swarms(
         serde_json::json!({
             "swarms": swarms,
             "current_swarm": state.current_swarm_id.as_str(),
+            "active_swarms": state.active_swarm_ids(),
         }),
     )
 }
 
-/// Handle `swarm.create_swarm` - create a new private swarm.
+/// Handle `swarm.get_swarm_history` - recent membership events of a known
+/// swarm, oldest first.
+async fn handle_get_swarm_history(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let swarm_id = match params.get("swarm_id").and_then(|v| v.as_str()) {
+        Some(s) if !s.trim().is_empty() => s,
+        _ => {
+            return SwarmResponse::invalid_params(id, "Missing 'swarm_id' parameter");
+        }
+    };
+
+    let state = state.read().await;
+    match state.known_swarms.get(swarm_id) {
+        Some(record) => SwarmResponse::success(
+            id,
+            serde_json::json!({
+                "swarm_id": swarm_id,
+                "agent_count": record.agent_count,
+                "events": record.history,
+            }),
+        ),
+        None => SwarmResponse::rpc_error(id, RpcErrorCode::Unauthorized, format!("Unknown swarm: {}", swarm_id)),
+    }
+}
+
+/// Handle `swarm.create_swarm` - create a new private swarm and join it
+/// alongside the current one.
 async fn handle_create_swarm(
     id: Option<String>,
     params: &serde_json::Value,
     state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
 ) -> SwarmResponse {
     let name = match params.get("name").and_then(|v| v.as_str()) {
         Some(n) => n.to_string(),
         None => {
-            return SwarmResponse::error(
+            return SwarmResponse::invalid_params(
                 id,
-                -32602,
-                "Missing 'name' parameter".into(),
+                "Missing 'name' parameter",
             );
         }
     };
@@ -1873,16 +2478,16 @@ async fn handle_create_swarm(
     let secret = match params.get("secret").and_then(|v| v.as_str()) {
         Some(s) => s.to_string(),
         None => {
-            return SwarmResponse::error(
+            return SwarmResponse::invalid_params(
                 id,
-                -32602,
-                "Missing 'secret' parameter".into(),
+                "Missing 'secret' parameter",
             );
         }
     };
 
     let swarm_id = SwarmId::generate();
     let token = SwarmToken::generate(&swarm_id, &secret);
+    let owner = state.read().await.agent_id.clone();
 
     let record = SwarmRecord {
         swarm_id: swarm_id.clone(),
@@ -1891,14 +2496,21 @@ async fn handle_create_swarm(
         agent_count: 1,
         joined: true,
         last_seen: chrono::Utc::now(),
+        secret: Some(secret),
+        owner: Some(owner.clone()),
+        history: Default::default(),
     };
 
-    {
+    let tiers = {
         let mut state = state.write().await;
         state
             .known_swarms
             .insert(swarm_id.as_str().to_string(), record);
-    }
+        state.memberships.join(swarm_id.clone(), Some(token.clone()));
+        network_handle.set_payload_codec(state.payload_codec());
+        WwsConnector::task_topic_tiers(state.my_tier, state.monitor_all_tiers)
+    };
+    subscribe_joined_swarm_topics(network_handle, swarm_id.as_str(), tiers);
 
     SwarmResponse::success(
         id,
@@ -1906,62 +2518,310 @@ async fn handle_create_swarm(
             "swarm_id": swarm_id.as_str(),
             "token": token.as_str(),
             "name": name,
+            "owner": owner,
         }),
     )
 }
 
+/// Subscribe in the background to a newly joined swarm's swarm-level, tier
+/// task and DM topics.
+fn subscribe_joined_swarm_topics(
+    network_handle: &wws_network::SwarmHandle,
+    swarm_id: &str,
+    tiers: Vec<u32>,
+) {
+    let mut topics: Vec<String> = tiers
+        .into_iter()
+        .map(|tier| SwarmTopics::tasks_for(swarm_id, tier))
+        .collect();
+    topics.push(SwarmTopics::dm_for(swarm_id));
+    let nh = network_handle.clone();
+    let swarm_id = swarm_id.to_string();
+    tokio::spawn(async move {
+        if let Err(e) = nh.subscribe_swarm_topics(&swarm_id).await {
+            tracing::debug!(error = %e, swarm_id = %swarm_id, "Failed to subscribe swarm topics");
+        }
+        for topic in topics {
+            if let Err(e) = nh.subscribe(&topic).await {
+                tracing::debug!(error = %e, topic = %topic, "Failed to subscribe joined swarm topic");
+            }
+        }
+    });
+}
+
+/// Unsubscribe in the background from a left swarm's swarm-level, tier task
+/// and DM topics.
+pub(crate) fn unsubscribe_left_swarm_topics(
+    network_handle: &wws_network::SwarmHandle,
+    swarm_id: &str,
+    tiers: Vec<u32>,
+) {
+    let mut topics = vec![
+        SwarmTopics::election_tier1_for(swarm_id),
+        SwarmTopics::keepalive_for(swarm_id),
+        SwarmTopics::hierarchy_for(swarm_id),
+        SwarmTopics::dm_for(swarm_id),
+    ];
+    topics.extend(tiers.into_iter().map(|tier| SwarmTopics::tasks_for(swarm_id, tier)));
+    let nh = network_handle.clone();
+    tokio::spawn(async move {
+        for topic in topics {
+            if let Err(e) = nh.unsubscribe(&topic).await {
+                tracing::debug!(error = %e, topic = %topic, "Failed to unsubscribe left swarm topic");
+            }
+        }
+    });
+}
+
 /// Handle `swarm.join_swarm` - join an existing swarm.
+///
+/// The swarm is joined alongside the current one: it gets its own task set
+/// and the connector subscribes to its swarm, task and DM topics. A
+/// `swarm.join` carrying a proof of the token is then published so that
+/// members holding the swarm secret can check it. The optional `owner`, as
+/// returned by `swarm.create_swarm`, names the member whose signed rejection
+/// makes us leave again.
 async fn handle_join_swarm(
     id: Option<String>,
     params: &serde_json::Value,
     state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
 ) -> SwarmResponse {
     let swarm_id_str = match params.get("swarm_id").and_then(|v| v.as_str()) {
         Some(s) => s.to_string(),
         None => {
-            return SwarmResponse::error(
+            return SwarmResponse::invalid_params(
                 id,
-                -32602,
-                "Missing 'swarm_id' parameter".into(),
+                "Missing 'swarm_id' parameter",
             );
         }
     };
 
     let token = params.get("token").and_then(|v| v.as_str()).map(String::from);
+    let owner = params
+        .get("owner")
+        .and_then(|v| v.as_str())
+        .map(|owner| AgentId::new(owner.to_string()));
 
     let mut state = state.write().await;
 
-    let record = match state.known_swarms.get_mut(&swarm_id_str) {
+    let record = match state.known_swarms.get(&swarm_id_str) {
         Some(r) => r,
         None => {
-            return SwarmResponse::error(
+            return SwarmResponse::rpc_error(
                 id,
-                -32001,
+                RpcErrorCode::Unauthorized,
                 format!("Unknown swarm: {}", swarm_id_str),
             );
         }
     };
 
     // Private swarms require a token.
-    if !record.is_public && token.is_none() {
-        return SwarmResponse::error(
+    if !record.is_public {
+        let token = match &token {
+            Some(t) => SwarmToken::new(t.clone()),
+            None => {
+                return SwarmResponse::invalid_params(
+                    id,
+                    "Token required for private swarm",
+                );
+            }
+        };
+        // When we hold the secret the token is checked here; otherwise the
+        // swarm's authoritative member validates it on `swarm.join` and
+        // rejects a bad one.
+        if state.verify_swarm_token(&swarm_id_str, &token) == Some(false) {
+            return SwarmResponse::rpc_error(
+                id,
+                RpcErrorCode::Forbidden,
+                format!("Unauthorized: invalid token for swarm {}", swarm_id_str),
+            );
+        }
+    }
+
+    let token = token.map(SwarmToken::new);
+    let join = SwarmJoinParams::new(
+        SwarmId::new(swarm_id_str.clone()),
+        state.agent_id.clone(),
+        token.as_ref(),
+    );
+    let mut newly_joined = false;
+    if let Some(record) = state.known_swarms.get_mut(&swarm_id_str) {
+        record.joined = true;
+        if owner.is_some() {
+            record.owner = owner;
+        }
+        let swarm_id = record.swarm_id.clone();
+        if swarm_id != state.current_swarm_id {
+            newly_joined = state.memberships.join(swarm_id, token);
+        }
+    }
+    if let Some(membership) = state.memberships.get_mut(&swarm_id_str) {
+        membership.join_nonce = Some(join.nonce.clone());
+    }
+    if newly_joined {
+        network_handle.set_payload_codec(state.payload_codec());
+    }
+    let tiers = WwsConnector::task_topic_tiers(state.my_tier, state.monitor_all_tiers);
+    let active_swarms = state.active_swarm_ids();
+    drop(state);
+
+    if newly_joined {
+        subscribe_joined_swarm_topics(network_handle, &swarm_id_str, tiers);
+    }
+
+    // Members holding the swarm secret check the proof and answer a bad
+    // one with a signed rejecting `swarm.join_response`; one from the swarm
+    // owner makes us leave.
+    let msg = SwarmMessage::new(
+        ProtocolMethod::SwarmJoin.as_str(),
+        serde_json::to_value(&join).unwrap_or_default(),
+        String::new(),
+    );
+    if let Ok(data) = serde_json::to_vec(&msg) {
+        let _ = network_handle.publish(&SwarmTopics::swarm_discovery(), data).await;
+    }
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "swarm_id": swarm_id_str,
+            "joined": true,
+            "active_swarms": active_swarms,
+        }),
+    )
+}
+
+/// Handle `swarm.leave_swarm` - stop participating in a joined swarm.
+///
+/// The membership and its task set are dropped, the swarm's payload key is
+/// forgotten and its swarm, task and DM topics are unsubscribed. The
+/// primary swarm cannot be left.
+async fn handle_leave_swarm(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+    network_handle: &wws_network::SwarmHandle,
+) -> SwarmResponse {
+    let swarm_id = match params.get("swarm_id").and_then(|v| v.as_str()) {
+        Some(s) if !s.trim().is_empty() => s.trim().to_string(),
+        _ => {
+            return SwarmResponse::invalid_params(
+                id,
+                "Missing 'swarm_id' parameter",
+            );
+        }
+    };
+
+    let mut state = state.write().await;
+    if swarm_id == state.current_swarm_id.as_str() {
+        return SwarmResponse::invalid_params(id, "Cannot leave the primary swarm");
+    }
+    if state.memberships.leave(&swarm_id).is_none() {
+        return SwarmResponse::invalid_params(
             id,
-            -32602,
-            "Token required for private swarm".into(),
+            format!("Not a member of swarm {}", swarm_id),
         );
     }
+    if let Some(record) = state.known_swarms.get_mut(&swarm_id) {
+        record.joined = false;
+    }
+    network_handle.set_payload_codec(state.payload_codec());
+    let tiers = WwsConnector::task_topic_tiers(state.my_tier, state.monitor_all_tiers);
+    let active_swarms = state.active_swarm_ids();
+    drop(state);
+
+    unsubscribe_left_swarm_topics(network_handle, &swarm_id, tiers);
+
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "swarm_id": swarm_id,
+            "joined": false,
+            "active_swarms": active_swarms,
+        }),
+    )
+}
 
-    record.joined = true;
+/// Handle `swarm.get_swarm_token` - re-derive the join token for a swarm this node created.
+///
+/// Only swarms whose secret is held locally (i.e. created via `swarm.create_swarm`)
+/// can be re-shared; any other swarm is rejected.
+async fn handle_get_swarm_token(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let swarm_id_str = match params.get("swarm_id").and_then(|v| v.as_str()) {
+        Some(s) if !s.trim().is_empty() => s.trim().to_string(),
+        _ => {
+            return SwarmResponse::invalid_params(
+                id,
+                "Missing 'swarm_id' parameter",
+            );
+        }
+    };
+
+    let state = state.read().await;
+    let record = match state.known_swarms.get(&swarm_id_str) {
+        Some(r) => r,
+        None => {
+            return SwarmResponse::rpc_error(
+                id,
+                RpcErrorCode::Unauthorized,
+                format!("Unknown swarm: {}", swarm_id_str),
+            );
+        }
+    };
+
+    let secret = match &record.secret {
+        Some(secret) => secret,
+        None => {
+            return SwarmResponse::rpc_error(
+                id,
+                RpcErrorCode::Forbidden,
+                format!("Unauthorized: this node is not an owner of swarm {}", swarm_id_str),
+            );
+        }
+    };
+
+    let token = SwarmToken::generate(&record.swarm_id, secret);
 
     SwarmResponse::success(
         id,
         serde_json::json!({
             "swarm_id": swarm_id_str,
-            "joined": true,
+            "token": token.as_str(),
+            "name": record.name,
+            "owner": state.agent_id,
         }),
     )
 }
 
+/// Response to an injection whose client-supplied `task_id` is already
+/// known: the existing task, with `injected: false`, instead of a duplicate.
+fn existing_injection(
+    id: &Option<String>,
+    state: &ConnectorState,
+    task_id: &str,
+) -> Option<SwarmResponse> {
+    let task = state.task_details.get(task_id)?;
+    let swarm_id = state.swarm_of_task(task_id);
+    Some(SwarmResponse::success(
+        id.clone(),
+        serde_json::json!({
+            "task_id": task_id,
+            "description": task.description,
+            "epoch": task.epoch,
+            "tier": task.tier_level,
+            "swarm_id": swarm_id,
+            "topic": SwarmTopics::tasks_for(&swarm_id, task.tier_level),
+            "status": task.status,
+            "injected": false,
+        }),
+    ))
+}
+
 /// Handle `swarm.inject_task` - inject a task into the swarm from the operator/external source.
 pub(crate) async fn handle_inject_task(
     id: Option<String>,
@@ -1972,16 +2832,71 @@ pub(crate) async fn handle_inject_task(
     let description = match params.get("description").and_then(|v| v.as_str()) {
         Some(d) => d.to_string(),
         None => {
-            return SwarmResponse::error(
+            return SwarmResponse::invalid_params(
                 id,
-                -32602,
-                "Missing 'description' parameter".into(),
+                "Missing 'description' parameter",
             );
         }
     };
 
-    if description.len() > 4096 {
-        return SwarmResponse::error(id, -32602, "Task description too long (max 4096 chars)".into());
+    let max_len = state.read().await.max_task_description_len;
+    let description = match wws_protocol::sanitize_task_description(&description, max_len) {
+        Ok(d) => d,
+        Err(e) => return SwarmResponse::invalid_params(id, e.to_string()),
+    };
+
+    // Optional fan-out tier; operators may target a deeper tier directly.
+    let tier = match params.get("tier") {
+        None | Some(serde_json::Value::Null) => 1,
+        Some(v) => match v.as_u64() {
+            Some(t) if (1..=wws_protocol::MAX_HIERARCHY_DEPTH as u64).contains(&t) => t as u32,
+            _ => {
+                return SwarmResponse::invalid_params(
+                    id,
+                    format!(
+                        "'tier' must be an integer between 1 and {}",
+                        wws_protocol::MAX_HIERARCHY_DEPTH
+                    ),
+                );
+            }
+        },
+    };
+
+    let priority = match params.get("priority") {
+        None | Some(serde_json::Value::Null) => wws_protocol::TaskPriority::Normal,
+        Some(v) => match v.as_str().map(str::parse::<wws_protocol::TaskPriority>) {
+            Some(Ok(priority)) => priority,
+            Some(Err(e)) => return SwarmResponse::invalid_params(id, e),
+            None => return SwarmResponse::invalid_params(id, "'priority' must be low, normal or high"),
+        },
+    };
+
+    // Optional hop budget limiting how far the task cascades.
+    let ttl_hops = match params.get("ttl_hops") {
+        None | Some(serde_json::Value::Null) => None,
+        Some(v) => match v.as_u64().and_then(|h| u32::try_from(h).ok()) {
+            Some(hops) if hops > 0 => Some(hops),
+            _ => {
+                return SwarmResponse::invalid_params(id, "'ttl_hops' must be a positive integer");
+            }
+        },
+    };
+
+    let target_swarm = match target_swarm_param(params, &*state.read().await) {
+        Ok(target) => target,
+        Err(failure) => return failure.into_response(id),
+    };
+
+    // A client retrying after a timeout sends the same `task_id` again.
+    let client_task_id = params
+        .get("task_id")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string());
+    if let Some(task_id) = client_task_id.as_deref() {
+        if let Some(resp) = existing_injection(&id, &*state.read().await, task_id) {
+            return resp;
+        }
     }
 
     // Reputation gate: require a registered agent with at least 1 completed task.
@@ -1994,17 +2909,16 @@ pub(crate) async fn handle_inject_task(
         let s = state.read().await;
         match &injector_agent_id {
             None => {
-                return SwarmResponse::error(
+                return SwarmResponse::invalid_params(
                     id,
-                    -32602,
-                    "Missing 'injector_agent_id': only registered agents with good standing can inject tasks".into(),
+                    "Missing 'injector_agent_id': only registered agents with good standing can inject tasks",
                 );
             }
             Some(agent_id) => {
                 if !s.has_inject_reputation(agent_id) {
-                    return SwarmResponse::error(
+                    return SwarmResponse::rpc_error(
                         id,
-                        -32003,
+                        RpcErrorCode::InsufficientReputation,
                         format!(
                             "insufficient_reputation: agent '{}' needs Member tier (score >= 100) to inject tasks",
                             agent_id
@@ -2020,9 +2934,9 @@ pub(crate) async fn handle_inject_task(
         let agent_id_for_rate = injector_agent_id.as_deref().unwrap_or("");
         let mut s = state.write().await;
         if !s.check_and_update_inject_rate_limit(agent_id_for_rate) {
-            return SwarmResponse::error(
+            return SwarmResponse::rpc_error(
                 id,
-                -32029,
+                RpcErrorCode::RateLimited,
                 format!(
                     "rate_limited: agent '{}' has exceeded the task injection rate limit (max 10 per 60s)",
                     agent_id_for_rate
@@ -2037,18 +2951,18 @@ pub(crate) async fn handle_inject_task(
         let s = state.read().await;
         let concurrent = s.principal_active_injection_count(&injector_id);
         if concurrent >= crate::connector::MAX_CONCURRENT_INJECTIONS {
-            return SwarmResponse::error(
+            return SwarmResponse::rpc_error(
                 id,
-                -32007,
+                RpcErrorCode::BudgetExceeded,
                 format!("Budget exceeded: {} concurrent active tasks (max {}). Retry when some complete.",
                     concurrent, crate::connector::MAX_CONCURRENT_INJECTIONS),
             );
         }
         let blast = s.principal_blast_radius(&injector_id);
         if blast >= crate::connector::MAX_BLAST_RADIUS {
-            return SwarmResponse::error(
+            return SwarmResponse::rpc_error(
                 id,
-                -32008,
+                RpcErrorCode::BlastRadiusExceeded,
                 format!("Blast radius budget exceeded: {} points (max {}). Close or verify pending receipts first.",
                     blast, crate::connector::MAX_BLAST_RADIUS),
             );
@@ -2057,12 +2971,21 @@ pub(crate) async fn handle_inject_task(
 
 
     let mut state_guard = state.write().await;
+    // Re-check under the write lock in case a concurrent retry got here first.
+    if let Some(resp) = client_task_id
+        .as_deref()
+        .and_then(|task_id| existing_injection(&id, &state_guard, task_id))
+    {
+        return resp;
+    }
     let epoch = state_guard.epoch_manager.current_epoch();
-    let mut task = wws_protocol::Task::new(description.clone(), 1, epoch);
-    // Accept an optional pre-specified task_id (for multi-node injection with same ID)
-    if let Some(v) = params.get("task_id").and_then(|v| v.as_str()).filter(|s| !s.is_empty()) {
-        task.task_id = v.to_string();
+    let mut task = wws_protocol::Task::new(description.clone(), tier, epoch);
+    // Accept an optional pre-specified task_id (for retries and multi-node
+    // injection with the same ID)
+    if let Some(task_id) = client_task_id {
+        task.task_id = task_id;
     }
+    task.priority = priority;
     // Accept extended holonic task fields if provided
     if let Some(v) = params.get("task_type").and_then(|v| v.as_str()) {
         task.task_type = v.to_string();
@@ -2098,9 +3021,19 @@ pub(crate) async fn handle_inject_task(
     task.injector_id = Some(state_guard.agent_id.clone());
     let task_id = task.task_id.clone();
 
-    // Add task to the local task set (CRDT).
-    state_guard.task_set.add(task_id.clone());
+    // Add task to the target swarm's task set (CRDT).
+    let swarm_id = target_swarm.unwrap_or_else(|| state_guard.current_swarm_id.as_str().to_string());
+    match state_guard.task_set_for_mut(&swarm_id) {
+        Some(task_set) => task_set.add(task_id.clone()),
+        None => {
+            return SwarmResponse::invalid_params(
+                id,
+                format!("Not a member of swarm '{}'", swarm_id),
+            );
+        }
+    }
     state_guard.task_details.insert(task_id.clone(), task.clone());
+    state_guard.record_hop_budget(&task_id, ttl_hops);
     let actor = state_guard.agent_id.to_string();
     state_guard.push_task_timeline_event(
         &task_id,
@@ -2129,8 +3062,14 @@ pub(crate) async fn handle_inject_task(
         WwsConnector::recompute_hierarchy_from_members(&mut state_guard, &active_members);
     }
 
-    // Always init RFP on the injecting node (no tier guard — injector IS Tier1 after rebuild).
-    {
+    // Always init RFP on the injecting node (no tier guard — injector IS Tier1 after rebuild),
+    // unless a single hop leaves no budget for subtasks: then it executes the task itself.
+    if state_guard.hop_budget_exhausted(&task_id) {
+        state_guard.execute_directly(&task_id);
+        if let Some(assigned) = state_guard.task_details.get(&task_id) {
+            task = assigned.clone();
+        }
+    } else {
         // Use the board-size formula for expected proposers/voters
         let total_members = state_guard.member_last_seen.len() + 1;
         let pool = total_members.saturating_sub(1); // minus injector
@@ -2144,11 +3083,7 @@ pub(crate) async fn handle_inject_task(
             ((pool as f64).sqrt()).round().max(5.0).min(10.0) as usize
         };
 
-        let mut rfp = wws_consensus::RfpCoordinator::new(
-            task_id.clone(),
-            epoch,
-            expected_participants,
-        );
+        let mut rfp = state_guard.new_rfp_coordinator(task_id.clone(), epoch, expected_participants);
         if let Err(e) = rfp.inject_task(&task) {
             tracing::warn!(error = %e, task_id = %task_id, "Failed to initialize local RFP on inject");
         } else {
@@ -2181,18 +3116,19 @@ pub(crate) async fn handle_inject_task(
         ProtocolMethod::TaskInjection.as_str(),
         serde_json::to_value(&inject_params).unwrap_or_default(),
         String::new(),
-    );
+    )
+    .with_ttl_hops(ttl_hops);
 
-    let swarm_id = state_guard.current_swarm_id.as_str().to_string();
     drop(state_guard);
 
+    let task_topic = SwarmTopics::tasks_for(&swarm_id, tier);
     // Fire-and-forget: publish task + subscribe to its topics in the background.
     // This prevents the inject RPC from blocking on swarm event loop replies under load.
     if let Ok(data) = serde_json::to_vec(&msg) {
         let nh = network_handle.clone();
-        let task_topic = SwarmTopics::tasks_for(&swarm_id, 1);
-        let proposals_topic = SwarmTopics::proposals_for(&swarm_id, &task_id);
-        let voting_topic = SwarmTopics::voting_for(&swarm_id, &task_id);
+        let task_topic = task_topic.clone();
+        let proposals_topic = SwarmTopics::proposals_for(&swarm_id, &task_id, epoch);
+        let voting_topic = SwarmTopics::voting_for(&swarm_id, &task_id, epoch);
         let results_topic = SwarmTopics::results_for(&swarm_id, &task_id);
         tokio::spawn(async move {
             if let Err(e) = nh.publish(&task_topic, data).await {
@@ -2216,6 +3152,9 @@ pub(crate) async fn handle_inject_task(
             "task_id": task_id,
             "description": description,
             "epoch": epoch,
+            "tier": tier,
+            "swarm_id": swarm_id,
+            "topic": task_topic,
             "injected": true,
         }),
     )
@@ -2237,6 +3176,8 @@ async fn handle_get_hierarchy(
         "is_self": true,
     });
 
+    // `active_member_ids` is sorted by agent ID, so peers keep a stable order
+    // across calls.
     let peers: Vec<serde_json::Value> = active_members
         .iter()
         .filter(|agent_id| *agent_id != &state.agent_id.to_string())
@@ -2270,6 +3211,38 @@ async fn handle_get_hierarchy(
     )
 }
 
+/// Handle `swarm.export_topology` - active members, tiers and parent links as
+/// JSON (`format: "json"`, the default) or Graphviz DOT (`format: "dot"`).
+async fn handle_export_topology(
+    id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let format = params.get("format").and_then(|v| v.as_str()).unwrap_or("json");
+    if format != "json" && format != "dot" {
+        return SwarmResponse::invalid_params(
+            id,
+            format!("Unknown format '{}': expected \"dot\" or \"json\"", format),
+        );
+    }
+
+    let state = state.read().await;
+    let topology = state.topology(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS));
+    let body = if format == "dot" {
+        serde_json::Value::String(topology.to_dot())
+    } else {
+        serde_json::json!(topology)
+    };
+    SwarmResponse::success(
+        id,
+        serde_json::json!({
+            "format": format,
+            "topology": body,
+            "epoch": state.epoch_manager.current_epoch(),
+        }),
+    )
+}
+
 /// Handle `swarm.get_board_status` - returns all active holons.
 async fn handle_get_board_status(
     request_id: Option<String>,
@@ -2304,7 +3277,7 @@ async fn handle_get_deliberation(
 ) -> SwarmResponse {
     let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
         Some(id) => id.to_string(),
-        None => return SwarmResponse::error(request_id, -32602, "task_id required".to_string()),
+        None => return SwarmResponse::invalid_params(request_id, "task_id required"),
     };
     let state = state.read().await;
     let messages: Vec<serde_json::Value> = state.deliberation_messages
@@ -2332,7 +3305,7 @@ async fn handle_get_ballots(
 ) -> SwarmResponse {
     let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
         Some(id) => id.to_string(),
-        None => return SwarmResponse::error(request_id, -32602, "task_id required".to_string()),
+        None => return SwarmResponse::invalid_params(request_id, "task_id required"),
     };
     let state = state.read().await;
     let ballots: Vec<serde_json::Value> = state.ballot_records
@@ -2357,7 +3330,7 @@ async fn handle_get_irv_rounds(
 ) -> SwarmResponse {
     let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
         Some(id) => id.to_string(),
-        None => return SwarmResponse::error(request_id, -32602, "task_id required".to_string()),
+        None => return SwarmResponse::invalid_params(request_id, "task_id required"),
     };
     let state = state.read().await;
     let rounds: Vec<serde_json::Value> = state.irv_rounds
@@ -2373,6 +3346,39 @@ async fn handle_get_irv_rounds(
     SwarmResponse::success(request_id, serde_json::json!({ "task_id": task_id, "irv_rounds": rounds }))
 }
 
+/// Handle `swarm.get_voting_result` - returns the IRV outcome for a task with
+/// the aggregated critic scores of every plan that received any.
+async fn handle_get_voting_result(
+    request_id: Option<String>,
+    params: &serde_json::Value,
+    state: &Arc<RwLock<ConnectorState>>,
+) -> SwarmResponse {
+    let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
+        Some(id) => id.to_string(),
+        None => return SwarmResponse::invalid_params(request_id, "task_id required"),
+    };
+    let state = state.read().await;
+    let Some(result) = state.voting_results.get(&task_id) else {
+        return SwarmResponse::not_found(
+            request_id,
+            format!("No voting result for task {}", task_id),
+        );
+    };
+    SwarmResponse::success(
+        request_id,
+        serde_json::json!({
+            "task_id": task_id,
+            "winner": result.winner,
+            "rounds": result.rounds,
+            "elimination_order": result.elimination_order,
+            "final_tallies": result.final_tallies,
+            "total_votes": result.total_votes,
+            "winner_critic_score": result.winner_critic_score,
+            "critic_scores": result.critic_scores,
+        }),
+    )
+}
+
 /// Handle `swarm.register_name` - bind a human-readable name to a DID in the local registry.
 async fn handle_register_name(
     id: Option<String>,
@@ -2381,11 +3387,11 @@ async fn handle_register_name(
 ) -> SwarmResponse {
     let name = match params.get("name").and_then(|v| v.as_str()) {
         Some(v) if !v.trim().is_empty() => v.trim().to_string(),
-        _ => return SwarmResponse::error(id, -32602, "Missing 'name' parameter".into()),
+        _ => return SwarmResponse::invalid_params(id, "Missing 'name' parameter"),
     };
     let did = match params.get("did").and_then(|v| v.as_str()) {
         Some(v) if !v.trim().is_empty() => v.trim().to_string(),
-        _ => return SwarmResponse::error(id, -32602, "Missing 'did' parameter".into()),
+        _ => return SwarmResponse::invalid_params(id, "Missing 'did' parameter"),
     };
     let mut s = state.write().await;
     s.name_registry.insert(name.clone(), did.clone());
@@ -2400,12 +3406,12 @@ async fn handle_resolve_name(
 ) -> SwarmResponse {
     let name = match params.get("name").and_then(|v| v.as_str()) {
         Some(v) if !v.trim().is_empty() => v.trim().to_string(),
-        _ => return SwarmResponse::error(id, -32602, "Missing 'name' parameter".into()),
+        _ => return SwarmResponse::invalid_params(id, "Missing 'name' parameter"),
     };
     let s = state.read().await;
     match s.name_registry.get(&name) {
         Some(did) => SwarmResponse::success(id, serde_json::json!({ "name": name, "did": did })),
-        None => SwarmResponse::error(id, -32001, format!("Name not found: {}", name)),
+        None => SwarmResponse::rpc_error(id, RpcErrorCode::Unauthorized, format!("Name not found: {}", name)),
     }
 }
 
@@ -2424,11 +3430,11 @@ async fn handle_send_message(
 ) -> SwarmResponse {
     let to = match params.get("to").and_then(|v| v.as_str()) {
         Some(v) if !v.trim().is_empty() => v.trim().to_string(),
-        _ => return SwarmResponse::error(id, -32602, "Missing 'to' parameter".into()),
+        _ => return SwarmResponse::invalid_params(id, "Missing 'to' parameter"),
     };
     let content = match params.get("content").and_then(|v| v.as_str()) {
         Some(v) if !v.trim().is_empty() => v.trim().to_string(),
-        _ => return SwarmResponse::error(id, -32602, "Missing 'content' parameter".into()),
+        _ => return SwarmResponse::invalid_params(id, "Missing 'content' parameter"),
     };
 
     let (from, swarm_id) = {
@@ -2596,7 +3602,7 @@ async fn handle_submit_reputation_event(
         .map(|s| s.to_string());
 
     if target_did.is_empty() {
-        return SwarmResponse::error(id, -32602, "missing target_did".to_string());
+        return SwarmResponse::invalid_params(id, "missing target_did");
     }
 
     let mut s = state.write().await;
@@ -2607,19 +3613,19 @@ async fn handle_submit_reputation_event(
         .map(|l| l.effective_score())
         .unwrap_or(0);
     if submitter_score < 100 {
-        return SwarmResponse::error(id, -32603, "insufficient reputation to submit events".to_string());
+        return SwarmResponse::rpc_error(id, RpcErrorCode::InsufficientReputation, "insufficient reputation to submit events");
     }
 
     // Rate limit: max 20 per hour per submitter
     if !s.check_rep_event_rate_limit(&submitter) {
-        return SwarmResponse::error(id, -32604, "reputation event rate limit exceeded".to_string());
+        return SwarmResponse::rpc_error(id, RpcErrorCode::RateLimited, "reputation event rate limit exceeded");
     }
 
     // Parse event type (only allow subjective positive events from external submitters)
     let event_type = match event_type_str.as_str() {
         "HighQualityResult" => RepEventType::HighQualityResult,
         "HelpedNewAgent" => RepEventType::HelpedNewAgent,
-        _ => return SwarmResponse::error(id, -32602, "unsupported event_type for external submission".to_string()),
+        _ => return SwarmResponse::invalid_params(id, "unsupported event_type for external submission"),
     };
 
     let base = event_type.base_points();
@@ -2656,7 +3662,7 @@ async fn handle_rotate_key(
     let rotation_timestamp = params.get("rotation_timestamp").and_then(|v| v.as_i64()).unwrap_or(0);
 
     if agent_did.is_empty() || new_pubkey_hex.is_empty() {
-        return SwarmResponse::error(id, -32602, "missing required fields".to_string());
+        return SwarmResponse::invalid_params(id, "missing required fields");
     }
 
     let grace_expires = chrono::Utc::now() + chrono::Duration::hours(48);
@@ -2691,7 +3697,7 @@ async fn handle_emergency_revocation(
     let revocation_timestamp = params.get("revocation_timestamp").and_then(|v| v.as_i64()).unwrap_or(0);
 
     if agent_did.is_empty() || recovery_pubkey_hex.is_empty() || new_primary_pubkey_hex.is_empty() {
-        return SwarmResponse::error(id, -32602, "missing required fields".to_string());
+        return SwarmResponse::invalid_params(id, "missing required fields");
     }
 
     let challenge_expires = chrono::Utc::now() + chrono::Duration::hours(24);
@@ -2728,10 +3734,10 @@ async fn handle_register_guardians(
     let threshold = params.get("threshold").and_then(|v| v.as_u64()).unwrap_or(2) as u32;
 
     if agent_did.is_empty() || guardians.is_empty() {
-        return SwarmResponse::error(id, -32602, "missing agent_did or guardians".to_string());
+        return SwarmResponse::invalid_params(id, "missing agent_did or guardians");
     }
     if threshold as usize > guardians.len() {
-        return SwarmResponse::error(id, -32602, "threshold exceeds guardian count".to_string());
+        return SwarmResponse::invalid_params(id, "threshold exceeds guardian count");
     }
 
     let designation = GuardianDesignation { agent_did: agent_did.clone(), guardians, threshold };
@@ -2753,7 +3759,7 @@ async fn handle_guardian_recovery_vote(
     let new_pubkey = params.get("new_pubkey").and_then(|v| v.as_str()).unwrap_or("").to_string();
 
     if guardian_did.is_empty() || target_did.is_empty() || new_pubkey.is_empty() {
-        return SwarmResponse::error(id, -32602, "missing required fields".to_string());
+        return SwarmResponse::invalid_params(id, "missing required fields");
     }
 
     // Guardian must have Trusted tier (score >= 500) per spec
@@ -2764,7 +3770,7 @@ async fn handle_guardian_recovery_vote(
             .unwrap_or(0)
     };
     if guardian_score < 500 {
-        return SwarmResponse::error(id, -32603, "guardian needs Trusted tier (score >= 500)".to_string());
+        return SwarmResponse::rpc_error(id, RpcErrorCode::InsufficientReputation, "guardian needs Trusted tier (score >= 500)");
     }
 
     let mut s = state.write().await;
@@ -2775,7 +3781,7 @@ async fn handle_guardian_recovery_vote(
         .unwrap_or((2, false));
 
     if !is_guardian {
-        return SwarmResponse::error(id, -32603, "guardian not in designated list for this agent".to_string());
+        return SwarmResponse::rpc_error(id, RpcErrorCode::Forbidden, "guardian not in designated list for this agent");
     }
 
     let vote = GuardianVote {
@@ -2840,11 +3846,11 @@ async fn handle_create_receipt(
 ) -> SwarmResponse {
     let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
         Some(s) if !s.is_empty() => s.to_string(),
-        _ => return SwarmResponse::error(id, -32602, "Missing 'task_id'".into()),
+        _ => return SwarmResponse::invalid_params(id, "Missing 'task_id'"),
     };
     let agent_id = match params.get("agent_id").and_then(|v| v.as_str()) {
         Some(s) if !s.is_empty() => s.to_string(),
-        _ => return SwarmResponse::error(id, -32602, "Missing 'agent_id'".into()),
+        _ => return SwarmResponse::invalid_params(id, "Missing 'agent_id'"),
     };
     let deliverable_type = params
         .get("deliverable_type")
@@ -2888,7 +3894,7 @@ async fn handle_fulfill_receipt(
 ) -> SwarmResponse {
     let receipt_id = match params.get("receipt_id").and_then(|v| v.as_str()) {
         Some(s) if !s.is_empty() => s.to_string(),
-        _ => return SwarmResponse::error(id, -32602, "Missing 'receipt_id'".into()),
+        _ => return SwarmResponse::invalid_params(id, "Missing 'receipt_id'"),
     };
     let evidence_hash = params
         .get("evidence_hash")
@@ -2908,8 +3914,8 @@ async fn handle_fulfill_receipt(
             r.confidence_delta = confidence_delta;
             SwarmResponse::success(id, serde_json::json!({ "ok": true, "state": "AgentFulfilled" }))
         }
-        Some(_) => SwarmResponse::error(id, -32600, "Receipt is not in Active state".into()),
-        None => SwarmResponse::error(id, -32602, format!("Receipt '{}' not found", receipt_id)),
+        Some(_) => SwarmResponse::rpc_error(id, RpcErrorCode::InvalidRequest, "Receipt is not in Active state"),
+        None => SwarmResponse::invalid_params(id, format!("Receipt '{}' not found", receipt_id)),
     }
 }
 
@@ -2921,7 +3927,7 @@ async fn handle_verify_receipt(
 ) -> SwarmResponse {
     let receipt_id = match params.get("receipt_id").and_then(|v| v.as_str()) {
         Some(s) if !s.is_empty() => s.to_string(),
-        _ => return SwarmResponse::error(id, -32602, "Missing 'receipt_id'".into()),
+        _ => return SwarmResponse::invalid_params(id, "Missing 'receipt_id'"),
     };
     let confirmed = params
         .get("confirmed")
@@ -2939,12 +3945,12 @@ async fn handle_verify_receipt(
             let new_state_str = format!("{:?}", r.commitment_state);
             SwarmResponse::success(id, serde_json::json!({ "ok": true, "state": new_state_str }))
         }
-        Some(_) => SwarmResponse::error(
+        Some(_) => SwarmResponse::rpc_error(
             id,
-            -32600,
-            "Receipt is not in AgentFulfilled state".into(),
+            RpcErrorCode::InvalidRequest,
+            "Receipt is not in AgentFulfilled state",
         ),
-        None => SwarmResponse::error(id, -32602, format!("Receipt '{}' not found", receipt_id)),
+        None => SwarmResponse::invalid_params(id, format!("Receipt '{}' not found", receipt_id)),
     }
 }
 
@@ -2956,7 +3962,7 @@ async fn handle_request_clarification(
 ) -> SwarmResponse {
     let task_id = match params.get("task_id").and_then(|v| v.as_str()) {
         Some(s) if !s.is_empty() => s.to_string(),
-        _ => return SwarmResponse::error(id, -32602, "Missing 'task_id'".into()),
+        _ => return SwarmResponse::invalid_params(id, "Missing 'task_id'"),
     };
     let requesting_agent = params.get("requesting_agent")
         .and_then(|v| v.as_str()).unwrap_or("").to_string();
@@ -2964,7 +3970,7 @@ async fn handle_request_clarification(
         .and_then(|v| v.as_str()).unwrap_or("").to_string();
     let question = match params.get("question").and_then(|v| v.as_str()) {
         Some(s) if !s.is_empty() => s.to_string(),
-        _ => return SwarmResponse::error(id, -32602, "Missing 'question'".into()),
+        _ => return SwarmResponse::invalid_params(id, "Missing 'question'"),
     };
 
     let cr = wws_protocol::ClarificationRequest {
@@ -2991,11 +3997,11 @@ async fn handle_resolve_clarification(
 ) -> SwarmResponse {
     let clar_id = match params.get("clarification_id").and_then(|v| v.as_str()) {
         Some(s) if !s.is_empty() => s.to_string(),
-        _ => return SwarmResponse::error(id, -32602, "Missing 'clarification_id'".into()),
+        _ => return SwarmResponse::invalid_params(id, "Missing 'clarification_id'"),
     };
     let resolution = match params.get("resolution").and_then(|v| v.as_str()) {
         Some(s) if !s.is_empty() => s.to_string(),
-        _ => return SwarmResponse::error(id, -32602, "Missing 'resolution'".into()),
+        _ => return SwarmResponse::invalid_params(id, "Missing 'resolution'"),
     };
 
     let mut s = state.write().await;
@@ -3005,8 +4011,8 @@ async fn handle_resolve_clarification(
             cr.resolved_at = Some(chrono::Utc::now());
             SwarmResponse::success(id, serde_json::json!({ "ok": true }))
         }
-        Some(_) => SwarmResponse::error(id, -32600, "Clarification already resolved".into()),
-        None => SwarmResponse::error(id, -32602, format!("Clarification '{}' not found", clar_id)),
+        Some(_) => SwarmResponse::rpc_error(id, RpcErrorCode::InvalidRequest, "Clarification already resolved"),
+        None => SwarmResponse::invalid_params(id, format!("Clarification '{}' not found", clar_id)),
     }
 }
 
@@ -3235,11 +4241,112 @@ mod tests {
         assert!(resp2.error.is_some(), "double-resolve should return error");
     }
 
+    #[tokio::test]
+    async fn test_get_swarm_token_returns_created_token() {
+        let state = make_minimal_state();
+        let params = make_params(&[
+            ("name", serde_json::json!("research")),
+            ("secret", serde_json::json!("hunter2")),
+        ]);
+        let created = handle_create_swarm(Some("1".into()), &params, &state, &make_test_network_handle()).await;
+        let created = created.result.expect("create_swarm should succeed");
+        let swarm_id = created["swarm_id"].as_str().unwrap().to_string();
+
+        let params = make_params(&[("swarm_id", serde_json::json!(swarm_id))]);
+        let resp = handle_get_swarm_token(Some("2".into()), &params, &state).await;
+        let result = resp.result.expect("get_swarm_token should succeed");
+        assert_eq!(result["token"], created["token"]);
+        assert_eq!(result["name"], "research");
+        assert_eq!(result["owner"], created["owner"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_swarm_token_rejects_non_owner() {
+        let state = make_minimal_state();
+        // `test-swarm` is known and joined but was not created locally.
+        let params = make_params(&[("swarm_id", serde_json::json!("test-swarm"))]);
+        let resp = handle_get_swarm_token(Some("1".into()), &params, &state).await;
+        let err = resp.error.expect("non-owner should be rejected");
+        assert_eq!(err.code, -32002);
+
+        let params = make_params(&[("swarm_id", serde_json::json!("no-such-swarm"))]);
+        let resp = handle_get_swarm_token(Some("2".into()), &params, &state).await;
+        assert_eq!(resp.error.expect("unknown swarm").code, -32001);
+    }
+
+    #[tokio::test]
+    async fn test_get_swarm_history_lists_events() {
+        let state = make_minimal_state();
+        state
+            .write()
+            .await
+            .known_swarms
+            .get_mut("test-swarm")
+            .unwrap()
+            .record_event(crate::connector::SwarmHistoryKind::Join, AgentId::new("did:swarm:peer".into()));
+
+        let params = make_params(&[("swarm_id", serde_json::json!("test-swarm"))]);
+        let result = handle_get_swarm_history(Some("1".into()), &params, &state)
+            .await
+            .result
+            .expect("history of a known swarm");
+        assert_eq!(result["events"][0]["kind"], "join");
+        assert_eq!(result["events"][0]["agent_id"], "did:swarm:peer");
+
+        let params = make_params(&[("swarm_id", serde_json::json!("no-such-swarm"))]);
+        let resp = handle_get_swarm_history(Some("2".into()), &params, &state).await;
+        assert_eq!(resp.error.expect("unknown swarm").code, -32001);
+    }
+
+    #[tokio::test]
+    async fn test_join_swarm_accepts_valid_token() {
+        let state = make_minimal_state();
+        let params = make_params(&[
+            ("name", serde_json::json!("private")),
+            ("secret", serde_json::json!("s3cret")),
+        ]);
+        let created = handle_create_swarm(Some("1".into()), &params, &state, &make_test_network_handle()).await;
+        let created = created.result.expect("create_swarm should succeed");
+        let swarm_id = created["swarm_id"].as_str().unwrap().to_string();
+        // The creator encrypts the new swarm's topics right away.
+        assert!(state.read().await.payload_codec().is_some());
+        state.write().await.known_swarms.get_mut(&swarm_id).unwrap().joined = false;
+
+        let params = make_params(&[
+            ("swarm_id", serde_json::json!(swarm_id)),
+            ("token", created["token"].clone()),
+        ]);
+        let resp = handle_join_swarm(Some("2".into()), &params, &state, &make_test_network_handle()).await;
+        assert!(resp.error.is_none(), "valid token should be accepted: {:?}", resp.error);
+        assert!(state.read().await.known_swarms[&swarm_id].joined);
+    }
+
+    #[tokio::test]
+    async fn test_join_swarm_rejects_forged_token() {
+        let state = make_minimal_state();
+        let params = make_params(&[
+            ("name", serde_json::json!("private")),
+            ("secret", serde_json::json!("s3cret")),
+        ]);
+        let created = handle_create_swarm(Some("1".into()), &params, &state, &make_test_network_handle()).await;
+        let swarm_id = created.result.unwrap()["swarm_id"].as_str().unwrap().to_string();
+        state.write().await.known_swarms.get_mut(&swarm_id).unwrap().joined = false;
+
+        let forged = SwarmToken::generate(&SwarmId::new(swarm_id.clone()), "guess");
+        let params = make_params(&[
+            ("swarm_id", serde_json::json!(swarm_id)),
+            ("token", serde_json::json!(forged.as_str())),
+        ]);
+        let resp = handle_join_swarm(Some("2".into()), &params, &state, &make_test_network_handle()).await;
+        assert_eq!(resp.error.expect("forged token must be rejected").code, -32002);
+        assert!(!state.read().await.known_swarms[&swarm_id].joined);
+    }
+
     /// Create a throwaway SwarmHandle for unit tests that need network_handle.
     fn make_test_network_handle() -> wws_network::SwarmHandle {
         use wws_network::{SwarmHost, SwarmHostConfig};
         let config = SwarmHostConfig {
-            listen_addr: "/ip4/127.0.0.1/tcp/0".parse().unwrap(),
+            listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
             ..Default::default()
         };
         let (_host, handle, _rx) = SwarmHost::new(config).expect("test SwarmHost");
@@ -3271,6 +4378,142 @@ mod tests {
         assert!((task.confidence_review_threshold - 0.3).abs() < 1e-4);
     }
 
+    #[tokio::test]
+    async fn test_inject_task_sanitizes_description() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        let params = serde_json::json!({
+            "task_id": "t-ansi-1",
+            "injector_agent_id": "did:swarm:test-self",
+            "description": "\u{1b}[2J\u{1b}[1mSummarize\u{1b}[0m report\n[WARN] spoofed",
+        });
+        let resp = handle_inject_task(Some("1".into()), &params, &state, &network_handle).await;
+        assert!(resp.error.is_none(), "inject should succeed: {:?}", resp.error);
+
+        let s = state.read().await;
+        let task = s.task_details.get("t-ansi-1").expect("task stored");
+        assert_eq!(task.description, "Summarize report [WARN] spoofed");
+    }
+
+    #[tokio::test]
+    async fn test_inject_task_retry_with_same_id_is_idempotent() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        let params = serde_json::json!({
+            "task_id": "t-retry-1",
+            "injector_agent_id": "did:swarm:test-self",
+            "description": "Summarize the report",
+        });
+        let first = handle_inject_task(Some("1".into()), &params, &state, &network_handle).await;
+        assert_eq!(first.result.as_ref().unwrap()["injected"], true);
+        let retry = handle_inject_task(Some("2".into()), &params, &state, &network_handle).await;
+        let retry = retry.result.expect("retry succeeds");
+        assert_eq!(retry["injected"], false);
+        assert_eq!(retry["task_id"], "t-retry-1");
+        assert_eq!(retry["description"], "Summarize the report");
+        {
+            let s = state.read().await;
+            assert_eq!(s.task_details.len(), 1);
+            assert_eq!(s.task_set.len(), 1);
+            // The retry is not counted against the injection rate limit.
+            assert_eq!(s.inject_rate_limiter["did:swarm:test-self"].len(), 1);
+        }
+
+        let params = serde_json::json!({
+            "task_id": "t-retry-2",
+            "injector_agent_id": "did:swarm:test-self",
+            "description": "Summarize the report",
+        });
+        let other = handle_inject_task(Some("3".into()), &params, &state, &network_handle).await;
+        assert_eq!(other.result.unwrap()["injected"], true);
+        assert_eq!(state.read().await.task_details.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_inject_task_records_hop_budget() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        let params = serde_json::json!({
+            "task_id": "t-ttl-1",
+            "injector_agent_id": "did:swarm:test-self",
+            "description": "Tier-local task",
+            "ttl_hops": 2,
+        });
+        let resp = handle_inject_task(Some("1".into()), &params, &state, &network_handle).await;
+        assert!(resp.error.is_none(), "inject should succeed: {:?}", resp.error);
+        assert_eq!(state.read().await.forward_ttl_hops("t-ttl-1"), Some(1));
+
+        let params = serde_json::json!({
+            "injector_agent_id": "did:swarm:test-self",
+            "description": "Goes nowhere",
+            "ttl_hops": 0,
+        });
+        let resp = handle_inject_task(Some("2".into()), &params, &state, &network_handle).await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::InvalidParams));
+    }
+
+    #[tokio::test]
+    async fn single_hop_injection_is_executed_directly_and_completes() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        let params = serde_json::json!({
+            "task_id": "t-one-hop",
+            "injector_agent_id": "did:swarm:test-self",
+            "description": "Answer locally",
+            "ttl_hops": 1,
+        });
+        let resp = handle_inject_task(Some("1".into()), &params, &state, &network_handle).await;
+        assert!(resp.error.is_none(), "inject should succeed: {:?}", resp.error);
+        {
+            let s = state.read().await;
+            assert!(s.hop_budget_exhausted("t-one-hop"));
+            assert!(!s.rfp_coordinators.contains_key("t-one-hop"), "not decomposed");
+            assert_eq!(s.task_details["t-one-hop"].assigned_to.as_ref(), Some(&s.agent_id));
+        }
+
+        let resp = handle_receive_task(Some("2".into()), &state).await;
+        let pending = resp.result.unwrap()["pending_tasks"].clone();
+        assert_eq!(pending, serde_json::json!(["t-one-hop"]));
+
+        let plan: Plan = serde_json::from_value(sample_plan_params("t-one-hop")).unwrap();
+        let err = state.write().await.apply_plan_proposal(plan).unwrap_err();
+        assert_eq!(err.code, RpcErrorCode::InvalidState);
+
+        let submit = serde_json::json!({
+            "task_id": "t-one-hop",
+            "artifact": {"content": "42"}
+        });
+        let resp = handle_submit_result(Some("3".into()), &submit, &state, &network_handle).await;
+        assert!(resp.error.is_none(), "result accepted: {:?}", resp.error);
+        assert_eq!(state.read().await.task_details["t-one-hop"].status, TaskStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_inject_task_rejects_empty_description_after_sanitizing() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        let params = serde_json::json!({
+            "injector_agent_id": "did:swarm:test-self",
+            "description": "\u{1b}[31m\u{1b}[0m \r\n",
+        });
+        let resp = handle_inject_task(Some("1".into()), &params, &state, &network_handle).await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::InvalidParams));
+        assert!(state.read().await.task_details.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_inject_task_enforces_configured_max_length() {
+        let state = make_minimal_state();
+        state.write().await.max_task_description_len = 8;
+        let network_handle = make_test_network_handle();
+        let params = serde_json::json!({
+            "injector_agent_id": "did:swarm:test-self",
+            "description": "nine char",
+        });
+        let resp = handle_inject_task(Some("1".into()), &params, &state, &network_handle).await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::InvalidParams));
+    }
+
     #[tokio::test]
     async fn test_submit_result_triggers_pending_review() {
         let state = make_minimal_state();
@@ -3348,4 +4591,1983 @@ mod tests {
         assert_eq!(task.status, wws_protocol::TaskStatus::Completed,
             "task below threshold should stay Completed, got {:?}", task.status);
     }
+
+    fn sample_plan_params(task_id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "plan_id": "plan-1",
+            "task_id": task_id,
+            "epoch": 1,
+            "subtasks": [
+                {"index": 1, "description": "Research", "estimated_complexity": 0.3},
+                {"index": 2, "description": "Write", "estimated_complexity": 0.4}
+            ],
+            "rationale": "split research from writing"
+        })
+    }
+
+    fn timeline_stages(state: &ConnectorState, task_id: &str) -> Vec<String> {
+        state
+            .task_timelines
+            .get(task_id)
+            .map(|events| events.iter().map(|e| e.stage.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn test_propose_plan_single_lock_matches_handler() {
+        let via_handler = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        let params = sample_plan_params("t-plan");
+        let resp = handle_propose_plan(Some("1".into()), &params, &via_handler, &network_handle).await;
+        assert!(resp.error.is_none(), "propose should succeed: {:?}", resp.error);
+        let result = resp.result.unwrap();
+        assert_eq!(result["subtasks_created"], 2);
+        // Subtasks are only assigned once voting picks a winning plan.
+        assert!(result.get("assignments_published").is_none());
+
+        let mut direct = ConnectorState::new_for_test();
+        let plan: Plan = serde_json::from_value(params).unwrap();
+        let outcome = direct.apply_plan_proposal(plan).expect("transition should succeed");
+        assert_eq!(outcome.swarm_id, "test-swarm");
+
+        let handled = via_handler.read().await;
+        let h_task = &handled.task_details["t-plan"];
+        let d_task = &direct.task_details["t-plan"];
+        assert_eq!(h_task.status, d_task.status);
+        assert_eq!(h_task.subtasks, d_task.subtasks);
+        assert_eq!(
+            handled.rfp_coordinators["t-plan"].commit_count(),
+            direct.rfp_coordinators["t-plan"].commit_count()
+        );
+        assert_eq!(
+            handled.rfp_coordinators["t-plan"].reveals.len(),
+            direct.rfp_coordinators["t-plan"].reveals.len()
+        );
+        let h_req = &handled.task_vote_requirements["t-plan"];
+        let d_req = &direct.task_vote_requirements["t-plan"];
+        assert_eq!(h_req.expected_voters, d_req.expected_voters);
+        assert_eq!(h_req.tier_level, d_req.tier_level);
+        assert_eq!(
+            handled.deliberation_messages["t-plan"].len(),
+            direct.deliberation_messages["t-plan"].len()
+        );
+        // The handler additionally records the post-publish stage.
+        let mut h_stages = timeline_stages(&handled, "t-plan");
+        assert_eq!(h_stages.pop().as_deref(), Some("published"));
+        assert_eq!(h_stages, timeline_stages(&direct, "t-plan"));
+    }
+
+    #[test]
+    fn test_propose_plan_rejects_empty_subtasks_without_mutation() {
+        let mut state = ConnectorState::new_for_test();
+        let plan: Plan = serde_json::from_value(serde_json::json!({
+            "plan_id": "plan-empty",
+            "task_id": "t-empty",
+            "subtasks": []
+        }))
+        .unwrap();
+        let err = state.apply_plan_proposal(plan).expect_err("empty plan must fail");
+        assert_eq!(err.code, RpcErrorCode::EmptyPlan);
+        assert!(!state.task_details.contains_key("t-empty"));
+        assert!(!state.rfp_coordinators.contains_key("t-empty"));
+    }
+
+    #[test]
+    fn test_propose_plan_expands_subtask_templates() {
+        let mut state = ConnectorState::new_for_test();
+        let mut parent = Task::new("Survey rust async runtimes".into(), 1, 1);
+        parent.task_id = "t-tmpl".into();
+        state.task_details.insert("t-tmpl".into(), parent);
+        let mut params = sample_plan_params("t-tmpl");
+        params["subtasks"][0]["description"] = "Research for: {parent_description}".into();
+        let plan: Plan = serde_json::from_value(params.clone()).unwrap();
+
+        let outcome = state.apply_plan_proposal(plan).expect("proposal accepted");
+        assert_eq!(outcome.plan.subtasks[0].description, "Research for: Survey rust async runtimes");
+        assert_eq!(outcome.plan.subtasks[1].description, "Write");
+
+        // Disabled, the template reaches the plan as written.
+        let mut state = ConnectorState::new_for_test();
+        state.expand_subtask_templates = false;
+        let mut parent = Task::new("Survey".into(), 1, 1);
+        parent.task_id = "t-tmpl".into();
+        state.task_details.insert("t-tmpl".into(), parent);
+        let plan: Plan = serde_json::from_value(params).unwrap();
+        let outcome = state.apply_plan_proposal(plan).expect("proposal accepted");
+        assert_eq!(outcome.plan.subtasks[0].description, "Research for: {parent_description}");
+    }
+
+    #[test]
+    fn test_propose_plan_sanitizes_subtask_descriptions() {
+        let mut state = ConnectorState::new_for_test();
+        let mut params = sample_plan_params("t-clean");
+        params["subtasks"][0]["description"] = "\u{1b}[31mResearch\u{1b}[0m\nnow".into();
+        let plan: Plan = serde_json::from_value(params.clone()).unwrap();
+        let outcome = state.apply_plan_proposal(plan).expect("proposal accepted");
+        assert_eq!(outcome.plan.subtasks[0].description, "Research now");
+
+        let mut state = ConnectorState::new_for_test();
+        params["subtasks"][1]["description"] = "\u{1b}[2J\r\n".into();
+        let plan: Plan = serde_json::from_value(params).unwrap();
+        let err = state.apply_plan_proposal(plan).expect_err("blank subtask rejected");
+        assert_eq!(err.code, RpcErrorCode::InvalidParams);
+        assert!(!state.rfp_coordinators.contains_key("t-clean"));
+    }
+
+    #[test]
+    fn test_propose_plan_limits_apply_to_expanded_descriptions() {
+        let mut state = ConnectorState::new_for_test();
+        state.plan_limits.max_subtask_description_len = 32;
+        let mut parent = Task::new("x".repeat(64), 1, 1);
+        parent.task_id = "t-tmpl".into();
+        state.task_details.insert("t-tmpl".into(), parent);
+        let mut params = sample_plan_params("t-tmpl");
+        params["subtasks"][0]["description"] = "{parent_description}".into();
+        let plan: Plan = serde_json::from_value(params).unwrap();
+
+        let err = state.apply_plan_proposal(plan).expect_err("expanded description is too long");
+        assert_eq!(err.code, RpcErrorCode::PlanTooLarge);
+        assert!(!state.rfp_coordinators.contains_key("t-tmpl"));
+    }
+
+    #[tokio::test]
+    async fn test_submit_result_single_lock_matches_handler() {
+        let seed = |state: &mut ConnectorState| {
+            state.task_details.insert(
+                "t-sub".to_string(),
+                Task {
+                    task_id: "t-sub".to_string(),
+                    parent_task_id: Some("t-root".to_string()),
+                    status: TaskStatus::InProgress,
+                    description: "leaf".to_string(),
+                    tier_level: 2,
+                    ..Default::default()
+                },
+            );
+        };
+        let params = serde_json::json!({
+            "task_id": "t-sub",
+            "content": "done",
+            "artifact": {"content_cid": "cid-1"}
+        });
+
+        let via_handler = make_minimal_state();
+        seed(&mut *via_handler.write().await);
+        let network_handle = make_test_network_handle();
+        let resp = handle_submit_result(Some("1".into()), &params, &via_handler, &network_handle).await;
+        assert!(resp.error.is_none(), "submit should succeed: {:?}", resp.error);
+
+        let mut direct = ConnectorState::new_for_test();
+        seed(&mut direct);
+        let mut submission: ResultSubmissionParams = serde_json::from_value(params.clone()).unwrap();
+        let outcome = direct
+            .apply_result_submission(&mut submission, &params)
+            .expect("transition should succeed");
+        assert_eq!(outcome.task_tier, 2);
+        assert_eq!(submission.agent_id.as_str(), "did:swarm:test-self");
+
+        let handled = via_handler.read().await;
+        let body = resp.result.expect("success body");
+        assert_eq!(body["dag_nodes"].as_u64(), Some(outcome.dag_nodes as u64));
+        assert_eq!(handled.task_details["t-sub"].status, direct.task_details["t-sub"].status);
+        assert_eq!(handled.merkle_dag.node_count(), direct.merkle_dag.node_count());
+        assert_eq!(handled.task_result_text.get("t-sub"), direct.task_result_text.get("t-sub"));
+        assert!(handled.task_results.contains_key("t-sub") && direct.task_results.contains_key("t-sub"));
+        assert_eq!(timeline_stages(&handled, "t-sub"), timeline_stages(&direct, "t-sub"));
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_reflects_state() {
+        let state = make_minimal_state();
+        {
+            let mut s = state.write().await;
+            s.mark_member_seen("did:swarm:peer-a");
+        }
+        let snapshot = state.read().await.snapshot();
+        assert_eq!(snapshot.agent_id.as_str(), "did:swarm:test-self");
+        assert_eq!(snapshot.current_swarm_id.as_str(), "test-swarm");
+        assert_eq!(
+            snapshot.active_member_count(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS)),
+            state.read().await.active_member_count(Duration::from_secs(ACTIVE_MEMBER_STALENESS_SECS))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_cascade_progress_reports_per_root_ratio() {
+        let state = make_minimal_state();
+        let leaf_id = {
+            let mut s = state.write().await;
+            let mut plan = Plan::new("root-a".into(), AgentId::new("p".into()), 1);
+            for i in 0..2 {
+                plan.subtasks.push(wws_protocol::PlanSubtask {
+                    index: i,
+                    description: format!("a{}", i),
+                    required_capabilities: vec![],
+                    estimated_complexity: 0.3,
+                    depends_on: Vec::new(),
+                });
+            }
+            let executors = vec![(AgentId::new("e1".into()), Tier::Executor)];
+            let assignments = s.cascade.distribute_subtasks("root-a", &plan, &executors, 1).unwrap();
+            let leaf_id = assignments[0].task.task_id.clone();
+            s.cascade.record_subtask_completion(&leaf_id).unwrap();
+            leaf_id
+        };
+
+        let resp = handle_get_cascade_progress(Some("1".into()), &state).await;
+        let body = resp.result.expect("success body");
+        let roots = body["roots"].as_array().unwrap();
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0]["root_task_id"], "root-a");
+        assert_eq!(roots[0]["completed"], 1);
+        assert_eq!(roots[0]["total"], 2);
+        assert!((roots[0]["ratio"].as_f64().unwrap() - 0.5).abs() < 1e-9);
+        let slowest = &roots[0]["slowest_pending"];
+        assert_eq!(slowest["parent_task_id"], "root-a");
+        assert_ne!(slowest["task_id"].as_str().unwrap(), leaf_id);
+    }
+
+    #[tokio::test]
+    async fn test_submit_result_rejects_unknown_task() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        let params = serde_json::json!({
+            "task_id": "t-unknown",
+            "artifact": {"content_cid": "cid-orphan"}
+        });
+        let resp = handle_submit_result(Some("1".into()), &params, &state, &network_handle).await;
+        assert_eq!(resp.error.expect("unknown task must be rejected").code, -32004);
+
+        let s = state.read().await;
+        assert_eq!(s.merkle_dag.node_count(), 0, "no orphan leaf may be recorded");
+        assert!(!s.task_results.contains_key("t-unknown"));
+    }
+
+    #[tokio::test]
+    async fn test_submit_result_known_task_records_leaf() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        state.write().await.task_details.insert(
+            "t-known".to_string(),
+            Task {
+                task_id: "t-known".to_string(),
+                parent_task_id: Some("t-root".to_string()),
+                ..Default::default()
+            },
+        );
+        let params = serde_json::json!({
+            "task_id": "t-known",
+            "artifact": {"content_cid": "cid-known"}
+        });
+        let resp = handle_submit_result(Some("1".into()), &params, &state, &network_handle).await;
+        assert!(resp.error.is_none(), "submit should succeed: {:?}", resp.error);
+
+        let s = state.read().await;
+        assert_eq!(s.merkle_dag.node_count(), 1);
+        assert_eq!(s.task_details["t-known"].status, TaskStatus::Completed);
+    }
+
+    /// Signed submission for `t-signed` whose signature covers `content`.
+    async fn signed_result_params(state: &Arc<RwLock<ConnectorState>>, content: &str) -> serde_json::Value {
+        let mut s = state.write().await;
+        s.agent_id = AgentId::new(format!("did:swarm:{}", s.identity_keypair.public().to_peer_id()));
+        s.task_details.insert(
+            "t-signed".to_string(),
+            Task {
+                task_id: "t-signed".to_string(),
+                parent_task_id: Some("t-root".to_string()),
+                ..Default::default()
+            },
+        );
+        let mut submission: ResultSubmissionParams = serde_json::from_value(serde_json::json!({
+            "task_id": "t-signed",
+            "agent_id": s.agent_id,
+            "artifact": {
+                "content_cid": s.content_store.hash_algo().cid(content.as_bytes()),
+                "content": content,
+            },
+        }))
+        .unwrap();
+        submission.artifact.signature = crate::peer_signature::sign_payload(
+            &s.identity_keypair,
+            &submission.artifact_signing_payload(),
+        );
+        serde_json::to_value(&submission).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_submit_result_accepts_signed_artifact_and_keeps_provenance() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        let params = signed_result_params(&state, "signed report").await;
+
+        let resp = handle_submit_result(Some("1".into()), &params, &state, &network_handle).await;
+        assert!(resp.error.is_none(), "signed result should be accepted: {:?}", resp.error);
+        let result = resp.result.unwrap();
+        assert_eq!(result["signed"], true);
+
+        let s = state.read().await;
+        assert_eq!(s.task_details["t-signed"].status, TaskStatus::Completed);
+        let cid = result["content_cid"].as_str().unwrap();
+        let provenance = s.content_store.get_info(cid).unwrap().provenance.clone().unwrap();
+        assert_eq!(provenance.signer, s.agent_id.to_string());
+        assert_eq!(provenance.signature, params["artifact"]["signature"]);
+    }
+
+    #[tokio::test]
+    async fn test_submit_result_rejects_tampered_artifact() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        let mut params = signed_result_params(&state, "signed report").await;
+        params["artifact"]["content"] = serde_json::json!("forged report");
+
+        let resp = handle_submit_result(Some("1".into()), &params, &state, &network_handle).await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::InvalidParams));
+
+        // Re-pointing the CID at the forged content breaks the signature instead.
+        params["artifact"]["content_cid"] =
+            serde_json::json!(state.read().await.content_store.hash_algo().cid(b"forged report"));
+        let resp = handle_submit_result(Some("1".into()), &params, &state, &network_handle).await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::InvalidParams));
+
+        let s = state.read().await;
+        assert_ne!(s.task_details["t-signed"].status, TaskStatus::Completed);
+        assert_eq!(s.merkle_dag.node_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_local_signing_never_covers_a_mismatched_cid() {
+        let state = make_minimal_state();
+        let s = state.read().await;
+        let mut submission: ResultSubmissionParams = serde_json::from_value(serde_json::json!({
+            "task_id": "t-local",
+            "agent_id": s.agent_id,
+            "artifact": {
+                "content_cid": s.content_store.hash_algo().cid(b"other content"),
+                "content": "local report",
+            },
+        }))
+        .unwrap();
+        assert!(s.check_artifact_signature(&mut submission, "local report", true).is_ok());
+        assert_eq!(
+            submission.artifact.content_cid,
+            s.content_store.hash_algo().cid(b"local report")
+        );
+        let signer = format!("did:swarm:{}", s.identity_keypair.public().to_peer_id());
+        assert!(crate::peer_signature::verify_agent_signature(
+            &signer,
+            &submission.artifact_signing_payload(),
+            &submission.artifact.signature,
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_peer_results_need_signature_when_required() {
+        let state = make_minimal_state();
+        let mut s = state.write().await;
+        let mut unsigned: ResultSubmissionParams = serde_json::from_value(serde_json::json!({
+            "task_id": "t-peer",
+            "agent_id": "did:swarm:peer",
+            "artifact": {"content": "peer report"},
+        }))
+        .unwrap();
+        assert!(s.check_artifact_signature(&mut unsigned, "peer report", false).is_ok());
+        assert!(unsigned.artifact.signature.is_empty(), "peer results are never signed for them");
+
+        s.require_artifact_signatures = true;
+        assert!(s.check_artifact_signature(&mut unsigned, "peer report", false).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_propose_plan_rejects_oversized_plan() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        state.write().await.plan_limits = wws_consensus::PlanLimits {
+            max_subtasks_per_plan: 1,
+            max_subtask_description_len: 64,
+            ..Default::default()
+        };
+
+        let resp = handle_propose_plan(
+            Some("1".into()),
+            &sample_plan_params("t-big"),
+            &state,
+            &network_handle,
+        )
+        .await;
+        assert_eq!(resp.error.expect("oversized plan must be rejected").code, -32006);
+        let s = state.read().await;
+        assert!(!s.task_details.contains_key("t-big"));
+        assert!(!s.rfp_coordinators.contains_key("t-big"));
+    }
+
+    #[tokio::test]
+    async fn test_propose_plan_accepts_plan_at_limit() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        state.write().await.plan_limits = wws_consensus::PlanLimits {
+            max_subtasks_per_plan: 2,
+            max_subtask_description_len: "Research".len(),
+            ..Default::default()
+        };
+
+        let resp = handle_propose_plan(
+            Some("1".into()),
+            &sample_plan_params("t-fit"),
+            &state,
+            &network_handle,
+        )
+        .await;
+        assert!(resp.error.is_none(), "at-limit plan should be accepted: {:?}", resp.error);
+        assert!(state.read().await.rfp_coordinators.contains_key("t-fit"));
+    }
+
+    #[tokio::test]
+    async fn test_propose_plan_rationale_requirement() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        state.write().await.plan_limits.min_rationale_len = 10;
+
+        let mut bare = sample_plan_params("t-bare");
+        bare["rationale"] = serde_json::json!("");
+        let resp = handle_propose_plan(Some("1".into()), &bare, &state, &network_handle).await;
+        assert_eq!(resp.error.expect("empty rationale must be rejected").code, -32006);
+        assert!(!state.read().await.rfp_coordinators.contains_key("t-bare"));
+
+        let resp = handle_propose_plan(
+            Some("2".into()),
+            &sample_plan_params("t-documented"),
+            &state,
+            &network_handle,
+        )
+        .await;
+        assert!(resp.error.is_none(), "documented plan should be accepted: {:?}", resp.error);
+        assert!(state.read().await.rfp_coordinators.contains_key("t-documented"));
+    }
+
+    #[tokio::test]
+    async fn test_get_election_reports_live_tally() {
+        use wws_hierarchy::elections::{ElectionConfig, ElectionManager};
+
+        let state = make_minimal_state();
+        let idle = handle_get_election(Some("1".into()), &state).await.result.unwrap();
+        assert_eq!(idle["in_election"], false);
+        assert_eq!(idle["candidates"], serde_json::json!([]));
+
+        let candidacy = |name: &str, reputation: f64| CandidacyParams {
+            agent_id: AgentId::new(name.into()),
+            epoch: 2,
+            score: NodeScore {
+                agent_id: AgentId::new(name.into()),
+                proof_of_compute: 0.8,
+                reputation,
+                uptime: 0.9,
+                stake: None,
+            },
+            location_vector: VivaldiCoordinates::origin(),
+        };
+        {
+            let mut s = state.write().await;
+            let mut election = ElectionManager::new(
+                ElectionConfig {
+                    tier1_slots: 3,
+                    ..Default::default()
+                },
+                2,
+            );
+            election.register_candidate(&candidacy("did:swarm:alice", 0.9)).unwrap();
+            election.register_candidate(&candidacy("did:swarm:bob", 0.7)).unwrap();
+            for voter in ["did:swarm:v1", "did:swarm:v2"] {
+                election
+                    .record_vote(ElectionVoteParams {
+                        voter: AgentId::new(voter.into()),
+                        epoch: 2,
+                        candidate_rankings: vec![
+                            AgentId::new("did:swarm:bob".into()),
+                            AgentId::new("did:swarm:alice".into()),
+                        ],
+                    })
+                    .unwrap();
+            }
+            s.election = Some(election);
+            s.status = ConnectorStatus::InElection;
+        }
+
+        let resp = handle_get_election(Some("2".into()), &state).await.result.unwrap();
+        assert_eq!(resp["in_election"], true);
+        assert_eq!(resp["epoch"], 2);
+        assert_eq!(resp["senate_size"], 3);
+        assert_eq!(resp["votes_cast"], 2);
+        let candidates = resp["candidates"].as_array().unwrap();
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0]["agent_id"], "did:swarm:bob");
+        assert_eq!(candidates[0]["rank"], 1);
+        assert_eq!(candidates[0]["tally"], 2.0);
+        assert_eq!(candidates[1]["agent_id"], "did:swarm:alice");
+        assert_eq!(candidates[1]["tally"], 0.0);
+
+        // A finalized election is no longer reported as running.
+        state.write().await.election.as_mut().unwrap().tally_and_elect().unwrap();
+        let done = handle_get_election(Some("3".into()), &state).await.result.unwrap();
+        assert_eq!(done["in_election"], false);
+    }
+
+    #[tokio::test]
+    async fn test_get_succession_history_lists_handovers() {
+        let state = make_minimal_state();
+        let empty = handle_get_succession_history(Some("1".into()), &state).await;
+        assert_eq!(empty.result.unwrap()["events"], serde_json::json!([]));
+
+        {
+            let mut s = state.write().await;
+            let leader = AgentId::new("did:swarm:leader".into());
+            let successor = AgentId::new("did:swarm:successor".into());
+            s.succession.set_branch(leader.clone(), vec![successor.clone()]);
+            s.succession
+                .initiate_succession(
+                    &leader,
+                    vec![NodeScore {
+                        agent_id: successor.clone(),
+                        proof_of_compute: 0.9,
+                        reputation: 0.9,
+                        uptime: 1.0,
+                        stake: None,
+                    }],
+                )
+                .unwrap();
+            s.succession.record_succession_vote(&leader, &successor, 4).unwrap();
+        }
+
+        let resp = handle_get_succession_history(Some("2".into()), &state).await;
+        let events = resp.result.unwrap()["events"].as_array().unwrap().clone();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["failed"], "did:swarm:leader");
+        assert_eq!(events[0]["successor"], "did:swarm:successor");
+        assert_eq!(events[0]["epoch"], 4);
+        assert!(events[0]["at"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_get_bootstrap_status_reports_failures() {
+        let state = make_minimal_state();
+        {
+            let mut s = state.write().await;
+            s.record_bootstrap_dial("/ip4/10.0.0.1/tcp/9000");
+            s.record_bootstrap_failure("/ip4/10.0.0.1/tcp/9000", "Connection refused");
+        }
+        let resp = handle_get_bootstrap_status(Some("1".into()), &state).await;
+        let peers = resp.result.unwrap()["peers"].as_array().unwrap().clone();
+        assert_eq!(peers.len(), 1);
+        assert_eq!(peers[0]["addr"], "/ip4/10.0.0.1/tcp/9000");
+        assert_eq!(peers[0]["state"], "failed");
+        assert_eq!(peers[0]["last_error"], "Connection refused");
+        assert_eq!(peers[0]["attempts"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_bootstrap_status_reports_pending_dht_puts() {
+        let state = make_minimal_state();
+        let resp = handle_get_bootstrap_status(Some("1".into()), &state).await;
+        assert_eq!(resp.result.unwrap()["pending_dht_puts"], serde_json::json!([]));
+
+        {
+            let mut s = state.write().await;
+            s.dht_put_retries.sent(b"registry:s1".to_vec(), b"{}".to_vec());
+            s.dht_put_retries
+                .failed(b"registry:s1", "quorum failed", std::time::Instant::now(), 0.0);
+        }
+        let resp = handle_get_bootstrap_status(Some("1".into()), &state).await;
+        let pending = resp.result.unwrap()["pending_dht_puts"].as_array().unwrap().clone();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0]["key"], "registry:s1");
+        assert_eq!(pending[0]["attempts"], 1);
+        assert_eq!(pending[0]["last_error"], "quorum failed");
+        assert!(pending[0]["retry_in_secs"].is_u64());
+    }
+
+    #[tokio::test]
+    async fn receive_task_caps_tasks_at_max_concurrent() {
+        let state = make_minimal_state();
+        {
+            let mut s = state.write().await;
+            s.max_concurrent_tasks = 2;
+            s.my_tier = Tier::Executor;
+            let me = s.agent_id.clone();
+            for i in 0..3 {
+                let mut task = Task::new(format!("subtask {}", i), 2, 1);
+                task.task_id = format!("t{}", i);
+                task.status = TaskStatus::InProgress;
+                task.assigned_to = Some(me.clone());
+                task.created_at = chrono::Utc::now() + chrono::Duration::seconds(i);
+                s.task_set.add(task.task_id.clone());
+                s.task_details.insert(task.task_id.clone(), task);
+            }
+        }
+
+        let resp = handle_receive_task(Some("1".into()), &state).await;
+        let result = resp.result.expect("receive_task should succeed");
+        assert_eq!(result["pending_tasks"], serde_json::json!(["t0", "t1"]));
+        assert_eq!(result["queued_tasks"], 1);
+        assert_eq!(result["max_concurrent_tasks"], 2);
+
+        // Finishing the oldest task releases the queued one.
+        state.write().await.task_details.get_mut("t0").unwrap().status = TaskStatus::Completed;
+        let resp = handle_receive_task(Some("2".into()), &state).await;
+        let result = resp.result.unwrap();
+        assert_eq!(result["pending_tasks"], serde_json::json!(["t1", "t2"]));
+        assert_eq!(result["queued_tasks"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_ping_not_ready_while_initializing() {
+        let state = make_minimal_state();
+        {
+            let mut s = state.write().await;
+            s.status = crate::connector::ConnectorStatus::Initializing;
+            s.listen_addrs.push("/ip4/127.0.0.1/tcp/4001".parse().unwrap());
+        }
+        let resp = handle_ping(Some("1".into()), &state).await;
+        let result = resp.result.expect("ping succeeds");
+        assert_eq!(result["ready"], false);
+        assert_eq!(result["status"], "Initializing");
+        assert!(result["uptime_secs"].as_i64().unwrap() >= 0);
+    }
+
+    #[tokio::test]
+    async fn test_ping_ready_once_running_and_listening() {
+        let state = make_minimal_state();
+        state.write().await.status = crate::connector::ConnectorStatus::Running;
+        let resp = handle_ping(None, &state).await;
+        assert_eq!(resp.result.as_ref().unwrap()["ready"], false, "no listen address yet");
+
+        state
+            .write()
+            .await
+            .listen_addrs
+            .push("/ip4/127.0.0.1/tcp/4001".parse().unwrap());
+        let result = handle_ping(None, &state).await.result.unwrap();
+        assert_eq!(result["ready"], true);
+        assert_eq!(result["status"], "Running");
+        assert_eq!(result["listen_addrs"][0], "/ip4/127.0.0.1/tcp/4001");
+    }
+
+    #[tokio::test]
+    async fn test_get_hierarchy_peer_order_is_stable() {
+        let state = make_minimal_state();
+        {
+            let mut s = state.write().await;
+            for id in ["did:swarm:zed", "did:swarm:amy", "did:swarm:mo", "did:swarm:bo"] {
+                s.mark_member_seen(id);
+            }
+        }
+        let peer_ids = |resp: SwarmResponse| -> Vec<String> {
+            resp.result.unwrap()["peers"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|p| p["agent_id"].as_str().unwrap().to_string())
+                .collect()
+        };
+        let first = peer_ids(handle_get_hierarchy(None, &state).await);
+        let mut sorted = first.clone();
+        sorted.sort();
+        assert_eq!(first, sorted);
+        for _ in 0..5 {
+            assert_eq!(peer_ids(handle_get_hierarchy(None, &state).await), first);
+        }
+    }
+
+    #[tokio::test]
+    async fn submitted_content_type_and_metadata_are_returned_by_get_content() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        let inject_params = serde_json::json!({
+            "task_id": "t-content",
+            "injector_agent_id": "did:swarm:test-self",
+            "description": "Produce a table"
+        });
+        let resp = handle_inject_task(Some("1".into()), &inject_params, &state, &network_handle).await;
+        assert!(resp.error.is_none(), "inject should succeed: {:?}", resp.error);
+        state.write().await.task_details.get_mut("t-content").unwrap().parent_task_id =
+            Some("parent-placeholder".to_string());
+
+        let submit_params = serde_json::json!({
+            "task_id": "t-content",
+            "agent_id": "did:swarm:test-self",
+            "artifact": {
+                "content_cid": "not-the-content-cid",
+                "content": "{\"rows\": 3}",
+                "content_type": "application/json",
+                "metadata": {"schema": "table-v2", "rows": 3}
+            }
+        });
+        let resp = handle_submit_result(Some("2".into()), &submit_params, &state, &network_handle).await;
+        assert!(resp.error.is_none(), "submit should succeed: {:?}", resp.error);
+        let cid = resp.result.unwrap()["content_cid"].as_str().expect("content stored").to_string();
+        // The claimed CID does not match the content, so the computed one is recorded.
+        assert_ne!(cid, "not-the-content-cid");
+        assert_eq!(state.read().await.task_results["t-content"].content_cid, cid);
+
+        let cancel = CancellationToken::new();
+        let params = serde_json::json!({"cid": cid});
+        let resp = handle_get_content(Some("3".into()), &params, &state, &network_handle, &cancel).await;
+        let result = resp.result.expect("content found");
+        assert_eq!(result["content"], "{\"rows\": 3}");
+        assert_eq!(result["content_type"], "application/json");
+        assert_eq!(result["metadata"], serde_json::json!({"schema": "table-v2", "rows": 3}));
+        assert_eq!(result["providers"], serde_json::json!(["did:swarm:test-self"]));
+
+        let params = serde_json::json!({"cid": "missing"});
+        let resp = handle_get_content(Some("4".into()), &params, &state, &network_handle, &cancel).await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::NotFound));
+    }
+
+    #[tokio::test]
+    async fn redundant_result_waits_for_majority() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        let inject_params = serde_json::json!({
+            "task_id": "t-redundant",
+            "injector_agent_id": "did:swarm:test-self",
+            "description": "Compute a checksum"
+        });
+        let resp = handle_inject_task(Some("1".into()), &inject_params, &state, &network_handle).await;
+        assert!(resp.error.is_none(), "inject should succeed: {:?}", resp.error);
+        let other = AgentId::new("did:swarm:other".into());
+        {
+            let mut s = state.write().await;
+            let me = s.agent_id.clone();
+            let task = s.task_details.get_mut("t-redundant").unwrap();
+            task.parent_task_id = Some("parent".into());
+            task.assigned_to = Some(other.clone());
+            task.redundant_assignees =
+                vec![other.clone(), me, AgentId::new("did:swarm:third".into())];
+            // Another executor already reported a different checksum.
+            s.tally_result("t-redundant", &other, "cid-other");
+        }
+
+        let submit_params = serde_json::json!({
+            "task_id": "t-redundant",
+            "artifact": {"content": "checksum 42"}
+        });
+        let resp = handle_submit_result(Some("2".into()), &submit_params, &state, &network_handle).await;
+        let result = resp.result.expect("submission accepted");
+        assert_eq!(result["settled"], false);
+
+        {
+            let s = state.read().await;
+            assert_ne!(s.task_details["t-redundant"].status, TaskStatus::Completed);
+            assert_eq!(s.result_tallies["t-redundant"].submission_count(), 2);
+        }
+
+        // An agent outside the redundant assignees cannot submit.
+        state.write().await.task_details.get_mut("t-redundant").unwrap().redundant_assignees =
+            vec![other.clone(), AgentId::new("did:swarm:third".into())];
+        let resp = handle_submit_result(Some("3".into()), &submit_params, &state, &network_handle).await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::StaleAssignee));
+    }
+
+    #[tokio::test]
+    async fn redundant_result_is_tallied_by_the_cid_of_its_content() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        let inject_params = serde_json::json!({
+            "task_id": "t-redundant",
+            "injector_agent_id": "did:swarm:test-self",
+            "description": "Compute a checksum"
+        });
+        let resp = handle_inject_task(Some("1".into()), &inject_params, &state, &network_handle).await;
+        assert!(resp.error.is_none(), "inject should succeed: {:?}", resp.error);
+        let other = AgentId::new("did:swarm:other".into());
+        let honest_cid = {
+            let mut s = state.write().await;
+            let me = s.agent_id.clone();
+            let task = s.task_details.get_mut("t-redundant").unwrap();
+            task.parent_task_id = Some("parent".into());
+            task.assigned_to = Some(other.clone());
+            task.redundant_assignees =
+                vec![other.clone(), me, AgentId::new("did:swarm:third".into())];
+            let honest_cid = s.content_store.hash_algo().cid(b"checksum 42");
+            s.tally_result("t-redundant", &other, &honest_cid);
+            honest_cid
+        };
+
+        let empty = serde_json::json!({
+            "task_id": "t-redundant",
+            "artifact": {"content_cid": honest_cid}
+        });
+        let resp = handle_submit_result(Some("2".into()), &empty, &state, &network_handle).await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::InvalidParams));
+        assert_eq!(state.read().await.result_tallies["t-redundant"].submission_count(), 1);
+
+        // A wrong claimed CID does not split the vote: the content's CID is counted.
+        let submit_params = serde_json::json!({
+            "task_id": "t-redundant",
+            "artifact": {"content_cid": "cid-other", "content": "checksum 42"}
+        });
+        let resp = handle_submit_result(Some("3".into()), &submit_params, &state, &network_handle).await;
+        let result = resp.result.expect("submission accepted");
+        assert_eq!(result["settled"], true);
+        assert_eq!(state.read().await.task_details["t-redundant"].status, TaskStatus::Completed);
+    }
+
+    #[test]
+    fn notification_detection_follows_json_rpc_spec() {
+        assert!(is_notification(r#"{"jsonrpc":"2.0","method":"swarm.ping","params":{},"signature":""}"#));
+        assert!(!is_notification(r#"{"jsonrpc":"2.0","method":"swarm.ping","id":"1","params":{},"signature":""}"#));
+        assert!(!is_notification(r#"{"jsonrpc":"2.0","method":"swarm.ping","id":null,"params":{},"signature":""}"#));
+        assert!(!is_notification("not json"));
+    }
+
+    #[tokio::test]
+    async fn notification_runs_without_reply_while_requests_still_answer() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server_state = Arc::clone(&state);
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let _ = handle_connection(stream, server_state, network_handle).await;
+        });
+
+        let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let notification = r#"{"jsonrpc":"2.0","method":"swarm.register_agent","params":{"agent_id":"scout"},"signature":""}"#;
+        let failing = r#"{"jsonrpc":"2.0","method":"swarm.no_such_method","params":{},"signature":""}"#;
+        let request = r#"{"jsonrpc":"2.0","method":"swarm.ping","id":"req-1","params":{},"signature":""}"#;
+        for line in [notification, failing, request] {
+            writer.write_all(format!("{line}\n").as_bytes()).await.unwrap();
+        }
+
+        // The first reply on the wire belongs to the request with an id.
+        let reply = tokio::time::timeout(Duration::from_secs(5), lines.next_line())
+            .await
+            .expect("reply arrives")
+            .unwrap()
+            .expect("connection open");
+        let reply: serde_json::Value = serde_json::from_str(&reply).unwrap();
+        assert_eq!(reply["id"], "req-1");
+        assert!(reply.get("result").is_some());
+
+        let s = state.read().await;
+        assert_eq!(s.agent_names.get(s.agent_id.as_str()).map(String::as_str), Some("scout"));
+        drop(s);
+
+        // Nothing else was written for the notifications.
+        drop(writer);
+        assert!(tokio::time::timeout(Duration::from_secs(5), lines.next_line())
+            .await
+            .expect("connection closes")
+            .unwrap()
+            .is_none());
+    }
+
+    async fn next_json_line(
+        lines: &mut tokio::io::Lines<BufReader<tokio::net::tcp::OwnedReadHalf>>,
+    ) -> serde_json::Value {
+        let line = tokio::time::timeout(Duration::from_secs(5), lines.next_line())
+            .await
+            .expect("line arrives")
+            .unwrap()
+            .expect("connection open");
+        serde_json::from_str(&line).unwrap()
+    }
+
+    #[tokio::test]
+    async fn tail_logs_returns_backlog_then_streams_new_entries() {
+        let state = make_minimal_state();
+        {
+            let mut s = state.write().await;
+            s.push_log(LogCategory::Task, "task before".into());
+            s.push_log(LogCategory::Peer, "peer before".into());
+        }
+        let network_handle = make_test_network_handle();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server_state = Arc::clone(&state);
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let _ = handle_connection(stream, server_state, network_handle).await;
+        });
+
+        let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let request = r#"{"jsonrpc":"2.0","method":"swarm.tail_logs","id":"tail-1","params":{"categories":["task"]},"signature":""}"#;
+        writer.write_all(format!("{request}\n").as_bytes()).await.unwrap();
+
+        let reply = next_json_line(&mut lines).await;
+        assert_eq!(reply["id"], "tail-1");
+        let entries = reply["result"]["entries"].as_array().unwrap();
+        let messages: Vec<_> = entries.iter().map(|e| e["message"].as_str().unwrap()).collect();
+        assert!(messages.contains(&"task before"));
+        assert!(!messages.contains(&"peer before"));
+
+        {
+            let mut s = state.write().await;
+            s.push_log(LogCategory::Peer, "peer after".into());
+            s.push_log(LogCategory::Task, "task after".into());
+        }
+        let pushed = next_json_line(&mut lines).await;
+        assert_eq!(pushed["method"], "swarm.log");
+        assert_eq!(pushed["params"]["message"], "task after");
+        assert_eq!(pushed["params"]["category"], "Task");
+        assert!(pushed["params"]["seq"].as_u64().unwrap() > reply["result"]["cursor"].as_u64().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_get_status_reports_transport_and_versions() {
+        let state = make_minimal_state();
+        let result = handle_get_status(None, &state).await.result.unwrap();
+        assert_eq!(result["transport"]["tcp"], true);
+        assert_eq!(result["transport"]["quic"], true);
+        assert_eq!(result["transport"]["ws"], false);
+        assert_eq!(result["protocol_version"], wws_protocol::constants::PROTOCOL_VERSION);
+        assert_eq!(result["wire_format"], "json-rpc/2.0");
+
+        state.write().await.transport_features = wws_network::transport::TransportConfig {
+            enable_quic: false,
+            ..Default::default()
+        }
+        .features();
+        let result = handle_get_status(None, &state).await.result.unwrap();
+        assert_eq!(result["transport"]["quic"], false);
+        assert_eq!(result["transport"]["tcp"], true);
+    }
+
+    #[tokio::test]
+    async fn inject_task_at_deeper_tier_uses_that_tier_topic() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        let params = serde_json::json!({
+            "task_id": "t-tier2",
+            "injector_agent_id": "did:swarm:test-self",
+            "description": "Targeted work",
+            "tier": 2
+        });
+        let resp = handle_inject_task(Some("1".into()), &params, &state, &network_handle).await;
+        let result = resp.result.expect("inject succeeds");
+        let swarm_id = state.read().await.current_swarm_id.as_str().to_string();
+        assert_eq!(result["tier"], 2);
+        assert_eq!(result["topic"], SwarmTopics::tasks_for(&swarm_id, 2));
+        let s = state.read().await;
+        assert_eq!(s.task_details["t-tier2"].tier_level, 2);
+        assert_eq!(s.task_vote_requirements["t-tier2"].tier_level, 2);
+    }
+
+    #[tokio::test]
+    async fn receive_task_serves_higher_priority_first() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        for (task_id, priority) in [("t-low", "low"), ("t-normal", "normal"), ("t-high", "HIGH")] {
+            let params = serde_json::json!({
+                "task_id": task_id,
+                "injector_agent_id": "did:swarm:test-self",
+                "description": "Answer locally",
+                "ttl_hops": 1,
+                "priority": priority,
+            });
+            let resp = handle_inject_task(Some("1".into()), &params, &state, &network_handle).await;
+            assert!(resp.error.is_none(), "inject should succeed: {:?}", resp.error);
+        }
+        let resp = handle_receive_task(Some("2".into()), &state).await;
+        let pending = resp.result.unwrap()["pending_tasks"].clone();
+        assert_eq!(pending, serde_json::json!(["t-high", "t-normal", "t-low"]));
+
+        let params = serde_json::json!({
+            "injector_agent_id": "did:swarm:test-self",
+            "description": "Whenever",
+            "priority": "urgent",
+        });
+        let resp = handle_inject_task(Some("3".into()), &params, &state, &network_handle).await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::InvalidParams));
+    }
+
+    #[tokio::test]
+    async fn inject_task_rejects_out_of_range_tier() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        for tier in [
+            serde_json::json!(0),
+            serde_json::json!(wws_protocol::MAX_HIERARCHY_DEPTH + 1),
+            serde_json::json!("2"),
+        ] {
+            let params = serde_json::json!({
+                "injector_agent_id": "did:swarm:test-self",
+                "description": "Targeted work",
+                "tier": tier
+            });
+            let resp = handle_inject_task(Some("1".into()), &params, &state, &network_handle).await;
+            assert_eq!(resp.error_code(), Some(RpcErrorCode::InvalidParams), "tier {tier}");
+        }
+        assert!(state.read().await.task_details.is_empty());
+    }
+
+    #[test]
+    fn only_changed_tier_assignments_are_rebroadcast() {
+        let previous: TierAssignments = [
+            ("a".to_string(), (Tier::Tier0, None)),
+            ("b".to_string(), (Tier::Executor, Some("a".to_string()))),
+            ("c".to_string(), (Tier::Executor, Some("a".to_string()))),
+        ]
+        .into_iter()
+        .collect();
+        let current = vec![
+            ("a".to_string(), Tier::Tier0, None),
+            ("b".to_string(), Tier::Tier0, None),
+            ("c".to_string(), Tier::Executor, Some("b".to_string())),
+            ("d".to_string(), Tier::Executor, Some("a".to_string())),
+        ];
+        let changed: Vec<String> = changed_assignments(&previous, current)
+            .into_iter()
+            .map(|(member_id, _, _)| member_id)
+            .collect();
+        // "a" kept its tier and parent; "b" was promoted, "c" got a new
+        // parent and "d" is new.
+        assert_eq!(changed, vec!["b", "c", "d"]);
+    }
+
+    #[test]
+    fn new_epoch_or_leader_rebroadcasts_every_assignment() {
+        let mut state = ConnectorState::new_for_test();
+        state.mark_member_seen("did:swarm:test-self");
+        let assignments = |parent: &str| {
+            vec![
+                ("did:swarm:test-self".to_string(), Tier::Tier0, None),
+                ("e1".to_string(), Tier::Executor, Some(parent.to_string())),
+            ]
+        };
+        let sent = |list: Vec<(String, Tier, Option<String>)>| -> Vec<String> {
+            list.into_iter().map(|(member_id, _, _)| member_id).collect()
+        };
+        assert!(state.is_epoch_leader("did:swarm:test-self"));
+
+        assert_eq!(sent(assignments_to_broadcast(&mut state, 1, assignments("did:swarm:test-self"))).len(), 2);
+        // Same epoch: an unchanged layout is not re-sent, a moved member is.
+        assert!(assignments_to_broadcast(&mut state, 1, assignments("did:swarm:test-self")).is_empty());
+        assert_eq!(sent(assignments_to_broadcast(&mut state, 1, assignments("x"))), vec!["e1"]);
+        // A new epoch re-sends the full set, even if nothing moved.
+        assert_eq!(sent(assignments_to_broadcast(&mut state, 2, assignments("x"))).len(), 2);
+
+        // A node that is not the leader sends nothing and forgets what it
+        // sent, so it re-sends everything once it leads again.
+        state.mark_member_seen("did:swarm:a-leader");
+        assert!(assignments_to_broadcast(&mut state, 2, assignments("x")).is_empty());
+        assert!(state.announced_assignments.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_routing_table_lists_known_peers() {
+        use wws_network::{SwarmHost, SwarmHostConfig};
+        let config = SwarmHostConfig {
+            listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+            discovery: wws_network::DiscoveryConfig::static_only(Vec::new()),
+            ..Default::default()
+        };
+        let (host, handle, _rx) = SwarmHost::new(config).expect("test SwarmHost");
+        let host_task = tokio::spawn(host.run());
+
+        let peer = wws_network::PeerId::random();
+        handle
+            .add_peer_address(peer, "/ip4/10.0.0.9/tcp/9000".parse().unwrap())
+            .await
+            .unwrap();
+        let resp = handle_get_routing_table(Some("1".into()), &handle).await;
+        let result = resp.result.expect("routing table result");
+        assert_eq!(result["peer_count"], 1);
+        assert_eq!(result["peers"][0]["peer_id"], peer.to_string());
+        assert!(result["peers"][0]["bucket"].as_u64().unwrap() < 256);
+
+        host_task.abort();
+    }
+
+    #[tokio::test]
+    async fn test_connect_rejects_malformed_multiaddr() {
+        use wws_network::{SwarmHost, SwarmHostConfig};
+        let (_host, handle, _rx) = SwarmHost::new(SwarmHostConfig::default()).expect("test SwarmHost");
+
+        let params = serde_json::json!({"addr": "/ip4/not-an-ip/tcp/9000"});
+        let error = handle_connect(Some("1".into()), &params, &handle).await.error.expect("parse error");
+        assert_eq!(error.code, -32602);
+        assert!(error.message.contains("Invalid multiaddress"), "{}", error.message);
+
+        let params = serde_json::json!({"addr": "/ip4/10.0.0.9"});
+        let error = handle_connect(Some("2".into()), &params, &handle).await.error.expect("no transport");
+        assert_eq!(error.code, -32602);
+        assert!(error.message.contains("no transport component"), "{}", error.message);
+    }
+
+    #[tokio::test]
+    async fn test_connect_reports_dial_failure_for_wellformed_addr() {
+        use wws_network::{SwarmHost, SwarmHostConfig};
+        // The host is never run, so the dial itself fails.
+        let (host, handle, _rx) = SwarmHost::new(SwarmHostConfig::default()).expect("test SwarmHost");
+        drop(host);
+
+        let params = serde_json::json!({"addr": "/ip4/10.0.0.9/tcp/9000"});
+        let error = handle_connect(Some("1".into()), &params, &handle).await.error.expect("dial error");
+        assert_eq!(error.code, -32000);
+        assert!(error.message.starts_with("Dial failed"), "{}", error.message);
+    }
+
+    #[tokio::test]
+    async fn test_get_connection_metrics_counts_failed_dial() {
+        use wws_network::{SwarmHost, SwarmHostConfig};
+        let config = SwarmHostConfig {
+            listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+            discovery: wws_network::DiscoveryConfig::static_only(Vec::new()),
+            ..Default::default()
+        };
+        let (host, handle, mut rx) = SwarmHost::new(config).expect("test SwarmHost");
+        let host_task = tokio::spawn(host.run());
+
+        let resp = handle_get_connection_metrics(Some("1".into()), &handle).await;
+        let result = resp.result.expect("metrics result");
+        assert_eq!(result["peers"], serde_json::json!([]));
+
+        // Nothing listens on port 1, so the dial to a known peer fails.
+        let peer = wws_network::PeerId::random();
+        let addr: wws_network::Multiaddr = format!("/ip4/127.0.0.1/tcp/1/p2p/{}", peer).parse().unwrap();
+        handle.dial(addr).await.unwrap();
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while !matches!(rx.recv().await, Some(wws_network::NetworkEvent::DialFailed { .. })) {}
+        })
+        .await
+        .expect("dial fails");
+
+        let resp = handle_get_connection_metrics(Some("2".into()), &handle).await;
+        let result = resp.result.expect("metrics result");
+        assert_eq!(result["peers"][0]["peer_id"], peer.to_string());
+        assert_eq!(result["peers"][0]["dials_failed"], 1);
+        assert_eq!(result["peers"][0]["open_connections"], 0);
+        assert!(result["peers"][0]["last_dial_error"].is_string());
+        host_task.abort();
+
+        let resp = handle_get_connection_metrics(Some("3".into()), &make_test_network_handle()).await;
+        assert!(resp.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_routing_starts_walk_only_with_kademlia() {
+        use wws_network::{SwarmHost, SwarmHostConfig};
+        for (discovery, expected) in [
+            (wws_network::DiscoveryConfig::standard(Vec::new(), false), true),
+            (wws_network::DiscoveryConfig::static_only(Vec::new()), false),
+        ] {
+            let config = SwarmHostConfig {
+                listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+                discovery,
+                ..Default::default()
+            };
+            let (host, handle, _rx) = SwarmHost::new(config).expect("test SwarmHost");
+            let host_task = tokio::spawn(host.run());
+
+            let resp = handle_refresh_routing(Some("1".into()), &handle).await;
+            assert_eq!(resp.result.expect("refresh result")["started"], expected);
+
+            host_task.abort();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_topic_stats_starts_empty_and_fails_when_host_stopped() {
+        use wws_network::{SwarmHost, SwarmHostConfig};
+        let config = SwarmHostConfig {
+            listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+            discovery: wws_network::DiscoveryConfig::static_only(Vec::new()),
+            ..Default::default()
+        };
+        let (host, handle, _rx) = SwarmHost::new(config).expect("test SwarmHost");
+        let host_task = tokio::spawn(host.run());
+
+        let resp = handle_get_topic_stats(Some("1".into()), &handle).await;
+        let result = resp.result.expect("topic stats result");
+        assert_eq!(result["total_sent"], 0);
+        assert_eq!(result["topics"], serde_json::json!({}));
+        host_task.abort();
+
+        let resp = handle_get_topic_stats(Some("1".into()), &make_test_network_handle()).await;
+        assert!(resp.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_get_routing_table_fails_when_host_stopped() {
+        let network_handle = make_test_network_handle();
+        let resp = handle_get_routing_table(Some("1".into()), &network_handle).await;
+        assert!(resp.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_get_task_timeline_pages_backward_with_cursor() {
+        let state = make_minimal_state();
+        {
+            let mut s = state.write().await;
+            for i in 0..7 {
+                s.push_task_timeline_event("t-page", "stage", format!("event {i}"), None);
+            }
+        }
+        let details = |resp: &SwarmResponse| -> Vec<String> {
+            resp.result.as_ref().unwrap()["events"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|e| e["detail"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        // No cursor: the tail, plus a cursor for the page before it.
+        let params = serde_json::json!({"task_id": "t-page", "limit": 4});
+        let first = handle_get_task_timeline(Some("1".into()), &params, &state).await;
+        let result = first.result.as_ref().unwrap();
+        assert_eq!(result["event_count"], 7);
+        assert_eq!(result["next_cursor"], 3);
+        let mut seen = details(&first);
+        assert_eq!(seen, vec!["event 3", "event 4", "event 5", "event 6"]);
+
+        let params = serde_json::json!({"task_id": "t-page", "limit": 4, "before": 3});
+        let second = handle_get_task_timeline(Some("2".into()), &params, &state).await;
+        assert!(second.result.as_ref().unwrap()["next_cursor"].is_null());
+        let mut older = details(&second);
+        assert_eq!(older, vec!["event 0", "event 1", "event 2"]);
+
+        // Together the pages cover the whole timeline exactly once.
+        older.append(&mut seen);
+        let all: Vec<String> = (0..7).map(|i| format!("event {i}")).collect();
+        assert_eq!(older, all);
+    }
+
+    #[tokio::test]
+    async fn test_get_task_timeline_cursor_survives_eviction() {
+        let state = make_minimal_state();
+        {
+            let mut s = state.write().await;
+            for i in 0..500 {
+                s.push_task_timeline_event("t-evict", "stage", format!("event {i}"), None);
+            }
+        }
+        let params = serde_json::json!({"task_id": "t-evict", "limit": 10});
+        let first = handle_get_task_timeline(Some("1".into()), &params, &state).await;
+        let cursor = first.result.as_ref().unwrap()["next_cursor"].as_u64().unwrap();
+        assert_eq!(cursor, 490);
+
+        // New events evict the oldest ones while the client is paging.
+        {
+            let mut s = state.write().await;
+            for i in 500..505 {
+                s.push_task_timeline_event("t-evict", "stage", format!("event {i}"), None);
+            }
+        }
+        let params = serde_json::json!({"task_id": "t-evict", "limit": 10, "before": cursor});
+        let second = handle_get_task_timeline(Some("2".into()), &params, &state).await;
+        let result = second.result.as_ref().unwrap();
+        let details: Vec<&str> = result["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["detail"].as_str().unwrap())
+            .collect();
+        let expected: Vec<String> = (480..490).map(|i| format!("event {i}")).collect();
+        assert_eq!(details, expected);
+        assert_eq!(result["next_cursor"], 480);
+    }
+
+    #[tokio::test]
+    async fn test_get_task_timeline_rejects_invalid_cursor() {
+        let state = make_minimal_state();
+        let params = serde_json::json!({"task_id": "t-page", "before": -1});
+        let resp = handle_get_task_timeline(Some("1".into()), &params, &state).await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::InvalidParams));
+    }
+
+    #[tokio::test]
+    async fn test_tasks_stay_in_their_swarm_task_set() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        state.write().await.known_swarms.insert(
+            "work".to_string(),
+            SwarmRecord {
+                swarm_id: SwarmId::new("work".to_string()),
+                name: "work".to_string(),
+                is_public: true,
+                agent_count: 2,
+                joined: false,
+                last_seen: chrono::Utc::now(),
+                secret: None,
+                owner: None,
+                history: Default::default(),
+            },
+        );
+        let params = make_params(&[("swarm_id", serde_json::json!("work"))]);
+        let joined = handle_join_swarm(Some("1".into()), &params, &state, &network_handle).await;
+        let joined = joined.result.expect("join succeeds");
+        assert_eq!(joined["active_swarms"], serde_json::json!(["test-swarm", "work"]));
+
+        let inject = |task_id: &str, swarm: Option<&str>| {
+            let mut params = serde_json::json!({
+                "task_id": task_id,
+                "injector_agent_id": "did:swarm:test-self",
+                "description": format!("task {task_id}"),
+            });
+            if let Some(swarm) = swarm {
+                params["swarm_id"] = serde_json::json!(swarm);
+            }
+            params
+        };
+        let resp = handle_inject_task(Some("2".into()), &inject("t-work", Some("work")), &state, &network_handle).await;
+        let result = resp.result.expect("inject into joined swarm succeeds");
+        assert_eq!(result["swarm_id"], "work");
+        assert_eq!(result["topic"], SwarmTopics::tasks_for("work", 1));
+        let resp = handle_inject_task(Some("3".into()), &inject("t-main", None), &state, &network_handle).await;
+        assert_eq!(resp.result.expect("default inject succeeds")["swarm_id"], "test-swarm");
+
+        let s = state.read().await;
+        let work = s.task_set_for("work").unwrap();
+        assert!(work.contains("t-work"));
+        assert!(!work.contains("t-main"));
+        assert!(s.task_set.contains("t-main"));
+        assert!(!s.task_set.contains("t-work"));
+        assert_eq!(s.swarm_of_task("t-work"), "work");
+        assert_eq!(s.swarm_of_task("t-main"), "test-swarm");
+        drop(s);
+
+        let mut s = state.write().await;
+        let task = s.task_details.get_mut("t-work").unwrap();
+        task.status = TaskStatus::InProgress;
+        task.parent_task_id = Some("t-root".to_string());
+        let params = serde_json::json!({
+            "task_id": "t-work",
+            "content": "done",
+            "artifact": {"content_cid": "cid-work"}
+        });
+        let mut submission: ResultSubmissionParams = serde_json::from_value(params.clone()).unwrap();
+        let outcome = s
+            .apply_result_submission(&mut submission, &params)
+            .expect("result accepted");
+        assert_eq!(outcome.swarm_id, "work");
+    }
+
+    #[tokio::test]
+    async fn test_leave_swarm_drops_membership_and_payload_key() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        state.write().await.known_swarms.insert(
+            "team".to_string(),
+            SwarmRecord {
+                swarm_id: SwarmId::new("team".to_string()),
+                name: "team".to_string(),
+                is_public: false,
+                agent_count: 2,
+                joined: false,
+                last_seen: chrono::Utc::now(),
+                secret: None,
+                owner: None,
+                history: Default::default(),
+            },
+        );
+        let params = make_params(&[
+            ("swarm_id", serde_json::json!("team")),
+            ("token", serde_json::json!("team-token")),
+        ]);
+        let resp = handle_join_swarm(Some("1".into()), &params, &state, &network_handle).await;
+        assert!(resp.error.is_none(), "join succeeds: {:?}", resp.error);
+        assert!(state.read().await.payload_codec().is_some());
+
+        let params = make_params(&[("swarm_id", serde_json::json!("test-swarm"))]);
+        let resp = handle_leave_swarm(Some("2".into()), &params, &state, &network_handle).await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::InvalidParams));
+
+        let params = make_params(&[("swarm_id", serde_json::json!("team"))]);
+        let resp = handle_leave_swarm(Some("3".into()), &params, &state, &network_handle).await;
+        let result = resp.result.expect("leave succeeds");
+        assert_eq!(result["active_swarms"], serde_json::json!(["test-swarm"]));
+        let s = state.read().await;
+        assert!(!s.memberships.contains("team"));
+        assert!(!s.known_swarms["team"].joined);
+        assert!(s.payload_codec().is_none());
+        drop(s);
+
+        let resp = handle_leave_swarm(Some("4".into()), &params, &state, &network_handle).await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::InvalidParams));
+    }
+
+    #[tokio::test]
+    async fn test_inject_task_rejects_swarm_not_joined() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        let params = serde_json::json!({
+            "injector_agent_id": "did:swarm:test-self",
+            "description": "elsewhere",
+            "swarm_id": "stranger",
+        });
+        let resp = handle_inject_task(Some("1".into()), &params, &state, &network_handle).await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::InvalidParams));
+        assert!(state.read().await.task_details.is_empty());
+    }
+
+    #[tokio::test]
+    async fn get_voting_result_returns_critic_aggregates_for_scored_plans() {
+        let state = make_minimal_state();
+        let resp = handle_get_voting_result(
+            Some("1".into()),
+            &serde_json::json!({ "task_id": "t1" }),
+            &state,
+        )
+        .await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::NotFound));
+
+        let mut engine = wws_consensus::VotingEngine::new(
+            wws_consensus::voting::VotingConfig {
+                prohibit_self_vote: false,
+                ..Default::default()
+            },
+            "t1".into(),
+            1,
+        );
+        engine.set_proposals(std::collections::HashMap::from([
+            ("planA".to_string(), AgentId::new("alice".into())),
+            ("planB".to_string(), AgentId::new("bob".into())),
+        ]));
+        let score = |feasibility: f64| wws_protocol::CriticScore {
+            feasibility,
+            parallelism: 0.5,
+            completeness: 0.5,
+            risk: 0.2,
+        };
+        for (voter, rankings, scored) in [
+            ("v1", ["planA", "planB"], vec![("planA", 0.8), ("planB", 0.3)]),
+            ("v2", ["planA", "planB"], vec![("planB", 0.5)]),
+        ] {
+            engine
+                .record_vote(wws_protocol::RankedVote {
+                    voter: AgentId::new(voter.into()),
+                    task_id: "t1".into(),
+                    epoch: 1,
+                    rankings: rankings.iter().map(|p| p.to_string()).collect(),
+                    critic_scores: scored
+                        .into_iter()
+                        .map(|(plan, f)| (plan.to_string(), score(f)))
+                        .collect(),
+                })
+                .unwrap();
+        }
+        let result = engine.run_irv().unwrap();
+        state.write().await.voting_results.insert("t1".into(), result);
+
+        let resp = handle_get_voting_result(
+            Some("1".into()),
+            &serde_json::json!({ "task_id": "t1" }),
+            &state,
+        )
+        .await;
+        let result = resp.result.unwrap();
+        assert_eq!(result["winner"], "planA");
+        assert_eq!(result["rounds"], 1);
+        assert_eq!(result["final_tallies"]["planA"], 2);
+        assert_eq!(result["total_votes"], 2);
+        let scores = result["critic_scores"].as_object().unwrap();
+        assert_eq!(scores.len(), 2);
+        assert_eq!(scores["planA"]["feasibility"], 0.8);
+        assert_eq!(scores["planB"]["feasibility"], 0.4);
+        assert_eq!(result["winner_critic_score"], scores["planA"]);
+    }
+
+    fn insert_in_progress_subtask(s: &mut ConnectorState, task_id: &str) {
+        let me = s.agent_id.clone();
+        let mut task = Task::new("overflow subtask".into(), 2, 1);
+        task.task_id = task_id.to_string();
+        task.parent_task_id = Some("root".to_string());
+        task.status = TaskStatus::InProgress;
+        task.assigned_to = Some(me);
+        s.task_set.add(task.task_id.clone());
+        s.task_details.insert(task.task_id.clone(), task);
+    }
+
+    #[tokio::test]
+    async fn delegate_task_reassigns_to_known_peer() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        {
+            let mut s = state.write().await;
+            insert_in_progress_subtask(&mut s, "sub-1");
+            s.mark_member_seen("did:swarm:helper");
+        }
+
+        let params = serde_json::json!({ "task_id": "sub-1", "to": "did:swarm:helper" });
+        let resp = handle_delegate_task(Some("1".into()), &params, &state, &network_handle).await;
+        let result = resp.result.expect("delegation should succeed");
+        assert_eq!(result["to"], "did:swarm:helper");
+        assert_eq!(result["delegated"], true);
+
+        let s = state.read().await;
+        let task = &s.task_details["sub-1"];
+        assert_eq!(task.assigned_to.as_ref().map(|a| a.as_str()), Some("did:swarm:helper"));
+        assert_eq!(task.status, TaskStatus::InProgress);
+        assert!(!s.task_set.contains("sub-1"), "delegated task leaves the local queue");
+        let last = s.task_timelines["sub-1"].last().unwrap();
+        assert_eq!(last.stage, "delegated");
+        assert_eq!(last.actor.as_deref(), Some(s.agent_id.as_str()));
+    }
+
+    #[tokio::test]
+    async fn delegate_task_rejects_unknown_agent() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        insert_in_progress_subtask(&mut *state.write().await, "sub-1");
+
+        let params = serde_json::json!({ "task_id": "sub-1", "to": "did:swarm:stranger" });
+        let resp = handle_delegate_task(Some("1".into()), &params, &state, &network_handle).await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::InvalidParams));
+
+        let s = state.read().await;
+        assert_eq!(s.task_details["sub-1"].assigned_to.as_ref(), Some(&s.agent_id));
+        assert!(s.task_set.contains("sub-1"));
+        assert!(!s.task_timelines.contains_key("sub-1"));
+    }
+
+    #[tokio::test]
+    async fn delegate_task_rejects_other_agents_tasks() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        {
+            let mut s = state.write().await;
+            insert_in_progress_subtask(&mut s, "sub-1");
+            s.task_details.get_mut("sub-1").unwrap().assigned_to =
+                Some(AgentId::new("did:swarm:busy".into()));
+            s.mark_member_seen("did:swarm:helper");
+        }
+
+        let params = serde_json::json!({
+            "task_id": "sub-1",
+            "from": "did:swarm:busy",
+            "to": "did:swarm:helper",
+        });
+        let resp = handle_delegate_task(Some("1".into()), &params, &state, &network_handle).await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::InvalidParams));
+        let s = state.read().await;
+        assert_eq!(
+            s.task_details["sub-1"].assigned_to.as_ref().map(|a| a.as_str()),
+            Some("did:swarm:busy")
+        );
+    }
+
+    #[test]
+    fn delegation_signed_by_another_agent_is_rejected() {
+        let s = ConnectorState::new_for_test();
+        let mut delegation = TaskDelegationParams {
+            task_id: "sub-1".into(),
+            from: AgentId::new(String::new()),
+            to: AgentId::new("did:swarm:helper".into()),
+            signature: String::new(),
+        };
+        s.sign_task_delegation(&mut delegation);
+        delegation.from = AgentId::new(format!(
+            "did:swarm:{}",
+            wws_network::libp2p::identity::Keypair::generate_ed25519().public().to_peer_id()
+        ));
+        assert!(s.verify_task_delegation(&delegation).is_err());
+    }
+
+    #[test]
+    fn incoming_delegation_queues_task_for_delegate() {
+        let mut s = ConnectorState::new_for_test();
+        let me = s.agent_id.clone();
+        let busy_key = wws_network::libp2p::identity::Keypair::generate_ed25519();
+        let busy = AgentId::new(format!("did:swarm:{}", busy_key.public().to_peer_id()));
+        let mut task = Task::new("overflow subtask".into(), 2, 1);
+        task.task_id = "sub-1".to_string();
+        task.status = TaskStatus::InProgress;
+        task.assigned_to = Some(busy.clone());
+        s.task_details.insert(task.task_id.clone(), task);
+
+        let mut delegation = TaskDelegationParams {
+            task_id: "sub-1".into(),
+            from: busy,
+            to: me.clone(),
+            signature: String::new(),
+        };
+        assert!(s.verify_task_delegation(&delegation).is_err(), "unsigned");
+        delegation.signature =
+            crate::peer_signature::sign_payload(&busy_key, &delegation.signing_payload());
+        s.verify_task_delegation(&delegation).unwrap();
+        s.apply_task_delegation(&delegation).unwrap();
+        assert_eq!(s.task_details["sub-1"].assigned_to.as_ref(), Some(&me));
+        assert!(s.task_set.contains("sub-1"));
+
+        // Replaying the same hand-off no longer matches the assignee.
+        let err = s.apply_task_delegation(&delegation).unwrap_err();
+        assert_eq!(err.code, RpcErrorCode::StaleAssignee);
+    }
+
+    #[tokio::test]
+    async fn finished_task_leaves_task_set_before_details_expire() {
+        use crate::task_gc::TaskRetention;
+        let state = make_minimal_state();
+        let t0 = chrono::Utc::now();
+        let stale_replica = {
+            let mut s = state.write().await;
+            s.task_retention = Some(TaskRetention::new(
+                Duration::from_secs(600),
+                Some(Duration::from_secs(86_400)),
+            ));
+            let mut task = Task::new("Done".into(), 1, 1);
+            task.task_id = "t-done".into();
+            task.status = TaskStatus::Completed;
+            s.task_details.insert("t-done".into(), task);
+            s.task_set.add("t-done".into());
+            s.collect_expired_tasks(t0);
+            s.task_set.clone()
+        };
+        let get = serde_json::json!({"task_id": "t-done"});
+
+        let report = state.write().await.collect_expired_tasks(t0 + chrono::Duration::minutes(5));
+        assert!(report.pruned.is_empty());
+        let report = state.write().await.collect_expired_tasks(t0 + chrono::Duration::minutes(11));
+        assert_eq!(report.pruned, vec!["t-done"]);
+        {
+            let mut s = state.write().await;
+            assert!(!s.task_set.contains("t-done"));
+            // Merging a replica from before the removal does not bring it back.
+            s.task_set.merge(&stale_replica);
+            assert!(!s.task_set.contains("t-done"));
+        }
+        let resp = handle_get_task(Some("1".into()), &get, &state).await;
+        let result = resp.result.expect("details kept until the longer retention");
+        assert_eq!(result["is_pending"], false);
+
+        let report = state.write().await.collect_expired_tasks(t0 + chrono::Duration::hours(25));
+        assert_eq!(report.forgotten, vec!["t-done"]);
+        let resp = handle_get_task(Some("2".into()), &get, &state).await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::NotFound));
+    }
+
+    #[test]
+    fn finished_task_is_pruned_from_its_joined_swarms_task_set() {
+        use crate::task_gc::TaskRetention;
+        let mut s = ConnectorState::new_for_test();
+        let t0 = chrono::Utc::now();
+        s.task_retention = Some(TaskRetention::new(Duration::from_secs(600), None));
+        s.memberships.join(SwarmId::new("work".into()), None);
+        let mut task = Task::new("Done".into(), 1, 1);
+        task.task_id = "t-work".into();
+        task.status = TaskStatus::Completed;
+        s.task_details.insert("t-work".into(), task);
+        s.task_set_for_mut("work").unwrap().add("t-work".into());
+        s.collect_expired_tasks(t0);
+
+        let report = s.collect_expired_tasks(t0 + chrono::Duration::minutes(11));
+        assert_eq!(report.pruned, vec!["t-work"]);
+        assert!(!s.task_set_for("work").unwrap().contains("t-work"));
+        assert!(s.task_details.contains_key("t-work"));
+    }
+
+    #[tokio::test]
+    async fn export_topology_dot_for_planted_hierarchy() {
+        let state = make_minimal_state();
+        let me = {
+            let mut s = state.write().await;
+            let me = s.agent_id.to_string();
+            s.my_tier = Tier::Tier1;
+            s.mark_member_seen(&me);
+            s.mark_member_seen_with_name("did:swarm:b", Some("bravo"));
+            s.agent_tiers.insert("did:swarm:b".into(), Tier::Tier2);
+            s.agent_parents.insert("did:swarm:b".into(), me.clone());
+            s.mark_member_seen("did:swarm:c");
+            s.agent_parents.insert("did:swarm:c".into(), "did:swarm:b".into());
+            s.mark_member_seen("did:swarm:e");
+            // Parent is not an active member, so the link is left out.
+            s.agent_parents.insert("did:swarm:e".into(), "did:swarm:gone".into());
+            s.peer_rtts.insert("did:swarm:b".into(), Duration::from_millis(12));
+            s.peer_rtts.insert("did:swarm:e".into(), Duration::from_millis(40));
+            me
+        };
+
+        let params = serde_json::json!({"format": "dot"});
+        let resp = handle_export_topology(Some("1".into()), &params, &state).await;
+        let result = resp.result.expect("dot export");
+        assert_eq!(result["format"], "dot");
+        let dot = result["topology"].as_str().unwrap();
+
+        assert!(dot.starts_with("digraph swarm {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert_eq!(dot.matches('{').count(), 1);
+        assert_eq!(dot.matches('}').count(), 1);
+        assert_eq!(dot.matches('"').count() % 2, 0);
+        let body: Vec<&str> = dot.lines().skip(1).filter(|l| *l != "}").collect();
+        assert!(body.iter().all(|l| l.ends_with(';')), "{}", dot);
+
+        let self_line = dot.lines().find(|l| l.contains(&format!("\"{}\" [label=", me))).unwrap();
+        assert!(self_line.ends_with("\\nTier1\", shape=doublecircle];"), "{}", self_line);
+        assert!(dot.contains("\"did:swarm:b\" [label=\"bravo\\nTier2\"];"));
+        assert!(dot.contains("\"did:swarm:c\" [label=\"did:swarm:c\\nExecutor\"];"));
+        assert!(dot.contains(&format!("\"{}\" -> \"did:swarm:b\" [label=\"12 ms\"];", me)));
+        assert!(dot.contains("\"did:swarm:b\" -> \"did:swarm:c\";"));
+        assert!(dot.contains(&format!(
+            "\"{}\" -> \"did:swarm:e\" [style=dashed, dir=none, label=\"40 ms\"];",
+            me
+        )));
+        assert!(!dot.contains("did:swarm:gone"));
+        assert_eq!(dot.matches(" -> ").count(), 3);
+
+        let resp = handle_export_topology(Some("2".into()), &serde_json::json!({}), &state).await;
+        let json = resp.result.expect("json export");
+        assert_eq!(json["format"], "json");
+        assert_eq!(json["topology"]["nodes"].as_array().unwrap().len(), 4);
+        assert_eq!(json["topology"]["edges"][0]["kind"], "parent");
+        assert_eq!(json["topology"]["edges"][2]["kind"], "peer");
+
+        let resp =
+            handle_export_topology(Some("3".into()), &serde_json::json!({"format": "svg"}), &state).await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::InvalidParams));
+    }
+
+    /// Server and client ends of a loopback TCP connection.
+    async fn tcp_pair() -> (tokio::net::TcpStream, tokio::net::TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = tokio::net::TcpStream::connect(listener.local_addr().unwrap());
+        let (client, accepted) = tokio::join!(client, listener.accept());
+        (accepted.unwrap().0, client.unwrap())
+    }
+
+    #[tokio::test]
+    async fn connection_reset_cancels_long_operation() {
+        let (server, client) = tcp_pair().await;
+        let (reader, _writer) = server.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let mut pending = VecDeque::new();
+        let mut read_closed = false;
+        let cancel = CancellationToken::new();
+        let long_operation = async {
+            match cancel.run_until_cancelled(tokio::time::sleep(Duration::from_secs(60))).await {
+                Some(()) => "finished",
+                None => "cancelled",
+            }
+        };
+
+        client.set_zero_linger().unwrap();
+        drop(client);
+        let outcome = tokio::time::timeout(
+            Duration::from_secs(5),
+            watch_disconnect(&mut lines, &mut pending, &mut read_closed, &cancel, long_operation),
+        )
+        .await
+        .expect("resetting the connection must not leave the operation running");
+        assert_eq!(outcome, "cancelled");
+        assert!(cancel.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn half_closed_client_still_gets_its_response() {
+        use tokio::io::AsyncReadExt;
+        let (server, mut client) = tcp_pair().await;
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        let connection = tokio::spawn(handle_connection(server, state, network_handle));
+
+        let ping = r#"{"jsonrpc":"2.0","method":"swarm.ping","id":"1","params":{},"signature":""}"#;
+        client.write_all(format!("{}\n", ping).as_bytes()).await.unwrap();
+        client.shutdown().await.unwrap();
+        let mut reply = String::new();
+        tokio::time::timeout(Duration::from_secs(5), client.read_to_string(&mut reply))
+            .await
+            .expect("server closes the connection after answering")
+            .unwrap();
+        let response: SwarmResponse = serde_json::from_str(reply.trim()).unwrap();
+        assert_eq!(response.id.as_deref(), Some("1"));
+        assert!(response.error.is_none());
+        connection.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn pipelined_requests_are_queued_not_cancelled() {
+        let (server, mut client) = tcp_pair().await;
+        let (reader, _writer) = server.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let mut pending = VecDeque::new();
+        let mut read_closed = false;
+        let cancel = CancellationToken::new();
+
+        client.write_all(b"{\"next\":1}\n").await.unwrap();
+        let operation = tokio::time::sleep(Duration::from_millis(200));
+        watch_disconnect(&mut lines, &mut pending, &mut read_closed, &cancel, operation).await;
+        assert_eq!(pending, VecDeque::from(vec!["{\"next\":1}".to_string()]));
+        assert!(!cancel.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn register_agent_keeps_the_depth_stable_near_a_boundary() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        let params = serde_json::json!({ "agent_id": "worker" });
+        let add_members = |range: std::ops::Range<usize>| {
+            let state = Arc::clone(&state);
+            async move {
+                let mut state = state.write().await;
+                for i in range {
+                    state.mark_member_seen(&format!("did:swarm:m{i:03}"));
+                }
+            }
+        };
+
+        // With k = 10, 100 members fill exactly two tiers.
+        add_members(0..99).await;
+        handle_register_agent(Some("1".into()), &params, &state, &network_handle).await;
+        assert_eq!(state.read().await.network_stats.hierarchy_depth, 2);
+
+        // 105 members are within 10% of the boundary: the depth holds.
+        add_members(99..104).await;
+        handle_register_agent(Some("2".into()), &params, &state, &network_handle).await;
+        let state = state.read().await;
+        assert_eq!(state.network_stats.hierarchy_depth, 2);
+        assert_eq!(state.current_layout.as_ref().map(|l| l.depth), Some(2));
+        assert_eq!(state.pyramid.current_layout().map(|l| l.swarm_size), Some(105));
+    }
+
+    #[tokio::test]
+    async fn network_methods_wait_for_running_status() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        state.write().await.status = ConnectorStatus::Initializing;
+        let inject = r#"{"jsonrpc":"2.0","method":"swarm.inject_task","id":"1","params":{"task_id":"t-early","injector_agent_id":"did:swarm:test-self","description":"Early task"},"signature":""}"#;
+
+        let resp = process_request(inject, &state, &network_handle, &CancellationToken::new()).await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::NotReady));
+        assert!(!state.read().await.task_details.contains_key("t-early"));
+
+        // Read-only methods are served while initializing.
+        let status = r#"{"jsonrpc":"2.0","method":"swarm.get_status","id":"2","params":{},"signature":""}"#;
+        assert!(process_request(status, &state, &network_handle, &CancellationToken::new()).await.error.is_none());
+        let ping = r#"{"jsonrpc":"2.0","method":"swarm.ping","id":"3","params":{},"signature":""}"#;
+        assert!(process_request(ping, &state, &network_handle, &CancellationToken::new()).await.error.is_none());
+
+        state.write().await.status = ConnectorStatus::Running;
+        let resp = process_request(inject, &state, &network_handle, &CancellationToken::new()).await;
+        assert!(resp.error.is_none(), "inject should succeed once running: {:?}", resp.error);
+        assert!(state.read().await.task_details.contains_key("t-early"));
+    }
+
+    #[tokio::test]
+    async fn get_content_stats_reports_store_utilization() {
+        let state = make_minimal_state();
+        {
+            let mut s = state.write().await;
+            s.content_store.set_capacity_bytes(Some(8));
+            s.content_store.store(b"first");
+            s.content_store.store(b"second");
+        }
+
+        let resp = handle_get_content_stats(Some("1".into()), &state).await;
+        let result = resp.result.expect("stats should succeed");
+        assert_eq!(result["item_count"], 1);
+        assert_eq!(result["total_bytes"], 6);
+        assert_eq!(result["capacity_bytes"], 8);
+        assert_eq!(result["evicted_items"], 1);
+        assert_eq!(result["evicted_bytes"], 5);
+    }
+
+    #[tokio::test]
+    async fn report_progress_records_percent_and_timeline() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        insert_in_progress_subtask(&mut *state.write().await, "sub-1");
+
+        let params = serde_json::json!({ "task_id": "sub-1", "percent": 42, "message": "parsed input" });
+        let resp = handle_report_progress(Some("1".into()), &params, &state, &network_handle).await;
+        let result = resp.result.expect("progress report should succeed");
+        assert_eq!(result["progress"]["percent"], 42);
+
+        let s = state.read().await;
+        let progress = s.task_details["sub-1"].progress.as_ref().unwrap();
+        assert_eq!(progress.percent, 42);
+        assert_eq!(progress.message, "parsed input");
+        let last = s.task_timelines["sub-1"].last().unwrap();
+        assert_eq!(last.stage, "progress");
+        assert_eq!(last.detail, "42%: parsed input");
+    }
+
+    #[tokio::test]
+    async fn report_progress_clamps_percent() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        insert_in_progress_subtask(&mut *state.write().await, "sub-1");
+
+        let params = serde_json::json!({ "task_id": "sub-1", "percent": 150.0 });
+        handle_report_progress(Some("1".into()), &params, &state, &network_handle).await;
+        assert_eq!(state.read().await.task_details["sub-1"].progress.as_ref().unwrap().percent, 100);
+
+        let params = serde_json::json!({ "task_id": "sub-1", "percent": -5 });
+        handle_report_progress(Some("2".into()), &params, &state, &network_handle).await;
+        assert_eq!(state.read().await.task_details["sub-1"].progress.as_ref().unwrap().percent, 0);
+
+        let params = serde_json::json!({ "task_id": "sub-1" });
+        let resp = handle_report_progress(Some("3".into()), &params, &state, &network_handle).await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::InvalidParams));
+    }
+
+    #[tokio::test]
+    async fn report_progress_sanitizes_and_bounds_the_message() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        insert_in_progress_subtask(&mut *state.write().await, "sub-1");
+
+        let params = serde_json::json!({ "task_id": "sub-1", "percent": 40, "message": "\u{1b}[2Jhalf\nway" });
+        let resp = handle_report_progress(Some("1".into()), &params, &state, &network_handle).await;
+        assert_eq!(resp.result.expect("report accepted")["progress"]["message"], "half way");
+
+        let params = serde_json::json!({ "task_id": "sub-1", "percent": 50, "message": "\u{1b}[2J" });
+        let resp = handle_report_progress(Some("2".into()), &params, &state, &network_handle).await;
+        assert_eq!(resp.result.expect("empty message allowed")["progress"]["message"], "");
+
+        let long = "x".repeat(crate::connector::MAX_PROGRESS_MESSAGE_LEN + 1);
+        let params = serde_json::json!({ "task_id": "sub-1", "percent": 60, "message": long });
+        let resp = handle_report_progress(Some("3".into()), &params, &state, &network_handle).await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::InvalidParams));
+        assert_eq!(state.read().await.task_details["sub-1"].progress.as_ref().unwrap().percent, 50);
+    }
+
+    #[test]
+    fn incoming_progress_requires_current_assignee() {
+        let mut s = ConnectorState::new_for_test();
+        insert_in_progress_subtask(&mut s, "sub-1");
+
+        let report = TaskProgressParams {
+            task_id: "sub-1".into(),
+            reporter: AgentId::new("did:swarm:someone-else".into()),
+            percent: 10.0,
+            message: String::new(),
+            signature: String::new(),
+        };
+        let err = s.apply_task_progress(&report).unwrap_err();
+        assert_eq!(err.code, RpcErrorCode::StaleAssignee);
+        assert!(s.task_details["sub-1"].progress.is_none());
+    }
+
+    #[tokio::test]
+    async fn consensus_actions_wait_for_min_peers() {
+        let state = make_minimal_state();
+        let network_handle = make_test_network_handle();
+        state.write().await.min_peers_for_consensus = 2;
+        state.write().await.agent_set.add("12D3KooWPeerA".into());
+
+        let plan_params = sample_plan_params("t-gated");
+        let resp = handle_propose_plan(Some("1".into()), &plan_params, &state, &network_handle).await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::InvalidState));
+        let vote_params = serde_json::json!({ "task_id": "t-gated", "rankings": ["plan-1"] });
+        let resp = handle_submit_vote(Some("2".into()), &vote_params, &state, &network_handle).await;
+        assert_eq!(resp.error_code(), Some(RpcErrorCode::InvalidState));
+        {
+            let s = state.read().await;
+            assert!(s.consensus_gated);
+            assert!(!s.task_details.contains_key("t-gated"));
+            assert!(!s.voting_engines.contains_key("t-gated"));
+        }
+
+        state.write().await.agent_set.add("12D3KooWPeerB".into());
+        let resp = handle_propose_plan(Some("3".into()), &plan_params, &state, &network_handle).await;
+        assert!(resp.error.is_none(), "propose should succeed: {:?}", resp.error);
+        let resp = handle_submit_vote(Some("4".into()), &vote_params, &state, &network_handle).await;
+        assert!(resp.error.is_none(), "vote should succeed: {:?}", resp.error);
+        assert!(!state.read().await.consensus_gated);
+    }
 }
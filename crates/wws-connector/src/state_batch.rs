@@ -0,0 +1,218 @@
+//! Coalescing of outbound state updates into `swarm.state_batch` messages.
+//!
+//! Keepalives, swarm announcements and task-set updates are small and often
+//! produced in bursts, e.g. when several queued subtasks unblock at once.
+//! [`StateBatcher`] holds the updates produced within one batching window
+//! and, when flushed, emits one message per topic: a lone update goes out
+//! unchanged, several are wrapped in a single [`StateBatchParams`] message
+//! in the order they were queued, split so that no batch exceeds the
+//! GossipSub message size limit. Receivers unpack a batch and handle each
+//! update as if it had arrived on its own.
+//!
+//! Batching is off by default, since nodes that predate `swarm.state_batch`
+//! drop batched updates.
+
+use std::time::Duration;
+
+use wws_protocol::{ProtocolMethod, StateBatchParams, SwarmMessage};
+
+/// Default window in milliseconds over which state updates are coalesced
+/// (0 = publish each update immediately).
+pub const DEFAULT_STATE_BATCH_WINDOW_MS: u64 = 0;
+
+/// Most updates carried by one batch message; larger bursts are split.
+pub const MAX_STATE_BATCH_UPDATES: usize = 64;
+
+/// Most serialized update bytes carried by one batch message. GossipSub
+/// rejects messages over 64 KiB; the rest is left for the batch envelope
+/// and framing.
+pub const MAX_STATE_BATCH_BYTES: usize = 60 * 1024;
+
+/// Pending state updates, grouped by topic.
+#[derive(Debug, Clone)]
+pub struct StateBatcher {
+    window: Duration,
+    /// Topics in the order their first pending update was queued.
+    pending: Vec<(String, Vec<SwarmMessage>)>,
+}
+
+impl StateBatcher {
+    /// Batcher flushed every `window`. A zero window disables batching.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Whether updates are queued rather than published immediately.
+    pub fn is_enabled(&self) -> bool {
+        !self.window.is_zero()
+    }
+
+    /// Number of updates waiting for the next flush.
+    pub fn len(&self) -> usize {
+        self.pending.iter().map(|(_, updates)| updates.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Queue `update` for publication on `topic`.
+    pub fn push(&mut self, topic: String, update: SwarmMessage) {
+        match self.pending.iter_mut().find(|(t, _)| *t == topic) {
+            Some((_, updates)) => updates.push(update),
+            None => self.pending.push((topic, vec![update])),
+        }
+    }
+
+    /// Take every pending update as the messages to publish, at most one
+    /// per topic unless a topic has more than [`MAX_STATE_BATCH_UPDATES`]
+    /// updates or [`MAX_STATE_BATCH_BYTES`] of them.
+    pub fn drain(&mut self) -> Vec<(String, SwarmMessage)> {
+        let mut messages = Vec::new();
+        for (topic, updates) in self.pending.drain(..) {
+            let mut chunk = Vec::new();
+            let mut chunk_bytes = 0;
+            for update in updates {
+                // One extra byte for the separating comma.
+                let size = serde_json::to_vec(&update).map_or(0, |data| data.len()) + 1;
+                if !chunk.is_empty()
+                    && (chunk.len() == MAX_STATE_BATCH_UPDATES || chunk_bytes + size > MAX_STATE_BATCH_BYTES)
+                {
+                    messages.push((topic.clone(), batch_message(std::mem::take(&mut chunk))));
+                    chunk_bytes = 0;
+                }
+                chunk.push(update);
+                chunk_bytes += size;
+            }
+            if !chunk.is_empty() {
+                messages.push((topic, batch_message(chunk)));
+            }
+        }
+        messages
+    }
+}
+
+impl Default for StateBatcher {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(DEFAULT_STATE_BATCH_WINDOW_MS))
+    }
+}
+
+/// The message carrying `updates`: the update itself when there is only
+/// one, a `swarm.state_batch` otherwise.
+fn batch_message(mut updates: Vec<SwarmMessage>) -> SwarmMessage {
+    if updates.len() == 1 {
+        return updates.remove(0);
+    }
+    SwarmMessage::new(
+        ProtocolMethod::StateBatch.as_str(),
+        serde_json::to_value(StateBatchParams { updates }).unwrap_or_default(),
+        String::new(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_millis(100);
+
+    fn update(method: &str, n: u64) -> SwarmMessage {
+        SwarmMessage::new(method, serde_json::json!({ "n": n }), String::new())
+    }
+
+    fn batched(message: &SwarmMessage) -> Vec<u64> {
+        assert_eq!(message.method, ProtocolMethod::StateBatch.as_str());
+        let params: StateBatchParams = serde_json::from_value(message.params.clone()).unwrap();
+        params.updates.iter().map(|u| u.params["n"].as_u64().unwrap()).collect()
+    }
+
+    #[test]
+    fn rapid_updates_on_one_topic_become_one_batch() {
+        let mut batcher = StateBatcher::new(WINDOW);
+        for n in 0..3 {
+            batcher.push("tasks".into(), update("task.assign", n));
+        }
+        assert_eq!(batcher.len(), 3);
+
+        let messages = batcher.drain();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].0, "tasks");
+        assert_eq!(batched(&messages[0].1), vec![0, 1, 2]);
+        assert!(batcher.is_empty());
+        assert!(batcher.drain().is_empty());
+    }
+
+    #[test]
+    fn lone_update_is_published_unwrapped() {
+        let mut batcher = StateBatcher::new(WINDOW);
+        let keepalive = update("swarm.keepalive", 7);
+        batcher.push("keepalive".into(), keepalive.clone());
+        batcher.push("tasks".into(), update("task.assign", 1));
+        batcher.push("tasks".into(), update("task.inject", 2));
+
+        let messages = batcher.drain();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].0, "keepalive");
+        assert_eq!(messages[0].1.id, keepalive.id);
+        assert_eq!(messages[0].1.method, "swarm.keepalive");
+        assert_eq!(messages[1].0, "tasks");
+        assert_eq!(batched(&messages[1].1), vec![1, 2]);
+    }
+
+    #[test]
+    fn oversized_bursts_are_split() {
+        let mut batcher = StateBatcher::new(WINDOW);
+        let total = MAX_STATE_BATCH_UPDATES as u64 + 2;
+        for n in 0..total {
+            batcher.push("tasks".into(), update("task.assign", n));
+        }
+
+        let messages = batcher.drain();
+        assert_eq!(messages.len(), 2);
+        let first = batched(&messages[0].1);
+        assert_eq!(first.len(), MAX_STATE_BATCH_UPDATES);
+        let all: Vec<u64> = first.into_iter().chain(batched(&messages[1].1)).collect();
+        assert_eq!(all, (0..total).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn batches_stay_under_the_size_limit() {
+        let mut batcher = StateBatcher::new(WINDOW);
+        let padding = "x".repeat(20 * 1024);
+        for n in 0..5 {
+            let large = SwarmMessage::new(
+                "swarm.announce",
+                serde_json::json!({ "n": n, "padding": padding }),
+                String::new(),
+            );
+            batcher.push("announce".into(), large);
+        }
+
+        let messages = batcher.drain();
+        assert!(messages.len() > 1);
+        let mut all = Vec::new();
+        for (_, message) in &messages {
+            assert!(serde_json::to_vec(message).unwrap().len() <= MAX_STATE_BATCH_BYTES + 1024);
+            match message.method.as_str() {
+                "swarm.announce" => all.push(message.params["n"].as_u64().unwrap()),
+                _ => all.extend(batched(message)),
+            }
+        }
+        assert_eq!(all, (0..5).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn batching_is_off_by_default() {
+        assert!(!StateBatcher::new(Duration::ZERO).is_enabled());
+        assert!(!StateBatcher::default().is_enabled());
+        assert!(StateBatcher::new(WINDOW).is_enabled());
+    }
+}
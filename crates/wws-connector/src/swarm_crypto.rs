@@ -0,0 +1,163 @@
+//! Payload encryption for private swarms.
+//!
+//! A private swarm's GossipSub topics (`/wws/1.0.0/s/<swarm_id>/...`) carry
+//! [`SwarmCipher`]-sealed payloads so relays and non-members only see
+//! ciphertext. Global discovery and announcement topics stay plaintext so
+//! other nodes can still find and join the swarm, and the public swarm is
+//! never encrypted.
+
+use wws_network::PayloadCodec;
+use wws_protocol::crypto::SwarmCipher;
+use wws_protocol::{SwarmId, SwarmToken};
+
+/// [`PayloadCodec`] sealing every payload on one private swarm's topics.
+#[derive(Debug)]
+pub struct PrivateSwarmCodec {
+    topic_scope: String,
+    cipher: SwarmCipher,
+}
+
+impl PrivateSwarmCodec {
+    /// Codec for `swarm_id`, or `None` for the public swarm.
+    pub fn new(swarm_id: &SwarmId, token: &SwarmToken) -> Option<Self> {
+        if swarm_id.is_public() {
+            return None;
+        }
+        Some(Self {
+            topic_scope: format!("{}/s/{}/", wws_protocol::constants::TOPIC_PREFIX, swarm_id),
+            cipher: SwarmCipher::from_token(swarm_id, token),
+        })
+    }
+
+    /// Whether `topic` belongs to this swarm and is therefore encrypted.
+    pub fn covers(&self, topic: &str) -> bool {
+        topic.starts_with(&self.topic_scope)
+    }
+}
+
+impl PayloadCodec for PrivateSwarmCodec {
+    fn encode(&self, topic: &str, data: Vec<u8>) -> Vec<u8> {
+        if self.covers(topic) {
+            self.cipher.seal(&data)
+        } else {
+            data
+        }
+    }
+
+    fn decode(&self, topic: &str, data: Vec<u8>) -> Option<Vec<u8>> {
+        if !self.covers(topic) {
+            return Some(data);
+        }
+        match self.cipher.open(&data) {
+            Ok(plaintext) => Some(plaintext),
+            Err(e) => {
+                tracing::debug!(topic, error = %e, "Dropping undecryptable private swarm message");
+                None
+            }
+        }
+    }
+}
+
+/// [`PayloadCodec`] for a node that is a member of several private swarms:
+/// each topic is handled by the codec of the swarm it belongs to.
+#[derive(Debug, Default)]
+pub struct PrivateSwarmCodecs {
+    codecs: Vec<PrivateSwarmCodec>,
+}
+
+impl PrivateSwarmCodecs {
+    pub fn new(codecs: Vec<PrivateSwarmCodec>) -> Self {
+        Self { codecs }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.codecs.is_empty()
+    }
+
+    fn codec_for(&self, topic: &str) -> Option<&PrivateSwarmCodec> {
+        self.codecs.iter().find(|codec| codec.covers(topic))
+    }
+}
+
+impl PayloadCodec for PrivateSwarmCodecs {
+    fn encode(&self, topic: &str, data: Vec<u8>) -> Vec<u8> {
+        match self.codec_for(topic) {
+            Some(codec) => codec.encode(topic, data),
+            None => data,
+        }
+    }
+
+    fn decode(&self, topic: &str, data: Vec<u8>) -> Option<Vec<u8>> {
+        match self.codec_for(topic) {
+            Some(codec) => codec.decode(topic, data),
+            None => Some(data),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wws_protocol::SwarmTopics;
+
+    fn codec(swarm: &str, secret: &str) -> PrivateSwarmCodec {
+        let swarm_id = SwarmId::new(swarm.into());
+        let token = SwarmToken::generate(&swarm_id, secret);
+        PrivateSwarmCodec::new(&swarm_id, &token).expect("private swarm")
+    }
+
+    #[test]
+    fn private_topic_payload_round_trips() {
+        let codec = codec("team", "s3cret");
+        let topic = SwarmTopics::tasks_for("team", 1);
+        let body = br#"{"jsonrpc":"2.0","method":"task.inject"}"#.to_vec();
+
+        let sealed = codec.encode(&topic, body.clone());
+        assert_ne!(sealed, body);
+        assert!(SwarmCipher::is_sealed(&sealed));
+        assert_eq!(codec.decode(&topic, sealed), Some(body));
+    }
+
+    #[test]
+    fn node_without_key_cannot_decrypt() {
+        let member = codec("team", "s3cret");
+        let outsider = codec("team", "guess");
+        let topic = SwarmTopics::results_for("team", "t1");
+
+        let sealed = member.encode(&topic, b"result".to_vec());
+        assert_eq!(outsider.decode(&topic, sealed), None);
+        // Plaintext injected onto a private topic is dropped too.
+        assert_eq!(member.decode(&topic, b"result".to_vec()), None);
+    }
+
+    #[test]
+    fn each_private_swarm_uses_its_own_key() {
+        let codecs = PrivateSwarmCodecs::new(vec![codec("team", "s3cret"), codec("lab", "other")]);
+        let team = SwarmTopics::tasks_for("team", 1);
+        let lab = SwarmTopics::tasks_for("lab", 1);
+
+        let sealed = codecs.encode(&team, b"work".to_vec());
+        assert_eq!(codec("team", "s3cret").decode(&team, sealed.clone()), Some(b"work".to_vec()));
+        assert_eq!(codecs.decode(&team, sealed), Some(b"work".to_vec()));
+        let sealed = codecs.encode(&lab, b"work".to_vec());
+        assert_eq!(codec("lab", "other").decode(&lab, sealed), Some(b"work".to_vec()));
+        let public = SwarmTopics::tasks_for("public", 1);
+        assert_eq!(codecs.encode(&public, b"hi".to_vec()), b"hi".to_vec());
+    }
+
+    #[test]
+    fn public_and_discovery_topics_stay_plaintext() {
+        let public = SwarmId::new(wws_protocol::constants::DEFAULT_SWARM_ID.into());
+        assert!(PrivateSwarmCodec::new(&public, &SwarmToken::new("t".into())).is_none());
+
+        let codec = codec("team", "s3cret");
+        for topic in [
+            SwarmTopics::swarm_discovery(),
+            SwarmTopics::swarm_announce("team"),
+            SwarmTopics::tasks_for("other", 1),
+        ] {
+            assert_eq!(codec.encode(&topic, b"hi".to_vec()), b"hi".to_vec());
+            assert_eq!(codec.decode(&topic, b"hi".to_vec()), Some(b"hi".to_vec()));
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Tracking of acknowledgements for published task assignments.
+//!
+//! A `task.assign` message is fire-and-forget; the assignee answers with a
+//! `task.ack` once it has received the assignment. [`TaskAckTracker`] keeps
+//! the assignments this node published that are still waiting for that
+//! answer, so the connector can reassign a subtask whose assignee declined
+//! it or never replied within the timeout.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Default seconds an assignment may go unacknowledged before it is
+/// reassigned. Zero, so mixed-version swarms whose older nodes never send
+/// `task.ack` keep their assignments unless an operator opts in.
+pub const DEFAULT_TASK_ACK_TIMEOUT_SECS: u64 = 0;
+
+#[derive(Debug, Clone)]
+struct PendingAck {
+    assignee: String,
+    sent_at: Instant,
+}
+
+/// What an incoming acknowledgement meant for a tracked assignment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AckOutcome {
+    Accepted,
+    Declined,
+    /// Not an assignment this node is waiting on, or from another agent
+    /// than the current assignee.
+    Untracked,
+}
+
+/// Assignments awaiting a `task.ack`, keyed by task ID.
+#[derive(Debug, Clone)]
+pub struct TaskAckTracker {
+    timeout: Duration,
+    pending: HashMap<String, PendingAck>,
+}
+
+impl TaskAckTracker {
+    /// Tracker giving assignees `timeout` to acknowledge. A zero timeout
+    /// disables tracking.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            pending: HashMap::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.timeout.is_zero()
+    }
+
+    /// Record that `task_id` was assigned to `assignee` at `now`, replacing
+    /// any earlier assignment of the same task.
+    pub fn expect(&mut self, task_id: &str, assignee: &str, now: Instant) {
+        if !self.is_enabled() {
+            return;
+        }
+        self.pending.insert(
+            task_id.to_string(),
+            PendingAck {
+                assignee: assignee.to_string(),
+                sent_at: now,
+            },
+        );
+    }
+
+    /// Whether `task_id` is still waiting for its assignee's answer.
+    pub fn is_pending(&self, task_id: &str) -> bool {
+        self.pending.contains_key(task_id)
+    }
+
+    /// Apply an acknowledgement from `assignee`, which stops tracking the
+    /// assignment if it matches.
+    pub fn acknowledge(&mut self, task_id: &str, assignee: &str, accepted: bool) -> AckOutcome {
+        match self.pending.get(task_id) {
+            Some(pending) if pending.assignee == assignee => {
+                self.pending.remove(task_id);
+                if accepted {
+                    AckOutcome::Accepted
+                } else {
+                    AckOutcome::Declined
+                }
+            }
+            _ => AckOutcome::Untracked,
+        }
+    }
+
+    /// Remove and return `(task_id, assignee)` for every assignment not
+    /// acknowledged within the timeout, sorted by task ID.
+    pub fn take_overdue(&mut self, now: Instant) -> Vec<(String, String)> {
+        let mut overdue = Vec::new();
+        self.pending.retain(|task_id, pending| {
+            if now.saturating_duration_since(pending.sent_at) >= self.timeout {
+                overdue.push((task_id.clone(), pending.assignee.clone()));
+                false
+            } else {
+                true
+            }
+        });
+        overdue.sort();
+        overdue
+    }
+}
+
+impl Default for TaskAckTracker {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(DEFAULT_TASK_ACK_TIMEOUT_SECS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(30);
+
+    #[test]
+    fn acknowledged_assignment_is_never_overdue() {
+        let mut tracker = TaskAckTracker::new(TIMEOUT);
+        let t0 = Instant::now();
+        tracker.expect("t1", "did:swarm:a", t0);
+        assert_eq!(tracker.acknowledge("t1", "did:swarm:a", true), AckOutcome::Accepted);
+        assert!(!tracker.is_pending("t1"));
+        assert!(tracker.take_overdue(t0 + TIMEOUT * 2).is_empty());
+    }
+
+    #[test]
+    fn unacknowledged_assignment_is_overdue_after_timeout() {
+        let mut tracker = TaskAckTracker::new(TIMEOUT);
+        let t0 = Instant::now();
+        tracker.expect("t2", "did:swarm:b", t0);
+        tracker.expect("t1", "did:swarm:a", t0);
+        assert!(tracker.take_overdue(t0 + TIMEOUT / 2).is_empty());
+        assert_eq!(
+            tracker.take_overdue(t0 + TIMEOUT),
+            vec![
+                ("t1".to_string(), "did:swarm:a".to_string()),
+                ("t2".to_string(), "did:swarm:b".to_string()),
+            ]
+        );
+        assert!(tracker.take_overdue(t0 + TIMEOUT * 2).is_empty());
+    }
+
+    #[test]
+    fn ack_from_a_previous_assignee_is_ignored() {
+        let mut tracker = TaskAckTracker::new(TIMEOUT);
+        let t0 = Instant::now();
+        tracker.expect("t1", "did:swarm:a", t0);
+        tracker.expect("t1", "did:swarm:b", t0);
+        assert_eq!(tracker.acknowledge("t1", "did:swarm:a", true), AckOutcome::Untracked);
+        assert!(tracker.is_pending("t1"));
+        assert_eq!(tracker.acknowledge("t1", "did:swarm:b", false), AckOutcome::Declined);
+        assert_eq!(tracker.acknowledge("t9", "did:swarm:b", true), AckOutcome::Untracked);
+    }
+
+    #[test]
+    fn zero_timeout_disables_tracking() {
+        let mut tracker = TaskAckTracker::new(Duration::ZERO);
+        tracker.expect("t1", "did:swarm:a", Instant::now());
+        assert!(!tracker.is_pending("t1"));
+    }
+}
@@ -0,0 +1,106 @@
+//! Retention-based cleanup of finished tasks.
+//!
+//! Completed and rejected tasks stayed in the `task_set` OR-Set, and so in
+//! `swarm.receive_task` and the TUI task list, forever. When
+//! `consensus.task_set_retention_secs` is set, [`TaskRetention`] notes when
+//! each task is first seen finished and reports the ones finished for
+//! longer than that; the connector removes them from `task_set`. Their
+//! details and timelines stay queryable through `swarm.get_task` until the
+//! longer `consensus.task_detail_retention_secs` has also passed.
+//!
+//! Removal goes through [`wws_state::OrSet::remove`], which tombstones the
+//! observed tags, so merging a stale replica does not bring a task back.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+/// Seconds between task cleanup passes.
+pub const TASK_GC_INTERVAL_SECS: u64 = 60;
+
+/// When each finished task was first seen finished.
+#[derive(Debug, Clone)]
+pub struct TaskRetention {
+    set_retention: Duration,
+    detail_retention: Option<Duration>,
+    finished_since: HashMap<String, DateTime<Utc>>,
+}
+
+impl TaskRetention {
+    /// Drop finished tasks from `task_set` after `set_retention` and their
+    /// details after `detail_retention` (never when `None`). Details are
+    /// never dropped before the task leaves the set.
+    pub fn new(set_retention: Duration, detail_retention: Option<Duration>) -> Self {
+        Self {
+            set_retention,
+            detail_retention: detail_retention.map(|d| d.max(set_retention)),
+            finished_since: HashMap::new(),
+        }
+    }
+
+    /// Record the currently finished tasks as of `now`. Tasks no longer
+    /// reported finished (reopened, or their details dropped) are forgotten.
+    pub fn observe<'a>(&mut self, finished: impl IntoIterator<Item = &'a str>, now: DateTime<Utc>) {
+        let mut still_finished = HashMap::with_capacity(self.finished_since.len());
+        for task_id in finished {
+            let since = self.finished_since.get(task_id).copied().unwrap_or(now);
+            still_finished.insert(task_id.to_string(), since);
+        }
+        self.finished_since = still_finished;
+    }
+
+    /// Tasks finished for longer than the `task_set` retention, sorted.
+    pub fn expired_from_set(&self, now: DateTime<Utc>) -> Vec<String> {
+        self.finished_longer_than(self.set_retention, now)
+    }
+
+    /// Tasks finished for longer than the detail retention, sorted.
+    pub fn expired_details(&self, now: DateTime<Utc>) -> Vec<String> {
+        match self.detail_retention {
+            Some(retention) => self.finished_longer_than(retention, now),
+            None => Vec::new(),
+        }
+    }
+
+    fn finished_longer_than(&self, retention: Duration, now: DateTime<Utc>) -> Vec<String> {
+        let mut expired: Vec<String> = self
+            .finished_since
+            .iter()
+            .filter(|(_, since)| (now - **since).to_std().is_ok_and(|age| age > retention))
+            .map(|(task_id, _)| task_id.clone())
+            .collect();
+        expired.sort();
+        expired
+    }
+}
+
+/// Outcome of one cleanup pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskGcReport {
+    /// Tasks removed from `task_set`.
+    pub pruned: Vec<String>,
+    /// Tasks whose details and timeline were dropped.
+    pub forgotten: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detail_retention_is_never_shorter_than_set_retention() {
+        let mut retention = TaskRetention::new(Duration::from_secs(60), Some(Duration::from_secs(10)));
+        let t0 = Utc::now();
+        retention.observe(["a"], t0);
+        let later = t0 + chrono::Duration::seconds(30);
+        assert!(retention.expired_from_set(later).is_empty());
+        assert!(retention.expired_details(later).is_empty());
+        let later = t0 + chrono::Duration::seconds(61);
+        assert_eq!(retention.expired_from_set(later), vec!["a"]);
+        assert_eq!(retention.expired_details(later), vec!["a"]);
+
+        let forever = TaskRetention::new(Duration::from_secs(60), None);
+        assert!(forever.expired_details(t0 + chrono::Duration::days(365)).is_empty());
+    }
+}
@@ -0,0 +1,98 @@
+//! Parent-context placeholders in plan subtask descriptions.
+//!
+//! A subtask description is normally handed to its executor verbatim, so
+//! the executing agent never sees the task it was split from. A proposer
+//! can reference the parent instead, e.g. `"Summarise the sources for:
+//! {parent_description}"`, and [`expand_subtask_description`] fills the
+//! placeholders in when the plan is proposed:
+//!
+//! | Placeholder | Value |
+//! |-------------|-------|
+//! | `{parent_description}` | Description of the task being decomposed |
+//! | `{parent_task_id}` | Its task ID |
+//! | `{subtask_index}` | The subtask's `index` in the plan |
+//!
+//! Any other text, including unknown `{...}` sequences, is left as is, so a
+//! description without placeholders passes through unchanged.
+
+use wws_protocol::{PlanSubtask, Task};
+
+const PLACEHOLDERS: [&str; 3] = ["{parent_description}", "{parent_task_id}", "{subtask_index}"];
+
+/// Whether `description` uses any placeholder.
+pub fn is_template(description: &str) -> bool {
+    PLACEHOLDERS.iter().any(|p| description.contains(p))
+}
+
+/// Expand the placeholders in `subtask`'s description with `parent`.
+pub fn expand_subtask_description(subtask: &PlanSubtask, parent: &Task) -> String {
+    if !is_template(&subtask.description) {
+        return subtask.description.clone();
+    }
+    let values = [
+        (PLACEHOLDERS[0], parent.description.clone()),
+        (PLACEHOLDERS[1], parent.task_id.clone()),
+        (PLACEHOLDERS[2], subtask.index.to_string()),
+    ];
+    // One pass, so placeholder text inside the parent description is not
+    // expanded again.
+    let mut out = String::with_capacity(subtask.description.len() + parent.description.len());
+    let mut rest = subtask.description.as_str();
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        match values.iter().find(|(placeholder, _)| tail.starts_with(placeholder)) {
+            Some((placeholder, value)) => {
+                out.push_str(value);
+                rest = &tail[placeholder.len()..];
+            }
+            None => {
+                out.push('{');
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subtask(description: &str) -> PlanSubtask {
+        PlanSubtask {
+            index: 2,
+            description: description.to_string(),
+            required_capabilities: Vec::new(),
+            estimated_complexity: 0.5,
+            depends_on: Vec::new(),
+        }
+    }
+
+    fn parent() -> Task {
+        let mut task = Task::new("Compare {a} with {parent_task_id}".into(), 1, 1);
+        task.task_id = "root-1".into();
+        task
+    }
+
+    #[test]
+    fn template_is_expanded_with_parent_context() {
+        let expanded = expand_subtask_description(
+            &subtask("Part {subtask_index} of {parent_task_id}: {parent_description} {unknown}"),
+            &parent(),
+        );
+        assert_eq!(
+            expanded,
+            "Part 2 of root-1: Compare {a} with {parent_task_id} {unknown}"
+        );
+    }
+
+    #[test]
+    fn plain_description_is_unchanged() {
+        for plain in ["Collect sources", "Use {braces} freely", ""] {
+            assert!(!is_template(plain));
+            assert_eq!(expand_subtask_description(&subtask(plain), &parent()), plain);
+        }
+    }
+}
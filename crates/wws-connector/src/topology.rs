@@ -0,0 +1,111 @@
+//! Swarm topology export for `swarm.export_topology`.
+//!
+//! A [`Topology`] is a snapshot of the active members, their tiers and the
+//! parent links between them, plus this node's measured ping RTT to the
+//! peers it is connected to. It serializes to JSON as-is and to Graphviz
+//! DOT through [`Topology::to_dot`], so operators can render the hierarchy
+//! with `dot -Tsvg` or any other standard tool.
+
+use serde::Serialize;
+
+/// One swarm member.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TopologyNode {
+    pub agent_id: String,
+    /// Registered name, if the agent has one.
+    pub name: Option<String>,
+    pub tier: String,
+    pub is_self: bool,
+}
+
+/// Why two members are linked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TopologyEdgeKind {
+    /// `from` is the hierarchy parent of `to`.
+    Parent,
+    /// A direct connection from this node with a measured RTT, where no
+    /// parent link already joins the two.
+    Peer,
+}
+
+/// A link between two members.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TopologyEdge {
+    pub from: String,
+    pub to: String,
+    pub kind: TopologyEdgeKind,
+    /// Last ping RTT in milliseconds, when one end is this node and a ping
+    /// has completed.
+    pub rtt_ms: Option<u64>,
+}
+
+/// Snapshot of the swarm hierarchy as seen by this node.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Topology {
+    /// Sorted by agent ID.
+    pub nodes: Vec<TopologyNode>,
+    /// Parent links first, sorted by (from, to); then peer links.
+    pub edges: Vec<TopologyEdge>,
+}
+
+impl Topology {
+    /// Render as a Graphviz `digraph`. Parent links point from parent to
+    /// child; peer links are dashed and undirected. This node is drawn as a
+    /// double circle.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph swarm {\n    rankdir=TB;\n    node [shape=ellipse];\n");
+        for node in &self.nodes {
+            let title = node.name.as_deref().unwrap_or(&node.agent_id);
+            let label = format!("{}\n{}", title, node.tier);
+            out.push_str(&format!("    {} [label={}", dot_id(&node.agent_id), dot_id(&label)));
+            if node.is_self {
+                out.push_str(", shape=doublecircle");
+            }
+            out.push_str("];\n");
+        }
+        for edge in &self.edges {
+            let mut attrs = Vec::new();
+            if edge.kind == TopologyEdgeKind::Peer {
+                attrs.push("style=dashed".to_string());
+                attrs.push("dir=none".to_string());
+            }
+            if let Some(rtt) = edge.rtt_ms {
+                attrs.push(format!("label={}", dot_id(&format!("{} ms", rtt))));
+            }
+            out.push_str(&format!("    {} -> {}", dot_id(&edge.from), dot_id(&edge.to)));
+            if !attrs.is_empty() {
+                out.push_str(&format!(" [{}]", attrs.join(", ")));
+            }
+            out.push_str(";\n");
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Quote `s` as a DOT ID string.
+fn dot_id(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dot_ids_escape_quotes_and_newlines() {
+        assert_eq!(dot_id("a\"b\\c\nd"), "\"a\\\"b\\\\c\\nd\"");
+    }
+}
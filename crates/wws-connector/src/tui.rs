@@ -7,6 +7,7 @@
 //! Launch with `wws-connector --tui` to run the TUI alongside
 //! the connector event loop.
 
+use std::collections::VecDeque;
 use std::io::{self, Stdout};
 use std::sync::Arc;
 use std::time::Duration;
@@ -21,13 +22,50 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Row, Table},
+    widgets::{Block, Borders, Paragraph, Row, Sparkline, Table},
     Frame, Terminal,
 };
 use tokio::sync::RwLock;
 
 use crate::connector::{ConnectorState, ConnectorStatus};
 
+/// Number of swarm-size samples kept for the network sparkline
+/// (one per frame, so roughly 30 seconds at 10fps).
+const SWARM_SIZE_HISTORY_LEN: usize = 300;
+
+/// Bounded ring buffer of recent samples, oldest first.
+#[derive(Debug, Clone)]
+struct SampleHistory {
+    samples: VecDeque<u64>,
+    capacity: usize,
+}
+
+impl SampleHistory {
+    fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Append a sample, evicting the oldest once at capacity.
+    fn push(&mut self, sample: u64) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// The most recent `n` samples, oldest first.
+    fn recent(&self, n: usize) -> Vec<u64> {
+        let skip = self.samples.len().saturating_sub(n);
+        self.samples.iter().skip(skip).copied().collect()
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 struct FlowSummary {
     injected: usize,
@@ -52,7 +90,7 @@ pub struct LogEntry {
 }
 
 /// Category of a log entry, used for coloring and filtering.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum LogCategory {
     Peer,
     Message,
@@ -65,6 +103,25 @@ pub enum LogCategory {
 }
 
 impl LogCategory {
+    /// Parse a category from its variant name (`"Vote"`) or display label
+    /// (`"VOTE"`, `"ERR"`), ignoring case.
+    pub fn parse(name: &str) -> Option<Self> {
+        [
+            LogCategory::Peer,
+            LogCategory::Message,
+            LogCategory::Task,
+            LogCategory::Vote,
+            LogCategory::Epoch,
+            LogCategory::Error,
+            LogCategory::System,
+            LogCategory::Swarm,
+        ]
+        .into_iter()
+        .find(|c| {
+            name.eq_ignore_ascii_case(c.label()) || name.eq_ignore_ascii_case(&format!("{:?}", c))
+        })
+    }
+
     /// Short label for display in the event log.
     fn label(&self) -> &'static str {
         match self {
@@ -111,6 +168,8 @@ struct SwarmTui {
     task_scroll: u16,
     /// Which panel has focus.
     focus: FocusPanel,
+    /// Estimated swarm size sampled on each snapshot.
+    swarm_size_history: SampleHistory,
 }
 
 impl SwarmTui {
@@ -120,13 +179,15 @@ impl SwarmTui {
             log_scroll: 0,
             task_scroll: 0,
             focus: FocusPanel::EventLog,
+            swarm_size_history: SampleHistory::new(SWARM_SIZE_HISTORY_LEN),
         }
     }
 
     /// Take a snapshot of the connector state for rendering.
     /// This minimizes the time we hold the read lock.
-    async fn snapshot(&self) -> StateSnapshot {
+    async fn snapshot(&mut self) -> StateSnapshot {
         let state = self.state.read().await;
+        self.swarm_size_history.push(state.estimated_swarm_size);
         let cascade_status = state.cascade.status();
         let flow_summary = summarize_flow(&state.task_timelines, &state.event_log);
         let (tier1_count, tier2_count, tiern_count, executor_count) = summarize_tiers(&state);
@@ -159,9 +220,21 @@ impl SwarmTui {
             status: format_status(&state.status),
             status_color: status_color(&state.status),
             parent_id: state.parent_id.as_ref().map(|p| p.to_string()),
-            active_tasks: state.task_set.elements(),
+            active_tasks: state
+                .task_set
+                .elements()
+                .into_iter()
+                .map(|task_id| {
+                    let percent = state
+                        .task_details
+                        .get(&task_id)
+                        .and_then(|t| t.progress.as_ref())
+                        .map(|p| p.percent);
+                    (task_id, percent)
+                })
+                .collect(),
             peer_count: state.agent_set.len(),
-            swarm_size: state.network_stats.total_agents,
+            swarm_size: state.estimated_swarm_size,
             depth: state.network_stats.hierarchy_depth,
             branching: state.network_stats.branching_factor,
             epoch_duration: state.epoch_manager.epoch_duration_secs(),
@@ -192,7 +265,7 @@ impl SwarmTui {
         let outer = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(9),   // Status + Network
+                Constraint::Length(10),  // Status + Network
                 Constraint::Length(10),  // Swarms + Consensus
                 Constraint::Length(8),   // Tasks
                 Constraint::Min(6),     // Event Log
@@ -299,12 +372,19 @@ impl SwarmTui {
         frame.render_widget(paragraph, area);
     }
 
-    /// Render the Network panel.
+    /// Render the Network panel, with a swarm-size sparkline along the bottom.
     fn render_network(&self, frame: &mut Frame, area: Rect, snap: &StateSnapshot) {
         let block = Block::default()
             .title(" Network ")
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::White));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let sections = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(6), Constraint::Length(2)])
+            .split(inner);
 
         let uptime = format_uptime(snap.start_time);
 
@@ -350,8 +430,20 @@ impl SwarmTui {
             ]),
         ];
 
-        let paragraph = Paragraph::new(text).block(block);
-        frame.render_widget(paragraph, area);
+        frame.render_widget(Paragraph::new(text), sections[0]);
+
+        let history = self
+            .swarm_size_history
+            .recent(sections[1].width.saturating_sub(2) as usize);
+        let sparkline = Sparkline::default()
+            .data(&history)
+            .style(Style::default().fg(Color::Green));
+        let spark_area = Rect {
+            x: sections[1].x + 2,
+            width: sections[1].width.saturating_sub(2),
+            ..sections[1]
+        };
+        frame.render_widget(sparkline, spark_area);
     }
 
     /// Render the Swarms panel showing all known swarms and agent counts.
@@ -452,7 +544,7 @@ impl SwarmTui {
             .active_tasks
             .iter()
             .skip(self.task_scroll as usize)
-            .map(|task_id| {
+            .map(|(task_id, percent)| {
                 let short_id = if task_id.len() > 16 {
                     format!("{}...", &task_id[..16])
                 } else {
@@ -464,7 +556,10 @@ impl SwarmTui {
                         Style::default().fg(Color::White),
                     )),
                     ratatui::widgets::Cell::from(Span::styled(
-                        "Active",
+                        match percent {
+                            Some(percent) => format!("Active {}%", percent),
+                            None => "Active".to_string(),
+                        },
                         Style::default().fg(Color::Yellow),
                     )),
                     ratatui::widgets::Cell::from(Span::styled(
@@ -750,7 +845,8 @@ struct StateSnapshot {
     status: String,
     status_color: Color,
     parent_id: Option<String>,
-    active_tasks: Vec<String>,
+    /// (task_id, reported progress percent)
+    active_tasks: Vec<(String, Option<u8>)>,
     peer_count: usize,
     swarm_size: u64,
     depth: u32,
@@ -973,18 +1069,21 @@ mod tests {
             "t1".to_string(),
             vec![
                 crate::connector::TaskTimelineEvent {
+                    seq: 0,
                     timestamp: chrono::Utc::now(),
                     stage: "injected".to_string(),
                     detail: "".to_string(),
                     actor: None,
                 },
                 crate::connector::TaskTimelineEvent {
+                    seq: 0,
                     timestamp: chrono::Utc::now(),
                     stage: "proposed".to_string(),
                     detail: "".to_string(),
                     actor: None,
                 },
                 crate::connector::TaskTimelineEvent {
+                    seq: 0,
                     timestamp: chrono::Utc::now(),
                     stage: "result_submitted".to_string(),
                     detail: "".to_string(),
@@ -1013,4 +1112,28 @@ mod tests {
         assert_eq!(summary.message_events, 1);
         assert_eq!(summary.peer_events, 1);
     }
+
+    #[test]
+    fn sample_history_evicts_oldest_at_capacity() {
+        let mut history = SampleHistory::new(3);
+        assert!(history.recent(10).is_empty());
+
+        history.push(1);
+        history.push(2);
+        assert_eq!(history.recent(10), vec![1, 2]);
+
+        history.push(3);
+        history.push(4);
+        history.push(5);
+        assert_eq!(history.recent(10), vec![3, 4, 5]);
+        assert_eq!(history.recent(2), vec![4, 5]);
+        assert_eq!(history.recent(0), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn sample_history_with_zero_capacity_stays_empty() {
+        let mut history = SampleHistory::new(0);
+        history.push(7);
+        assert!(history.recent(5).is_empty());
+    }
 }
@@ -0,0 +1,229 @@
+//! Measured peer uptime for election scoring.
+//!
+//! `NodeScore::uptime` feeds `composite_score`, but candidates report it
+//! themselves. [`UptimeTracker`] records when each agent connects and
+//! disconnects (after flap debouncing) and computes the fraction of a
+//! rolling window it was connected, which the connector substitutes for the
+//! self-reported value when registering a candidate it has observed.
+//!
+//! With `hierarchy.uptime_file` set, the tracker is saved periodically and
+//! on shutdown and reloaded on startup. Sessions open at save time are
+//! closed at that time: the node cannot tell whether peers stayed up while
+//! it was down.
+
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Default rolling window uptime is measured over.
+pub const DEFAULT_UPTIME_WINDOW_SECS: u64 = 3600;
+
+/// Seconds between saves of the uptime file.
+pub const UPTIME_SAVE_INTERVAL_SECS: u64 = 60;
+
+/// One connected period; `end` is `None` while still connected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct Session {
+    start: DateTime<Utc>,
+    end: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct AgentUptime {
+    /// First connect observed; the window never reaches before it, so a
+    /// newly seen agent is not penalised for time it was unknown.
+    first_seen: Option<DateTime<Utc>>,
+    sessions: VecDeque<Session>,
+}
+
+/// Connect/disconnect history per agent over a rolling window.
+#[derive(Debug, Clone)]
+pub struct UptimeTracker {
+    window: Duration,
+    agents: HashMap<String, AgentUptime>,
+}
+
+/// On-disk form of an [`UptimeTracker`].
+#[derive(Debug, Serialize, Deserialize)]
+struct UptimeFile {
+    saved_at: DateTime<Utc>,
+    agents: HashMap<String, AgentUptime>,
+}
+
+impl UptimeTracker {
+    /// Measure uptime over the last `window`.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            agents: HashMap::new(),
+        }
+    }
+
+    /// `agent_id` connected at `now`. Ignored while already connected.
+    pub fn record_connect(&mut self, agent_id: &str, now: DateTime<Utc>) {
+        let agent = self.agents.entry(agent_id.to_string()).or_default();
+        agent.first_seen.get_or_insert(now);
+        if agent.sessions.back().is_some_and(|s| s.end.is_none()) {
+            return;
+        }
+        agent.sessions.push_back(Session { start: now, end: None });
+        self.prune(now);
+    }
+
+    /// `agent_id` disconnected at `now`. Ignored while not connected.
+    pub fn record_disconnect(&mut self, agent_id: &str, now: DateTime<Utc>) {
+        if let Some(session) = self
+            .agents
+            .get_mut(agent_id)
+            .and_then(|a| a.sessions.back_mut())
+            .filter(|s| s.end.is_none())
+        {
+            session.end = Some(now.max(session.start));
+        }
+        self.prune(now);
+    }
+
+    /// Whether `agent_id` is currently connected.
+    pub fn is_connected(&self, agent_id: &str) -> bool {
+        self.agents
+            .get(agent_id)
+            .and_then(|a| a.sessions.back())
+            .is_some_and(|s| s.end.is_none())
+    }
+
+    /// Fraction (0.0 - 1.0) of the window up to `now` that `agent_id` was
+    /// connected, counted from when it was first seen if that is later.
+    /// `None` for an agent never seen.
+    pub fn uptime(&self, agent_id: &str, now: DateTime<Utc>) -> Option<f64> {
+        let agent = self.agents.get(agent_id)?;
+        let first_seen = agent.first_seen?;
+        let from = first_seen.max(now - self.window_chrono());
+        let secs = |d: chrono::Duration| d.to_std().map_or(0.0, |d| d.as_secs_f64());
+        let span = secs(now - from);
+        if span <= 0.0 {
+            return Some(if self.is_connected(agent_id) { 1.0 } else { 0.0 });
+        }
+        let connected: f64 = agent
+            .sessions
+            .iter()
+            .map(|s| secs(s.end.unwrap_or(now).min(now) - s.start.max(from)))
+            .sum();
+        Some((connected / span).clamp(0.0, 1.0))
+    }
+
+    /// Drop sessions that ended before the window.
+    fn prune(&mut self, now: DateTime<Utc>) {
+        let cutoff = now - self.window_chrono();
+        for agent in self.agents.values_mut() {
+            while agent.sessions.front().is_some_and(|s| s.end.is_some_and(|end| end < cutoff)) {
+                agent.sessions.pop_front();
+            }
+        }
+    }
+
+    fn window_chrono(&self) -> chrono::Duration {
+        chrono::Duration::from_std(self.window).unwrap_or(chrono::Duration::MAX)
+    }
+
+    /// Write the history to `path` as JSON, via a temporary file so a crash
+    /// never leaves a truncated file behind.
+    pub fn save(&self, path: &Path, now: DateTime<Utc>) -> std::io::Result<()> {
+        let file = UptimeFile {
+            saved_at: now,
+            agents: self.agents.clone(),
+        };
+        let json = serde_json::to_vec(&file)?;
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, path)
+    }
+
+    /// Load history saved by [`UptimeTracker::save`], closing sessions that
+    /// were open at save time. A missing file yields an empty tracker.
+    pub fn load(path: &Path, window: Duration) -> std::io::Result<Self> {
+        let mut tracker = Self::new(window);
+        let bytes = match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(tracker),
+            Err(e) => return Err(e),
+        };
+        let file: UptimeFile = serde_json::from_slice(&bytes)?;
+        tracker.agents = file.agents;
+        for agent in tracker.agents.values_mut() {
+            for session in agent.sessions.iter_mut().filter(|s| s.end.is_none()) {
+                session.end = Some(file.saved_at.max(session.start));
+            }
+        }
+        Ok(tracker)
+    }
+}
+
+impl Default for UptimeTracker {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(DEFAULT_UPTIME_WINDOW_SECS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(t0: DateTime<Utc>, minutes: i64) -> DateTime<Utc> {
+        t0 + chrono::Duration::minutes(minutes)
+    }
+
+    #[test]
+    fn uptime_fraction_follows_connect_disconnect_sequence() {
+        let t0 = Utc::now();
+        let mut tracker = UptimeTracker::new(Duration::from_secs(60 * 60));
+        assert_eq!(tracker.uptime("a", t0), None);
+
+        // Up 0-20, down 20-30, up 30-40 (connect repeated), down since 40.
+        tracker.record_connect("a", t0);
+        assert_eq!(tracker.uptime("a", t0), Some(1.0));
+        tracker.record_disconnect("a", at(t0, 20));
+        tracker.record_connect("a", at(t0, 30));
+        tracker.record_connect("a", at(t0, 35));
+        tracker.record_disconnect("a", at(t0, 40));
+        tracker.record_disconnect("a", at(t0, 45));
+
+        // Measured since first seen: 30 of 40 minutes, then 30 of 50.
+        assert_eq!(tracker.uptime("a", at(t0, 40)), Some(0.75));
+        assert_eq!(tracker.uptime("a", at(t0, 50)), Some(0.6));
+
+        // Once the hour-long window slides past the start, only 10-70
+        // counts: 10 + 10 of 60 minutes.
+        assert!(!tracker.is_connected("a"));
+        let uptime = tracker.uptime("a", at(t0, 70)).unwrap();
+        assert!((uptime - 20.0 / 60.0).abs() < 1e-9, "{uptime}");
+
+        tracker.record_connect("a", at(t0, 70));
+        let uptime = tracker.uptime("a", at(t0, 100)).unwrap();
+        assert!((uptime - 30.0 / 60.0).abs() < 1e-9, "{uptime}");
+        assert_eq!(tracker.uptime("a", at(t0, 200)), Some(1.0));
+    }
+
+    #[test]
+    fn reload_closes_sessions_open_at_save_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("uptime.json");
+        let t0 = Utc::now();
+        let window = Duration::from_secs(60 * 60);
+        let mut tracker = UptimeTracker::new(window);
+        tracker.record_connect("a", t0);
+        tracker.save(&path, at(t0, 30)).unwrap();
+
+        let reloaded = UptimeTracker::load(&path, window).unwrap();
+        assert!(!reloaded.is_connected("a"));
+        assert_eq!(reloaded.uptime("a", at(t0, 60)), Some(0.5));
+
+        let missing = UptimeTracker::load(&dir.path().join("none.json"), window).unwrap();
+        assert_eq!(missing.uptime("a", t0), None);
+    }
+}
@@ -131,6 +131,8 @@ fn e2e_task_lifecycle_messages() {
         size_bytes: 2048,
         created_at: chrono::Utc::now(),
         content: "analysis result".into(),
+        metadata: serde_json::Value::Null,
+        signature: String::new(),
     };
     let result_msg = ResultSubmissionParams {
         task_id: task.task_id.clone(),
@@ -270,12 +272,14 @@ fn e2e_multilevel_cascade_decomposition_and_backprop() {
             description: "Domain A".into(),
             required_capabilities: vec!["analysis".into()],
             estimated_complexity: 0.6,
+            depends_on: Vec::new(),
         },
         PlanSubtask {
             index: 1,
             description: "Domain B".into(),
             required_capabilities: vec!["analysis".into()],
             estimated_complexity: 0.4,
+            depends_on: Vec::new(),
         },
     ];
 
@@ -302,12 +306,14 @@ fn e2e_multilevel_cascade_decomposition_and_backprop() {
             description: "Leaf A1".into(),
             required_capabilities: vec!["exec".into()],
             estimated_complexity: 0.5,
+            depends_on: Vec::new(),
         },
         PlanSubtask {
             index: 1,
             description: "Leaf A2".into(),
             required_capabilities: vec!["exec".into()],
             estimated_complexity: 0.5,
+            depends_on: Vec::new(),
         },
     ];
 
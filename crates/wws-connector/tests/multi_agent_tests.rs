@@ -22,12 +22,9 @@ use wws_protocol::{
 /// Helper: create a SwarmHost on a random port with mDNS enabled.
 fn spawn_node() -> (SwarmHost, SwarmHandle, tokio::sync::mpsc::Receiver<NetworkEvent>) {
     let config = SwarmHostConfig {
-        listen_addr: "/ip4/127.0.0.1/tcp/0".parse().unwrap(),
+        listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
         transport: TransportConfig::default(),
-        discovery: DiscoveryConfig {
-            mdns_enabled: true,
-            ..Default::default()
-        },
+        discovery: DiscoveryConfig::standard(Vec::new(), true),
         ..Default::default()
     };
     SwarmHost::new(config).expect("Failed to create SwarmHost")
@@ -252,7 +249,7 @@ async fn test_proposal_commit_topic_exchange_between_peers() {
         .expect("Connection should establish");
 
     let task_id = "task-proposal-topic-test";
-    let proposals_topic = SwarmTopics::proposals(task_id);
+    let proposals_topic = SwarmTopics::proposals(task_id, 1);
     handle_a
         .subscribe(&proposals_topic)
         .await
@@ -596,6 +593,7 @@ fn test_full_consensus_flow_offline() {
         prohibit_self_vote: true,
         min_votes: 1,
         senate_seed: Some(42),
+        ..Default::default()
     };
     let mut voting = VotingEngine::new(voting_config, task_id.clone(), 1);
 
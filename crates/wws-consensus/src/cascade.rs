@@ -313,6 +313,47 @@ impl CascadeEngine {
         }
     }
 
+    /// Per-task completion as `(completed, total)` direct subtasks, keyed by
+    /// parent task ID.
+    ///
+    /// Each cascade level is reported independently, so completing a subtask
+    /// only changes the entry for its own parent.
+    pub fn progress_by_task(&self) -> HashMap<String, (usize, usize)> {
+        self.levels
+            .iter()
+            .map(|(parent_id, level)| {
+                let done = level
+                    .assignments
+                    .iter()
+                    .filter(|a| self.completed.get(&a.task.task_id).copied().unwrap_or(false))
+                    .count();
+                (parent_id.clone(), (done, level.assignments.len()))
+            })
+            .collect()
+    }
+
+    /// Walk up the cascade from `task_id` to the task that started its branch.
+    pub fn root_of(&self, task_id: &str) -> String {
+        let mut current = task_id;
+        // Bounded by the number of tracked subtasks in case of a malformed cycle.
+        for _ in 0..=self.subtask_to_parent.len() {
+            match self.subtask_to_parent.get(current) {
+                Some(parent) => current = parent,
+                None => break,
+            }
+        }
+        current.to_string()
+    }
+
+    /// Get all assignments whose subtask has not completed yet.
+    pub fn pending_assignments(&self) -> Vec<&SubtaskAssignment> {
+        self.levels
+            .values()
+            .flat_map(|level| level.assignments.iter())
+            .filter(|a| !self.completed.get(&a.task.task_id).copied().unwrap_or(false))
+            .collect()
+    }
+
     /// Get the cascade level for a specific parent task.
     pub fn get_level(&self, parent_task_id: &str) -> Option<&CascadeLevel> {
         self.levels.get(parent_task_id)
@@ -369,18 +410,21 @@ mod tests {
                 description: "Part A".into(),
                 required_capabilities: vec![],
                 estimated_complexity: 0.3,
+                depends_on: Vec::new(),
             },
             PlanSubtask {
                 index: 1,
                 description: "Part B".into(),
                 required_capabilities: vec![],
                 estimated_complexity: 0.4,
+                depends_on: Vec::new(),
             },
             PlanSubtask {
                 index: 2,
                 description: "Part C".into(),
                 required_capabilities: vec![],
                 estimated_complexity: 0.3,
+                depends_on: Vec::new(),
             },
         ];
         plan
@@ -4,16 +4,21 @@
 //! - Request for Proposal (RFP) protocol with commit-reveal scheme
 //! - Ranked Choice Voting with Instant Runoff Voting (IRV)
 //! - Recursive decomposition cascade for multi-tier task distribution
+//! - Majority-CID tallying of redundant execution results
 
 pub mod cascade;
 pub mod mock_planner;
+pub mod result_tally;
 pub mod rfp;
 pub mod voting;
 
 pub use cascade::CascadeEngine;
 pub use mock_planner::MockPlanGenerator;
-pub use rfp::{PlanGenerator, RfpCoordinator};
-pub use voting::VotingEngine;
+pub use result_tally::{ResultTally, TallyOutcome};
+pub use rfp::{
+    adaptive_expected_proposers, check_plan_dependencies, PlanGenerator, PlanLimits, RfpCoordinator,
+};
+pub use voting::{MajorityBasis, SelfVotePolicy, VotingEngine, VotingResult};
 
 use thiserror::Error;
 
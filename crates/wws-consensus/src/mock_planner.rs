@@ -52,6 +52,7 @@ impl PlanGenerator for MockPlanGenerator {
                     ),
                     required_capabilities: vec![],
                     estimated_complexity: 1.0 / subtask_count as f64,
+                    depends_on: Vec::new(),
                 });
             }
 
@@ -0,0 +1,143 @@
+//! Majority-CID comparison of redundant execution results.
+//!
+//! When several executors run the same subtask, each submits the content ID
+//! of its result. A [`ResultTally`] counts submissions per CID and settles
+//! on the CID held by a configurable majority of the expected executors, so
+//! a minority of faulty or Byzantine executors cannot decide the outcome
+//! and the last submission no longer overwrites earlier ones. A tie never
+//! completes the subtask.
+
+use std::collections::BTreeMap;
+
+use wws_protocol::AgentId;
+
+/// Default fraction of expected executors that must agree on a CID.
+pub const DEFAULT_RESULT_MAJORITY: f64 = 0.5;
+
+/// Outcome of recording one result submission.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TallyOutcome {
+    /// No CID has reached the majority yet and submissions are outstanding.
+    Pending,
+    /// This submission gave `cid` its majority.
+    Decided {
+        cid: String,
+        /// Executors that submitted `cid`, in agent ID order.
+        supporters: Vec<AgentId>,
+        /// Executors that submitted a different CID, in agent ID order.
+        dissenters: Vec<AgentId>,
+    },
+    /// The tally was already decided; reports whether this late
+    /// submission matches the winning CID.
+    AlreadyDecided { cid: String, agrees: bool },
+    /// Every expected executor has submitted and no CID reached the majority.
+    NoMajority,
+    /// The executor had already submitted; the repeat was ignored.
+    Duplicate,
+}
+
+/// Per-subtask count of submitted result CIDs.
+#[derive(Debug, Clone)]
+pub struct ResultTally {
+    task_id: String,
+    expected: usize,
+    majority: f64,
+    /// Executor -> submitted CID. Only the first submission per executor counts.
+    submissions: BTreeMap<String, String>,
+    decided: Option<String>,
+}
+
+impl ResultTally {
+    /// Tally for `task_id` run by `expected` executors, deciding once a CID
+    /// is held by more than `majority` of them (clamped to `[0.5, 1.0]`).
+    pub fn new(task_id: impl Into<String>, expected: usize, majority: f64) -> Self {
+        Self {
+            task_id: task_id.into(),
+            expected: expected.max(1),
+            majority: majority.clamp(DEFAULT_RESULT_MAJORITY, 1.0),
+            submissions: BTreeMap::new(),
+            decided: None,
+        }
+    }
+
+    pub fn task_id(&self) -> &str {
+        &self.task_id
+    }
+
+    /// Matching submissions a CID needs to win.
+    ///
+    /// More than `majority × expected`, capped at `expected` so a
+    /// unanimous requirement is still reachable.
+    pub fn required_votes(&self) -> usize {
+        let threshold = (self.expected as f64 * self.majority).floor() as usize + 1;
+        threshold.min(self.expected)
+    }
+
+    /// The winning CID, once decided.
+    pub fn decided_cid(&self) -> Option<&str> {
+        self.decided.as_deref()
+    }
+
+    /// Number of distinct executors that have submitted.
+    pub fn submission_count(&self) -> usize {
+        self.submissions.len()
+    }
+
+    /// Submissions per CID, most common first (ties ordered by CID).
+    pub fn counts(&self) -> Vec<(String, usize)> {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for cid in self.submissions.values() {
+            *counts.entry(cid).or_default() += 1;
+        }
+        let mut counts: Vec<(String, usize)> =
+            counts.into_iter().map(|(cid, n)| (cid.to_string(), n)).collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        counts
+    }
+
+    /// Record `agent`'s result CID and report the tally state.
+    ///
+    /// Only an executor's first submission counts, so it cannot change its
+    /// answer after seeing others and a result relayed twice is not
+    /// counted twice.
+    pub fn record(&mut self, agent: &AgentId, cid: &str) -> TallyOutcome {
+        if self.submissions.contains_key(agent.as_str()) {
+            return TallyOutcome::Duplicate;
+        }
+        self.submissions.insert(agent.to_string(), cid.to_string());
+
+        if let Some(decided) = &self.decided {
+            return TallyOutcome::AlreadyDecided {
+                cid: decided.clone(),
+                agrees: decided == cid,
+            };
+        }
+
+        let required = self.required_votes();
+        if let Some((winner, _)) = self.counts().into_iter().find(|(_, n)| *n >= required) {
+            let (supporters, dissenters): (Vec<_>, Vec<_>) = self
+                .submissions
+                .iter()
+                .partition(|(_, submitted)| **submitted == winner);
+            let ids = |group: Vec<(&String, &String)>| {
+                group
+                    .into_iter()
+                    .map(|(agent, _)| AgentId::new(agent.clone()))
+                    .collect()
+            };
+            let outcome = TallyOutcome::Decided {
+                cid: winner.clone(),
+                supporters: ids(supporters),
+                dissenters: ids(dissenters),
+            };
+            self.decided = Some(winner);
+            return outcome;
+        }
+
+        if self.submissions.len() >= self.expected {
+            TallyOutcome::NoMajority
+        } else {
+            TallyOutcome::Pending
+        }
+    }
+}
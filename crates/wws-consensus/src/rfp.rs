@@ -22,11 +22,15 @@ use sha2::{Digest, Sha256};
 
 use wws_protocol::{
     AgentId, CriticScore, Plan, ProposalCommitParams, ProposalRevealParams, Task,
-    COMMIT_REVEAL_TIMEOUT_SECS,
+    COMMIT_REVEAL_TIMEOUT_SECS, DEFAULT_MAX_SUBTASKS_PER_PLAN,
+    DEFAULT_MAX_SUBTASK_DESCRIPTION_LEN,
 };
 
 use crate::ConsensusError;
 
+/// Maximum number of early reveals held per coordinator.
+const EARLY_REVEAL_BUFFER_CAP: usize = 16;
+
 // ---------------------------------------------------------------------------
 // Plan Generator trait
 // ---------------------------------------------------------------------------
@@ -103,6 +107,122 @@ pub struct RevealedProposal {
     pub plan_hash: String,
 }
 
+/// Bounds a plan must respect before it is committed or revealed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlanLimits {
+    /// Maximum number of subtasks in one plan.
+    pub max_subtasks_per_plan: usize,
+    /// Maximum length of a subtask description, in bytes.
+    pub max_subtask_description_len: usize,
+    /// Minimum length of the plan rationale, in bytes, ignoring surrounding
+    /// whitespace. Zero accepts plans without a rationale.
+    pub min_rationale_len: usize,
+}
+
+impl Default for PlanLimits {
+    fn default() -> Self {
+        Self {
+            max_subtasks_per_plan: DEFAULT_MAX_SUBTASKS_PER_PLAN,
+            max_subtask_description_len: DEFAULT_MAX_SUBTASK_DESCRIPTION_LEN,
+            min_rationale_len: 0,
+        }
+    }
+}
+
+impl PlanLimits {
+    /// Reject plans that exceed these limits or lack a sufficient rationale.
+    pub fn check(&self, plan: &Plan) -> Result<(), ConsensusError> {
+        let rationale_len = plan.rationale.trim().len();
+        if rationale_len < self.min_rationale_len {
+            return Err(ConsensusError::PlanGenerationFailed(format!(
+                "plan {} has a {}-byte rationale (minimum {})",
+                plan.plan_id, rationale_len, self.min_rationale_len
+            )));
+        }
+        if plan.subtasks.len() > self.max_subtasks_per_plan {
+            return Err(ConsensusError::PlanGenerationFailed(format!(
+                "plan {} has {} subtasks (limit {})",
+                plan.plan_id,
+                plan.subtasks.len(),
+                self.max_subtasks_per_plan
+            )));
+        }
+        if let Some(subtask) = plan
+            .subtasks
+            .iter()
+            .find(|s| s.description.len() > self.max_subtask_description_len)
+        {
+            return Err(ConsensusError::PlanGenerationFailed(format!(
+                "subtask {} of plan {} has a {}-byte description (limit {})",
+                subtask.index,
+                plan.plan_id,
+                subtask.description.len(),
+                self.max_subtask_description_len
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Reject plans whose `depends_on` edges name a missing subtask, the subtask
+/// itself, or form a cycle.
+pub fn check_plan_dependencies(plan: &Plan) -> Result<(), ConsensusError> {
+    let position: HashMap<usize, usize> = plan
+        .subtasks
+        .iter()
+        .enumerate()
+        .map(|(pos, s)| (s.index as usize, pos))
+        .collect();
+    let mut remaining_deps = vec![0usize; plan.subtasks.len()];
+    let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); plan.subtasks.len()];
+    for (pos, subtask) in plan.subtasks.iter().enumerate() {
+        for dep in &subtask.depends_on {
+            let Some(&dep_pos) = position.get(dep) else {
+                return Err(ConsensusError::PlanGenerationFailed(format!(
+                    "subtask {} of plan {} depends on unknown subtask {}",
+                    subtask.index, plan.plan_id, dep
+                )));
+            };
+            if dep_pos == pos {
+                return Err(ConsensusError::PlanGenerationFailed(format!(
+                    "subtask {} of plan {} depends on itself",
+                    subtask.index, plan.plan_id
+                )));
+            }
+            remaining_deps[pos] += 1;
+            dependents[dep_pos].push(pos);
+        }
+    }
+
+    // Kahn's algorithm: every subtask is released exactly once iff acyclic.
+    let mut ready: Vec<usize> = (0..plan.subtasks.len())
+        .filter(|&pos| remaining_deps[pos] == 0)
+        .collect();
+    let mut released = 0;
+    while let Some(pos) = ready.pop() {
+        released += 1;
+        for &next in &dependents[pos] {
+            remaining_deps[next] -= 1;
+            if remaining_deps[next] == 0 {
+                ready.push(next);
+            }
+        }
+    }
+    if released != plan.subtasks.len() {
+        return Err(ConsensusError::PlanGenerationFailed(format!(
+            "plan {} has a dependency cycle among its subtasks",
+            plan.plan_id
+        )));
+    }
+    Ok(())
+}
+
+/// Expected proposer count for adaptive RFP sizing: every current swarm
+/// member may propose, capped at `max` (and never below one).
+pub fn adaptive_expected_proposers(member_count: usize, max: usize) -> usize {
+    member_count.min(max).max(1)
+}
+
 /// Coordinates the Request for Proposal process for a single task.
 ///
 /// Lifecycle:
@@ -130,6 +250,14 @@ pub struct RfpCoordinator {
     pub critique_scores: HashMap<AgentId, HashMap<String, CriticScore>>,
     /// Critique content messages.
     pub critique_content: HashMap<AgentId, String>,
+    /// Size limits enforced on revealed plans.
+    plan_limits: PlanLimits,
+    /// Reveals received before their commit, keyed by proposer.
+    early_reveals: HashMap<AgentId, (Plan, DateTime<Utc>)>,
+    /// How long an early reveal is held waiting for its commit.
+    reveal_grace: std::time::Duration,
+    /// Minimum time the commit phase stays open before enough commits close it.
+    min_commit_window: std::time::Duration,
 }
 
 impl RfpCoordinator {
@@ -146,9 +274,41 @@ impl RfpCoordinator {
             expected_proposers,
             critique_scores: HashMap::new(),
             critique_content: HashMap::new(),
+            plan_limits: PlanLimits::default(),
+            early_reveals: HashMap::new(),
+            reveal_grace: std::time::Duration::ZERO,
+            min_commit_window: std::time::Duration::ZERO,
         }
     }
 
+    /// Override the size limits enforced on revealed plans.
+    pub fn with_plan_limits(mut self, plan_limits: PlanLimits) -> Self {
+        self.plan_limits = plan_limits;
+        self
+    }
+
+    /// Hold reveals that arrive before their commit for up to `grace`.
+    ///
+    /// Zero (the default) rejects such reveals immediately.
+    pub fn with_reveal_grace(mut self, grace: std::time::Duration) -> Self {
+        self.reveal_grace = grace;
+        self
+    }
+
+    /// Keep the commit phase open for at least `window`, even once the
+    /// expected number of commits has arrived. Zero (the default) closes it
+    /// on the last expected commit.
+    pub fn with_min_commit_window(mut self, window: std::time::Duration) -> Self {
+        self.min_commit_window = window;
+        self
+    }
+
+    /// Override the commit phase timeout.
+    pub fn with_commit_timeout_secs(mut self, secs: u64) -> Self {
+        self.commit_timeout_secs = secs;
+        self
+    }
+
     /// Start the RFP by injecting a task. Moves to CommitPhase.
     pub fn inject_task(&mut self, task: &Task) -> Result<(), ConsensusError> {
         if self.phase != RfpPhase::Idle {
@@ -234,8 +394,9 @@ impl RfpCoordinator {
             "Recorded proposal commit"
         );
 
-        // Auto-transition if all expected commits received.
-        if self.commits.len() >= self.expected_proposers {
+        // Auto-transition if all expected commits received and the minimum
+        // window has passed; otherwise `should_close_commit_phase` picks it up.
+        if self.commits.len() >= self.expected_proposers && self.commit_window_elapsed() {
             self.phase = RfpPhase::RevealPhase;
             tracing::info!(
                 task_id = %self.task_id,
@@ -268,6 +429,26 @@ impl RfpCoordinator {
         Ok(())
     }
 
+    /// Whether the commit phase has been open for the minimum window.
+    pub fn commit_window_elapsed(&self) -> bool {
+        self.commit_started_at.is_some_and(|started| {
+            Utc::now()
+                .signed_duration_since(started)
+                .to_std()
+                .is_ok_and(|open| open >= self.min_commit_window)
+        })
+    }
+
+    /// Whether a commit phase with at least one commit is due to close:
+    /// either every expected proposer committed and the minimum window has
+    /// passed, or the commit timeout expired.
+    pub fn should_close_commit_phase(&self) -> bool {
+        self.phase == RfpPhase::CommitPhase
+            && !self.commits.is_empty()
+            && (self.is_commit_timed_out()
+                || (self.commits.len() >= self.expected_proposers && self.commit_window_elapsed()))
+    }
+
     /// Check if the commit phase has timed out.
     pub fn is_commit_timed_out(&self) -> bool {
         if let Some(started) = self.commit_started_at {
@@ -283,11 +464,20 @@ impl RfpCoordinator {
     /// Record a reveal (full plan) from a proposer.
     ///
     /// Verifies that the plan's hash matches the previously committed hash.
+    /// A reveal from a proposer that has not committed yet is held for the
+    /// reveal grace window and handed back by [`Self::take_released_reveals`]
+    /// once the commit arrives, to be recorded again through this method; it
+    /// still has to match the committed hash then, and is dropped if the
+    /// window lapses.
     pub fn record_reveal(
         &mut self,
         params: &ProposalRevealParams,
     ) -> Result<(), ConsensusError> {
-        if self.phase != RfpPhase::RevealPhase {
+        let early = !self.reveal_grace.is_zero()
+            && !self.commits.contains_key(&params.plan.proposer)
+            && matches!(self.phase, RfpPhase::CommitPhase | RfpPhase::RevealPhase);
+
+        if self.phase != RfpPhase::RevealPhase && !early {
             return Err(ConsensusError::RfpFailed(format!(
                 "Not in reveal phase (currently {:?})",
                 self.phase
@@ -298,7 +488,90 @@ impl RfpCoordinator {
             return Err(ConsensusError::TaskNotFound(self.task_id.clone()));
         }
 
-        let proposer = &params.plan.proposer;
+        self.plan_limits.check(&params.plan)?;
+        check_plan_dependencies(&params.plan)?;
+
+        if early {
+            return self.buffer_early_reveal(params.plan.clone());
+        }
+        self.apply_reveal(&params.plan)
+    }
+
+    /// Number of reveals currently held waiting for their commit.
+    pub fn early_reveal_count(&self) -> usize {
+        self.early_reveals.len()
+    }
+
+    fn buffer_early_reveal(&mut self, plan: Plan) -> Result<(), ConsensusError> {
+        self.prune_early_reveals();
+        if self.early_reveals.len() >= EARLY_REVEAL_BUFFER_CAP
+            && !self.early_reveals.contains_key(&plan.proposer)
+        {
+            return Err(ConsensusError::RfpFailed(format!(
+                "No commit found for proposer {} and early reveal buffer is full",
+                plan.proposer
+            )));
+        }
+        tracing::debug!(
+            task_id = %self.task_id,
+            proposer = %plan.proposer,
+            "Holding reveal until its commit arrives"
+        );
+        self.early_reveals
+            .insert(plan.proposer.clone(), (plan, Utc::now()));
+        Ok(())
+    }
+
+    /// Drop early reveals whose grace window has lapsed.
+    fn prune_early_reveals(&mut self) {
+        let now = Utc::now();
+        let grace = self.reveal_grace;
+        let task_id = &self.task_id;
+        self.early_reveals.retain(|proposer, (_, received_at)| {
+            let fresh = now
+                .signed_duration_since(*received_at)
+                .to_std()
+                .map(|age| age <= grace)
+                .unwrap_or(true);
+            if !fresh {
+                tracing::debug!(
+                    task_id = %task_id,
+                    proposer = %proposer,
+                    "Discarding early reveal: commit never arrived"
+                );
+            }
+            fresh
+        });
+    }
+
+    /// Remove and return the held reveals whose commit has since arrived,
+    /// sorted by proposer, once the reveal phase is open. Callers record
+    /// each one with [`Self::record_reveal`] like any other reveal.
+    pub fn take_released_reveals(&mut self) -> Vec<ProposalRevealParams> {
+        self.prune_early_reveals();
+        if self.phase != RfpPhase::RevealPhase {
+            return Vec::new();
+        }
+        let mut ready: Vec<AgentId> = self
+            .early_reveals
+            .keys()
+            .filter(|p| self.commits.contains_key(*p))
+            .cloned()
+            .collect();
+        ready.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        ready
+            .into_iter()
+            .filter_map(|proposer| self.early_reveals.remove(&proposer))
+            .map(|(plan, _)| ProposalRevealParams {
+                task_id: self.task_id.clone(),
+                plan,
+            })
+            .collect()
+    }
+
+    /// Verify a reveal against its commit and record it.
+    fn apply_reveal(&mut self, plan: &Plan) -> Result<(), ConsensusError> {
+        let proposer = &plan.proposer;
 
         // Verify the reveal matches the commit.
         let commit = self.commits.get(proposer).ok_or_else(|| {
@@ -309,7 +582,7 @@ impl RfpCoordinator {
         })?;
 
         // Compute hash of the revealed plan.
-        let plan_json = serde_json::to_vec(&params.plan)
+        let plan_json = serde_json::to_vec(plan)
             .map_err(|e| ConsensusError::Serialization(e.to_string()))?;
         let computed_hash = hex_encode(&Sha256::digest(&plan_json));
 
@@ -324,7 +597,7 @@ impl RfpCoordinator {
             proposer.clone(),
             RevealedProposal {
                 proposer: proposer.clone(),
-                plan: params.plan.clone(),
+                plan: plan.clone(),
                 plan_hash: computed_hash,
             },
         );
@@ -432,6 +705,11 @@ impl RfpCoordinator {
         &self.task_id
     }
 
+    /// Get the epoch the RFP runs in.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
     /// Get the number of commits received.
     pub fn commit_count(&self) -> usize {
         self.commits.len()
@@ -479,6 +757,7 @@ mod tests {
             description: "Subtask A".to_string(),
             required_capabilities: vec!["python".to_string()],
             estimated_complexity: 0.5,
+            depends_on: Vec::new(),
         });
         plan.rationale = "Test plan".to_string();
         plan
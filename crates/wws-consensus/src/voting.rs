@@ -2,37 +2,102 @@
 //!
 //! Implements the plan selection mechanism:
 //! - Agents rank proposed plans from most to least preferred
-//! - Self-vote prohibition: an agent cannot rank their own proposal first
+//! - Self-vote prohibition: an agent cannot rank their own proposal first, or
+//!   with [`SelfVotePolicy::FullyProhibit`] cannot vote on a task it proposed for
 //! - Senate sampling: for large swarms, a random subset votes to reduce overhead
 //! - IRV elimination: if no plan has majority, the plan with fewest first-choice
 //!   votes is eliminated and its votes are redistributed
 //!
 //! The IRV algorithm:
 //! 1. Count first-choice votes for each plan
-//! 2. If a plan has > 50% of first-choice votes, it wins
+//! 2. If a plan has > 50% of first-choice votes, it wins (measured against
+//!    continuing or all ballots, per [`MajorityBasis`])
 //! 3. Otherwise, eliminate the plan with the fewest first-choice votes
 //! 4. Redistribute eliminated plan's votes to each voter's next preference
 //! 5. Repeat until a plan has majority or one plan remains
+//!
+//! Proposals and tallies are kept in ordered collections, so the same
+//! ballots always produce the same round-by-round trace and result.
+//!
+//! With [`VotingConfig::collection_window`] set, ballots are collected from
+//! the moment proposals are registered until the window closes or every
+//! senator has voted; [`VotingEngine::ready_to_finalize_at`] tells the
+//! driver when to run IRV, and later ballots are rejected.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::time::Duration;
 
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use wws_protocol::{AgentId, CriticScore, RankedVote};
 use rand::seq::SliceRandom;
 
 use crate::ConsensusError;
 
+/// Maximum times a single plan may appear in one ballot's rankings.
+/// Repeats up to this count are collapsed to the first occurrence; a
+/// ballot exceeding it is rejected as malformed.
+pub const MAX_RANKING_REPEATS: usize = 2;
+
+/// Ballot count an IRV round's majority threshold is measured against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MajorityBasis {
+    /// More than half of the ballots still ranking a continuing plan.
+    /// Exhausted ballots drop out of the denominator, so a round always
+    /// produces a winner once enough plans are eliminated.
+    #[default]
+    ActiveBallots,
+    /// More than half of every ballot cast, exhausted ones included.
+    /// If too many ballots exhaust, no plan can reach this and the
+    /// election fails instead of crowning a minority winner.
+    TotalBallots,
+}
+
+/// How a proposer may vote on its own task while self-voting is prohibited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelfVotePolicy {
+    /// A proposer votes like anyone else but may not rank its own plan
+    /// first.
+    #[default]
+    AllowNonFirst,
+    /// Any ballot from a proposer of one of the task's plans is rejected.
+    FullyProhibit,
+}
+
+impl std::str::FromStr for SelfVotePolicy {
+    type Err = ConsensusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "allow_non_first" => Ok(Self::AllowNonFirst),
+            "fully_prohibit" => Ok(Self::FullyProhibit),
+            other => Err(ConsensusError::VotingError(format!(
+                "unknown self-vote policy {other:?} (expected allow_non_first or fully_prohibit)"
+            ))),
+        }
+    }
+}
+
 /// Configuration for the voting engine.
 #[derive(Debug, Clone)]
 pub struct VotingConfig {
     /// Maximum number of voters (senate size). If the swarm is larger,
     /// a random subset is selected.
     pub senate_size: usize,
-    /// Whether self-voting is prohibited (agent cannot rank own plan first).
+    /// Whether self-voting is prohibited, as `self_vote_policy` specifies.
     pub prohibit_self_vote: bool,
+    /// What self-vote prohibition forbids a proposer.
+    pub self_vote_policy: SelfVotePolicy,
     /// Minimum number of votes required for a valid election.
     pub min_votes: usize,
     /// Random seed for reproducible senate sampling (None = random).
     pub senate_seed: Option<u64>,
+    /// Which ballots the per-round majority threshold counts.
+    pub majority_basis: MajorityBasis,
+    /// How long ballots are collected once proposals are registered.
+    /// `None` leaves it to the caller to decide when to run IRV.
+    pub collection_window: Option<Duration>,
 }
 
 impl Default for VotingConfig {
@@ -40,8 +105,11 @@ impl Default for VotingConfig {
         Self {
             senate_size: 100,
             prohibit_self_vote: true,
+            self_vote_policy: SelfVotePolicy::AllowNonFirst,
             min_votes: 1,
             senate_seed: None,
+            majority_basis: MajorityBasis::ActiveBallots,
+            collection_window: None,
         }
     }
 }
@@ -55,12 +123,15 @@ pub struct VotingResult {
     pub rounds: usize,
     /// Plan IDs in elimination order (first eliminated = weakest).
     pub elimination_order: Vec<String>,
-    /// Final vote counts for remaining plans.
-    pub final_tallies: HashMap<String, usize>,
+    /// Final vote counts for remaining plans, ordered by plan ID.
+    pub final_tallies: BTreeMap<String, usize>,
     /// Total number of votes processed.
     pub total_votes: usize,
     /// Aggregate critic scores for the winning plan.
     pub winner_critic_score: Option<CriticScore>,
+    /// Aggregate critic scores for every plan scored by at least one ballot,
+    /// including eliminated ones.
+    pub critic_scores: BTreeMap<String, CriticScore>,
 }
 
 /// A single ballot in the IRV system.
@@ -86,7 +157,7 @@ pub struct VotingEngine {
     task_id: String,
     epoch: u64,
     /// Plan IDs eligible for voting.
-    proposal_ids: HashSet<String>,
+    proposal_ids: BTreeSet<String>,
     /// Map from plan ID to proposer agent ID (for self-vote checking).
     plan_proposers: HashMap<String, AgentId>,
     /// Collected ballots.
@@ -95,6 +166,8 @@ pub struct VotingEngine {
     senate: Option<HashSet<AgentId>>,
     /// Whether voting has been finalized.
     finalized: bool,
+    /// When the first proposals were registered, opening ballot collection.
+    collection_opened_at: Option<DateTime<Utc>>,
     /// IRV round history (populated after run_irv()).
     pub irv_rounds: Vec<wws_protocol::IrvRound>,
 }
@@ -106,19 +179,24 @@ impl VotingEngine {
             config,
             task_id,
             epoch,
-            proposal_ids: HashSet::new(),
+            proposal_ids: BTreeSet::new(),
             plan_proposers: HashMap::new(),
             ballots: Vec::new(),
             senate: None,
             finalized: false,
+            collection_opened_at: None,
             irv_rounds: Vec::new(),
         }
     }
 
     /// Register the proposals being voted on.
     ///
-    /// `proposals` maps plan_id to the proposer's agent_id.
+    /// `proposals` maps plan_id to the proposer's agent_id. The first
+    /// non-empty call opens the collection window.
     pub fn set_proposals(&mut self, proposals: HashMap<String, AgentId>) {
+        if !proposals.is_empty() {
+            self.open_collection_at(Utc::now());
+        }
         for (plan_id, proposer) in &proposals {
             self.proposal_ids.insert(plan_id.clone());
             self.plan_proposers
@@ -159,19 +237,72 @@ impl VotingEngine {
         self.senate = Some(senate);
     }
 
+    /// Open the collection window at `now`, unless it is already open.
+    pub fn open_collection_at(&mut self, now: DateTime<Utc>) {
+        self.collection_opened_at.get_or_insert(now);
+    }
+
+    /// When the collection window closes; `None` while it has not opened
+    /// or when no window is configured.
+    pub fn collection_closes_at(&self) -> Option<DateTime<Utc>> {
+        let window = chrono::Duration::from_std(self.config.collection_window?).ok()?;
+        Some(self.collection_opened_at? + window)
+    }
+
+    /// Whether the collection window has closed by `now`.
+    pub fn is_collection_closed_at(&self, now: DateTime<Utc>) -> bool {
+        self.collection_closes_at().is_some_and(|closes| now >= closes)
+    }
+
+    /// Whether a senate was selected and every senator has voted.
+    pub fn all_senators_voted(&self) -> bool {
+        self.senate.as_ref().is_some_and(|senate| {
+            senate.iter().all(|s| self.ballots.iter().any(|b| &b.voter == s))
+        })
+    }
+
+    /// Whether IRV should run now: collection is open, enough ballots are
+    /// in, and either the window has closed or every senator has voted.
+    pub fn ready_to_finalize_at(&self, now: DateTime<Utc>) -> bool {
+        !self.finalized
+            && self.collection_opened_at.is_some()
+            && self.ballots.len() >= self.config.min_votes.max(1)
+            && (self.is_collection_closed_at(now) || self.all_senators_voted())
+    }
+
     /// Record a ranked choice vote from an agent.
+    pub fn record_vote(&mut self, vote: RankedVote) -> Result<(), ConsensusError> {
+        self.record_vote_at(vote, Utc::now())
+    }
+
+    /// Record a ranked choice vote from an agent, received at `now`.
     ///
     /// Validates:
+    /// - The collection window, if configured, has not closed
     /// - The voter is in the senate (if senate sampling is active)
-    /// - Self-vote prohibition (voter cannot rank own plan first)
+    /// - Self-vote prohibition, per [`SelfVotePolicy`]
     /// - All ranked plan IDs are valid proposals
-    pub fn record_vote(&mut self, vote: RankedVote) -> Result<(), ConsensusError> {
+    /// - No plan is ranked more than `MAX_RANKING_REPEATS` times
+    ///
+    /// Repeated rankings are deduplicated, keeping each plan's first position.
+    pub fn record_vote_at(
+        &mut self,
+        vote: RankedVote,
+        now: DateTime<Utc>,
+    ) -> Result<(), ConsensusError> {
         if self.finalized {
             return Err(ConsensusError::VotingError(
                 "Voting already finalized".into(),
             ));
         }
 
+        if self.is_collection_closed_at(now) {
+            return Err(ConsensusError::VotingError(format!(
+                "Vote collection window for task {} closed",
+                self.task_id
+            )));
+        }
+
         if vote.task_id != self.task_id {
             return Err(ConsensusError::TaskNotFound(self.task_id.clone()));
         }
@@ -193,24 +324,46 @@ impl VotingEngine {
             }
         }
 
-        // Self-vote prohibition: voter cannot rank their own plan first.
-        if self.config.prohibit_self_vote && self.proposal_ids.len() > 1 {
-            if let Some(first_choice) = vote.rankings.first() {
-                if let Some(proposer) = self.plan_proposers.get(first_choice) {
-                    if proposer == &vote.voter {
-                        return Err(ConsensusError::SelfVoteProhibited(
-                            vote.voter.to_string(),
-                        ));
-                    }
+        if self.config.prohibit_self_vote {
+            let self_vote = match self.config.self_vote_policy {
+                // Voter cannot rank their own plan first, unless it is the
+                // only plan.
+                SelfVotePolicy::AllowNonFirst => {
+                    self.proposal_ids.len() > 1
+                        && vote
+                            .rankings
+                            .first()
+                            .and_then(|first_choice| self.plan_proposers.get(first_choice))
+                            .is_some_and(|proposer| proposer == &vote.voter)
+                }
+                // Proposers do not vote on their own task at all.
+                SelfVotePolicy::FullyProhibit => {
+                    self.plan_proposers.values().any(|proposer| proposer == &vote.voter)
                 }
+            };
+            if self_vote {
+                return Err(ConsensusError::SelfVoteProhibited(vote.voter.to_string()));
+            }
+        }
+
+        let mut occurrences: HashMap<&str, usize> = HashMap::new();
+        for id in &vote.rankings {
+            let count = occurrences.entry(id.as_str()).or_insert(0);
+            *count += 1;
+            if *count > MAX_RANKING_REPEATS {
+                return Err(ConsensusError::VotingError(format!(
+                    "Malformed ballot from {}: plan {} ranked more than {} times",
+                    vote.voter, id, MAX_RANKING_REPEATS
+                )));
             }
         }
 
-        // Filter rankings to only include valid proposal IDs.
+        // Keep the first occurrence of each valid proposal ID.
+        let mut seen = HashSet::new();
         let valid_rankings: Vec<String> = vote
             .rankings
             .iter()
-            .filter(|id| self.proposal_ids.contains(*id))
+            .filter(|id| self.proposal_ids.contains(*id) && seen.insert(id.as_str()))
             .cloned()
             .collect();
 
@@ -246,7 +399,7 @@ impl VotingEngine {
         }
 
         let mut active_ballots: Vec<Ballot> = self.ballots.clone();
-        let mut eliminated: HashSet<String> = HashSet::new();
+        let mut eliminated: BTreeSet<String> = BTreeSet::new();
         let mut elimination_order: Vec<String> = Vec::new();
         let mut round = 0;
 
@@ -254,7 +407,7 @@ impl VotingEngine {
             round += 1;
 
             // Count first-choice votes for each active proposal.
-            let mut tallies: HashMap<String, usize> = HashMap::new();
+            let mut tallies: BTreeMap<String, usize> = BTreeMap::new();
             for proposal_id in &self.proposal_ids {
                 if !eliminated.contains(proposal_id) {
                     tallies.insert(proposal_id.clone(), 0);
@@ -279,7 +432,10 @@ impl VotingEngine {
                 ));
             }
 
-            let majority_threshold = valid_ballot_count / 2 + 1;
+            let majority_threshold = match self.config.majority_basis {
+                MajorityBasis::ActiveBallots => valid_ballot_count / 2 + 1,
+                MajorityBasis::TotalBallots => self.ballots.len() / 2 + 1,
+            };
 
             tracing::debug!(
                 round,
@@ -293,7 +449,12 @@ impl VotingEngine {
                 .iter()
                 .max_by_key(|(_, &count)| count)
             {
-                if count >= majority_threshold || tallies.len() == 1 {
+                // A lone survivor wins outright on active ballots (it holds
+                // every one of them), but must still clear the threshold when
+                // exhausted ballots count against it.
+                let last_standing = tallies.len() == 1
+                    && self.config.majority_basis == MajorityBasis::ActiveBallots;
+                if count >= majority_threshold || last_standing {
                     // Record final round (no elimination).
                     self.irv_rounds.push(wws_protocol::IrvRound {
                         task_id: self.task_id.clone(),
@@ -303,7 +464,8 @@ impl VotingEngine {
                         continuing_candidates: tallies.keys().cloned().collect(),
                     });
 
-                    let winner_critic = self.aggregate_critic_scores(&winner);
+                    let winner_critic = self.aggregate_critic_scores(winner);
+                    let critic_scores = self.critic_score_aggregates();
                     self.finalized = true;
 
                     return Ok(VotingResult {
@@ -313,10 +475,18 @@ impl VotingEngine {
                         final_tallies: tallies,
                         total_votes: self.ballots.len(),
                         winner_critic_score: winner_critic,
+                        critic_scores,
                     });
                 }
             }
 
+            if tallies.len() == 1 {
+                return Err(ConsensusError::VotingError(format!(
+                    "No plan reached a majority of {} ballots cast",
+                    self.ballots.len()
+                )));
+            }
+
             // Find the plan with fewest first-choice votes (to eliminate).
             let (to_eliminate, _) = tallies
                 .iter()
@@ -370,6 +540,22 @@ impl VotingEngine {
         }).collect()
     }
 
+    /// Aggregate critic scores for every plan that at least one ballot scored.
+    pub fn critic_score_aggregates(&self) -> BTreeMap<String, CriticScore> {
+        let scored: BTreeSet<&String> = self
+            .ballots
+            .iter()
+            .flat_map(|ballot| ballot.critic_scores.keys())
+            .collect();
+        scored
+            .into_iter()
+            .filter_map(|plan_id| {
+                self.aggregate_critic_scores(plan_id)
+                    .map(|score| (plan_id.clone(), score))
+            })
+            .collect()
+    }
+
     /// Aggregate critic scores for a plan across all ballots that scored it.
     fn aggregate_critic_scores(&self, plan_id: &str) -> Option<CriticScore> {
         let mut total_feasibility = 0.0;
@@ -711,4 +897,36 @@ mod tests {
             assert_eq!(round.task_id, "specific-task-id");
         }
     }
+
+    #[test]
+    fn test_irv_trace_is_reproducible_across_runs() {
+        let run = || {
+            let mut engine = VotingEngine::new(
+                VotingConfig { prohibit_self_vote: false, ..Default::default() },
+                "task1".into(),
+                1,
+            );
+            let mut proposals = HashMap::new();
+            for plan in ["planA", "planB", "planC", "planD"] {
+                proposals.insert(plan.to_string(), AgentId::new(format!("{}-author", plan)));
+            }
+            engine.set_proposals(proposals);
+
+            // planC and planD tie at the bottom; planA and planB tie at the top.
+            engine.record_vote(make_vote("v1", "task1", 1, vec!["planA", "planC"])).unwrap();
+            engine.record_vote(make_vote("v2", "task1", 1, vec!["planA", "planD"])).unwrap();
+            engine.record_vote(make_vote("v3", "task1", 1, vec!["planB", "planD"])).unwrap();
+            engine.record_vote(make_vote("v4", "task1", 1, vec!["planB", "planC"])).unwrap();
+            engine.record_vote(make_vote("v5", "task1", 1, vec!["planC", "planA"])).unwrap();
+            engine.record_vote(make_vote("v6", "task1", 1, vec!["planD", "planB"])).unwrap();
+
+            let result = engine.run_irv().unwrap();
+            format!("{:?} {:?}", result, engine.irv_rounds())
+        };
+
+        let first = run();
+        for _ in 0..20 {
+            assert_eq!(run(), first);
+        }
+    }
 }
@@ -8,6 +8,7 @@
 use wws_consensus::cascade::{CascadeEngine, StopCondition};
 use wws_protocol::types::{Plan, PlanSubtask};
 use wws_protocol::identity::AgentId;
+use wws_protocol::Tier;
 
 // ═══════════════════════════════════════════════════════════════
 // § 6.5 Recursive Decomposition
@@ -22,6 +23,7 @@ fn cascade_assigns_subtasks_to_agents() {
             description: format!("Subtask {}", i),
             required_capabilities: vec![],
             estimated_complexity: 0.5,
+            depends_on: Vec::new(),
         });
     }
 
@@ -91,6 +93,7 @@ fn cascade_subtask_count_matches_plan() {
             description: format!("Sub {}", i),
             required_capabilities: vec![],
             estimated_complexity: 0.3,
+            depends_on: Vec::new(),
         });
     }
     let agents: Vec<AgentId> = (0..10)
@@ -109,6 +112,7 @@ fn cascade_fewer_agents_than_subtasks() {
             description: format!("Sub {}", i),
             required_capabilities: vec![],
             estimated_complexity: 0.3,
+            depends_on: Vec::new(),
         });
     }
     // Only 5 agents for 10 subtasks
@@ -120,3 +124,72 @@ fn cascade_fewer_agents_than_subtasks() {
     assert!(assignments.len() <= 10);
     assert!(assignments.len() >= 5, "At least one assignment per agent");
 }
+
+// ═══════════════════════════════════════════════════════════════
+// Per-task progress
+// ═══════════════════════════════════════════════════════════════
+
+fn plan_with_subtasks(task_id: &str, count: u32) -> Plan {
+    let mut plan = Plan::new(task_id.into(), AgentId::new("p".into()), 1);
+    for i in 0..count {
+        plan.subtasks.push(PlanSubtask {
+            index: i,
+            description: format!("{} sub {}", task_id, i),
+            required_capabilities: vec![],
+            estimated_complexity: 0.3,
+            depends_on: Vec::new(),
+        });
+    }
+    plan
+}
+
+#[test]
+fn cascade_progress_by_task_updates_only_owning_parent() {
+    let mut engine = CascadeEngine::new();
+    let subordinates = vec![
+        (AgentId::new("c1".into()), Tier::Tier2),
+        (AgentId::new("c2".into()), Tier::Tier2),
+    ];
+    let root = engine
+        .distribute_subtasks("root", &plan_with_subtasks("root", 2), &subordinates, 1)
+        .unwrap();
+    let branch_id = root[0].task.task_id.clone();
+    let executors = vec![(AgentId::new("e1".into()), Tier::Executor)];
+    let branch = engine
+        .distribute_subtasks(&branch_id, &plan_with_subtasks(&branch_id, 3), &executors, 1)
+        .unwrap();
+
+    let before = engine.progress_by_task();
+    assert_eq!(before["root"], (0, 2));
+    assert_eq!(before[&branch_id], (0, 3));
+
+    engine.record_subtask_completion(&branch[0].task.task_id).unwrap();
+    engine.record_subtask_completion(&branch[1].task.task_id).unwrap();
+
+    let after = engine.progress_by_task();
+    assert_eq!(after[&branch_id], (2, 3), "branch ratio must advance");
+    assert_eq!(after["root"], (0, 2), "root ratio must not move for grandchild completions");
+
+    engine.record_subtask_completion(&root[1].task.task_id).unwrap();
+    let after = engine.progress_by_task();
+    assert_eq!(after["root"], (1, 2));
+    assert_eq!(after[&branch_id], (2, 3));
+}
+
+#[test]
+fn cascade_root_of_walks_to_branch_origin() {
+    let mut engine = CascadeEngine::new();
+    let subordinates = vec![(AgentId::new("c1".into()), Tier::Tier2)];
+    let root = engine
+        .distribute_subtasks("root", &plan_with_subtasks("root", 1), &subordinates, 1)
+        .unwrap();
+    let branch_id = root[0].task.task_id.clone();
+    let executors = vec![(AgentId::new("e1".into()), Tier::Executor)];
+    let leaf = engine
+        .distribute_subtasks(&branch_id, &plan_with_subtasks(&branch_id, 1), &executors, 1)
+        .unwrap();
+
+    assert_eq!(engine.root_of(&leaf[0].task.task_id), "root");
+    assert_eq!(engine.root_of("root"), "root");
+    assert_eq!(engine.pending_assignments().len(), 2);
+}
@@ -0,0 +1,91 @@
+//! Tests for majority-CID comparison of redundant execution results.
+//!
+//! Verifies:
+//! - A clear majority decides the subtask and names the dissenters
+//! - A tie never completes the subtask
+//! - A single expected executor passes straight through
+//! - Repeat and late submissions cannot flip the outcome
+
+use wws_consensus::result_tally::DEFAULT_RESULT_MAJORITY;
+use wws_consensus::{ResultTally, TallyOutcome};
+use wws_protocol::AgentId;
+
+fn agent(name: &str) -> AgentId {
+    AgentId::new(format!("did:swarm:{name}"))
+}
+
+#[test]
+fn clear_majority_decides_and_names_dissenters() {
+    let mut tally = ResultTally::new("sub-1", 3, DEFAULT_RESULT_MAJORITY);
+    assert_eq!(tally.required_votes(), 2);
+
+    assert_eq!(tally.record(&agent("a"), "cid-good"), TallyOutcome::Pending);
+    assert_eq!(tally.record(&agent("b"), "cid-bad"), TallyOutcome::Pending);
+    assert_eq!(
+        tally.record(&agent("c"), "cid-good"),
+        TallyOutcome::Decided {
+            cid: "cid-good".into(),
+            supporters: vec![agent("a"), agent("c")],
+            dissenters: vec![agent("b")],
+        }
+    );
+    assert_eq!(tally.decided_cid(), Some("cid-good"));
+}
+
+#[test]
+fn tie_does_not_complete() {
+    let mut tally = ResultTally::new("sub-2", 2, DEFAULT_RESULT_MAJORITY);
+    assert_eq!(tally.record(&agent("a"), "cid-x"), TallyOutcome::Pending);
+    assert_eq!(tally.record(&agent("b"), "cid-y"), TallyOutcome::NoMajority);
+    assert_eq!(tally.decided_cid(), None);
+
+    let mut tally = ResultTally::new("sub-3", 4, DEFAULT_RESULT_MAJORITY);
+    for (name, cid) in [("a", "x"), ("b", "x"), ("c", "y")] {
+        assert_eq!(tally.record(&agent(name), cid), TallyOutcome::Pending);
+    }
+    assert_eq!(tally.record(&agent("d"), "y"), TallyOutcome::NoMajority);
+    assert_eq!(tally.counts(), vec![("x".to_string(), 2), ("y".to_string(), 2)]);
+}
+
+#[test]
+fn single_submission_passes_through() {
+    let mut tally = ResultTally::new("sub-4", 1, DEFAULT_RESULT_MAJORITY);
+    assert_eq!(
+        tally.record(&agent("a"), "cid"),
+        TallyOutcome::Decided {
+            cid: "cid".into(),
+            supporters: vec![agent("a")],
+            dissenters: vec![],
+        }
+    );
+}
+
+#[test]
+fn repeat_and_late_submissions_cannot_flip_outcome() {
+    let mut tally = ResultTally::new("sub-5", 3, DEFAULT_RESULT_MAJORITY);
+    tally.record(&agent("a"), "x");
+    // A second answer from the same executor is ignored.
+    assert_eq!(tally.record(&agent("a"), "y"), TallyOutcome::Duplicate);
+    assert_eq!(tally.submission_count(), 1);
+
+    assert!(matches!(tally.record(&agent("b"), "x"), TallyOutcome::Decided { .. }));
+    assert_eq!(
+        tally.record(&agent("c"), "y"),
+        TallyOutcome::AlreadyDecided { cid: "x".into(), agrees: false }
+    );
+    assert_eq!(tally.record(&agent("b"), "x"), TallyOutcome::Duplicate);
+    assert_eq!(tally.decided_cid(), Some("x"));
+}
+
+#[test]
+fn majority_threshold_is_configurable() {
+    // Unanimity over three executors.
+    let mut tally = ResultTally::new("sub-6", 3, 1.0);
+    assert_eq!(tally.required_votes(), 3);
+    tally.record(&agent("a"), "x");
+    tally.record(&agent("b"), "x");
+    assert_eq!(tally.record(&agent("c"), "y"), TallyOutcome::NoMajority);
+
+    // Ratios below a simple majority are raised to it.
+    assert_eq!(ResultTally::new("sub-7", 4, 0.1).required_votes(), 3);
+}
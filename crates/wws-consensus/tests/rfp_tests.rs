@@ -8,7 +8,9 @@
 //! - Multiple proposers
 //! - Edge cases: reveal without commit, finalize with no reveals
 
-use wws_consensus::rfp::{RfpCoordinator, RfpPhase};
+use wws_consensus::rfp::{
+    adaptive_expected_proposers, check_plan_dependencies, PlanLimits, RfpCoordinator, RfpPhase,
+};
 use wws_consensus::ConsensusError;
 use wws_protocol::{AgentId, Plan, PlanSubtask, ProposalCommitParams, ProposalRevealParams, Task};
 
@@ -28,6 +30,7 @@ fn make_plan(task_id: &str, proposer: &str, epoch: u64) -> Plan {
         description: "Subtask A".to_string(),
         required_capabilities: vec!["python".to_string()],
         estimated_complexity: 0.5,
+        depends_on: Vec::new(),
     });
     plan.rationale = "Test plan".to_string();
     plan
@@ -488,3 +491,284 @@ fn rfp_metadata_accessors() {
     commit_plan(&mut rfp2, &task_id, "alice", 5, &plan);
     assert_eq!(rfp2.commit_count(), 1);
 }
+
+// ═══════════════════════════════════════════════════════════════
+// Plan size limits
+// ═══════════════════════════════════════════════════════════════
+
+fn plan_with_subtasks(task_id: &str, proposer: &str, count: u32, desc_len: usize) -> Plan {
+    let mut plan = Plan::new(task_id.to_string(), AgentId::new(proposer.to_string()), 1);
+    for i in 0..count {
+        plan.subtasks.push(PlanSubtask {
+            index: i,
+            description: "x".repeat(desc_len),
+            required_capabilities: vec![],
+            estimated_complexity: 0.2,
+            depends_on: Vec::new(),
+        });
+    }
+    plan
+}
+
+const TEST_LIMITS: PlanLimits = PlanLimits {
+    max_subtasks_per_plan: 3,
+    max_subtask_description_len: 16,
+    min_rationale_len: 0,
+};
+
+#[test]
+fn plan_limits_accept_plan_at_limit() {
+    let plan = plan_with_subtasks("t", "alice", 3, 16);
+    assert!(TEST_LIMITS.check(&plan).is_ok());
+}
+
+#[test]
+fn plan_limits_reject_too_many_subtasks() {
+    let plan = plan_with_subtasks("t", "alice", 4, 8);
+    assert!(matches!(
+        TEST_LIMITS.check(&plan),
+        Err(ConsensusError::PlanGenerationFailed(_))
+    ));
+}
+
+#[test]
+fn plan_limits_reject_long_description() {
+    let plan = plan_with_subtasks("t", "alice", 1, 17);
+    assert!(matches!(
+        TEST_LIMITS.check(&plan),
+        Err(ConsensusError::PlanGenerationFailed(_))
+    ));
+}
+
+#[test]
+fn plan_limits_require_rationale_when_configured() {
+    let limits = PlanLimits {
+        min_rationale_len: 10,
+        ..TEST_LIMITS
+    };
+    let mut plan = plan_with_subtasks("t", "alice", 1, 8);
+    plan.rationale = "   ".into();
+    assert!(matches!(
+        limits.check(&plan),
+        Err(ConsensusError::PlanGenerationFailed(_))
+    ));
+
+    plan.rationale = "Split research from writing so both run in parallel".into();
+    assert!(limits.check(&plan).is_ok());
+}
+
+#[test]
+fn rfp_reveal_over_limit_rejected() {
+    let task = Task::new("Test task".into(), 1, 1);
+    let task_id = task.task_id.clone();
+    let mut rfp = RfpCoordinator::new(task_id.clone(), 1, 1).with_plan_limits(TEST_LIMITS);
+    rfp.inject_task(&task).unwrap();
+
+    let plan = plan_with_subtasks(&task_id, "alice", 4, 8);
+    commit_plan(&mut rfp, &task_id, "alice", 1, &plan);
+    let result = rfp.record_reveal(&ProposalRevealParams {
+        task_id: task_id.clone(),
+        plan,
+    });
+    assert!(matches!(result, Err(ConsensusError::PlanGenerationFailed(_))));
+    assert_eq!(rfp.reveal_count(), 0);
+}
+
+#[test]
+fn rfp_reveal_at_limit_accepted() {
+    let task = Task::new("Test task".into(), 1, 1);
+    let task_id = task.task_id.clone();
+    let mut rfp = RfpCoordinator::new(task_id.clone(), 1, 1).with_plan_limits(TEST_LIMITS);
+    rfp.inject_task(&task).unwrap();
+
+    let plan = plan_with_subtasks(&task_id, "alice", 3, 16);
+    commit_plan(&mut rfp, &task_id, "alice", 1, &plan);
+    rfp.record_reveal(&ProposalRevealParams {
+        task_id: task_id.clone(),
+        plan,
+    })
+    .unwrap();
+    assert_eq!(*rfp.phase(), RfpPhase::ReadyForVoting);
+}
+
+// ═══════════════════════════════════════════════════════════════
+// Reveal-before-commit grace
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn rfp_reveal_before_commit_applied_within_grace() {
+    let task = Task::new("Reordered reveal".into(), 1, 1);
+    let task_id = task.task_id.clone();
+    let mut rfp = RfpCoordinator::new(task_id.clone(), 1, 2)
+        .with_reveal_grace(std::time::Duration::from_secs(30));
+    rfp.inject_task(&task).unwrap();
+
+    let plan_a = make_plan(&task_id, "alice", 1);
+    commit_plan(&mut rfp, &task_id, "alice", 1, &plan_a);
+    rfp.transition_to_reveal().unwrap();
+
+    // bob's reveal overtakes his commit.
+    let plan_b = make_plan(&task_id, "bob", 1);
+    rfp.record_reveal(&ProposalRevealParams {
+        task_id: task_id.clone(),
+        plan: plan_b.clone(),
+    })
+    .unwrap();
+    assert_eq!(rfp.early_reveal_count(), 1);
+    assert_eq!(rfp.reveal_count(), 0);
+
+    commit_plan(&mut rfp, &task_id, "bob", 1, &plan_b);
+    assert_eq!(rfp.reveal_count(), 0, "released reveals are recorded by the caller");
+    let released = rfp.take_released_reveals();
+    assert_eq!(released.len(), 1);
+    assert_eq!(rfp.early_reveal_count(), 0);
+    rfp.record_reveal(&released[0]).unwrap();
+    assert_eq!(rfp.reveal_count(), 1);
+    assert!(rfp.reveals.contains_key(&AgentId::new("bob".into())));
+}
+
+#[test]
+fn rfp_early_reveal_still_hash_checked() {
+    let task = Task::new("Forged early reveal".into(), 1, 1);
+    let task_id = task.task_id.clone();
+    let mut rfp = RfpCoordinator::new(task_id.clone(), 1, 1)
+        .with_reveal_grace(std::time::Duration::from_secs(30));
+    rfp.inject_task(&task).unwrap();
+
+    let committed = make_plan(&task_id, "bob", 1);
+    let mut forged = committed.clone();
+    forged.rationale = "something else".into();
+    rfp.record_reveal(&ProposalRevealParams {
+        task_id: task_id.clone(),
+        plan: forged,
+    })
+    .unwrap();
+
+    commit_plan(&mut rfp, &task_id, "bob", 1, &committed);
+    let released = rfp.take_released_reveals();
+    assert_eq!(released.len(), 1);
+    assert!(matches!(
+        rfp.record_reveal(&released[0]),
+        Err(ConsensusError::HashMismatch { .. })
+    ));
+    assert_eq!(rfp.reveal_count(), 0, "mismatched early reveal must be dropped");
+    assert_eq!(rfp.early_reveal_count(), 0);
+}
+
+#[test]
+fn rfp_early_reveal_discarded_after_grace() {
+    let task = Task::new("Late commit".into(), 1, 1);
+    let task_id = task.task_id.clone();
+    let mut rfp = RfpCoordinator::new(task_id.clone(), 1, 2)
+        .with_reveal_grace(std::time::Duration::from_millis(20));
+    rfp.inject_task(&task).unwrap();
+
+    let plan_a = make_plan(&task_id, "alice", 1);
+    commit_plan(&mut rfp, &task_id, "alice", 1, &plan_a);
+    rfp.transition_to_reveal().unwrap();
+
+    let plan_c = make_plan(&task_id, "carol", 1);
+    rfp.record_reveal(&ProposalRevealParams {
+        task_id: task_id.clone(),
+        plan: plan_c.clone(),
+    })
+    .unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(50));
+    commit_plan(&mut rfp, &task_id, "carol", 1, &plan_c);
+    assert!(rfp.take_released_reveals().is_empty());
+    assert_eq!(rfp.early_reveal_count(), 0);
+    assert_eq!(rfp.reveal_count(), 0, "expired early reveal must not be applied");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// Subtask dependency validation
+// ═══════════════════════════════════════════════════════════════
+
+fn plan_with_deps(deps: &[&[usize]]) -> Plan {
+    let mut plan = plan_with_subtasks("t", "alice", deps.len() as u32, 8);
+    for (subtask, d) in plan.subtasks.iter_mut().zip(deps) {
+        subtask.depends_on = d.to_vec();
+    }
+    plan
+}
+
+#[test]
+fn plan_dependencies_accept_linear_chain() {
+    let plan = plan_with_deps(&[&[], &[0], &[1]]);
+    assert!(check_plan_dependencies(&plan).is_ok());
+}
+
+#[test]
+fn plan_dependencies_reject_cycle() {
+    let plan = plan_with_deps(&[&[2], &[0], &[1]]);
+    assert!(matches!(
+        check_plan_dependencies(&plan),
+        Err(ConsensusError::PlanGenerationFailed(_))
+    ));
+}
+
+#[test]
+fn plan_dependencies_reject_unknown_and_self_reference() {
+    let unknown = plan_with_deps(&[&[], &[5]]);
+    assert!(check_plan_dependencies(&unknown).is_err());
+    let self_dep = plan_with_deps(&[&[0]]);
+    assert!(check_plan_dependencies(&self_dep).is_err());
+}
+
+// ═══════════════════════════════════════════════════════════════
+// Adaptive proposer sizing and minimum commit window
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn adaptive_expected_proposers_tracks_members_with_cap() {
+    assert_eq!(adaptive_expected_proposers(0, 5), 1);
+    assert_eq!(adaptive_expected_proposers(3, 5), 3);
+    assert_eq!(adaptive_expected_proposers(40, 5), 5);
+}
+
+#[test]
+fn adaptive_commit_phase_waits_for_every_member() {
+    let task = Task::new("Three members".into(), 1, 1);
+    let task_id = task.task_id.clone();
+    let mut rfp = RfpCoordinator::new(task_id.clone(), 1, adaptive_expected_proposers(3, 10));
+    rfp.inject_task(&task).unwrap();
+
+    for (i, proposer) in ["alice", "bob"].into_iter().enumerate() {
+        commit_plan(&mut rfp, &task_id, proposer, 1, &make_plan(&task_id, proposer, 1));
+        assert_eq!(*rfp.phase(), RfpPhase::CommitPhase, "closed after {} commits", i + 1);
+        assert!(!rfp.should_close_commit_phase());
+    }
+
+    commit_plan(&mut rfp, &task_id, "carol", 1, &make_plan(&task_id, "carol", 1));
+    assert_eq!(*rfp.phase(), RfpPhase::RevealPhase);
+}
+
+#[test]
+fn adaptive_commit_phase_closes_on_timer_with_partial_commits() {
+    let task = Task::new("Three members".into(), 1, 1);
+    let task_id = task.task_id.clone();
+    let mut rfp = RfpCoordinator::new(task_id.clone(), 1, 3).with_commit_timeout_secs(0);
+    rfp.inject_task(&task).unwrap();
+    assert!(!rfp.should_close_commit_phase(), "no commits yet");
+
+    commit_plan(&mut rfp, &task_id, "alice", 1, &make_plan(&task_id, "alice", 1));
+    assert_eq!(*rfp.phase(), RfpPhase::CommitPhase);
+    assert!(rfp.should_close_commit_phase());
+    rfp.transition_to_reveal().unwrap();
+    assert_eq!(*rfp.phase(), RfpPhase::RevealPhase);
+}
+
+#[test]
+fn min_commit_window_holds_phase_open_after_quorum() {
+    let task = Task::new("Windowed".into(), 1, 1);
+    let task_id = task.task_id.clone();
+    let mut rfp = RfpCoordinator::new(task_id.clone(), 1, 1)
+        .with_min_commit_window(std::time::Duration::from_secs(3600));
+    rfp.inject_task(&task).unwrap();
+
+    commit_plan(&mut rfp, &task_id, "alice", 1, &make_plan(&task_id, "alice", 1));
+    assert_eq!(*rfp.phase(), RfpPhase::CommitPhase);
+    assert!(!rfp.commit_window_elapsed());
+    assert!(!rfp.should_close_commit_phase());
+}
@@ -4,12 +4,14 @@
 //! - IRV produces a winner when one candidate has >50% first-choice votes
 //! - IRV eliminates lowest-vote candidates and redistributes
 //! - Self-vote prohibition
-//! - Critic score aggregation on the winning plan
+//! - Critic score aggregation on the winning plan and on every scored plan
 //! - Edge cases: single candidate, epoch mismatch, voting after finalized
 
 use std::collections::HashMap;
 
-use wws_consensus::voting::{VotingConfig, VotingEngine};
+use wws_consensus::voting::{
+    MajorityBasis, SelfVotePolicy, VotingConfig, VotingEngine, MAX_RANKING_REPEATS,
+};
 use wws_consensus::ConsensusError;
 use wws_protocol::{AgentId, CriticScore, RankedVote};
 
@@ -307,6 +309,66 @@ fn self_vote_prohibition_disabled_allows_own_plan_first() {
     );
 }
 
+fn engine_with_self_vote_policy(policy: SelfVotePolicy) -> VotingEngine {
+    let config = VotingConfig {
+        self_vote_policy: policy,
+        ..Default::default()
+    };
+    let mut engine = VotingEngine::new(config, "t1".to_string(), 1);
+    engine.set_proposals(
+        [("planA", "alice"), ("planB", "bob")]
+            .iter()
+            .map(|(plan, proposer)| (plan.to_string(), AgentId::new(proposer.to_string())))
+            .collect(),
+    );
+    engine
+}
+
+#[test]
+fn allow_non_first_policy_accepts_own_plan_ranked_lower() {
+    let mut engine = engine_with_self_vote_policy(SelfVotePolicy::AllowNonFirst);
+    assert!(matches!(
+        engine.record_vote(vote("alice", "t1", 1, &["planA", "planB"])),
+        Err(ConsensusError::SelfVoteProhibited(_))
+    ));
+    assert!(engine.record_vote(vote("alice", "t1", 1, &["planB", "planA"])).is_ok());
+}
+
+#[test]
+fn fully_prohibit_policy_rejects_any_ballot_from_a_proposer() {
+    let mut engine = engine_with_self_vote_policy(SelfVotePolicy::FullyProhibit);
+    for rankings in [&["planA", "planB"][..], &["planB", "planA"], &["planB"]] {
+        assert!(
+            matches!(
+                engine.record_vote(vote("alice", "t1", 1, rankings)),
+                Err(ConsensusError::SelfVoteProhibited(_))
+            ),
+            "proposer ballot {:?} must be rejected",
+            rankings
+        );
+    }
+    assert!(engine.record_vote(vote("carol", "t1", 1, &["planA", "planB"])).is_ok());
+    assert_eq!(engine.ballot_count(), 1);
+}
+
+#[test]
+fn fully_prohibit_policy_is_inert_without_prohibition() {
+    let mut engine = VotingEngine::new(
+        VotingConfig {
+            prohibit_self_vote: false,
+            self_vote_policy: SelfVotePolicy::FullyProhibit,
+            ..Default::default()
+        },
+        "t1".to_string(),
+        1,
+    );
+    engine.set_proposals(HashMap::from([
+        ("planA".to_string(), AgentId::new("alice".to_string())),
+        ("planB".to_string(), AgentId::new("bob".to_string())),
+    ]));
+    assert!(engine.record_vote(vote("alice", "t1", 1, &["planA", "planB"])).is_ok());
+}
+
 // ═══════════════════════════════════════════════════════════════
 // Critic Score Aggregation
 // ═══════════════════════════════════════════════════════════════
@@ -358,6 +420,60 @@ fn winner_has_aggregated_critic_scores() {
     );
 }
 
+#[test]
+fn result_aggregates_critic_scores_for_every_scored_plan() {
+    let mut engine = engine_with_proposals(
+        "t1",
+        1,
+        &[("planA", "alice"), ("planB", "bob"), ("planC", "carol")],
+        false,
+    );
+    let score = |feasibility: f64| CriticScore {
+        feasibility,
+        parallelism: 0.5,
+        completeness: 0.5,
+        risk: 0.5,
+    };
+
+    engine
+        .record_vote(vote_with_scores(
+            "v1",
+            "t1",
+            1,
+            &["planA", "planB", "planC"],
+            &[("planA", score(0.9)), ("planB", score(0.4))],
+        ))
+        .unwrap();
+    engine
+        .record_vote(vote_with_scores(
+            "v2",
+            "t1",
+            1,
+            &["planA", "planC", "planB"],
+            &[("planB", score(0.6))],
+        ))
+        .unwrap();
+    // planC is ranked but never scored.
+    engine
+        .record_vote(vote("v3", "t1", 1, &["planC", "planA", "planB"]))
+        .unwrap();
+
+    let result = engine.run_irv().unwrap();
+    assert_eq!(result.winner, "planA");
+
+    let mut scored: Vec<&str> = result.critic_scores.keys().map(String::as_str).collect();
+    scored.sort();
+    assert_eq!(scored, vec!["planA", "planB"]);
+    assert!((result.critic_scores["planA"].feasibility - 0.9).abs() < 1e-6);
+    // planB lost, but its two scores are still averaged.
+    assert!((result.critic_scores["planB"].feasibility - 0.5).abs() < 1e-6);
+    assert_eq!(
+        result.critic_scores["planA"].feasibility,
+        result.winner_critic_score.unwrap().feasibility
+    );
+    assert_eq!(engine.critic_score_aggregates().len(), 2);
+}
+
 // ═══════════════════════════════════════════════════════════════
 // Error Handling
 // ═══════════════════════════════════════════════════════════════
@@ -433,6 +549,64 @@ fn all_invalid_proposals_rejected() {
     assert!(result.is_err(), "Vote with no valid proposals must fail");
 }
 
+#[test]
+fn duplicated_rankings_are_normalized() {
+    let mut engine = engine_with_proposals(
+        "t1",
+        1,
+        &[("planA", "alice"), ("planB", "bob")],
+        false,
+    );
+    engine
+        .record_vote(vote("v1", "t1", 1, &["planA", "planA", "planB"]))
+        .unwrap();
+    let ballot = &engine.ballots[0];
+    assert_eq!(ballot.original_rankings, vec!["planA", "planB"]);
+    assert_eq!(ballot.remaining_choices, vec!["planA", "planB"]);
+}
+
+#[test]
+fn ballot_repeating_a_plan_past_limit_rejected() {
+    let mut engine = engine_with_proposals("t1", 1, &[("planA", "alice"), ("planB", "bob")], false);
+    let rankings = vec!["planA"; MAX_RANKING_REPEATS + 1];
+    let result = engine.record_vote(vote("v1", "t1", 1, &rankings));
+    assert!(matches!(result, Err(ConsensusError::VotingError(_))));
+    assert_eq!(engine.ballot_count(), 0);
+}
+
+#[test]
+fn dedupe_does_not_change_irv_outcome() {
+    let proposals = [("planA", "alice"), ("planB", "bob"), ("planC", "carol")];
+    let clean: [&[&str]; 5] = [
+        &["planA", "planB", "planC"],
+        &["planA", "planC", "planB"],
+        &["planB", "planC", "planA"],
+        &["planB", "planA", "planC"],
+        &["planC", "planB", "planA"],
+    ];
+    let duplicated: [&[&str]; 5] = [
+        &["planA", "planA", "planB", "planC"],
+        &["planA", "planC", "planC", "planB"],
+        &["planB", "planC", "planB", "planA"],
+        &["planB", "planA", "planC", "planA"],
+        &["planC", "planB", "planC", "planA"],
+    ];
+
+    let mut clean_engine = engine_with_proposals("t1", 1, &proposals, false);
+    let mut dup_engine = engine_with_proposals("t1", 1, &proposals, false);
+    for (i, (c, d)) in clean.iter().zip(duplicated.iter()).enumerate() {
+        let voter = format!("v{}", i);
+        clean_engine.record_vote(vote(&voter, "t1", 1, c)).unwrap();
+        dup_engine.record_vote(vote(&voter, "t1", 1, d)).unwrap();
+    }
+
+    let clean_result = clean_engine.run_irv().unwrap();
+    let dup_result = dup_engine.run_irv().unwrap();
+    assert_eq!(dup_result.winner, clean_result.winner);
+    assert_eq!(dup_result.elimination_order, clean_result.elimination_order);
+    assert_eq!(dup_result.final_tallies, clean_result.final_tallies);
+}
+
 // ═══════════════════════════════════════════════════════════════
 // Senate Sampling
 // ═══════════════════════════════════════════════════════════════
@@ -444,6 +618,7 @@ fn senate_restricts_eligible_voters() {
         prohibit_self_vote: false,
         min_votes: 1,
         senate_seed: Some(42),
+        ..Default::default()
     };
     let mut engine = VotingEngine::new(config, "t1".to_string(), 1);
     let mut proposals = HashMap::new();
@@ -495,3 +670,148 @@ fn proposal_and_ballot_counts() {
         .unwrap();
     assert_eq!(engine.ballot_count(), 1);
 }
+
+// ═══════════════════════════════════════════════════════════════
+// Majority basis
+// ═══════════════════════════════════════════════════════════════
+
+/// Six ballots, none ranking past its first choice: A=3, B=2, C=1.
+/// Eliminating C exhausts one ballot, leaving A with 3 of 5 active
+/// ballots but only 3 of 6 cast.
+fn exhausting_election(basis: MajorityBasis) -> VotingEngine {
+    let config = VotingConfig {
+        prohibit_self_vote: false,
+        majority_basis: basis,
+        ..Default::default()
+    };
+    let mut engine = VotingEngine::new(config, "t1".to_string(), 1);
+    engine.set_proposals(
+        [("planA", "alice"), ("planB", "bob"), ("planC", "carol")]
+            .iter()
+            .map(|(plan, proposer)| (plan.to_string(), AgentId::new(proposer.to_string())))
+            .collect(),
+    );
+    for (voter, plan) in [
+        ("v1", "planA"),
+        ("v2", "planA"),
+        ("v3", "planA"),
+        ("v4", "planB"),
+        ("v5", "planB"),
+        ("v6", "planC"),
+    ] {
+        engine.record_vote(vote(voter, "t1", 1, &[plan])).unwrap();
+    }
+    engine
+}
+
+#[test]
+fn majority_basis_defaults_to_active_ballots() {
+    assert_eq!(VotingConfig::default().majority_basis, MajorityBasis::ActiveBallots);
+}
+
+#[test]
+fn active_basis_elects_winner_after_ballots_exhaust() {
+    let mut engine = exhausting_election(MajorityBasis::ActiveBallots);
+    let result = engine.run_irv().unwrap();
+    assert_eq!(result.winner, "planA");
+    assert_eq!(result.rounds, 2);
+    assert_eq!(result.elimination_order, vec!["planC".to_string()]);
+}
+
+#[test]
+fn total_basis_fails_when_exhaustion_leaves_no_majority() {
+    let mut engine = exhausting_election(MajorityBasis::TotalBallots);
+    let err = engine.run_irv().unwrap_err();
+    assert!(matches!(err, ConsensusError::VotingError(_)));
+}
+
+#[test]
+fn bases_agree_when_no_ballot_exhausts() {
+    for basis in [MajorityBasis::ActiveBallots, MajorityBasis::TotalBallots] {
+        let config = VotingConfig {
+            prohibit_self_vote: false,
+            majority_basis: basis,
+            ..Default::default()
+        };
+        let mut engine = VotingEngine::new(config, "t1".to_string(), 1);
+        engine.set_proposals(
+            [("planA", "alice"), ("planB", "bob"), ("planC", "carol")]
+                .iter()
+                .map(|(plan, proposer)| (plan.to_string(), AgentId::new(proposer.to_string())))
+                .collect(),
+        );
+        engine.record_vote(vote("v1", "t1", 1, &["planA", "planB"])).unwrap();
+        engine.record_vote(vote("v2", "t1", 1, &["planA", "planB"])).unwrap();
+        engine.record_vote(vote("v3", "t1", 1, &["planB", "planA"])).unwrap();
+        engine.record_vote(vote("v4", "t1", 1, &["planC", "planA"])).unwrap();
+        let result = engine.run_irv().unwrap();
+        assert_eq!(result.winner, "planA", "basis {basis:?}");
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════
+// Vote collection window
+// ═══════════════════════════════════════════════════════════════
+
+fn windowed_engine(window_secs: u64) -> VotingEngine {
+    let config = VotingConfig {
+        prohibit_self_vote: false,
+        collection_window: Some(std::time::Duration::from_secs(window_secs)),
+        ..Default::default()
+    };
+    let mut engine = VotingEngine::new(config, "t1".to_string(), 1);
+    engine.set_proposals(
+        [("planA", "alice"), ("planB", "bob")]
+            .iter()
+            .map(|(plan, proposer)| (plan.to_string(), AgentId::new(proposer.to_string())))
+            .collect(),
+    );
+    engine
+}
+
+#[test]
+fn votes_within_window_count_and_engine_finalizes_at_close() {
+    let mut engine = windowed_engine(30);
+    let closes = engine.collection_closes_at().expect("window opens with the proposals");
+    let before_close = closes - chrono::Duration::seconds(1);
+
+    let early = before_close - chrono::Duration::seconds(20);
+    engine.record_vote_at(vote("v1", "t1", 1, &["planB"]), early).unwrap();
+    engine.record_vote_at(vote("v2", "t1", 1, &["planB", "planA"]), before_close).unwrap();
+    assert!(!engine.ready_to_finalize_at(before_close), "window still open");
+
+    // A ballot arriving once the window has closed is not counted.
+    let late = engine.record_vote_at(vote("v3", "t1", 1, &["planA"]), closes);
+    assert!(matches!(late, Err(ConsensusError::VotingError(_))));
+
+    assert!(engine.ready_to_finalize_at(closes));
+    let result = engine.run_irv().unwrap();
+    assert_eq!(result.winner, "planB");
+    assert_eq!(result.total_votes, 2);
+    assert!(!engine.ready_to_finalize_at(closes), "finalized engines are not re-run");
+}
+
+#[test]
+fn window_closes_early_once_every_senator_voted() {
+    let mut engine = windowed_engine(3600);
+    engine.select_senate(&[AgentId::new("v1".into()), AgentId::new("v2".into())]);
+    let now = chrono::Utc::now();
+
+    engine.record_vote_at(vote("v1", "t1", 1, &["planA"]), now).unwrap();
+    assert!(!engine.ready_to_finalize_at(now));
+    engine.record_vote_at(vote("v2", "t1", 1, &["planA"]), now).unwrap();
+    assert!(engine.all_senators_voted());
+    assert!(engine.ready_to_finalize_at(now));
+}
+
+#[test]
+fn empty_window_is_not_ready_and_no_window_never_closes() {
+    let engine = windowed_engine(0);
+    let now = chrono::Utc::now();
+    assert!(engine.is_collection_closed_at(now));
+    assert!(!engine.ready_to_finalize_at(now), "no ballots to count");
+
+    let engine = engine_with_proposals("t1", 1, &[("planA", "alice")], false);
+    assert_eq!(engine.collection_closes_at(), None);
+    assert!(!engine.is_collection_closed_at(now + chrono::Duration::days(1)));
+}
@@ -14,7 +14,7 @@
 
 use std::collections::HashMap;
 
-use wws_protocol::{AgentId, CandidacyParams, ElectionVoteParams, NodeScore};
+use wws_protocol::{AgentId, CandidacyParams, ElectionVoteParams, NodeScore, ScoreWeights};
 
 use crate::HierarchyError;
 
@@ -29,6 +29,15 @@ pub struct ElectionConfig {
     pub tier1_slots: u32,
     /// Maximum number of candidates to consider (prevents DoS).
     pub max_candidates: usize,
+    /// Minimum stake required for candidacy. When set, candidates that
+    /// report no stake are ineligible.
+    pub min_stake: Option<f64>,
+    /// Extra weight given to (normalized) stake in the composite used to
+    /// rank candidates, on top of the stake share in `score_weights`.
+    pub stake_weight: f64,
+    /// Component weights of the candidate composite score, also used for
+    /// the `min_candidacy_score` check.
+    pub score_weights: ScoreWeights,
 }
 
 impl Default for ElectionConfig {
@@ -38,10 +47,23 @@ impl Default for ElectionConfig {
             min_uptime: 0.5,
             tier1_slots: 10,
             max_candidates: 100,
+            min_stake: None,
+            stake_weight: 0.0,
+            score_weights: ScoreWeights::default(),
         }
     }
 }
 
+impl ElectionConfig {
+    /// Composite used to rank candidates: the node's composite score under
+    /// `score_weights` plus `stake_weight` times its stake (clamped to
+    /// `[0.0, 1.0]`).
+    pub fn ranking_score(&self, score: &NodeScore) -> f64 {
+        let stake = score.stake.unwrap_or(0.0).clamp(0.0, 1.0);
+        score.composite_score_with(&self.score_weights) + self.stake_weight * stake
+    }
+}
+
 /// A registered election candidate with their score.
 #[derive(Debug, Clone)]
 pub struct Candidate {
@@ -63,6 +85,16 @@ pub struct ElectionResult {
     pub total_votes: usize,
 }
 
+/// A candidate's standing in an election that is still open.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandidateTally {
+    pub agent_id: AgentId,
+    /// Ranking composite of the candidate's score.
+    pub composite: f64,
+    /// Weighted Borda points from the votes received so far.
+    pub tally: f64,
+}
+
 /// Manages the Tier-1 election process for a single epoch.
 ///
 /// The election lifecycle:
@@ -97,7 +129,8 @@ impl ElectionManager {
 
     /// Register a candidate for Tier-1 election.
     ///
-    /// Validates that the candidate meets minimum score and uptime requirements.
+    /// Validates that the candidate meets minimum score, uptime and (if
+    /// configured) stake requirements.
     pub fn register_candidate(
         &mut self,
         params: &CandidacyParams,
@@ -115,12 +148,12 @@ impl ElectionManager {
             });
         }
 
-        let composite = params.score.composite_score();
+        let base_score = params.score.composite_score_with(&self.config.score_weights);
 
-        if composite < self.config.min_candidacy_score {
+        if base_score < self.config.min_candidacy_score {
             return Err(HierarchyError::ElectionFailed(format!(
                 "Candidate score {:.3} below minimum {:.3}",
-                composite, self.config.min_candidacy_score
+                base_score, self.config.min_candidacy_score
             )));
         }
 
@@ -131,6 +164,26 @@ impl ElectionManager {
             )));
         }
 
+        if let Some(min_stake) = self.config.min_stake {
+            match params.score.stake {
+                Some(stake) if stake >= min_stake => {}
+                Some(stake) => {
+                    return Err(HierarchyError::ElectionFailed(format!(
+                        "Candidate stake {:.3} below minimum {:.3}",
+                        stake, min_stake
+                    )));
+                }
+                None => {
+                    return Err(HierarchyError::ElectionFailed(format!(
+                        "Candidate has no stake; minimum is {:.3}",
+                        min_stake
+                    )));
+                }
+            }
+        }
+
+        let composite = self.config.ranking_score(&params.score);
+
         if self.candidates.len() >= self.config.max_candidates {
             // Only replace if new candidate has higher score than the weakest.
             let weakest = self
@@ -213,6 +266,38 @@ impl ElectionManager {
             return Err(HierarchyError::NoCandidates);
         }
 
+        let tallies = self.weighted_tallies();
+        let ranked = self.rank(&tallies);
+
+        // Select the top tier1_slots candidates.
+        let leaders: Vec<AgentId> = ranked
+            .iter()
+            .take(self.config.tier1_slots as usize)
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        let result = ElectionResult {
+            epoch: self.current_epoch,
+            leaders,
+            tallies,
+            total_votes: self.votes.len(),
+        };
+
+        self.finalized = true;
+        self.result = Some(result.clone());
+
+        tracing::info!(
+            epoch = self.current_epoch,
+            leaders = result.leaders.len(),
+            votes = result.total_votes,
+            "Election completed"
+        );
+
+        Ok(result)
+    }
+
+    /// Weighted Borda points per registered candidate from the votes so far.
+    fn weighted_tallies(&self) -> HashMap<AgentId, f64> {
         let mut tallies: HashMap<AgentId, f64> = HashMap::new();
 
         // Initialize tallies for all candidates.
@@ -242,8 +327,13 @@ impl ElectionManager {
             }
         }
 
-        // Sort candidates by tally (descending), break ties by composite score.
-        let mut ranked: Vec<(AgentId, f64)> = tallies.clone().into_iter().collect();
+        tallies
+    }
+
+    /// Candidates ordered by tally (descending), ties broken by composite score.
+    fn rank(&self, tallies: &HashMap<AgentId, f64>) -> Vec<(AgentId, f64)> {
+        let mut ranked: Vec<(AgentId, f64)> =
+            tallies.iter().map(|(id, t)| (id.clone(), *t)).collect();
         ranked.sort_by(|a, b| {
             b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal).then_with(|| {
                 let score_a = self.candidates.get(&a.0).map(|c| c.composite).unwrap_or(0.0);
@@ -251,32 +341,21 @@ impl ElectionManager {
                 score_b.partial_cmp(&score_a).unwrap_or(std::cmp::Ordering::Equal)
             })
         });
+        ranked
+    }
 
-        // Select the top tier1_slots candidates.
-        let leaders: Vec<AgentId> = ranked
-            .iter()
-            .take(self.config.tier1_slots as usize)
-            .map(|(id, _)| id.clone())
-            .collect();
-
-        let result = ElectionResult {
-            epoch: self.current_epoch,
-            leaders,
-            tallies,
-            total_votes: self.votes.len(),
-        };
-
-        self.finalized = true;
-        self.result = Some(result.clone());
-
-        tracing::info!(
-            epoch = self.current_epoch,
-            leaders = result.leaders.len(),
-            votes = result.total_votes,
-            "Election completed"
-        );
-
-        Ok(result)
+    /// Running tally of every candidate, in the order `tally_and_elect`
+    /// would rank them if the election closed now.
+    pub fn live_tally(&self) -> Vec<CandidateTally> {
+        let tallies = self.weighted_tallies();
+        self.rank(&tallies)
+            .into_iter()
+            .map(|(agent_id, tally)| CandidateTally {
+                composite: self.candidates.get(&agent_id).map(|c| c.composite).unwrap_or(0.0),
+                agent_id,
+                tally,
+            })
+            .collect()
     }
 
     /// Get the election result if finalized.
@@ -304,6 +383,11 @@ impl ElectionManager {
         self.current_epoch
     }
 
+    /// Number of Tier-1 leaders this election fills.
+    pub fn tier1_slots(&self) -> u32 {
+        self.config.tier1_slots
+    }
+
     /// Update the number of Tier-1 slots (called when pyramid layout changes).
     pub fn set_tier1_slots(&mut self, slots: u32) {
         self.config.tier1_slots = slots;
@@ -387,4 +471,26 @@ mod tests {
         assert_eq!(result.leaders.len(), 2);
         assert!(result.total_votes == 2);
     }
+
+    #[test]
+    fn test_live_tally_tracks_votes_before_finalization() {
+        let mut em = ElectionManager::new(ElectionConfig::default(), 1);
+        em.register_candidate(&make_candidacy("alice", 0.9, 0.9, 1)).unwrap();
+        em.register_candidate(&make_candidacy("bob", 0.8, 0.8, 1)).unwrap();
+        assert!(em.live_tally().iter().all(|c| c.tally == 0.0));
+
+        em.record_vote(ElectionVoteParams {
+            voter: AgentId::new("voter1".into()),
+            epoch: 1,
+            candidate_rankings: vec![AgentId::new("bob".into()), AgentId::new("alice".into())],
+        }).unwrap();
+
+        let tally = em.live_tally();
+        assert_eq!(tally[0].agent_id, AgentId::new("bob".into()));
+        assert_eq!(tally[0].tally, 1.0);
+        assert_eq!(tally[1].tally, 0.0);
+        assert!(!em.is_finalized());
+        let result = em.tally_and_elect().unwrap();
+        assert_eq!(result.tallies[&tally[0].agent_id], tally[0].tally);
+    }
 }
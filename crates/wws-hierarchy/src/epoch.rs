@@ -8,19 +8,38 @@
 //! 4. Stale state is garbage-collected
 //!
 //! Epochs are numbered monotonically. The first epoch starts at 1.
+//!
+//! Epoch length is fixed by default. With [`AdaptiveEpochConfig`] set, each
+//! new epoch is shortened when many members joined or left during the last
+//! one and lengthened when membership stayed stable, within configured bounds.
+//!
+//! Until the first election completes there are no Tier-1 leaders to
+//! coordinate tasks. For that genesis period every node derives the same
+//! bootstrap leader from the member set (see
+//! [`EpochManager::bootstrap_leader`]): the configured genesis leader when it
+//! is a member, otherwise the member with the lowest ID.
 
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
 use wws_protocol::{AgentId, DEFAULT_EPOCH_DURATION_SECS};
 
 /// Configuration for epoch management.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EpochConfig {
     /// Duration of each epoch in seconds.
     pub duration_secs: u64,
     /// Grace period after epoch boundary before triggering re-election.
     /// Allows late keep-alives to arrive.
     pub grace_period_secs: u64,
+    /// Tune the epoch length to membership churn. `None` keeps every epoch
+    /// at `duration_secs`.
+    #[serde(default)]
+    pub adaptive: Option<AdaptiveEpochConfig>,
+    /// Leader used until the first election completes, when it is a
+    /// member. `None` picks the member with the lowest ID.
+    #[serde(default)]
+    pub genesis_leader: Option<AgentId>,
 }
 
 impl Default for EpochConfig {
@@ -28,12 +47,61 @@ impl Default for EpochConfig {
         Self {
             duration_secs: DEFAULT_EPOCH_DURATION_SECS,
             grace_period_secs: 10,
+            adaptive: None,
+            genesis_leader: None,
+        }
+    }
+}
+
+/// Bounds and thresholds for churn-driven epoch length.
+///
+/// Churn is the number of member joins and leaves seen during an epoch,
+/// relative to the swarm size.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdaptiveEpochConfig {
+    /// Shortest epoch the tuner may choose, in seconds.
+    pub min_duration_secs: u64,
+    /// Longest epoch the tuner may choose, in seconds.
+    pub max_duration_secs: u64,
+    /// Churn ratio at or above which the next epoch is shortened.
+    pub high_churn_ratio: f64,
+    /// Churn ratio at or below which the next epoch is lengthened.
+    pub low_churn_ratio: f64,
+    /// Factor the epoch length is divided or multiplied by per step.
+    pub step_factor: f64,
+}
+
+impl Default for AdaptiveEpochConfig {
+    fn default() -> Self {
+        Self {
+            min_duration_secs: DEFAULT_EPOCH_DURATION_SECS / 12,
+            max_duration_secs: DEFAULT_EPOCH_DURATION_SECS * 4,
+            high_churn_ratio: 0.2,
+            low_churn_ratio: 0.02,
+            step_factor: 2.0,
         }
     }
 }
 
+impl AdaptiveEpochConfig {
+    /// Length of the epoch following one of `current_secs` that saw
+    /// `churn_ratio` membership changes per member.
+    pub fn next_duration_secs(&self, current_secs: u64, churn_ratio: f64) -> u64 {
+        let factor = self.step_factor.max(1.0);
+        let next = if churn_ratio >= self.high_churn_ratio {
+            (current_secs as f64 / factor).round() as u64
+        } else if churn_ratio <= self.low_churn_ratio {
+            (current_secs as f64 * factor).round() as u64
+        } else {
+            current_secs
+        };
+        let floor = self.min_duration_secs.min(self.max_duration_secs);
+        next.clamp(floor, self.max_duration_secs)
+    }
+}
+
 /// Information about the current epoch.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EpochInfo {
     /// The epoch number (monotonically increasing, starts at 1).
     pub epoch_number: u64,
@@ -63,6 +131,7 @@ pub enum EpochAction {
 ///
 /// Tracks the current epoch, determines when boundaries are crossed,
 /// and signals upper layers to trigger re-elections.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EpochManager {
     config: EpochConfig,
     /// The current epoch info.
@@ -73,6 +142,12 @@ pub struct EpochManager {
     history: Vec<EpochInfo>,
     /// Maximum history entries to retain.
     max_history: usize,
+    /// Length of the current epoch when chosen by the adaptive tuner.
+    #[serde(default)]
+    adaptive_duration_secs: Option<u64>,
+    /// Member joins and leaves recorded during the current epoch.
+    #[serde(default)]
+    churn_events: u64,
 }
 
 impl EpochManager {
@@ -94,9 +169,22 @@ impl EpochManager {
             transition_in_progress: false,
             history: Vec::new(),
             max_history: 100,
+            adaptive_duration_secs: None,
+            churn_events: 0,
         }
     }
 
+    /// Record a member joining or leaving the swarm. Only consulted in
+    /// adaptive mode, when choosing the next epoch's length.
+    pub fn record_membership_change(&mut self) {
+        self.churn_events = self.churn_events.saturating_add(1);
+    }
+
+    /// Membership changes per member seen so far in the current epoch.
+    pub fn churn_ratio(&self, estimated_swarm_size: u64) -> f64 {
+        self.churn_events as f64 / estimated_swarm_size.max(1) as f64
+    }
+
     /// Check if the current epoch has expired and return any actions needed.
     ///
     /// Should be called periodically (e.g., every second or on each event loop tick).
@@ -150,8 +238,24 @@ impl EpochManager {
             self.history.remove(0);
         }
 
+        if let Some(adaptive) = &self.config.adaptive {
+            let size = self.current.estimated_swarm_size.max(estimated_swarm_size);
+            let next = adaptive.next_duration_secs(self.epoch_duration_secs(), self.churn_ratio(size));
+            if next != self.epoch_duration_secs() {
+                tracing::info!(
+                    churn_events = self.churn_events,
+                    swarm_size = size,
+                    from_secs = self.epoch_duration_secs(),
+                    to_secs = next,
+                    "Adjusting epoch length to membership churn"
+                );
+            }
+            self.adaptive_duration_secs = Some(next);
+        }
+        self.churn_events = 0;
+
         let now = Utc::now();
-        let duration = chrono::Duration::seconds(self.config.duration_secs as i64);
+        let duration = chrono::Duration::seconds(self.epoch_duration_secs() as i64);
         let new_epoch_number = self.current.epoch_number + 1;
 
         self.current = EpochInfo {
@@ -180,7 +284,7 @@ impl EpochManager {
         estimated_swarm_size: u64,
     ) {
         let now = Utc::now();
-        let duration = chrono::Duration::seconds(self.config.duration_secs as i64);
+        let duration = chrono::Duration::seconds(self.epoch_duration_secs() as i64);
 
         self.current = EpochInfo {
             epoch_number,
@@ -207,6 +311,35 @@ impl EpochManager {
         &self.current.tier1_leaders
     }
 
+    /// Whether any epoch so far had elected Tier-1 leaders.
+    pub fn has_elected_leaders(&self) -> bool {
+        !self.current.tier1_leaders.is_empty()
+            || self.history.iter().any(|e| !e.tier1_leaders.is_empty())
+    }
+
+    /// Leader standing in for Tier-1 before the first election completes:
+    /// the configured genesis leader if it is among `members`, otherwise the
+    /// lowest member ID. Nodes seeing the same members agree on it regardless
+    /// of order. `None` once leaders have been elected or without members.
+    pub fn bootstrap_leader<'a>(
+        &self,
+        members: impl IntoIterator<Item = &'a AgentId>,
+    ) -> Option<AgentId> {
+        if self.has_elected_leaders() {
+            return None;
+        }
+        let mut lowest: Option<&AgentId> = None;
+        for member in members {
+            if self.config.genesis_leader.as_ref() == Some(member) {
+                return Some(member.clone());
+            }
+            if lowest.is_none_or(|l| member.as_str() < l.as_str()) {
+                lowest = Some(member);
+            }
+        }
+        lowest.cloned()
+    }
+
     /// Get the remaining time in the current epoch.
     pub fn remaining_time(&self) -> chrono::Duration {
         let now = Utc::now();
@@ -217,14 +350,27 @@ impl EpochManager {
         }
     }
 
+    /// Time left before an election in progress is finalized, i.e. until
+    /// the grace period after the epoch end passes. Zero when no
+    /// transition is in progress.
+    pub fn election_remaining_time(&self) -> chrono::Duration {
+        if !self.transition_in_progress {
+            return chrono::Duration::zero();
+        }
+        let closes_at =
+            self.current.ends_at + chrono::Duration::seconds(self.config.grace_period_secs as i64);
+        (closes_at - Utc::now()).max(chrono::Duration::zero())
+    }
+
     /// Check if a transition is currently in progress.
     pub fn is_transitioning(&self) -> bool {
         self.transition_in_progress
     }
 
-    /// Get the epoch duration configuration.
+    /// Length of the current epoch in seconds: the configured duration, or
+    /// the adaptive tuner's latest choice.
     pub fn epoch_duration_secs(&self) -> u64 {
-        self.config.duration_secs
+        self.adaptive_duration_secs.unwrap_or(self.config.duration_secs)
     }
 
     /// Look up historical epoch info by epoch number.
@@ -237,12 +383,30 @@ impl EpochManager {
             .find(|e| e.epoch_number == epoch_number)
     }
 
+    /// Take over `other`'s epoch progress (current epoch, history and churn)
+    /// while keeping this manager's configuration. An adaptive length chosen
+    /// by `other` is only kept if this manager is adaptive too.
+    pub fn restore_progress(&mut self, other: EpochManager) {
+        self.current = other.current;
+        self.transition_in_progress = other.transition_in_progress;
+        self.history = other.history;
+        let excess = self.history.len().saturating_sub(self.max_history);
+        self.history.drain(..excess);
+        self.adaptive_duration_secs = match &self.config.adaptive {
+            Some(adaptive) => other
+                .adaptive_duration_secs
+                .map(|secs| secs.clamp(adaptive.min_duration_secs, adaptive.max_duration_secs)),
+            None => None,
+        };
+        self.churn_events = other.churn_events;
+    }
+
     /// Convert current epoch to the protocol Epoch type.
     pub fn to_protocol_epoch(&self) -> wws_protocol::Epoch {
         wws_protocol::Epoch {
             epoch_number: self.current.epoch_number,
             started_at: self.current.started_at,
-            duration_secs: self.config.duration_secs,
+            duration_secs: self.epoch_duration_secs(),
             tier1_leaders: self.current.tier1_leaders.clone(),
             estimated_swarm_size: self.current.estimated_swarm_size,
         }
@@ -286,4 +450,126 @@ mod tests {
         assert_eq!(proto.epoch_number, 1);
         assert_eq!(proto.duration_secs, DEFAULT_EPOCH_DURATION_SECS);
     }
+
+    fn adaptive_manager() -> EpochManager {
+        EpochManager::new(EpochConfig {
+            duration_secs: 600,
+            adaptive: Some(AdaptiveEpochConfig {
+                min_duration_secs: 200,
+                max_duration_secs: 2000,
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_high_churn_shortens_next_epoch() {
+        let mut em = adaptive_manager();
+        for _ in 0..5 {
+            em.record_membership_change();
+        }
+        em.advance_epoch(Vec::new(), 10);
+        assert_eq!(em.epoch_duration_secs(), 300);
+
+        // Sustained churn bottoms out at the floor.
+        for _ in 0..2 {
+            for _ in 0..5 {
+                em.record_membership_change();
+            }
+            em.advance_epoch(Vec::new(), 10);
+        }
+        assert_eq!(em.epoch_duration_secs(), 200);
+        assert_eq!(em.to_protocol_epoch().duration_secs, 200);
+    }
+
+    #[test]
+    fn test_stable_membership_lengthens_next_epoch() {
+        let mut em = adaptive_manager();
+        em.advance_epoch(Vec::new(), 10);
+        assert_eq!(em.epoch_duration_secs(), 1200);
+        let info = em.current_info();
+        assert_eq!((info.ends_at - info.started_at).num_seconds(), 1200);
+
+        em.advance_epoch(Vec::new(), 10);
+        assert_eq!(em.epoch_duration_secs(), 2000, "capped at max_duration_secs");
+
+        // Moderate churn keeps the length unchanged.
+        em.record_membership_change();
+        em.advance_epoch(Vec::new(), 10);
+        assert_eq!(em.epoch_duration_secs(), 2000);
+    }
+
+    #[test]
+    fn test_fixed_duration_ignores_churn() {
+        let mut em = EpochManager::default();
+        for _ in 0..50 {
+            em.record_membership_change();
+        }
+        em.advance_epoch(Vec::new(), 10);
+        assert_eq!(em.epoch_duration_secs(), DEFAULT_EPOCH_DURATION_SECS);
+    }
+
+    #[test]
+    fn test_restore_progress_keeps_local_config() {
+        let mut source = adaptive_manager();
+        for _ in 0..50 {
+            source.record_membership_change();
+        }
+        source.advance_epoch(Vec::new(), 10);
+        let chosen = source.epoch_duration_secs();
+        assert_ne!(chosen, DEFAULT_EPOCH_DURATION_SECS);
+
+        let mut fixed = EpochManager::new(EpochConfig {
+            duration_secs: 600,
+            ..Default::default()
+        });
+        fixed.restore_progress(source.clone());
+        assert_eq!(fixed.current_epoch(), 2);
+        assert_eq!(fixed.epoch_duration_secs(), 600);
+
+        let mut adaptive = adaptive_manager();
+        adaptive.restore_progress(source);
+        assert_eq!(adaptive.current_epoch(), 2);
+        assert_eq!(adaptive.epoch_duration_secs(), chosen);
+    }
+
+    #[test]
+    fn test_bootstrap_leader_is_deterministic() {
+        let members: Vec<AgentId> = ["did:swarm:c", "did:swarm:a", "did:swarm:b"]
+            .into_iter()
+            .map(|id| AgentId::new(id.into()))
+            .collect();
+        let reversed: Vec<AgentId> = members.iter().rev().cloned().collect();
+        let a = EpochManager::default();
+        let b = EpochManager::default();
+        assert_eq!(a.bootstrap_leader(&members), Some(AgentId::new("did:swarm:a".into())));
+        assert_eq!(a.bootstrap_leader(&members), b.bootstrap_leader(&reversed));
+        assert_eq!(a.bootstrap_leader(&[]), None);
+
+        let genesis = EpochManager::new(EpochConfig {
+            genesis_leader: Some(AgentId::new("did:swarm:b".into())),
+            ..Default::default()
+        });
+        assert_eq!(genesis.bootstrap_leader(&members), Some(AgentId::new("did:swarm:b".into())));
+        // An absent genesis leader falls back to the lowest ID.
+        assert_eq!(
+            genesis.bootstrap_leader(&members[..2]),
+            Some(AgentId::new("did:swarm:a".into()))
+        );
+    }
+
+    #[test]
+    fn test_election_supersedes_bootstrap_leader() {
+        let members = [AgentId::new("did:swarm:a".into()), AgentId::new("did:swarm:b".into())];
+        let mut em = EpochManager::default();
+        em.advance_epoch(Vec::new(), 2);
+        assert!(em.bootstrap_leader(&members).is_some(), "no election completed yet");
+
+        em.advance_epoch(vec![AgentId::new("did:swarm:b".into())], 2);
+        assert_eq!(em.bootstrap_leader(&members), None);
+        // Later epochs without leaders do not bring it back.
+        em.advance_epoch(Vec::new(), 2);
+        assert_eq!(em.bootstrap_leader(&members), None);
+    }
 }
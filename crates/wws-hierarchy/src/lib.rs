@@ -17,7 +17,7 @@ pub use elections::ElectionManager;
 pub use epoch::EpochManager;
 pub use geo_cluster::GeoCluster;
 pub use pyramid::PyramidAllocator;
-pub use succession::SuccessionManager;
+pub use succession::{SuccessionEvent, SuccessionManager, SuccessionPolicy};
 
 use thiserror::Error;
 
@@ -50,4 +50,7 @@ pub enum HierarchyError {
 
     #[error("Serialization error: {0}")]
     Serialization(String),
+
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
 }
@@ -21,13 +21,21 @@ pub struct PyramidConfig {
     pub branching_factor: u32,
     /// Maximum allowed hierarchy depth.
     pub max_depth: u32,
+    /// Fraction by which the swarm size must pass a `k^d` boundary before
+    /// [`PyramidAllocator::recompute`] changes the depth. Zero follows
+    /// `ceil(log_k(N))` exactly.
+    pub depth_hysteresis: f64,
 }
 
+/// Default [`PyramidConfig::depth_hysteresis`]: 10% past the boundary.
+pub const DEFAULT_DEPTH_HYSTERESIS: f64 = 0.1;
+
 impl Default for PyramidConfig {
     fn default() -> Self {
         Self {
             branching_factor: wws_protocol::DEFAULT_BRANCHING_FACTOR,
             max_depth: MAX_HIERARCHY_DEPTH,
+            depth_hysteresis: DEFAULT_DEPTH_HYSTERESIS,
         }
     }
 }
@@ -47,6 +55,102 @@ pub struct PyramidLayout {
     pub branching_factor: u32,
 }
 
+impl PyramidLayout {
+    /// Tier of the agent at `rank` (0 = highest composite score), or `None`
+    /// if the layout has no such rank.
+    pub fn tier_of_rank(&self, rank: usize) -> Option<Tier> {
+        let mut cumulative = 0u64;
+        let last_tier_idx = self.agents_per_tier.len().saturating_sub(1);
+        for (tier_idx, &count) in self.agents_per_tier.iter().enumerate() {
+            cumulative += count as u64;
+            if (rank as u64) < cumulative {
+                return Some(match tier_idx {
+                    0 if self.agents_per_tier.len() == 1 => Tier::Executor,
+                    0 => Tier::Tier0,
+                    n if n == last_tier_idx => Tier::Executor,
+                    1 => Tier::Tier1,
+                    2 => Tier::Tier2,
+                    n => Tier::TierN(n as u32),
+                });
+            }
+        }
+        None
+    }
+
+    /// Compare this layout against `previous`, rank by rank.
+    ///
+    /// A rank is promoted when its tier moves toward Tier-0 and demoted when
+    /// it moves toward the executors. Ranks that only exist on one side
+    /// (the swarm grew or shrank) are reported as added or removed.
+    pub fn diff(&self, previous: &PyramidLayout) -> LayoutDiff {
+        let mut diff = LayoutDiff::default();
+        let ranks = self.swarm_size.max(previous.swarm_size) as usize;
+        for rank in 0..ranks {
+            match (previous.tier_of_rank(rank), self.tier_of_rank(rank)) {
+                (Some(from), Some(to)) if from == to => diff.unchanged.push(rank),
+                (Some(from), Some(to)) => {
+                    let change = TierChange { rank, from, to };
+                    if to.depth() < from.depth() {
+                        diff.promoted.push(change);
+                    } else {
+                        diff.demoted.push(change);
+                    }
+                }
+                (None, Some(to)) => diff.added.push((rank, to)),
+                (Some(from), None) => diff.removed.push((rank, from)),
+                (None, None) => {}
+            }
+        }
+        diff
+    }
+}
+
+/// A rank whose tier differs between two layouts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TierChange {
+    pub rank: usize,
+    pub from: Tier,
+    pub to: Tier,
+}
+
+/// Rank-by-rank difference between two [`PyramidLayout`]s.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LayoutDiff {
+    /// Ranks moved to a higher tier.
+    pub promoted: Vec<TierChange>,
+    /// Ranks moved to a lower tier.
+    pub demoted: Vec<TierChange>,
+    /// Ranks whose tier is the same in both layouts.
+    pub unchanged: Vec<usize>,
+    /// Ranks new in this layout, with their tier.
+    pub added: Vec<(usize, Tier)>,
+    /// Ranks that no longer exist, with their previous tier.
+    pub removed: Vec<(usize, Tier)>,
+}
+
+impl LayoutDiff {
+    /// Whether every rank kept its tier.
+    pub fn is_empty(&self) -> bool {
+        self.promoted.is_empty()
+            && self.demoted.is_empty()
+            && self.added.is_empty()
+            && self.removed.is_empty()
+    }
+
+    /// Ranks that need a fresh tier assignment, in ascending order.
+    pub fn reassigned_ranks(&self) -> Vec<usize> {
+        let mut ranks: Vec<usize> = self
+            .promoted
+            .iter()
+            .chain(&self.demoted)
+            .map(|change| change.rank)
+            .chain(self.added.iter().map(|(rank, _)| *rank))
+            .collect();
+        ranks.sort_unstable();
+        ranks
+    }
+}
+
 /// Tier distribution result from the static `distribute` function.
 ///
 /// Contains the number of agents at each tier level, with index 0
@@ -90,12 +194,43 @@ impl PyramidAllocator {
         depth.clamp(1, self.config.max_depth)
     }
 
+    /// Depth to use when recomputing for `swarm_size`.
+    ///
+    /// Keeps the current layout's depth while the size is within
+    /// `depth_hysteresis` of the `k^d` boundary it crossed, so a swarm
+    /// hovering around a boundary does not flap between depths. A move of
+    /// more than one level is always taken.
+    pub fn stable_depth(&self, swarm_size: u64) -> u32 {
+        let target = self.compute_depth(swarm_size);
+        let Some(current) = self.current_layout.as_ref().map(|l| l.depth) else {
+            return target;
+        };
+        let k = self.config.branching_factor as f64;
+        let margin = self.config.depth_hysteresis.max(0.0);
+        let n = swarm_size as f64;
+        let hold = if target == current + 1 {
+            n <= k.powi(current as i32) * (1.0 + margin)
+        } else if target + 1 == current {
+            n > k.powi(target as i32) * (1.0 - margin)
+        } else {
+            false
+        };
+        if hold {
+            current
+        } else {
+            target
+        }
+    }
+
     /// Compute the full pyramid layout for a given swarm size.
     ///
     /// Returns the number of agents needed at each tier level.
     pub fn compute_layout(&self, swarm_size: u64) -> Result<PyramidLayout, HierarchyError> {
-        let depth = self.compute_depth(swarm_size);
+        self.layout_with_depth(swarm_size, self.compute_depth(swarm_size))
+    }
 
+    /// Lay out `swarm_size` agents over `depth` tiers.
+    fn layout_with_depth(&self, swarm_size: u64, depth: u32) -> Result<PyramidLayout, HierarchyError> {
         if depth > self.config.max_depth {
             return Err(HierarchyError::MaxDepthExceeded(self.config.max_depth));
         }
@@ -136,8 +271,11 @@ impl PyramidAllocator {
     }
 
     /// Recompute the layout for a new swarm size and store it.
+    ///
+    /// The depth follows [`Self::stable_depth`], so it only changes once the
+    /// size has decisively crossed a boundary.
     pub fn recompute(&mut self, swarm_size: u64) -> Result<&PyramidLayout, HierarchyError> {
-        let layout = self.compute_layout(swarm_size)?;
+        let layout = self.layout_with_depth(swarm_size, self.stable_depth(swarm_size))?;
         self.current_layout = Some(layout);
         Ok(self.current_layout.as_ref().expect("just set"))
     }
@@ -156,22 +294,7 @@ impl PyramidAllocator {
         rank: usize,
         layout: &PyramidLayout,
     ) -> Tier {
-        let mut cumulative = 0u32;
-        let last_tier_idx = layout.agents_per_tier.len().saturating_sub(1);
-        for (tier_idx, &count) in layout.agents_per_tier.iter().enumerate() {
-            cumulative += count;
-            if (rank as u32) < cumulative {
-                return match tier_idx {
-                    0 if layout.agents_per_tier.len() == 1 => Tier::Executor,
-                    0 => Tier::Tier0,
-                    n if n == last_tier_idx => Tier::Executor,
-                    1 => Tier::Tier1,
-                    2 => Tier::Tier2,
-                    n => Tier::TierN(n as u32),
-                };
-            }
-        }
-        Tier::Executor
+        layout.tier_of_rank(rank).unwrap_or(Tier::Executor)
     }
 
     /// Compute the parent assignment for an agent based on tier and branch.
@@ -7,8 +7,8 @@
 //! Succession protocol:
 //! 1. Leader sends keep-alive every KEEPALIVE_INTERVAL_SECS
 //! 2. Subordinates track last-seen timestamp for their leader
-//! 3. If timeout exceeded, the highest-scored subordinate in the branch
-//!    announces succession
+//! 3. If timeout exceeded, the subordinate ranked first by the configured
+//!    [`SuccessionPolicy`] announces succession
 //! 4. Branch agents vote to confirm the new leader
 //! 5. New leader inherits the branch and notifies the swarm
 
@@ -16,8 +16,11 @@ use std::collections::HashMap;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
-use wws_protocol::{AgentId, NodeScore, KEEPALIVE_INTERVAL_SECS, LEADER_TIMEOUT_SECS};
+use wws_protocol::{
+    AgentId, NodeScore, ScoreWeights, VivaldiCoordinates, KEEPALIVE_INTERVAL_SECS, LEADER_TIMEOUT_SECS,
+};
 
 use crate::HierarchyError;
 
@@ -55,6 +58,97 @@ pub struct SuccessionResult {
     pub epoch: u64,
 }
 
+/// How succession candidates are ranked. Ties fall back to composite score.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuccessionPolicy {
+    /// Highest composite score under the manager's [`ScoreWeights`].
+    #[default]
+    HighestScore,
+    /// Highest reputation component.
+    HighestReputation,
+    /// Closest to the branch's centroid in Vivaldi coordinates, i.e. the
+    /// lowest estimated latency to the rest of the branch. Coordinates are
+    /// the ones agents advertise, so every branch member ranks alike;
+    /// candidates without coordinates rank last.
+    LowestLatency,
+    /// Highest uptime component.
+    LongestUptime,
+}
+
+impl SuccessionPolicy {
+    /// Order `a` before `b` when it is the better successor.
+    fn compare(
+        self,
+        a: &NodeScore,
+        b: &NodeScore,
+        centroid_distances: &HashMap<AgentId, f64>,
+        weights: &ScoreWeights,
+    ) -> std::cmp::Ordering {
+        let desc = |x: f64, y: f64| y.partial_cmp(&x).unwrap_or(std::cmp::Ordering::Equal);
+        let primary = match self {
+            Self::HighestScore => std::cmp::Ordering::Equal,
+            Self::HighestReputation => desc(a.reputation, b.reputation),
+            Self::LongestUptime => desc(a.uptime, b.uptime),
+            Self::LowestLatency => {
+                match (centroid_distances.get(&a.agent_id), centroid_distances.get(&b.agent_id)) {
+                    (Some(x), Some(y)) => x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal),
+                    (Some(_), None) => std::cmp::Ordering::Less,
+                    (None, Some(_)) => std::cmp::Ordering::Greater,
+                    (None, None) => std::cmp::Ordering::Equal,
+                }
+            }
+        };
+        primary.then_with(|| desc(a.composite_score_with(weights), b.composite_score_with(weights)))
+    }
+}
+
+impl std::fmt::Display for SuccessionPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::HighestScore => "highest_score",
+            Self::HighestReputation => "highest_reputation",
+            Self::LowestLatency => "lowest_latency",
+            Self::LongestUptime => "longest_uptime",
+        })
+    }
+}
+
+impl std::str::FromStr for SuccessionPolicy {
+    type Err = HierarchyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "highest_score" => Ok(Self::HighestScore),
+            "highest_reputation" => Ok(Self::HighestReputation),
+            "lowest_latency" => Ok(Self::LowestLatency),
+            "longest_uptime" => Ok(Self::LongestUptime),
+            other => Err(HierarchyError::InvalidConfig(format!(
+                "unknown succession policy {other:?} (expected highest_score, highest_reputation, lowest_latency or longest_uptime)"
+            ))),
+        }
+    }
+}
+
+/// Maximum number of succession events retained for audit.
+pub const MAX_SUCCESSION_HISTORY: usize = 256;
+
+/// Audit record of a confirmed leader handover.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuccessionEvent {
+    /// The leader that failed.
+    pub failed: AgentId,
+    /// The agent that replaced it.
+    pub successor: AgentId,
+    /// Epoch in which the handover happened.
+    pub epoch: u64,
+    /// When the succession was confirmed.
+    pub at: DateTime<Utc>,
+    /// Policy that ranked the successor.
+    #[serde(default)]
+    pub policy: SuccessionPolicy,
+}
+
 /// Manages leader keep-alive monitoring and succession elections.
 pub struct SuccessionManager {
     /// Timeout duration before declaring a leader failed.
@@ -67,6 +161,15 @@ pub struct SuccessionManager {
     active_successions: HashMap<AgentId, Vec<SuccessionCandidate>>,
     /// Agents in each leader's branch (for succession voting).
     branches: HashMap<AgentId, Vec<AgentId>>,
+    /// Confirmed handovers, oldest first, capped at `MAX_SUCCESSION_HISTORY`.
+    history: Vec<SuccessionEvent>,
+    /// How candidates are ranked.
+    policy: SuccessionPolicy,
+    /// Advertised Vivaldi coordinates of each agent, for
+    /// `SuccessionPolicy::LowestLatency`.
+    coordinates: HashMap<AgentId, VivaldiCoordinates>,
+    /// Component weights of the composite score candidates are ranked by.
+    score_weights: ScoreWeights,
 }
 
 impl SuccessionManager {
@@ -78,6 +181,10 @@ impl SuccessionManager {
             leaders: HashMap::new(),
             active_successions: HashMap::new(),
             branches: HashMap::new(),
+            history: Vec::new(),
+            policy: SuccessionPolicy::default(),
+            coordinates: HashMap::new(),
+            score_weights: ScoreWeights::default(),
         }
     }
 
@@ -89,9 +196,62 @@ impl SuccessionManager {
             leaders: HashMap::new(),
             active_successions: HashMap::new(),
             branches: HashMap::new(),
+            history: Vec::new(),
+            policy: SuccessionPolicy::default(),
+            coordinates: HashMap::new(),
+            score_weights: ScoreWeights::default(),
         }
     }
 
+    /// Rank succession candidates with `policy`.
+    pub fn with_policy(mut self, policy: SuccessionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Rank candidates by their composite score under `weights`.
+    pub fn with_score_weights(mut self, weights: ScoreWeights) -> Self {
+        self.score_weights = weights;
+        self
+    }
+
+    /// The policy used to rank succession candidates.
+    pub fn policy(&self) -> SuccessionPolicy {
+        self.policy
+    }
+
+    /// Record the Vivaldi coordinates `agent_id` advertised.
+    pub fn record_coordinates(&mut self, agent_id: AgentId, coordinates: VivaldiCoordinates) {
+        self.coordinates.insert(agent_id, coordinates);
+    }
+
+    /// Forget the coordinates of an agent that left.
+    pub fn forget_coordinates(&mut self, agent_id: &AgentId) {
+        self.coordinates.remove(agent_id);
+    }
+
+    /// Distance of each candidate with known coordinates to the centroid
+    /// of all of them.
+    fn centroid_distances(&self, candidates: &[SuccessionCandidate]) -> HashMap<AgentId, f64> {
+        let located: Vec<(&AgentId, &VivaldiCoordinates)> = candidates
+            .iter()
+            .filter_map(|c| self.coordinates.get(&c.agent_id).map(|coords| (&c.agent_id, coords)))
+            .collect();
+        if located.is_empty() {
+            return HashMap::new();
+        }
+        let n = located.len() as f64;
+        let centroid = VivaldiCoordinates {
+            x: located.iter().map(|(_, c)| c.x).sum::<f64>() / n,
+            y: located.iter().map(|(_, c)| c.y).sum::<f64>() / n,
+            z: located.iter().map(|(_, c)| c.z).sum::<f64>() / n,
+        };
+        located
+            .into_iter()
+            .map(|(agent_id, coords)| (agent_id.clone(), coords.distance_to(&centroid)))
+            .collect()
+    }
+
     /// Register a leader to be monitored.
     pub fn monitor_leader(&mut self, leader_id: AgentId, score: Option<NodeScore>) {
         self.leaders.insert(
@@ -173,8 +333,9 @@ impl SuccessionManager {
 
     /// Initiate a succession process for a failed leader.
     ///
-    /// Collects candidates from the branch and ranks them by composite score.
-    /// The caller should broadcast a succession announcement for the top candidate.
+    /// Collects candidates from the branch and ranks them by the configured
+    /// [`SuccessionPolicy`]. The caller should broadcast a succession
+    /// announcement for the top candidate.
     pub fn initiate_succession(
         &mut self,
         failed_leader: &AgentId,
@@ -196,12 +357,14 @@ impl SuccessionManager {
             })
             .collect();
 
-        // Sort by composite score, highest first.
+        // Best candidate first.
+        let policy = self.policy;
+        let centroid_distances = match policy {
+            SuccessionPolicy::LowestLatency => self.centroid_distances(&candidates),
+            _ => HashMap::new(),
+        };
         candidates.sort_by(|a, b| {
-            b.score
-                .composite_score()
-                .partial_cmp(&a.score.composite_score())
-                .unwrap_or(std::cmp::Ordering::Equal)
+            policy.compare(&a.score, &b.score, &centroid_distances, &self.score_weights)
         });
 
         let proposed_leader = candidates[0].agent_id.clone();
@@ -210,6 +373,7 @@ impl SuccessionManager {
             failed = %failed_leader,
             proposed = %proposed_leader,
             candidates = candidates.len(),
+            policy = %policy,
             "Succession initiated"
         );
 
@@ -271,6 +435,18 @@ impl SuccessionManager {
                 epoch,
             };
 
+            self.history.push(SuccessionEvent {
+                failed: failed_leader.clone(),
+                successor: candidate_id.clone(),
+                epoch,
+                at: Utc::now(),
+                policy: self.policy,
+            });
+            if self.history.len() > MAX_SUCCESSION_HISTORY {
+                let excess = self.history.len() - MAX_SUCCESSION_HISTORY;
+                self.history.drain(..excess);
+            }
+
             // Clean up succession state.
             self.active_successions.remove(failed_leader);
             if let Some(status) = self.leaders.get_mut(failed_leader) {
@@ -312,6 +488,11 @@ impl SuccessionManager {
         self.timeout
     }
 
+    /// Confirmed handovers, oldest first.
+    pub fn history(&self) -> &[SuccessionEvent] {
+        &self.history
+    }
+
     /// Check if a succession is in progress for a leader.
     pub fn is_succession_in_progress(&self, leader_id: &AgentId) -> bool {
         self.active_successions.contains_key(leader_id)
@@ -371,4 +552,186 @@ mod tests {
         // agent1 should have the highest composite score.
         assert_eq!(proposed, AgentId::new("agent1".into()));
     }
+
+    #[test]
+    fn test_score_weights_change_successor() {
+        let leader = AgentId::new("leader1".into());
+        let pool = || {
+            vec![
+                NodeScore {
+                    agent_id: AgentId::new("trusted".into()),
+                    proof_of_compute: 0.3,
+                    reputation: 0.9,
+                    uptime: 0.6,
+                    stake: None,
+                },
+                NodeScore {
+                    agent_id: AgentId::new("steady".into()),
+                    proof_of_compute: 0.3,
+                    reputation: 0.4,
+                    uptime: 1.0,
+                    stake: None,
+                },
+            ]
+        };
+
+        let mut sm = SuccessionManager::new();
+        assert_eq!(sm.initiate_succession(&leader, pool()).unwrap().as_str(), "trusted");
+
+        let uptime_heavy = ScoreWeights {
+            proof_of_compute: 0.1,
+            reputation: 0.1,
+            uptime: 0.7,
+            stake: 0.1,
+        };
+        let mut sm = SuccessionManager::new().with_score_weights(uptime_heavy);
+        assert_eq!(sm.initiate_succession(&leader, pool()).unwrap().as_str(), "steady");
+    }
+
+    fn score(id: &str, quality: f64) -> NodeScore {
+        NodeScore {
+            agent_id: AgentId::new(id.into()),
+            proof_of_compute: quality,
+            reputation: quality,
+            uptime: 1.0,
+            stake: None,
+        }
+    }
+
+    #[test]
+    fn test_handover_appends_one_history_event() {
+        let mut sm = SuccessionManager::new();
+        let leader = AgentId::new("leader1".into());
+        let successor = AgentId::new("agent1".into());
+        sm.monitor_leader(leader.clone(), None);
+        sm.set_branch(leader.clone(), vec![successor.clone()]);
+        sm.initiate_succession(&leader, vec![score("agent1", 0.9), score("agent2", 0.5)])
+            .unwrap();
+        assert!(sm.history().is_empty());
+
+        let before = Utc::now();
+        let result = sm.record_succession_vote(&leader, &successor, 7).unwrap();
+        assert!(result.is_some());
+
+        let history = sm.history();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].failed, leader);
+        assert_eq!(history[0].successor, successor);
+        assert_eq!(history[0].epoch, 7);
+        assert!(history[0].at >= before);
+    }
+
+    #[test]
+    fn test_unconfirmed_vote_records_no_history() {
+        let mut sm = SuccessionManager::new();
+        let leader = AgentId::new("leader1".into());
+        let branch: Vec<AgentId> = (0..4).map(|i| AgentId::new(format!("agent{}", i))).collect();
+        sm.set_branch(leader.clone(), branch);
+        sm.initiate_succession(&leader, vec![score("agent0", 0.9)]).unwrap();
+
+        // One vote out of a branch of four is not a majority.
+        let result = sm
+            .record_succession_vote(&leader, &AgentId::new("agent0".into()), 1)
+            .unwrap();
+        assert!(result.is_none());
+        assert!(sm.history().is_empty());
+    }
+
+    #[test]
+    fn test_policies_pick_different_successors() {
+        let leader = AgentId::new("leader1".into());
+        let pool = || {
+            vec![
+                // Best all-rounder.
+                NodeScore {
+                    agent_id: AgentId::new("balanced".into()),
+                    proof_of_compute: 0.9,
+                    reputation: 0.7,
+                    uptime: 0.8,
+                    stake: Some(0.9),
+                },
+                NodeScore {
+                    agent_id: AgentId::new("trusted".into()),
+                    proof_of_compute: 0.2,
+                    reputation: 0.95,
+                    uptime: 0.5,
+                    stake: None,
+                },
+                NodeScore {
+                    agent_id: AgentId::new("steady".into()),
+                    proof_of_compute: 0.3,
+                    reputation: 0.4,
+                    uptime: 1.0,
+                    stake: None,
+                },
+                NodeScore {
+                    agent_id: AgentId::new("nearby".into()),
+                    proof_of_compute: 0.1,
+                    reputation: 0.1,
+                    uptime: 0.2,
+                    stake: None,
+                },
+            ]
+        };
+        let expected = [
+            (SuccessionPolicy::HighestScore, "balanced"),
+            (SuccessionPolicy::HighestReputation, "trusted"),
+            (SuccessionPolicy::LongestUptime, "steady"),
+            (SuccessionPolicy::LowestLatency, "nearby"),
+        ];
+        for (policy, winner) in expected {
+            let mut sm = SuccessionManager::new().with_policy(policy);
+            // "nearby" sits closest to the branch's centroid (0, 27.5, 0).
+            for (name, x, y) in [("balanced", 100.0, 0.0), ("trusted", -100.0, 0.0), ("steady", 0.0, 100.0), ("nearby", 0.0, 10.0)] {
+                sm.record_coordinates(AgentId::new(name.into()), VivaldiCoordinates { x, y, z: 0.0 });
+            }
+            let proposed = sm.initiate_succession(&leader, pool()).unwrap();
+            assert_eq!(proposed, AgentId::new(winner.into()), "{policy}");
+        }
+    }
+
+    #[test]
+    fn test_lowest_latency_ranks_unlocated_candidates_last() {
+        let leader = AgentId::new("leader1".into());
+        let mut sm = SuccessionManager::new().with_policy(SuccessionPolicy::LowestLatency);
+        sm.record_coordinates(AgentId::new("far".into()), VivaldiCoordinates { x: 50.0, y: 0.0, z: 0.0 });
+        sm.record_coordinates(AgentId::new("near".into()), VivaldiCoordinates { x: 10.0, y: 0.0, z: 0.0 });
+        sm.record_coordinates(AgentId::new("mid".into()), VivaldiCoordinates { x: 0.0, y: 0.0, z: 0.0 });
+        // The unlocated candidate has the best score but no coordinates.
+        let pool = vec![score("unlocated", 0.99), score("far", 0.5), score("near", 0.5), score("mid", 0.5)];
+        assert_eq!(sm.initiate_succession(&leader, pool).unwrap(), AgentId::new("near".into()));
+        let ranked: Vec<&str> = sm.active_successions[&leader].iter().map(|c| c.agent_id.as_str()).collect();
+        assert_eq!(ranked.last(), Some(&"unlocated"));
+    }
+
+    #[test]
+    fn test_history_records_policy() {
+        let leader = AgentId::new("leader1".into());
+        let successor = AgentId::new("agent1".into());
+        let mut sm = SuccessionManager::new().with_policy(SuccessionPolicy::LongestUptime);
+        sm.set_branch(leader.clone(), vec![successor.clone()]);
+        sm.initiate_succession(&leader, vec![score("agent1", 0.9)]).unwrap();
+        sm.record_succession_vote(&leader, &successor, 2).unwrap();
+        assert_eq!(sm.history()[0].policy, SuccessionPolicy::LongestUptime);
+        assert_eq!(
+            "longest_uptime".parse::<SuccessionPolicy>().unwrap(),
+            SuccessionPolicy::LongestUptime
+        );
+        assert!("fastest".parse::<SuccessionPolicy>().is_err());
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let mut sm = SuccessionManager::new();
+        let successor = AgentId::new("agent1".into());
+        for i in 0..MAX_SUCCESSION_HISTORY + 3 {
+            let leader = AgentId::new(format!("leader{}", i));
+            sm.set_branch(leader.clone(), vec![successor.clone()]);
+            sm.initiate_succession(&leader, vec![score("agent1", 0.9)]).unwrap();
+            sm.record_succession_vote(&leader, &successor, i as u64).unwrap();
+        }
+        let history = sm.history();
+        assert_eq!(history.len(), MAX_SUCCESSION_HISTORY);
+        assert_eq!(history[0].epoch, 3, "oldest events are evicted first");
+    }
 }
@@ -8,7 +8,9 @@
 use wws_hierarchy::elections::{ElectionConfig, ElectionManager};
 use wws_hierarchy::geo_cluster::GeoCluster;
 use wws_hierarchy::succession::SuccessionManager;
-use wws_protocol::{AgentId, CandidacyParams, ElectionVoteParams, NodeScore, VivaldiCoordinates};
+use wws_protocol::{
+    AgentId, CandidacyParams, ElectionVoteParams, NodeScore, ScoreWeights, VivaldiCoordinates,
+};
 
 /// Helper to build a CandidacyParams with the given scores and epoch.
 fn make_candidacy(
@@ -147,6 +149,103 @@ fn election_single_candidate() {
     assert_eq!(result.leaders[0].as_str(), "sole-agent");
 }
 
+#[test]
+fn election_min_stake_filters_under_staked_candidates() {
+    let config = ElectionConfig {
+        min_stake: Some(0.4),
+        ..Default::default()
+    };
+    let mut em = ElectionManager::new(config, 1);
+
+    em.register_candidate(&make_candidacy("staked", 0.8, 0.8, 1.0, Some(0.5), 1))
+        .unwrap();
+    em.register_candidate(&make_candidacy("exact", 0.8, 0.8, 1.0, Some(0.4), 1))
+        .unwrap();
+    assert!(
+        em.register_candidate(&make_candidacy("under", 0.9, 0.9, 1.0, Some(0.1), 1))
+            .is_err(),
+        "Stake below the minimum must be rejected"
+    );
+    assert!(
+        em.register_candidate(&make_candidacy("unstaked", 0.9, 0.9, 1.0, None, 1))
+            .is_err(),
+        "Candidates without stake are ineligible when a minimum is set"
+    );
+    assert_eq!(em.candidate_count(), 2);
+
+    em.record_vote(ElectionVoteParams {
+        voter: AgentId::new("voter1".into()),
+        epoch: 1,
+        candidate_rankings: vec![
+            AgentId::new("under".into()),
+            AgentId::new("unstaked".into()),
+            AgentId::new("staked".into()),
+            AgentId::new("exact".into()),
+        ],
+    })
+    .unwrap();
+
+    let result = em.tally_and_elect().unwrap();
+    assert_eq!(result.tallies.len(), 2);
+    assert!(!result.tallies.contains_key(&AgentId::new("under".into())));
+    assert!(!result.tallies.contains_key(&AgentId::new("unstaked".into())));
+}
+
+#[test]
+fn election_stake_weight_influences_rank() {
+    // "rich" has a slightly lower base composite but far more stake.
+    let register = |em: &mut ElectionManager| {
+        em.register_candidate(&make_candidacy("rich", 0.7, 0.7, 1.0, Some(1.0), 1))
+            .unwrap();
+        em.register_candidate(&make_candidacy("poor", 1.0, 1.0, 1.0, None, 1))
+            .unwrap();
+    };
+    let config = |stake_weight| ElectionConfig {
+        tier1_slots: 1,
+        stake_weight,
+        ..Default::default()
+    };
+
+    // Without votes the tallies tie and the ranking composite decides.
+    let mut unweighted = ElectionManager::new(config(0.0), 1);
+    register(&mut unweighted);
+    assert_eq!(unweighted.tally_and_elect().unwrap().leaders[0].as_str(), "poor");
+
+    let mut weighted = ElectionManager::new(config(0.5), 1);
+    register(&mut weighted);
+    assert_eq!(weighted.tally_and_elect().unwrap().leaders[0].as_str(), "rich");
+}
+
+#[test]
+fn score_weights_change_candidate_ranking() {
+    let register = |em: &mut ElectionManager| {
+        em.register_candidate(&make_candidacy("benchmarked", 1.0, 0.5, 0.9, None, 1))
+            .unwrap();
+        em.register_candidate(&make_candidacy("reputable", 0.4, 1.0, 0.9, None, 1))
+            .unwrap();
+    };
+    let config = |score_weights| ElectionConfig {
+        tier1_slots: 1,
+        score_weights,
+        ..Default::default()
+    };
+
+    // Default weights favour reputation (0.40) over PoC (0.25).
+    let mut default_weights = ElectionManager::new(config(ScoreWeights::default()), 1);
+    register(&mut default_weights);
+    assert_eq!(default_weights.tally_and_elect().unwrap().leaders[0].as_str(), "reputable");
+
+    let compute_heavy = ScoreWeights {
+        proof_of_compute: 0.6,
+        reputation: 0.2,
+        uptime: 0.2,
+        stake: 0.0,
+    };
+    let mut reweighted = ElectionManager::new(config(compute_heavy), 1);
+    register(&mut reweighted);
+    assert_eq!(reweighted.tally_and_elect().unwrap().leaders[0].as_str(), "benchmarked");
+}
+
 // ═══════════════════════════════════════════════════════════════
 // § 5.6 Geo-Clustering
 // ═══════════════════════════════════════════════════════════════
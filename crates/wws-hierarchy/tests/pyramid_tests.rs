@@ -5,8 +5,9 @@
 //! - Tier population distribution
 //! - Edge cases: N=1, N=k, N=k^2, etc.
 //! - Partial last tier
+//! - Layout diffs across swarm size changes
 
-use wws_hierarchy::pyramid::{PyramidAllocator, PyramidConfig};
+use wws_hierarchy::pyramid::{PyramidAllocator, PyramidConfig, TierChange};
 use wws_protocol::{Tier, MAX_HIERARCHY_DEPTH};
 
 /// Helper: create a default allocator (k=10, max_depth=MAX_HIERARCHY_DEPTH).
@@ -19,6 +20,7 @@ fn allocator_with_k(k: u32) -> PyramidAllocator {
     PyramidAllocator::new(PyramidConfig {
         branching_factor: k,
         max_depth: MAX_HIERARCHY_DEPTH,
+        ..Default::default()
     })
 }
 
@@ -274,6 +276,37 @@ fn recompute_updates_on_new_size() {
     assert_eq!(alloc.current_layout().unwrap().depth, 3);
 }
 
+#[test]
+fn recompute_holds_depth_near_boundary() {
+    // k=10, 10% hysteresis: the depth-2/3 boundary is N=100.
+    let mut alloc = default_allocator();
+    alloc.recompute(95).unwrap();
+    assert_eq!(alloc.current_layout().unwrap().depth, 2);
+    for size in [101, 98, 105, 99, 110, 100] {
+        let layout = alloc.recompute(size).unwrap();
+        assert_eq!(layout.depth, 2, "size {} should not deepen the pyramid", size);
+        assert_eq!(layout.agents_per_tier.iter().sum::<u32>() as u64, size);
+    }
+
+    // A decisive cross changes the depth, which is then held on the way back.
+    assert_eq!(alloc.recompute(111).unwrap().depth, 3);
+    for size in [99, 91, 100, 95] {
+        assert_eq!(alloc.recompute(size).unwrap().depth, 3, "size {} should keep depth 3", size);
+    }
+    assert_eq!(alloc.recompute(90).unwrap().depth, 2);
+}
+
+#[test]
+fn zero_hysteresis_follows_log_depth() {
+    let mut alloc = PyramidAllocator::new(PyramidConfig {
+        depth_hysteresis: 0.0,
+        ..Default::default()
+    });
+    alloc.recompute(100).unwrap();
+    assert_eq!(alloc.recompute(101).unwrap().depth, 3);
+    assert_eq!(alloc.recompute(100).unwrap().depth, 2);
+}
+
 #[test]
 fn layout_metadata_fields() {
     let alloc = default_allocator();
@@ -283,3 +316,87 @@ fn layout_metadata_fields() {
     assert_eq!(layout.depth, 2);
     assert_eq!(layout.tier1_count, 10);
 }
+
+// =====================================================================
+// Layout diff
+// =====================================================================
+
+#[test]
+fn diff_of_identical_layouts_is_empty() {
+    let alloc = default_allocator();
+    let layout = alloc.compute_layout(50).unwrap();
+    let diff = layout.diff(&layout);
+    assert!(diff.is_empty());
+    assert_eq!(diff.unchanged.len(), 50);
+    assert!(diff.reassigned_ranks().is_empty());
+}
+
+#[test]
+fn diff_growing_past_k_promotes_top_ranks() {
+    // k=10: 10 agents form one flat tier of executors; 11 agents split into
+    // two Tier-0 leaders (ceil(11/10)) over nine executors.
+    let alloc = default_allocator();
+    let before = alloc.compute_layout(10).unwrap();
+    let after = alloc.compute_layout(11).unwrap();
+    let diff = after.diff(&before);
+
+    assert_eq!(
+        diff.promoted,
+        vec![
+            TierChange { rank: 0, from: Tier::Executor, to: Tier::Tier0 },
+            TierChange { rank: 1, from: Tier::Executor, to: Tier::Tier0 },
+        ]
+    );
+    assert!(diff.demoted.is_empty());
+    assert_eq!(diff.unchanged, (2..10).collect::<Vec<_>>());
+    assert_eq!(diff.added, vec![(10, Tier::Executor)]);
+    assert_eq!(diff.reassigned_ranks(), vec![0, 1, 10]);
+}
+
+#[test]
+fn diff_shrinking_demotes_and_removes() {
+    let alloc = default_allocator();
+    let before = alloc.compute_layout(11).unwrap();
+    let after = alloc.compute_layout(10).unwrap();
+    let diff = after.diff(&before);
+
+    assert_eq!(diff.demoted.len(), 2);
+    assert!(diff.demoted.iter().all(|c| c.from == Tier::Tier0 && c.to == Tier::Executor));
+    assert!(diff.promoted.is_empty());
+    assert_eq!(diff.removed, vec![(10, Tier::Executor)]);
+    assert!(diff.added.is_empty());
+}
+
+#[test]
+fn diff_adding_a_tier_moves_ranks_both_ways() {
+    // k=3: 9 agents -> [Tier0 x3, Executor x6]; 10 agents -> depth 3,
+    // [Tier0 x2, Tier1 x6, Executor x2].
+    let alloc = allocator_with_k(3);
+    let before = alloc.compute_layout(9).unwrap();
+    let after = alloc.compute_layout(10).unwrap();
+    assert_eq!(before.agents_per_tier, vec![3, 6]);
+    assert_eq!(after.agents_per_tier, vec![2, 6, 2]);
+
+    let diff = after.diff(&before);
+    // The third leader drops to the new middle tier...
+    assert_eq!(
+        diff.demoted,
+        vec![TierChange { rank: 2, from: Tier::Tier0, to: Tier::Tier1 }]
+    );
+    // ...and the top five executors are promoted into it.
+    let promoted: Vec<_> = diff.promoted.iter().map(|c| (c.rank, c.from, c.to)).collect();
+    assert_eq!(
+        promoted,
+        (3..8).map(|rank| (rank, Tier::Executor, Tier::Tier1)).collect::<Vec<_>>()
+    );
+    assert_eq!(diff.unchanged, vec![0, 1, 8]);
+    assert_eq!(diff.added, vec![(9, Tier::Executor)]);
+    assert_eq!(diff.reassigned_ranks(), vec![2, 3, 4, 5, 6, 7, 9]);
+}
+
+#[test]
+fn tier_of_rank_is_none_past_swarm_size() {
+    let layout = default_allocator().compute_layout(5).unwrap();
+    assert_eq!(layout.tier_of_rank(4), Some(Tier::Executor));
+    assert_eq!(layout.tier_of_rank(5), None);
+}
@@ -11,30 +11,66 @@
 //! - **AutoNAT**: NAT traversal status detection
 //! - **Relay client**: Circuit relay for NAT traversal via public relay nodes
 //! - **DCUtR**: Direct Connection Upgrade through Relay (hole-punching)
+//! - **Peer filter**: Peer ID allow and deny lists
+//! - **Content requests**: Direct request-response retrieval of content by CID
 
+use std::num::NonZeroUsize;
 use std::time::Duration;
 
 use libp2p::{
     autonat, dcutr, gossipsub, identify, kad, mdns, ping, relay,
     identity::Keypair,
-    swarm::NetworkBehaviour,
+    swarm::{behaviour::toggle::Toggle, NetworkBehaviour},
     StreamProtocol,
 };
 
+use crate::content_request;
+use crate::peer_filter::{self, PeerFilter};
 use crate::NetworkError;
 
+/// Prefix of the Identify agent version advertised by WWS connectors:
+/// `wws-connector/<protocol version>[/name:<agent name>]`.
+pub const AGENT_VERSION_PREFIX: &str = "wws-connector/";
+
+/// Protocol version carried in a peer's Identify agent version, or `None`
+/// if the peer is not a WWS connector.
+pub fn agent_protocol_version(agent_version: &str) -> Option<&str> {
+    let rest = agent_version.strip_prefix(AGENT_VERSION_PREFIX)?;
+    let version = rest.split("/name:").next().unwrap_or(rest);
+    (!version.is_empty()).then_some(version)
+}
+
+/// Whether a peer on `protocol_version` (e.g. `/wws/1.2.0`) can interoperate
+/// with this node: same protocol family and same major version.
+pub fn is_protocol_compatible(protocol_version: &str) -> bool {
+    fn family_and_major(version: &str) -> Option<(&str, &str)> {
+        let (family, semver) = version.rsplit_once('/')?;
+        Some((family, semver.split('.').next()?))
+    }
+    match (
+        family_and_major(protocol_version),
+        family_and_major(wws_protocol::PROTOCOL_VERSION),
+    ) {
+        (Some(theirs), Some(ours)) => theirs == ours,
+        _ => false,
+    }
+}
+
 /// Composite NetworkBehaviour for a WWS node.
 ///
 /// The libp2p derive macro auto-generates a `SwarmBehaviourEvent` enum
 /// with one variant per field, used for routing events in the swarm host.
 #[derive(NetworkBehaviour)]
 pub struct SwarmBehaviour {
+    /// Peer allow and deny lists, checked before any other protocol sees
+    /// the connection.
+    pub peer_filter: peer_filter::Behaviour,
     /// Kademlia DHT for distributed routing and storage.
     pub kademlia: kad::Behaviour<kad::store::MemoryStore>,
     /// GossipSub pub/sub for broadcasting protocol messages.
     pub gossipsub: gossipsub::Behaviour,
-    /// mDNS for automatic local peer discovery.
-    pub mdns: mdns::tokio::Behaviour,
+    /// mDNS for automatic local peer discovery (disabled unless configured).
+    pub mdns: Toggle<mdns::tokio::Behaviour>,
     /// Identify protocol for exchanging peer metadata.
     pub identify: identify::Behaviour,
     /// Ping for measuring round-trip times and liveness.
@@ -45,6 +81,8 @@ pub struct SwarmBehaviour {
     pub relay_client: relay::client::Behaviour,
     /// DCUtR: Direct Connection Upgrade through Relay for hole-punching.
     pub dcutr: dcutr::Behaviour,
+    /// Direct content requests to peers holding a CID.
+    pub content_request: content_request::Behaviour,
 }
 
 /// Configuration for constructing the composite behaviour.
@@ -61,10 +99,30 @@ pub struct BehaviourConfig {
     pub gossipsub_heartbeat: Duration,
     /// Whether to use strict GossipSub validation.
     pub gossipsub_strict: bool,
+    /// GossipSub duplicate detection and message history.
+    pub gossip: GossipConfig,
+    /// Whether to run mDNS local discovery.
+    pub mdns_enabled: bool,
     /// mDNS query interval.
     pub mdns_query_interval: Duration,
-    /// Ping interval.
+    /// Interval between pings on each connection; every ping yields an RTT
+    /// sample for the Vivaldi coordinates.
     pub ping_interval: Duration,
+    /// How long an outbound ping may wait for its pong before it fails.
+    pub ping_timeout: Duration,
+    /// Timeout for a single Kademlia query (lookups, bootstrap, random walks).
+    pub kad_query_timeout: Duration,
+    /// How often stored Kademlia records are re-replicated to the closest
+    /// peers. `None` disables replication.
+    pub kad_replication_interval: Option<Duration>,
+    /// Lifetime of Kademlia records. `None` keeps records until removed.
+    pub kad_record_ttl: Option<Duration>,
+    /// Number of closest peers each Kademlia record is stored on.
+    pub kad_replication_factor: NonZeroUsize,
+    /// Peers allowed or denied a connection.
+    pub peer_filter: PeerFilter,
+    /// How long a direct content request waits for its response.
+    pub content_request_timeout: Duration,
 }
 
 impl Default for BehaviourConfig {
@@ -75,12 +133,89 @@ impl Default for BehaviourConfig {
             kad_protocol: "/wws/kad/1.0.0".to_string(),
             gossipsub_heartbeat: Duration::from_secs(1),
             gossipsub_strict: false,
+            gossip: GossipConfig::default(),
+            mdns_enabled: true,
             mdns_query_interval: Duration::from_secs(5),
             ping_interval: Duration::from_secs(1),
+            ping_timeout: Duration::from_secs(20),
+            kad_query_timeout: Duration::from_secs(60),
+            kad_replication_interval: Some(Duration::from_secs(60 * 60)),
+            kad_record_ttl: Some(Duration::from_secs(48 * 60 * 60)),
+            kad_replication_factor: kad::K_VALUE,
+            peer_filter: PeerFilter::default(),
+            content_request_timeout: content_request::DEFAULT_CONTENT_REQUEST_TIMEOUT,
+        }
+    }
+}
+
+/// GossipSub deduplication settings.
+///
+/// High-throughput swarms may need a longer duplicate cache to avoid
+/// re-processing messages that arrive late over another mesh path; small
+/// nodes may want a shorter one to save memory. Defaults match GossipSub's.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GossipConfig {
+    /// How long message IDs are remembered to drop duplicates.
+    pub duplicate_cache_time: Duration,
+    /// Number of heartbeats of message history kept for IWANT replies.
+    pub history_length: usize,
+}
+
+impl Default for GossipConfig {
+    fn default() -> Self {
+        Self {
+            duplicate_cache_time: Duration::from_secs(60),
+            history_length: 5,
         }
     }
 }
 
+/// Heartbeats of history advertised in IHAVE gossip, capped by
+/// [`GossipConfig::history_length`].
+const GOSSIP_HISTORY_ADVERTISED: usize = 3;
+
+impl BehaviourConfig {
+    /// Build the GossipSub configuration from the heartbeat, validation mode
+    /// and deduplication settings.
+    pub fn gossipsub_config(&self) -> Result<gossipsub::Config, NetworkError> {
+        gossipsub::ConfigBuilder::default()
+            .heartbeat_interval(self.gossipsub_heartbeat)
+            .validation_mode(if self.gossipsub_strict {
+                gossipsub::ValidationMode::Strict
+            } else {
+                gossipsub::ValidationMode::Permissive
+            })
+            .duplicate_cache_time(self.gossip.duplicate_cache_time)
+            .history_length(self.gossip.history_length)
+            .history_gossip(GOSSIP_HISTORY_ADVERTISED.min(self.gossip.history_length))
+            .flood_publish(true)
+            .mesh_outbound_min(1)
+            .build()
+            .map_err(|e| NetworkError::Behaviour(format!("GossipSub config error: {e}")))
+    }
+
+    /// Build the ping configuration from the interval and timeout.
+    pub fn ping_config(&self) -> ping::Config {
+        ping::Config::new()
+            .with_interval(self.ping_interval)
+            .with_timeout(self.ping_timeout)
+    }
+
+    /// Build the Kademlia configuration from the protocol name, timers and
+    /// replication factor.
+    pub fn kademlia_config(&self) -> Result<kad::Config, NetworkError> {
+        let kad_protocol = StreamProtocol::try_from_owned(self.kad_protocol.clone())
+            .map_err(|e| NetworkError::Behaviour(format!("Invalid Kademlia protocol: {e}")))?;
+        let mut kad_config = kad::Config::new(kad_protocol);
+        kad_config
+            .set_query_timeout(self.kad_query_timeout)
+            .set_replication_interval(self.kad_replication_interval)
+            .set_record_ttl(self.kad_record_ttl)
+            .set_replication_factor(self.kad_replication_factor);
+        Ok(kad_config)
+    }
+}
+
 impl SwarmBehaviour {
     /// Construct a new composite behaviour from a keypair, configuration, and relay client.
     ///
@@ -95,24 +230,10 @@ impl SwarmBehaviour {
 
         // -- Kademlia --
         let store = kad::store::MemoryStore::new(peer_id);
-        let kad_protocol = StreamProtocol::try_from_owned(config.kad_protocol.clone())
-            .map_err(|e| NetworkError::Behaviour(format!("Invalid Kademlia protocol: {e}")))?;
-        let mut kad_config = kad::Config::new(kad_protocol);
-        kad_config.set_query_timeout(Duration::from_secs(60));
-        let kademlia = kad::Behaviour::with_config(peer_id, store, kad_config);
+        let kademlia = kad::Behaviour::with_config(peer_id, store, config.kademlia_config()?);
 
         // -- GossipSub --
-        let gossipsub_config = gossipsub::ConfigBuilder::default()
-            .heartbeat_interval(config.gossipsub_heartbeat)
-            .validation_mode(if config.gossipsub_strict {
-                gossipsub::ValidationMode::Strict
-            } else {
-                gossipsub::ValidationMode::Permissive
-            })
-            .flood_publish(true)
-            .mesh_outbound_min(1)
-            .build()
-            .map_err(|e| NetworkError::Behaviour(format!("GossipSub config error: {e}")))?;
+        let gossipsub_config = config.gossipsub_config()?;
 
         let gossipsub = gossipsub::Behaviour::new(
             gossipsub::MessageAuthenticity::Signed(key.clone()),
@@ -121,20 +242,26 @@ impl SwarmBehaviour {
         .map_err(|e| NetworkError::Behaviour(format!("GossipSub init error: {e}")))?;
 
         // -- mDNS --
-        let mdns_config = mdns::Config {
-            query_interval: config.mdns_query_interval,
-            ..Default::default()
+        let mdns = if config.mdns_enabled {
+            let mdns_config = mdns::Config {
+                query_interval: config.mdns_query_interval,
+                ..Default::default()
+            };
+            Some(
+                mdns::tokio::Behaviour::new(mdns_config, peer_id)
+                    .map_err(|e| NetworkError::Behaviour(format!("mDNS init error: {e}")))?,
+            )
+        } else {
+            None
         };
-        let mdns = mdns::tokio::Behaviour::new(mdns_config, peer_id)
-            .map_err(|e| NetworkError::Behaviour(format!("mDNS init error: {e}")))?;
 
         // -- Identify --
         // Encode the agent name in the user-agent so peers learn our name
         // immediately on connect, even from brief bootstrap connections.
         let agent_version = if config.agent_name.is_empty() {
-            format!("wws-connector/{}", wws_protocol::PROTOCOL_VERSION)
+            format!("{}{}", AGENT_VERSION_PREFIX, wws_protocol::PROTOCOL_VERSION)
         } else {
-            format!("wws-connector/{}/name:{}", wws_protocol::PROTOCOL_VERSION, config.agent_name)
+            format!("{}{}/name:{}", AGENT_VERSION_PREFIX, wws_protocol::PROTOCOL_VERSION, config.agent_name)
         };
         let identify_config =
             identify::Config::new(config.protocol_version.clone(), key.public())
@@ -143,9 +270,7 @@ impl SwarmBehaviour {
         let identify = identify::Behaviour::new(identify_config);
 
         // -- Ping --
-        let ping = ping::Behaviour::new(
-            ping::Config::new().with_interval(config.ping_interval),
-        );
+        let ping = ping::Behaviour::new(config.ping_config());
 
         // -- AutoNAT --
         let autonat = autonat::Behaviour::new(peer_id, autonat::Config::default());
@@ -155,14 +280,16 @@ impl SwarmBehaviour {
         let dcutr = dcutr::Behaviour::new(peer_id);
 
         Ok(Self {
+            peer_filter: peer_filter::Behaviour::new(config.peer_filter.clone()),
             kademlia,
             gossipsub,
-            mdns,
+            mdns: Toggle::from(mdns),
             identify,
             ping,
             autonat,
             relay_client,
             dcutr,
+            content_request: content_request::behaviour(config.content_request_timeout),
         })
     }
 }
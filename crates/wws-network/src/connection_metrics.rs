@@ -0,0 +1,124 @@
+//! Per-peer connection counters for diagnosing flaky links.
+//!
+//! The swarm host feeds a [`ConnectionTracker`] from `ConnectionEstablished`,
+//! `ConnectionClosed` and `OutgoingConnectionError` events. A snapshot
+//! through [`SwarmHandle::connection_metrics`](crate::SwarmHandle::connection_metrics)
+//! lists, per peer, how many connections are open and on which addresses,
+//! and how many were established, closed or failed to dial since startup.
+//! Peers stay listed after they disconnect so their history remains visible.
+
+use std::collections::{BTreeMap, HashMap};
+
+use libp2p::swarm::ConnectionId;
+use libp2p::{Multiaddr, PeerId};
+
+/// Connection history of one peer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PeerConnectionMetrics {
+    /// Connections currently open.
+    pub open_connections: usize,
+    /// Remote addresses of the open connections, sorted.
+    pub addrs: Vec<Multiaddr>,
+    /// Connections established, inbound and outbound.
+    pub established: u64,
+    /// Outbound connections among `established`.
+    pub dials_succeeded: u64,
+    /// Outbound connection attempts to this peer that failed.
+    pub dials_failed: u64,
+    /// Connections closed.
+    pub closed: u64,
+    /// Error from the most recent failed dial.
+    pub last_dial_error: Option<String>,
+}
+
+/// Snapshot of [`ConnectionTracker`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectionMetrics {
+    /// Every peer seen, ordered by peer ID.
+    pub peers: BTreeMap<PeerId, PeerConnectionMetrics>,
+    /// Failed dials to an address whose peer ID was not known.
+    pub unattributed_dial_failures: u64,
+}
+
+#[derive(Debug, Default)]
+struct PeerEntry {
+    open: HashMap<ConnectionId, Multiaddr>,
+    metrics: PeerConnectionMetrics,
+}
+
+/// Running per-peer connection counters.
+#[derive(Debug, Default)]
+pub struct ConnectionTracker {
+    peers: HashMap<PeerId, PeerEntry>,
+    unattributed_dial_failures: u64,
+}
+
+impl ConnectionTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A connection to `peer` over `addr` was established; `outbound` if
+    /// this node dialed it.
+    pub fn record_established(
+        &mut self,
+        peer: PeerId,
+        connection: ConnectionId,
+        addr: Multiaddr,
+        outbound: bool,
+    ) {
+        let entry = self.peers.entry(peer).or_default();
+        entry.open.insert(connection, addr);
+        entry.metrics.established += 1;
+        if outbound {
+            entry.metrics.dials_succeeded += 1;
+        }
+    }
+
+    /// A connection to `peer` closed. Unknown connections only count as
+    /// closed.
+    pub fn record_closed(&mut self, peer: PeerId, connection: ConnectionId) {
+        let entry = self.peers.entry(peer).or_default();
+        entry.open.remove(&connection);
+        entry.metrics.closed += 1;
+    }
+
+    /// An outbound connection attempt failed, to `peer` if it was known.
+    pub fn record_dial_failure(&mut self, peer: Option<PeerId>, error: String) {
+        match peer {
+            Some(peer) => {
+                let metrics = &mut self.peers.entry(peer).or_default().metrics;
+                metrics.dials_failed += 1;
+                metrics.last_dial_error = Some(error);
+            }
+            None => self.unattributed_dial_failures += 1,
+        }
+    }
+
+    /// Counters for `peer`, if it has been seen.
+    pub fn peer(&self, peer: &PeerId) -> Option<PeerConnectionMetrics> {
+        self.peers.get(peer).map(Self::metrics_of)
+    }
+
+    pub fn snapshot(&self) -> ConnectionMetrics {
+        ConnectionMetrics {
+            peers: self
+                .peers
+                .iter()
+                .map(|(peer, entry)| (*peer, Self::metrics_of(entry)))
+                .collect(),
+            unattributed_dial_failures: self.unattributed_dial_failures,
+        }
+    }
+
+    fn metrics_of(entry: &PeerEntry) -> PeerConnectionMetrics {
+        let mut addrs: Vec<Multiaddr> = entry.open.values().cloned().collect();
+        addrs.sort_by_key(|a| a.to_string());
+        addrs.dedup();
+        PeerConnectionMetrics {
+            open_connections: entry.open.len(),
+            addrs,
+            ..entry.metrics.clone()
+        }
+    }
+}
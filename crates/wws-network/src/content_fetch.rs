@@ -0,0 +1,193 @@
+//! Chunked content retrieval over the Kademlia DHT.
+//!
+//! Large artifacts do not fit in a single DHT record, so they are split
+//! into fixed-size chunks. Each chunk is stored under its own CID and a
+//! `ContentManifest` listing the chunk CIDs in order is stored under the
+//! root CID:
+//!
+//! ```text
+//! root_cid  -> ContentManifest { chunk_cids: [c0, c1, ...], total_size }
+//! c0        -> bytes[0..CHUNK]
+//! c1        -> bytes[CHUNK..2*CHUNK]
+//! ```
+//!
+//! `ContentFetch` tracks one in-flight retrieval: it accepts the manifest
+//! and chunks in any order, verifies each chunk against its CID, and
+//! reports monotonic `(received, total)` progress.
+//!
+//! CIDs use the content store's encoding (see [`wws_state::format_cid`]),
+//! and are compared by their parsed digest, so the CIDv1 and legacy hex
+//! forms of the same content match.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use wws_state::{parse_cid, HashAlgo};
+
+use crate::NetworkError;
+
+/// Maximum chunk size in bytes. Kept below the default Kademlia
+/// record size limit (65 KiB) to leave room for record framing.
+pub const CONTENT_CHUNK_SIZE: usize = 60 * 1024;
+
+/// Compute the SHA-256 CIDv1 for a piece of content.
+pub fn compute_cid(data: &[u8]) -> String {
+    HashAlgo::Sha256.cid(data)
+}
+
+/// Whether `data` hashes to `cid`, using the algorithm the CID declares.
+/// Malformed CIDs never match.
+pub fn cid_matches(cid: &str, data: &[u8]) -> bool {
+    parse_cid(cid).is_ok_and(|parsed| parsed.hash_algo.digest(data) == parsed.digest)
+}
+
+/// Whether two CIDs name the same content.
+fn same_cid(a: &str, b: &str) -> bool {
+    match (parse_cid(a), parse_cid(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Ordered list of chunk CIDs making up a piece of content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentManifest {
+    /// CID of the fully assembled content.
+    pub root_cid: String,
+    /// Chunk CIDs, in assembly order.
+    pub chunk_cids: Vec<String>,
+    /// Total content size in bytes.
+    pub total_size: u64,
+}
+
+impl ContentManifest {
+    /// Split content into chunks of at most `chunk_size` bytes.
+    ///
+    /// Returns the manifest together with `(chunk_cid, chunk_bytes)` pairs
+    /// ready to be stored in the DHT.
+    pub fn split(data: &[u8], chunk_size: usize) -> (Self, Vec<(String, Vec<u8>)>) {
+        let chunks: Vec<(String, Vec<u8>)> = data
+            .chunks(chunk_size.max(1))
+            .map(|c| (compute_cid(c), c.to_vec()))
+            .collect();
+        let manifest = Self {
+            root_cid: compute_cid(data),
+            chunk_cids: chunks.iter().map(|(cid, _)| cid.clone()).collect(),
+            total_size: data.len() as u64,
+        };
+        (manifest, chunks)
+    }
+}
+
+/// Progress of a single content fetch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FetchProgress {
+    pub received_chunks: usize,
+    pub total_chunks: usize,
+}
+
+/// State for one in-flight chunked content fetch.
+#[derive(Debug)]
+pub struct ContentFetch {
+    root_cid: String,
+    manifest: Option<ContentManifest>,
+    chunks: HashMap<String, Vec<u8>>,
+}
+
+impl ContentFetch {
+    /// Start tracking a fetch for `root_cid`.
+    pub fn new(root_cid: impl Into<String>) -> Self {
+        Self {
+            root_cid: root_cid.into(),
+            manifest: None,
+            chunks: HashMap::new(),
+        }
+    }
+
+    /// The root CID being fetched.
+    pub fn root_cid(&self) -> &str {
+        &self.root_cid
+    }
+
+    /// Accept the manifest record. Returns the distinct chunk CIDs still missing.
+    pub fn on_manifest(&mut self, manifest: ContentManifest) -> Result<Vec<String>, NetworkError> {
+        if !same_cid(&manifest.root_cid, &self.root_cid) {
+            return Err(NetworkError::DhtError(format!(
+                "manifest root {} does not match requested {}",
+                manifest.root_cid, self.root_cid
+            )));
+        }
+        let mut seen = HashSet::new();
+        let missing = manifest
+            .chunk_cids
+            .iter()
+            .filter(|cid| !self.chunks.contains_key(*cid) && seen.insert(*cid))
+            .cloned()
+            .collect();
+        self.manifest = Some(manifest);
+        Ok(missing)
+    }
+
+    /// Accept a chunk. Returns the new progress if the chunk was new and valid.
+    ///
+    /// Chunks whose bytes do not hash to `cid`, that are not part of the
+    /// manifest, or that were already received are ignored, so progress
+    /// never moves backwards.
+    pub fn on_chunk(&mut self, cid: &str, data: Vec<u8>) -> Option<FetchProgress> {
+        if !cid_matches(cid, &data) || self.chunks.contains_key(cid) {
+            return None;
+        }
+        if let Some(manifest) = &self.manifest {
+            if !manifest.chunk_cids.iter().any(|c| c == cid) {
+                return None;
+            }
+        }
+        self.chunks.insert(cid.to_string(), data);
+        Some(self.progress())
+    }
+
+    /// Current progress. `total_chunks` is 0 until the manifest arrives.
+    pub fn progress(&self) -> FetchProgress {
+        let total_chunks = self.manifest.as_ref().map(|m| m.chunk_cids.len()).unwrap_or(0);
+        let received_chunks = self
+            .manifest
+            .as_ref()
+            .map(|m| m.chunk_cids.iter().filter(|c| self.chunks.contains_key(*c)).count())
+            .unwrap_or(self.chunks.len());
+        FetchProgress {
+            received_chunks,
+            total_chunks,
+        }
+    }
+
+    /// Whether the manifest and every chunk it lists have arrived.
+    pub fn is_complete(&self) -> bool {
+        match &self.manifest {
+            Some(m) => m.chunk_cids.iter().all(|c| self.chunks.contains_key(c)),
+            None => false,
+        }
+    }
+
+    /// Assemble the chunks in manifest order and verify the root CID.
+    pub fn assemble(&self) -> Result<Vec<u8>, NetworkError> {
+        let manifest = self
+            .manifest
+            .as_ref()
+            .ok_or_else(|| NetworkError::DhtError("manifest not yet received".into()))?;
+        let mut out = Vec::with_capacity(manifest.total_size as usize);
+        for cid in &manifest.chunk_cids {
+            let chunk = self
+                .chunks
+                .get(cid)
+                .ok_or_else(|| NetworkError::DhtError(format!("missing chunk {}", cid)))?;
+            out.extend_from_slice(chunk);
+        }
+        if !cid_matches(&self.root_cid, &out) {
+            return Err(NetworkError::DhtError(format!(
+                "assembled content does not match root CID {}",
+                self.root_cid
+            )));
+        }
+        Ok(out)
+    }
+}
@@ -0,0 +1,68 @@
+//! Direct content requests between peers.
+//!
+//! Chunked DHT records (see [`crate::content_fetch`]) only hold content
+//! someone explicitly published there. Every node also keeps the artifacts
+//! it produced or received in its local content store, so a peer known to
+//! hold a CID, such as a result's producer or a Kademlia provider, can
+//! simply be asked for it over the `/wws/content/1.0.0` request-response
+//! protocol.
+//!
+//! The swarm host does not own the content store: an inbound request is
+//! surfaced as [`NetworkEvent::ContentRequested`](crate::NetworkEvent) and
+//! answered through [`SwarmHandle::respond_content`](crate::SwarmHandle::respond_content).
+//! Requests left unanswered fail on the requester's side once the protocol
+//! timeout elapses.
+
+use std::time::Duration;
+
+use libp2p::request_response::{self, ProtocolSupport};
+use libp2p::StreamProtocol;
+use serde::{Deserialize, Serialize};
+
+/// Protocol name of the content request protocol.
+pub const CONTENT_PROTOCOL: &str = "/wws/content/1.0.0";
+
+/// How long a content request waits for its response by default.
+pub const DEFAULT_CONTENT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Ask a peer for the content stored under `cid`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentRequest {
+    pub cid: String,
+}
+
+/// Reply to a [`ContentRequest`]: the content, hex-encoded on the wire, or
+/// `None` if the peer does not hold it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentResponse {
+    #[serde(with = "hex_bytes")]
+    pub data: Option<Vec<u8>>,
+}
+
+/// Request-response behaviour for the content protocol.
+pub type Behaviour = request_response::json::Behaviour<ContentRequest, ContentResponse>;
+
+/// Build the content request behaviour; requests fail after `timeout`.
+pub fn behaviour(timeout: Duration) -> Behaviour {
+    Behaviour::new(
+        [(StreamProtocol::new(CONTENT_PROTOCOL), ProtocolSupport::Full)],
+        request_response::Config::default().with_request_timeout(timeout),
+    )
+}
+
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(data: &Option<Vec<u8>>, s: S) -> Result<S::Ok, S::Error> {
+        match data {
+            Some(bytes) => s.serialize_some(&hex::encode(bytes)),
+            None => s.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Vec<u8>>, D::Error> {
+        Option::<String>::deserialize(d)?
+            .map(|h| hex::decode(h).map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
@@ -1,39 +1,298 @@
-//! Peer discovery: mDNS local scanning, bootstrap nodes, DHT integration.
+//! Peer discovery: pluggable sources driven by the swarm host.
 //!
-//! This module handles the three peer discovery mechanisms:
-//! 1. **mDNS**: Automatic discovery of peers on the local network
-//! 2. **Bootstrap**: Connecting to well-known seed nodes for initial DHT population
-//! 3. **Kademlia DHT**: Ongoing peer discovery through random walks
+//! Each mechanism implements [`DiscoverySource`] and is enabled by listing
+//! it in [`DiscoveryConfig::sources`]:
+//! 1. **mDNS** ([`MdnsDiscovery`]): automatic discovery of peers on the local network
+//! 2. **Kademlia DHT** ([`KademliaDiscovery`]): bootstrap from seed nodes, then
+//!    ongoing discovery through random walks
+//! 3. **Static list** ([`StaticListDiscovery`]): dial a fixed set of addresses
+//!    and nothing else, for air-gapped deployments
 
 use std::collections::HashSet;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
 use std::time::Duration;
 
+use libp2p::kad;
+use libp2p::multiaddr::Protocol;
 use libp2p::{Multiaddr, PeerId};
 
+use crate::behaviour::BehaviourConfig;
 use crate::NetworkError;
 
+/// Kademlia behaviour the discovery sources operate on.
+pub type KademliaBehaviour = kad::Behaviour<kad::store::MemoryStore>;
+
+/// A peer discovery mechanism the swarm host drives.
+///
+/// Sources are stateless with respect to the swarm: they act on the Kademlia
+/// behaviour and return addresses for the host to dial.
+pub trait DiscoverySource: std::fmt::Debug + Send + Sync {
+    /// Short name used in logs.
+    fn name(&self) -> &'static str;
+
+    /// Whether this source needs the mDNS behaviour running.
+    fn uses_mdns(&self) -> bool {
+        false
+    }
+
+    /// Called once when the swarm starts. Returns addresses to dial.
+    fn start(&self, _kademlia: &mut KademliaBehaviour) -> Result<Vec<Multiaddr>, NetworkError> {
+        Ok(Vec::new())
+    }
+
+    /// Peers this source contacts without returning them as dials, such as
+    /// Kademlia bootstrap peers. They are registered as known on start.
+    fn seed_peers(&self) -> Vec<PeerId> {
+        Vec::new()
+    }
+
+    /// Called on every discovery interval tick.
+    fn tick(&self, _kademlia: &mut KademliaBehaviour) {}
+}
+
+/// Local network discovery via mDNS. The swarm host feeds mDNS events to
+/// [`DiscoveryManager::on_mdns_discovered`] while this source is configured.
+#[derive(Debug, Clone, Default)]
+pub struct MdnsDiscovery;
+
+impl DiscoverySource for MdnsDiscovery {
+    fn name(&self) -> &'static str {
+        "mdns"
+    }
+
+    fn uses_mdns(&self) -> bool {
+        true
+    }
+}
+
+/// Kademlia bootstrap from seed peers followed by periodic random walks.
+#[derive(Debug, Clone, Default)]
+pub struct KademliaDiscovery {
+    /// Seed peers added to the routing table before bootstrapping.
+    pub bootstrap_peers: Vec<(PeerId, Multiaddr)>,
+}
+
+impl KademliaDiscovery {
+    pub fn new(bootstrap_peers: Vec<(PeerId, Multiaddr)>) -> Self {
+        Self { bootstrap_peers }
+    }
+}
+
+impl DiscoverySource for KademliaDiscovery {
+    fn name(&self) -> &'static str {
+        "kademlia"
+    }
+
+    /// Register bootstrap peers in the routing table and start a bootstrap
+    /// query to populate the DHT.
+    fn start(&self, kademlia: &mut KademliaBehaviour) -> Result<Vec<Multiaddr>, NetworkError> {
+        for (peer_id, addr) in &self.bootstrap_peers {
+            kademlia.add_address(peer_id, addr.clone());
+            tracing::info!(
+                peer = %peer_id,
+                addr = %addr,
+                "Added bootstrap peer to Kademlia routing table"
+            );
+        }
+
+        if !self.bootstrap_peers.is_empty() {
+            kademlia
+                .bootstrap()
+                .map_err(|e| NetworkError::DhtError(format!("Bootstrap failed: {e}")))?;
+            tracing::info!("Kademlia bootstrap initiated");
+        }
+
+        Ok(Vec::new())
+    }
+
+    fn seed_peers(&self) -> Vec<PeerId> {
+        self.bootstrap_peers.iter().map(|(peer_id, _)| *peer_id).collect()
+    }
+
+    /// Query the DHT for a random PeerId, which has the side effect of
+    /// populating routing table buckets.
+    fn tick(&self, kademlia: &mut KademliaBehaviour) {
+        kademlia.get_closest_peers(PeerId::random());
+        tracing::debug!("Initiated Kademlia random walk for peer discovery");
+    }
+}
+
+/// A fixed list of peer addresses, dialed once at startup.
+///
+/// Addresses ending in `/p2p/<PeerId>` are also added to the Kademlia
+/// routing table so DHT record lookups can reach them; no bootstrap query
+/// or random walk is issued.
+#[derive(Debug, Clone, Default)]
+pub struct StaticListDiscovery {
+    pub peers: Vec<Multiaddr>,
+}
+
+impl StaticListDiscovery {
+    pub fn new(peers: Vec<Multiaddr>) -> Self {
+        Self { peers }
+    }
+}
+
+impl DiscoverySource for StaticListDiscovery {
+    fn name(&self) -> &'static str {
+        "static"
+    }
+
+    fn start(&self, kademlia: &mut KademliaBehaviour) -> Result<Vec<Multiaddr>, NetworkError> {
+        for addr in &self.peers {
+            if let Some(Protocol::P2p(peer_id)) = addr.iter().last() {
+                kademlia.add_address(&peer_id, addr.clone());
+            }
+        }
+        Ok(self.peers.clone())
+    }
+}
+
+/// Which of the standard discovery sources a node runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscoveryMode {
+    /// Kademlia from the bootstrap peers, plus mDNS unless it is turned off.
+    #[default]
+    Auto,
+    /// mDNS on the local network only; no Kademlia bootstrap or random walks.
+    MdnsOnly,
+    /// Kademlia from the bootstrap peers only, for networks without
+    /// multicast (cloud, Kubernetes). Requires at least one bootstrap peer.
+    BootstrapOnly,
+    /// Dial the bootstrap peers as a fixed list and nothing else: no mDNS,
+    /// Kademlia bootstrap query or random walks. For air-gapped deployments.
+    StaticOnly,
+}
+
+impl std::str::FromStr for DiscoveryMode {
+    type Err = NetworkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "auto" => Ok(Self::Auto),
+            "mdns_only" => Ok(Self::MdnsOnly),
+            "bootstrap_only" => Ok(Self::BootstrapOnly),
+            "static_only" => Ok(Self::StaticOnly),
+            other => Err(NetworkError::InvalidConfig(format!(
+                "unknown discovery mode {other:?} (expected auto, mdns_only, bootstrap_only or static_only)"
+            ))),
+        }
+    }
+}
+
 /// Configuration for peer discovery.
 #[derive(Debug, Clone)]
 pub struct DiscoveryConfig {
-    /// Bootstrap peer addresses to connect to on startup.
-    pub bootstrap_peers: Vec<(PeerId, Multiaddr)>,
-    /// Whether mDNS local discovery is enabled.
-    pub mdns_enabled: bool,
-    /// Interval between Kademlia random walk queries for peer discovery.
+    /// Discovery sources the swarm host drives, in order.
+    pub sources: Vec<Arc<dyn DiscoverySource>>,
+    /// Interval between discovery ticks (Kademlia random walk queries).
     pub kademlia_walk_interval: Duration,
     /// Maximum number of peers to maintain in the routing table.
     pub max_peers: usize,
+    /// Timeout for a single Kademlia query.
+    pub kademlia_query_timeout: Duration,
+    /// Interval at which stored records are re-replicated; `None` disables it.
+    pub kademlia_replication_interval: Option<Duration>,
+    /// Lifetime of Kademlia records; `None` means records never expire.
+    pub kademlia_record_ttl: Option<Duration>,
+    /// Number of peers each record (e.g. a swarm registry entry) is
+    /// replicated to. Defaults to Kademlia's `k` of 20.
+    pub kademlia_replication_factor: NonZeroUsize,
 }
 
 impl Default for DiscoveryConfig {
     fn default() -> Self {
+        Self::standard(Vec::new(), true)
+    }
+}
+
+impl DiscoveryConfig {
+    /// Kademlia with the given bootstrap peers, plus mDNS when `mdns_enabled`.
+    pub fn standard(bootstrap_peers: Vec<(PeerId, Multiaddr)>, mdns_enabled: bool) -> Self {
+        let mut sources: Vec<Arc<dyn DiscoverySource>> = Vec::new();
+        if mdns_enabled {
+            sources.push(Arc::new(MdnsDiscovery));
+        }
+        sources.push(Arc::new(KademliaDiscovery::new(bootstrap_peers)));
+        Self::with_sources(sources)
+    }
+
+    /// Sources for `mode`. `mdns_enabled` only matters in
+    /// [`DiscoveryMode::Auto`]; [`DiscoveryMode::BootstrapOnly`] fails
+    /// without bootstrap peers, since the node could never find anyone.
+    ///
+    /// `dialed_bootstrap` are bootstrap addresses without a `/p2p` peer ID,
+    /// such as those resolved from DNS TXT records. Kademlia cannot seed its
+    /// routing table from them, so the caller dials them and the swarm host
+    /// adds them once connected; they still satisfy `BootstrapOnly`.
+    /// [`DiscoveryMode::StaticOnly`] uses both kinds as its fixed list.
+    pub fn for_mode(
+        mode: DiscoveryMode,
+        bootstrap_peers: Vec<(PeerId, Multiaddr)>,
+        dialed_bootstrap: &[Multiaddr],
+        mdns_enabled: bool,
+    ) -> Result<Self, NetworkError> {
+        match mode {
+            DiscoveryMode::Auto => Ok(Self::standard(bootstrap_peers, mdns_enabled)),
+            DiscoveryMode::MdnsOnly => Ok(Self::with_sources(vec![Arc::new(MdnsDiscovery)])),
+            DiscoveryMode::BootstrapOnly
+                if bootstrap_peers.is_empty() && dialed_bootstrap.is_empty() =>
+            {
+                Err(NetworkError::InvalidConfig(
+                    "bootstrap_only discovery requires at least one bootstrap peer".into(),
+                ))
+            }
+            DiscoveryMode::BootstrapOnly => Ok(Self::standard(bootstrap_peers, false)),
+            DiscoveryMode::StaticOnly => Ok(Self::static_only(
+                bootstrap_peers
+                    .into_iter()
+                    .map(|(_, addr)| addr)
+                    .chain(dialed_bootstrap.iter().cloned())
+                    .collect(),
+            )),
+        }
+    }
+
+    /// Only dial `peers`; mDNS and Kademlia random walks are disabled.
+    pub fn static_only(peers: Vec<Multiaddr>) -> Self {
+        Self::with_sources(vec![Arc::new(StaticListDiscovery::new(peers))])
+    }
+
+    /// Use exactly the given sources with default timing and limits.
+    pub fn with_sources(sources: Vec<Arc<dyn DiscoverySource>>) -> Self {
         Self {
-            bootstrap_peers: Vec::new(),
-            mdns_enabled: true,
+            sources,
             kademlia_walk_interval: Duration::from_secs(30),
             max_peers: 1000,
+            kademlia_query_timeout: Duration::from_secs(60),
+            kademlia_replication_interval: Some(Duration::from_secs(60 * 60)),
+            kademlia_record_ttl: Some(Duration::from_secs(48 * 60 * 60)),
+            kademlia_replication_factor: kad::K_VALUE,
         }
     }
+
+    /// Copy the discovery-related settings (mDNS toggle, Kademlia timers and
+    /// replication factor) into the behaviour configuration the swarm is
+    /// built from.
+    pub fn apply_to(&self, behaviour: &mut BehaviourConfig) {
+        behaviour.mdns_enabled = self.mdns_enabled();
+        behaviour.kad_query_timeout = self.kademlia_query_timeout;
+        behaviour.kad_replication_interval = self.kademlia_replication_interval;
+        behaviour.kad_record_ttl = self.kademlia_record_ttl;
+        behaviour.kad_replication_factor = self.kademlia_replication_factor;
+    }
+
+    /// Whether any configured source needs mDNS.
+    pub fn mdns_enabled(&self) -> bool {
+        self.sources.iter().any(|s| s.uses_mdns())
+    }
+
+    /// Names of the configured sources, in order.
+    pub fn source_names(&self) -> Vec<&'static str> {
+        self.sources.iter().map(|s| s.name()).collect()
+    }
 }
 
 /// Manages peer discovery state and orchestrates the discovery process.
@@ -46,9 +305,6 @@ pub struct DiscoveryManager {
     known_peers: HashSet<PeerId>,
     /// Peers discovered via mDNS.
     mdns_peers: HashSet<PeerId>,
-    /// Peers from bootstrap configuration.
-    #[allow(dead_code)]
-    bootstrap_peers: HashSet<PeerId>,
     /// Whether the initial bootstrap has completed.
     bootstrap_complete: bool,
 }
@@ -56,44 +312,31 @@ pub struct DiscoveryManager {
 impl DiscoveryManager {
     /// Create a new discovery manager with the given configuration.
     pub fn new(config: DiscoveryConfig) -> Self {
-        let bootstrap_peers: HashSet<PeerId> =
-            config.bootstrap_peers.iter().map(|(id, _)| *id).collect();
         Self {
             config,
             known_peers: HashSet::new(),
             mdns_peers: HashSet::new(),
-            bootstrap_peers,
             bootstrap_complete: false,
         }
     }
 
-    /// Register bootstrap peers in the Kademlia routing table and start bootstrap.
+    /// Start every configured source and collect the addresses to dial.
     ///
     /// This should be called once after the swarm is created and listening.
-    /// It adds all configured bootstrap peers to Kademlia's routing table
-    /// and triggers a bootstrap query to populate the DHT.
-    pub fn initiate_bootstrap(
-        &mut self,
-        kademlia: &mut libp2p::kad::Behaviour<libp2p::kad::store::MemoryStore>,
-    ) -> Result<(), NetworkError> {
-        for (peer_id, addr) in &self.config.bootstrap_peers {
-            kademlia.add_address(peer_id, addr.clone());
-            self.known_peers.insert(*peer_id);
-            tracing::info!(
-                peer = %peer_id,
-                addr = %addr,
-                "Added bootstrap peer to Kademlia routing table"
-            );
-        }
-
-        if !self.config.bootstrap_peers.is_empty() {
-            kademlia
-                .bootstrap()
-                .map_err(|e| NetworkError::DhtError(format!("Bootstrap failed: {e}")))?;
-            tracing::info!("Kademlia bootstrap initiated");
+    pub fn start(&mut self, kademlia: &mut KademliaBehaviour) -> Result<Vec<Multiaddr>, NetworkError> {
+        let mut dials = Vec::new();
+        for source in &self.config.sources {
+            let addrs = source.start(kademlia)?;
+            tracing::info!(source = source.name(), dials = addrs.len(), "Discovery source started");
+            self.known_peers.extend(source.seed_peers());
+            for addr in &addrs {
+                if let Some(Protocol::P2p(peer_id)) = addr.iter().last() {
+                    self.known_peers.insert(peer_id);
+                }
+            }
+            dials.extend(addrs);
         }
-
-        Ok(())
+        Ok(dials)
     }
 
     /// Handle an mDNS discovered event: register newly found peers.
@@ -120,17 +363,22 @@ impl DiscoveryManager {
         tracing::info!("Kademlia bootstrap complete");
     }
 
-    /// Trigger a Kademlia random walk to discover more peers.
-    ///
-    /// This generates a random PeerId and queries the DHT for it,
-    /// which has the side effect of populating routing table buckets.
-    pub fn trigger_random_walk(
-        &self,
-        kademlia: &mut libp2p::kad::Behaviour<libp2p::kad::store::MemoryStore>,
-    ) {
-        let random_peer = PeerId::random();
-        kademlia.get_closest_peers(random_peer);
-        tracing::debug!("Initiated Kademlia random walk for peer discovery");
+    /// Give every configured source its periodic tick.
+    pub fn tick(&self, kademlia: &mut KademliaBehaviour) {
+        for source in &self.config.sources {
+            source.tick(kademlia);
+        }
+    }
+
+    /// Run the periodic discovery step now instead of waiting for the next
+    /// interval. Returns whether any source started a Kademlia query; with
+    /// only static or mDNS sources there is no walk to run.
+    pub fn trigger_random_walk(&self, kademlia: &mut KademliaBehaviour) -> bool {
+        let before = kademlia.iter_queries().count();
+        self.tick(kademlia);
+        let started = kademlia.iter_queries().count() > before;
+        tracing::debug!(started, "Random walk triggered on demand");
+        started
     }
 
     /// Register a peer discovered through any mechanism.
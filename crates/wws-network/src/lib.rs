@@ -1,27 +1,48 @@
 //! WWS Network - P2P networking layer using libp2p
 //!
 //! Provides the foundational networking for the World Wide Swarm (WWS) Protocol:
-//! - Peer discovery via mDNS and Kademlia DHT
+//! - Pluggable peer discovery (mDNS, Kademlia DHT, static peer lists)
 //! - Message passing via GossipSub pub/sub
 //! - Swarm size estimation from Kademlia routing table density
 //! - Transport configuration with TCP + Noise + Yamux
+//! - Optional private networks gated by a pre-shared key
 
 pub mod behaviour;
+pub mod connection_metrics;
+pub mod content_fetch;
+pub mod content_request;
 pub mod discovery;
 pub mod dns_bootstrap;
 pub mod name_registry;
+pub mod peer_filter;
+pub mod pnet;
+pub mod publish_retry;
 pub mod size_estimator;
 pub mod swarm_host;
 pub mod topics;
 pub mod transport;
 
 pub use behaviour::SwarmBehaviour;
-pub use discovery::DiscoveryConfig;
+pub use connection_metrics::{ConnectionMetrics, ConnectionTracker, PeerConnectionMetrics};
+pub use content_fetch::{ContentFetch, ContentManifest};
+pub use content_request::{ContentRequest, ContentResponse};
+pub use discovery::{
+    DiscoveryConfig, DiscoveryMode, DiscoverySource, KademliaDiscovery, MdnsDiscovery,
+    StaticListDiscovery,
+};
 pub use libp2p::{self, Multiaddr, PeerId};
-pub use size_estimator::SwarmSizeEstimator;
-pub use swarm_host::{NetworkEvent, SwarmHandle, SwarmHost, SwarmHostConfig};
-pub use topics::TopicManager;
-pub use transport::build_swarm;
+pub use peer_filter::{PeerFilter, PeerRejected, RejectReason};
+pub use pnet::PskFingerprint;
+pub use publish_retry::{PublishRetryConfig, PublishRetryQueue};
+pub use size_estimator::{SwarmGrowth, SwarmSizeEstimator, SwarmTrendTracker};
+pub use swarm_host::{
+    NetworkEvent, PayloadCodec, RoutingTableEntry, SwarmHandle, SwarmHost, SwarmHostConfig,
+};
+pub use topics::{
+    TopicCategory, TopicCounters, TopicHashing, TopicHashingConfig, TopicManager, TopicMeshConfig,
+    TopicPriorities, TopicPriority,
+};
+pub use transport::{build_swarm, TransportFeatures};
 
 use thiserror::Error;
 
@@ -37,6 +58,12 @@ pub enum NetworkError {
     #[error("Failed to publish message: {0}")]
     PublishError(String),
 
+    #[error("No mesh peers for topic {0}")]
+    NoMeshPeers(String),
+
+    #[error("Message too large for topic {0}")]
+    MessageTooLarge(String),
+
     #[error("Failed to subscribe to topic: {0}")]
     SubscriptionError(String),
 
@@ -49,6 +76,12 @@ pub enum NetworkError {
     #[error("DHT operation failed: {0}")]
     DhtError(String),
 
+    #[error("Content request failed: {0}")]
+    ContentRequest(String),
+
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+
     #[error("Internal channel closed")]
     ChannelClosed,
 
@@ -61,3 +94,28 @@ pub enum NetworkError {
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
 }
+
+impl NetworkError {
+    /// Map a GossipSub publish failure on `topic` to a network error.
+    ///
+    /// `InsufficientPeers` is common while the mesh is still forming and is
+    /// reported as [`NetworkError::NoMeshPeers`] so callers can retry quietly.
+    /// `MessageTooLarge` is reported as [`NetworkError::MessageTooLarge`] so
+    /// callers can split the payload.
+    pub fn from_publish_error(topic: &str, err: libp2p::gossipsub::PublishError) -> Self {
+        match err {
+            libp2p::gossipsub::PublishError::InsufficientPeers => {
+                Self::NoMeshPeers(topic.to_string())
+            }
+            libp2p::gossipsub::PublishError::MessageTooLarge => {
+                Self::MessageTooLarge(topic.to_string())
+            }
+            other => Self::PublishError(other.to_string()),
+        }
+    }
+
+    /// Whether the operation may succeed if retried later without changes.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::NoMeshPeers(_))
+    }
+}
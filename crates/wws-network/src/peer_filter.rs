@@ -0,0 +1,162 @@
+//! Peer ID allow and deny lists enforced on every connection.
+//!
+//! Private deployments can restrict which peers may connect at all,
+//! independently of swarm tokens. A [`PeerFilter`] denies a peer that is on
+//! its deny list, or that is missing from a non-empty allow list. The
+//! [`Behaviour`] in the composite swarm behaviour applies it when a
+//! connection is established, in either direction, and to outbound dials
+//! whose peer is known, so a rejected peer never reaches GossipSub or
+//! Kademlia. The swarm host reports each rejection as
+//! [`NetworkEvent::ConnectionRejected`](crate::NetworkEvent).
+
+use std::collections::BTreeSet;
+use std::convert::Infallible;
+use std::fmt;
+use std::task::{Context, Poll};
+
+use libp2p::core::transport::PortUse;
+use libp2p::core::Endpoint;
+use libp2p::swarm::{
+    dummy, ConnectionDenied, ConnectionId, FromSwarm, NetworkBehaviour, THandler,
+    THandlerInEvent, THandlerOutEvent, ToSwarm,
+};
+use libp2p::{Multiaddr, PeerId};
+
+/// Why a peer was refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RejectReason {
+    /// The peer is on the deny list.
+    Denied,
+    /// An allow list is set and the peer is not on it.
+    NotAllowed,
+}
+
+impl fmt::Display for RejectReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Denied => f.write_str("peer is on the deny list"),
+            Self::NotAllowed => f.write_str("peer is not on the allow list"),
+        }
+    }
+}
+
+/// Which peers may connect.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PeerFilter {
+    /// Peers allowed to connect; empty allows every peer not denied.
+    pub allowed: BTreeSet<PeerId>,
+    /// Peers never allowed to connect, even if also allowed.
+    pub denied: BTreeSet<PeerId>,
+}
+
+impl PeerFilter {
+    /// Whether the filter admits every peer.
+    pub fn is_open(&self) -> bool {
+        self.allowed.is_empty() && self.denied.is_empty()
+    }
+
+    /// Admit `peer`, or say why it is refused.
+    pub fn check(&self, peer: &PeerId) -> Result<(), RejectReason> {
+        if self.denied.contains(peer) {
+            Err(RejectReason::Denied)
+        } else if !self.allowed.is_empty() && !self.allowed.contains(peer) {
+            Err(RejectReason::NotAllowed)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Error a connection is denied with, recovered by the swarm host from the
+/// resulting connection error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerRejected {
+    pub peer: PeerId,
+    pub reason: RejectReason,
+}
+
+impl fmt::Display for PeerRejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "connection to {} rejected: {}", self.peer, self.reason)
+    }
+}
+
+impl std::error::Error for PeerRejected {}
+
+/// Network behaviour that denies connections refused by a [`PeerFilter`].
+#[derive(Debug, Default)]
+pub struct Behaviour {
+    filter: PeerFilter,
+}
+
+impl Behaviour {
+    pub fn new(filter: PeerFilter) -> Self {
+        Self { filter }
+    }
+
+    pub fn filter(&self) -> &PeerFilter {
+        &self.filter
+    }
+
+    fn admit(&self, peer: PeerId) -> Result<(), ConnectionDenied> {
+        self.filter
+            .check(&peer)
+            .map_err(|reason| ConnectionDenied::new(PeerRejected { peer, reason }))
+    }
+}
+
+impl NetworkBehaviour for Behaviour {
+    type ConnectionHandler = dummy::ConnectionHandler;
+    type ToSwarm = Infallible;
+
+    fn handle_established_inbound_connection(
+        &mut self,
+        _connection_id: ConnectionId,
+        peer: PeerId,
+        _local_addr: &Multiaddr,
+        _remote_addr: &Multiaddr,
+    ) -> Result<THandler<Self>, ConnectionDenied> {
+        self.admit(peer)?;
+        Ok(dummy::ConnectionHandler)
+    }
+
+    fn handle_pending_outbound_connection(
+        &mut self,
+        _connection_id: ConnectionId,
+        maybe_peer: Option<PeerId>,
+        _addresses: &[Multiaddr],
+        _effective_role: Endpoint,
+    ) -> Result<Vec<Multiaddr>, ConnectionDenied> {
+        if let Some(peer) = maybe_peer {
+            self.admit(peer)?;
+        }
+        Ok(vec![])
+    }
+
+    fn handle_established_outbound_connection(
+        &mut self,
+        _connection_id: ConnectionId,
+        peer: PeerId,
+        _addr: &Multiaddr,
+        _role_override: Endpoint,
+        _port_use: PortUse,
+    ) -> Result<THandler<Self>, ConnectionDenied> {
+        self.admit(peer)?;
+        Ok(dummy::ConnectionHandler)
+    }
+
+    fn on_swarm_event(&mut self, _event: FromSwarm) {}
+
+    fn on_connection_handler_event(
+        &mut self,
+        _peer_id: PeerId,
+        _connection_id: ConnectionId,
+        event: THandlerOutEvent<Self>,
+    ) {
+        match event {}
+    }
+
+    fn poll(&mut self, _cx: &mut Context<'_>) -> Poll<ToSwarm<Self::ToSwarm, THandlerInEvent<Self>>> {
+        Poll::Pending
+    }
+}
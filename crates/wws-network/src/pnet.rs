@@ -0,0 +1,54 @@
+//! Private networks keyed by a 32-byte pre-shared key (PSK).
+//!
+//! When [`crate::transport::TransportConfig::psk`] is set, every TCP
+//! connection is upgraded with libp2p's [`PnetConfig`] before Noise: both
+//! sides exchange random 24-byte nonces, after which each direction is
+//! encrypted with XSalsa20 keyed by the PSK, as in the libp2p private
+//! network specification (and so interoperable with go-libp2p's pnet). A
+//! peer holding a different key (or none) sees only noise and the Noise
+//! handshake fails, so nodes outside the private network can never
+//! complete a connection.
+//!
+//! QUIC carries its own handshake that cannot be wrapped this way, so it is
+//! left out of swarms built with a PSK.
+
+use std::fmt;
+
+pub use libp2p::pnet::{PnetConfig, PreSharedKey};
+use sha2::{Digest, Sha256};
+
+use crate::NetworkError;
+
+/// Parse a PSK written as 64 hex characters.
+pub fn parse_psk_hex(hex_str: &str) -> Result<[u8; 32], NetworkError> {
+    let bytes = hex::decode(hex_str.trim())
+        .map_err(|e| NetworkError::Transport(format!("invalid PSK hex: {e}")))?;
+    bytes.try_into().map_err(|bytes: Vec<u8>| {
+        NetworkError::Transport(format!("PSK must be 32 bytes, got {}", bytes.len()))
+    })
+}
+
+/// Short, non-secret identifier of a PSK.
+///
+/// Two nodes can compare fingerprints to tell whether they hold the same
+/// key without revealing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PskFingerprint([u8; 8]);
+
+impl PskFingerprint {
+    pub fn of(psk: &[u8; 32]) -> Self {
+        let digest = Sha256::new()
+            .chain_update(b"wws-pnet-fingerprint")
+            .chain_update(psk)
+            .finalize();
+        let mut fingerprint = [0u8; 8];
+        fingerprint.copy_from_slice(&digest[..8]);
+        Self(fingerprint)
+    }
+}
+
+impl fmt::Display for PskFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex::encode(self.0))
+    }
+}
@@ -0,0 +1,153 @@
+//! Bounded retry of GossipSub publishes that found no mesh peers.
+//!
+//! Right after startup, or after the last subscriber of a topic leaves, a
+//! publish fails with [`NetworkError::NoMeshPeers`](crate::NetworkError).
+//! For topic categories listed in [`PublishRetryConfig::categories`] the
+//! swarm host keeps the message in a [`PublishRetryQueue`] and re-attempts
+//! it every [`PublishRetryConfig::interval`], dropping it once
+//! [`PublishRetryConfig::max_attempts`] retries have failed. Keepalives and
+//! announcements are superseded by the next one and are not retried.
+
+use std::collections::{BTreeSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::topics::TopicCategory;
+
+/// Default number of retries before a message is dropped.
+pub const DEFAULT_PUBLISH_RETRY_ATTEMPTS: u32 = 5;
+
+/// Default delay between retries.
+pub const DEFAULT_PUBLISH_RETRY_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Default number of messages held for retry at once.
+pub const DEFAULT_PUBLISH_RETRY_CAPACITY: usize = 256;
+
+/// Which publishes are retried, how often and how many are held.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublishRetryConfig {
+    /// Retries per message before it is dropped; 0 disables retrying.
+    pub max_attempts: u32,
+    /// Delay between retries.
+    pub interval: Duration,
+    /// Messages held at once; the oldest is dropped to make room.
+    pub capacity: usize,
+    /// Topic categories whose publishes are retried.
+    pub categories: BTreeSet<TopicCategory>,
+}
+
+impl Default for PublishRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_PUBLISH_RETRY_ATTEMPTS,
+            interval: DEFAULT_PUBLISH_RETRY_INTERVAL,
+            capacity: DEFAULT_PUBLISH_RETRY_CAPACITY,
+            categories: BTreeSet::from([
+                TopicCategory::Consensus,
+                TopicCategory::Tasks,
+                TopicCategory::Results,
+                TopicCategory::Hierarchy,
+            ]),
+        }
+    }
+}
+
+impl PublishRetryConfig {
+    /// No publish is retried.
+    pub fn disabled() -> Self {
+        Self {
+            max_attempts: 0,
+            ..Default::default()
+        }
+    }
+
+    /// Whether a publish to `topic` that found no mesh peers is retried.
+    pub fn applies_to(&self, topic: &str) -> bool {
+        self.max_attempts > 0 && self.categories.contains(&TopicCategory::of(topic))
+    }
+}
+
+/// A message waiting to be published again.
+#[derive(Debug, Clone)]
+pub struct PendingPublish {
+    pub topic: String,
+    pub data: Vec<u8>,
+    /// Retries already made.
+    pub attempts: u32,
+    due: Instant,
+}
+
+/// Messages waiting for a mesh to form on their topic.
+#[derive(Debug)]
+pub struct PublishRetryQueue {
+    config: PublishRetryConfig,
+    pending: VecDeque<PendingPublish>,
+}
+
+impl PublishRetryQueue {
+    pub fn new(config: PublishRetryConfig) -> Self {
+        Self {
+            config,
+            pending: VecDeque::new(),
+        }
+    }
+
+    pub fn config(&self) -> &PublishRetryConfig {
+        &self.config
+    }
+
+    /// Number of messages waiting.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Hold a message whose first publish to `topic` found no mesh peers.
+    /// Returns `false` if publishes to `topic` are not retried.
+    pub fn push(&mut self, topic: &str, data: Vec<u8>, now: Instant) -> bool {
+        if !self.config.applies_to(topic) {
+            return false;
+        }
+        if self.pending.len() >= self.config.capacity.max(1) {
+            if let Some(dropped) = self.pending.pop_front() {
+                tracing::warn!(topic = %dropped.topic, "Publish retry queue full, dropping oldest message");
+            }
+        }
+        self.pending.push_back(PendingPublish {
+            topic: topic.to_string(),
+            data,
+            attempts: 0,
+            due: now + self.config.interval,
+        });
+        true
+    }
+
+    /// Remove and return the messages due for a retry at `now`, oldest first.
+    pub fn take_due(&mut self, now: Instant) -> Vec<PendingPublish> {
+        let (due, waiting): (VecDeque<_>, VecDeque<_>) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|p| p.due <= now);
+        self.pending = waiting;
+        due.into()
+    }
+
+    /// Put back a message whose retry found no mesh peers again. Returns
+    /// `false`, dropping it, once it has used all its retries.
+    pub fn retry_later(&mut self, mut publish: PendingPublish, now: Instant) -> bool {
+        publish.attempts += 1;
+        if publish.attempts >= self.config.max_attempts {
+            return false;
+        }
+        publish.due = now + self.config.interval;
+        self.pending.push_back(publish);
+        true
+    }
+}
+
+impl Default for PublishRetryQueue {
+    fn default() -> Self {
+        Self::new(PublishRetryConfig::default())
+    }
+}
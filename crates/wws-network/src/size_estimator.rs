@@ -14,8 +14,23 @@
 //!
 //! By observing the non-empty buckets closest to our ID, we can
 //! estimate N by inverting this relationship.
+//!
+//! Each raw estimate is also fed to a [`SwarmTrendTracker`], which fits a
+//! line through the recent samples to tell whether the swarm is growing,
+//! stable, or shrinking. A trend only counts once the window agrees with
+//! and without the newest sample, so one noisy reading cannot flip it.
 
 use std::collections::VecDeque;
+use std::time::Instant;
+
+use wws_protocol::SwarmTrend;
+
+/// Size change per minute, as a fraction of the mean sampled size, at or
+/// beyond which the swarm counts as growing or shrinking.
+pub const TREND_THRESHOLD_PER_MIN: f64 = 0.05;
+
+/// Samples needed before any trend other than `Stable` is reported.
+const MIN_TREND_SAMPLES: usize = 3;
 
 /// Estimates the total swarm size from Kademlia routing table observations.
 ///
@@ -30,6 +45,8 @@ pub struct SwarmSizeEstimator {
     current_estimate: u64,
     /// Minimum plausible network size (always at least 1: ourselves).
     min_size: u64,
+    /// Timestamped raw estimates for growth tracking.
+    trend: SwarmTrendTracker,
 }
 
 impl SwarmSizeEstimator {
@@ -40,6 +57,7 @@ impl SwarmSizeEstimator {
             window_size,
             current_estimate: 1,
             min_size: 1,
+            trend: SwarmTrendTracker::new(window_size),
         }
     }
 
@@ -55,13 +73,19 @@ impl SwarmSizeEstimator {
     pub fn update_from_buckets(&mut self, bucket_populations: &[usize]) {
         let estimate = self.estimate_from_buckets(bucket_populations);
 
+        self.record(estimate);
+
+        // Compute the median of recent estimates for robustness.
+        self.current_estimate = self.compute_median();
+    }
+
+    /// Add a raw estimate to the smoothing window and the trend tracker.
+    fn record(&mut self, estimate: u64) {
         self.recent_estimates.push_back(estimate);
         if self.recent_estimates.len() > self.window_size {
             self.recent_estimates.pop_front();
         }
-
-        // Compute the median of recent estimates for robustness.
-        self.current_estimate = self.compute_median();
+        self.trend.observe(Instant::now(), estimate);
     }
 
     /// Compute a raw estimate from bucket populations.
@@ -138,16 +162,19 @@ impl SwarmSizeEstimator {
             (est.round() as u64).max(self.min_size)
         };
 
-        self.recent_estimates.push_back(estimate);
-        if self.recent_estimates.len() > self.window_size {
-            self.recent_estimates.pop_front();
-        }
+        self.record(estimate);
         self.current_estimate = self.compute_median();
     }
 
+    /// Growth rate and trend over the recent estimates.
+    pub fn growth(&self) -> SwarmGrowth {
+        self.trend.growth()
+    }
+
     /// Reset the estimator, clearing all history.
     pub fn reset(&mut self) {
         self.recent_estimates.clear();
+        self.trend = SwarmTrendTracker::new(self.window_size);
         self.current_estimate = self.min_size;
     }
 }
@@ -158,6 +185,100 @@ impl Default for SwarmSizeEstimator {
     }
 }
 
+/// How the swarm size moved over the recent sample window.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SwarmGrowth {
+    /// Least-squares slope of the sampled sizes, in agents per minute.
+    pub growth_rate_per_min: f64,
+    pub trend: SwarmTrend,
+    /// The newest sample fell below the one before it without the window
+    /// confirming a shrink, i.e. possibly a single noisy reading.
+    pub transient_dip: bool,
+}
+
+/// Classifies swarm growth from a bounded window of timestamped sizes.
+#[derive(Debug, Clone)]
+pub struct SwarmTrendTracker {
+    samples: VecDeque<(Instant, u64)>,
+    window_size: usize,
+}
+
+impl SwarmTrendTracker {
+    pub fn new(window_size: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(window_size),
+            window_size: window_size.max(MIN_TREND_SAMPLES),
+        }
+    }
+
+    /// Record the swarm size observed at `at`.
+    pub fn observe(&mut self, at: Instant, size: u64) {
+        self.samples.push_back((at, size));
+        if self.samples.len() > self.window_size {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn growth(&self) -> SwarmGrowth {
+        let samples: Vec<(Instant, u64)> = self.samples.iter().copied().collect();
+        let Some((rate, mean)) = fit_per_min(&samples) else {
+            return SwarmGrowth::default();
+        };
+        let mut trend = SwarmTrend::Stable;
+        if samples.len() >= MIN_TREND_SAMPLES {
+            // Require the window without its newest sample to agree, so a
+            // single outlier at the end cannot establish a trend by itself.
+            let prior = fit_per_min(&samples[..samples.len() - 1]);
+            let current = classify(rate, mean);
+            if prior.map(|(rate, mean)| classify(rate, mean)) == Some(current) {
+                trend = current;
+            }
+        }
+        let dropped = match samples[..] {
+            [.., (_, previous), (_, latest)] => latest < previous,
+            _ => false,
+        };
+        SwarmGrowth {
+            growth_rate_per_min: rate,
+            trend,
+            transient_dip: dropped && trend != SwarmTrend::Shrinking,
+        }
+    }
+}
+
+/// Least-squares slope (agents per minute) and mean size of `samples`, or
+/// `None` when they do not span any time.
+fn fit_per_min(samples: &[(Instant, u64)]) -> Option<(f64, f64)> {
+    let (origin, _) = *samples.first()?;
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .map(|&(at, size)| (at.duration_since(origin).as_secs_f64() / 60.0, size as f64))
+        .collect();
+    let n = points.len() as f64;
+    let mean_t = points.iter().map(|(t, _)| t).sum::<f64>() / n;
+    let mean_size = points.iter().map(|(_, s)| s).sum::<f64>() / n;
+    let var_t: f64 = points.iter().map(|(t, _)| (t - mean_t).powi(2)).sum();
+    if var_t < f64::EPSILON {
+        return None;
+    }
+    let cov: f64 = points
+        .iter()
+        .map(|(t, s)| (t - mean_t) * (s - mean_size))
+        .sum();
+    Some((cov / var_t, mean_size))
+}
+
+fn classify(rate_per_min: f64, mean_size: f64) -> SwarmTrend {
+    let relative = rate_per_min / mean_size.max(1.0);
+    if relative >= TREND_THRESHOLD_PER_MIN {
+        SwarmTrend::Growing
+    } else if relative <= -TREND_THRESHOLD_PER_MIN {
+        SwarmTrend::Shrinking
+    } else {
+        SwarmTrend::Stable
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -207,4 +328,48 @@ mod tests {
         assert!(est > 10);
         assert!(est < 200);
     }
+
+    fn tracker_with(sizes: &[u64]) -> SwarmTrendTracker {
+        let t0 = Instant::now();
+        let mut tracker = SwarmTrendTracker::new(10);
+        for (i, &size) in sizes.iter().enumerate() {
+            tracker.observe(t0 + std::time::Duration::from_secs(30 * i as u64), size);
+        }
+        tracker
+    }
+
+    #[test]
+    fn test_trend_growing_and_shrinking() {
+        let growing = tracker_with(&[10, 12, 14, 16, 18]).growth();
+        assert_eq!(growing.trend, SwarmTrend::Growing);
+        assert!((growing.growth_rate_per_min - 4.0).abs() < 1e-9);
+
+        let shrinking = tracker_with(&[20, 18, 16, 14, 12]).growth();
+        assert_eq!(shrinking.trend, SwarmTrend::Shrinking);
+        assert!((shrinking.growth_rate_per_min + 4.0).abs() < 1e-9);
+        assert!(!shrinking.transient_dip);
+    }
+
+    #[test]
+    fn test_trend_stable_with_noise() {
+        let flat = tracker_with(&[20, 21, 20, 19, 20, 20]).growth();
+        assert_eq!(flat.trend, SwarmTrend::Stable);
+
+        // Too few samples to call a trend yet.
+        assert_eq!(tracker_with(&[10, 20]).growth().trend, SwarmTrend::Stable);
+        assert_eq!(tracker_with(&[]).growth(), SwarmGrowth::default());
+    }
+
+    #[test]
+    fn test_single_dip_is_not_a_shrink() {
+        let dip = tracker_with(&[20, 20, 20, 20, 10]).growth();
+        assert_eq!(dip.trend, SwarmTrend::Stable);
+        assert!(dip.growth_rate_per_min < 0.0);
+        assert!(dip.transient_dip);
+
+        // Once the lower size holds, the shrink is confirmed.
+        let sustained = tracker_with(&[20, 20, 20, 10, 10]).growth();
+        assert_eq!(sustained.trend, SwarmTrend::Shrinking);
+        assert!(!sustained.transient_dip);
+    }
 }
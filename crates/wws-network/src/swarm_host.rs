@@ -8,22 +8,35 @@
 //! - Communication between the handle and the host uses bounded MPSC channels
 //!   for commands and a broadcast-style channel for events.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
 use futures::StreamExt;
-use libp2p::gossipsub::IdentTopic;
-use libp2p::swarm::SwarmEvent;
+use libp2p::swarm::dial_opts::DialOpts;
+use libp2p::swarm::{ConnectionDenied, ConnectionId, DialError, ListenError, SwarmEvent};
+use libp2p::request_response::{self, InboundRequestId, OutboundRequestId, ResponseChannel};
 use libp2p::{gossipsub, identify, kad, mdns, ping, Multiaddr, PeerId, Swarm};
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{broadcast, mpsc, oneshot};
 
 use crate::behaviour::{SwarmBehaviour, SwarmBehaviourEvent};
+use crate::connection_metrics::{ConnectionMetrics, ConnectionTracker};
+use crate::content_fetch::{ContentFetch, ContentManifest};
+use crate::content_request::{ContentRequest, ContentResponse};
 use crate::discovery::{DiscoveryConfig, DiscoveryManager};
-use crate::size_estimator::SwarmSizeEstimator;
-use crate::topics::TopicManager;
+use crate::peer_filter::{PeerRejected, RejectReason};
+use crate::publish_retry::{PublishRetryConfig, PublishRetryQueue};
+use crate::size_estimator::{SwarmGrowth, SwarmSizeEstimator};
+use crate::topics::{
+    TopicCounters, TopicHashingConfig, TopicManager, TopicPriorities, TopicPriority,
+};
 use crate::transport::{self, TransportConfig};
 use crate::NetworkError;
 
+/// Overall deadline for [`SwarmHandle::fetch_content`], covering the
+/// manifest lookup and every chunk.
+pub const CONTENT_FETCH_TIMEOUT: Duration = Duration::from_secs(60);
+
 // ---------------------------------------------------------------------------
 // Configuration
 // ---------------------------------------------------------------------------
@@ -31,8 +44,9 @@ use crate::NetworkError;
 /// Configuration for the SwarmHost.
 #[derive(Debug, Clone)]
 pub struct SwarmHostConfig {
-    /// Address to listen on (e.g. "/ip4/0.0.0.0/tcp/0").
-    pub listen_addr: Multiaddr,
+    /// Addresses to listen on (e.g. "/ip4/0.0.0.0/tcp/0" and
+    /// "/ip6/::/tcp/0"). `SwarmHost::new` calls `listen_on` for each.
+    pub listen_addrs: Vec<Multiaddr>,
     /// Transport configuration.
     pub transport: TransportConfig,
     /// Discovery configuration.
@@ -41,8 +55,20 @@ pub struct SwarmHostConfig {
     pub command_buffer: usize,
     /// Event channel buffer size.
     pub event_buffer: usize,
+    /// Per-subscriber buffer for [`SwarmHandle::subscribe_events`]. Slow
+    /// subscribers that fall further behind miss the oldest events.
+    pub event_broadcast_buffer: usize,
     /// Interval between Kademlia random walks.
     pub random_walk_interval: Duration,
+    /// How often intended GossipSub subscriptions are checked and any
+    /// dropped topic re-subscribed. `None` disables the check.
+    pub resubscribe_interval: Option<Duration>,
+    /// Topic categories published and meshed with high priority.
+    pub topic_priorities: TopicPriorities,
+    /// How topic names are hashed into GossipSub topics, per swarm.
+    pub topic_hashing: TopicHashingConfig,
+    /// Retrying of publishes made before a topic's mesh formed.
+    pub publish_retry: PublishRetryConfig,
     /// Persistent identity keypair.  When `Some`, the swarm reuses this
     /// key so the PeerId survives restarts.  When `None`, a fresh random
     /// Ed25519 keypair is generated (non-deterministic PeerId).
@@ -52,19 +78,34 @@ pub struct SwarmHostConfig {
 impl Default for SwarmHostConfig {
     fn default() -> Self {
         Self {
-            listen_addr: "/ip4/0.0.0.0/tcp/0"
+            listen_addrs: vec!["/ip4/0.0.0.0/tcp/0"
                 .parse()
-                .expect("valid default listen addr"),
+                .expect("valid default listen addr")],
             transport: TransportConfig::default(),
             discovery: DiscoveryConfig::default(),
             command_buffer: 256,
             event_buffer: 256,
+            event_broadcast_buffer: 1024,
             random_walk_interval: Duration::from_secs(30),
+            resubscribe_interval: Some(Duration::from_secs(30)),
+            topic_priorities: TopicPriorities::default(),
+            topic_hashing: TopicHashingConfig::default(),
+            publish_retry: PublishRetryConfig::default(),
             keypair: None,
         }
     }
 }
 
+impl SwarmHostConfig {
+    /// Default configuration listening on a single address.
+    pub fn with_listen_addr(listen_addr: Multiaddr) -> Self {
+        Self {
+            listen_addrs: vec![listen_addr],
+            ..Default::default()
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Events emitted to upper layers
 // ---------------------------------------------------------------------------
@@ -100,6 +141,43 @@ pub enum NetworkEvent {
     },
     /// Swarm is now listening on an address.
     Listening(Multiaddr),
+    /// Chunks of a content fetch started via `SwarmHandle::fetch_content` arrived.
+    ContentFetchProgress {
+        cid: String,
+        received_chunks: usize,
+        total_chunks: usize,
+    },
+    /// A dial started via `SwarmHandle::dial` established a connection.
+    DialSucceeded { addr: Multiaddr, peer: PeerId },
+    /// A dial started via `SwarmHandle::dial` failed.
+    DialFailed { addr: Multiaddr, error: String },
+    /// A GossipSub subscription had been dropped and was restored.
+    TopicResubscribed { topic: String },
+    /// A DHT record put reached its quorum.
+    DhtPutSucceeded { key: Vec<u8> },
+    /// A DHT record put finished without reaching its quorum.
+    DhtPutFailed { key: Vec<u8>, error: String },
+    /// A connection was refused by the peer allow or deny list.
+    ConnectionRejected { peer: PeerId, reason: RejectReason },
+    /// A peer asked for the content under `cid`; answer with
+    /// `SwarmHandle::respond_content(request_id, ..)`.
+    ContentRequested {
+        request_id: u64,
+        peer: PeerId,
+        cid: String,
+    },
+}
+
+/// One peer in the Kademlia routing table, as returned by
+/// [`SwarmHandle::routing_table`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoutingTableEntry {
+    pub peer_id: PeerId,
+    /// Addresses Kademlia knows for the peer.
+    pub addrs: Vec<Multiaddr>,
+    /// Index of the k-bucket holding the peer: the base-2 log of its XOR
+    /// distance from the local peer ID (0..=255).
+    pub bucket: u32,
 }
 
 // ---------------------------------------------------------------------------
@@ -134,6 +212,21 @@ enum SwarmCommand {
     GetEstimatedSwarmSize {
         reply: oneshot::Sender<u64>,
     },
+    GetSwarmGrowth {
+        reply: oneshot::Sender<SwarmGrowth>,
+    },
+    DumpRoutingTable {
+        reply: oneshot::Sender<Vec<RoutingTableEntry>>,
+    },
+    GetTopicStats {
+        reply: oneshot::Sender<BTreeMap<String, TopicCounters>>,
+    },
+    GetConnectionMetrics {
+        reply: oneshot::Sender<ConnectionMetrics>,
+    },
+    Shutdown {
+        reply: oneshot::Sender<()>,
+    },
     PutDhtRecord {
         key: Vec<u8>,
         value: Vec<u8>,
@@ -142,6 +235,9 @@ enum SwarmCommand {
     Bootstrap {
         reply: oneshot::Sender<Result<(), NetworkError>>,
     },
+    TriggerRandomWalk {
+        reply: oneshot::Sender<bool>,
+    },
     #[allow(dead_code)]
     GetLocalPeerId {
         reply: oneshot::Sender<PeerId>,
@@ -151,10 +247,12 @@ enum SwarmCommand {
     },
     SubscribeTaskTopics {
         task_id: String,
+        epoch: u64,
         reply: oneshot::Sender<Result<(), NetworkError>>,
     },
     UnsubscribeTaskTopics {
         task_id: String,
+        epoch: u64,
         reply: oneshot::Sender<Result<(), NetworkError>>,
     },
     SubscribeTierTopics {
@@ -169,6 +267,30 @@ enum SwarmCommand {
         swarm_id: String,
         reply: oneshot::Sender<Result<(), NetworkError>>,
     },
+    FetchContent {
+        root_cid: String,
+        reply: oneshot::Sender<Result<Vec<u8>, NetworkError>>,
+    },
+    CancelFetch {
+        root_cid: String,
+    },
+    RequestContent {
+        peer: PeerId,
+        cid: String,
+        reply: oneshot::Sender<Result<Option<Vec<u8>>, NetworkError>>,
+    },
+    RespondContent {
+        request_id: u64,
+        data: Option<Vec<u8>>,
+    },
+    StartProviding {
+        cid: String,
+        reply: oneshot::Sender<Result<(), NetworkError>>,
+    },
+    GetProviders {
+        cid: String,
+        reply: oneshot::Sender<Result<Vec<PeerId>, NetworkError>>,
+    },
     /// Add a peer to the gossipsub explicit peer list so it is immediately
     /// GRAFTed without waiting for the heartbeat. Keeps connection alive.
     AddExplicitGossipPeer {
@@ -176,6 +298,30 @@ enum SwarmCommand {
     },
 }
 
+// ---------------------------------------------------------------------------
+// Payload codec
+// ---------------------------------------------------------------------------
+
+/// Transforms GossipSub payloads on their way out and in, e.g. to encrypt
+/// traffic on a private swarm's topics.
+///
+/// Installed with [`SwarmHandle::set_payload_codec`]; the codec decides per
+/// topic whether to touch the payload.
+pub trait PayloadCodec: Send + Sync {
+    /// Encode an outbound payload published on `topic`.
+    fn encode(&self, topic: &str, data: Vec<u8>) -> Vec<u8>;
+    /// Decode an inbound payload received on `topic`. Returning `None`
+    /// drops the message before it reaches any subscriber.
+    fn decode(&self, topic: &str, data: Vec<u8>) -> Option<Vec<u8>>;
+}
+
+/// Codec slot shared between the handle (publish) and the host (receive).
+type SharedPayloadCodec = Arc<RwLock<Option<Arc<dyn PayloadCodec>>>>;
+
+fn current_codec(slot: &SharedPayloadCodec) -> Option<Arc<dyn PayloadCodec>> {
+    slot.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
 // ---------------------------------------------------------------------------
 // SwarmHandle (clonable, Send-able API for upper layers)
 // ---------------------------------------------------------------------------
@@ -186,7 +332,12 @@ enum SwarmCommand {
 #[derive(Clone)]
 pub struct SwarmHandle {
     command_tx: mpsc::Sender<SwarmCommand>,
+    /// Publishes on high-priority topics, drained before `command_tx`.
+    priority_tx: mpsc::Sender<SwarmCommand>,
+    topic_priorities: Arc<TopicPriorities>,
     local_peer_id: PeerId,
+    event_broadcast: broadcast::Sender<NetworkEvent>,
+    payload_codec: SharedPayloadCodec,
 }
 
 impl SwarmHandle {
@@ -195,10 +346,45 @@ impl SwarmHandle {
         self.local_peer_id
     }
 
+    /// Subscribe to every network event emitted after this call.
+    ///
+    /// Independent of the primary `mpsc::Receiver` returned by
+    /// [`SwarmHost::new`]; any number of subscribers may observe the same
+    /// events. A subscriber that falls behind receives
+    /// `RecvError::Lagged` and skips ahead.
+    pub fn subscribe_events(&self) -> broadcast::Receiver<NetworkEvent> {
+        self.event_broadcast.subscribe()
+    }
+
+    /// Install (or with `None`, remove) the codec applied to every published
+    /// and received GossipSub payload. Affects all clones of this handle.
+    pub fn set_payload_codec(&self, codec: Option<Arc<dyn PayloadCodec>>) {
+        *self.payload_codec.write().unwrap_or_else(|e| e.into_inner()) = codec;
+    }
+
+    /// Priority `topic` is published with.
+    pub fn topic_priority(&self, topic: &str) -> TopicPriority {
+        self.topic_priorities.priority(topic)
+    }
+
     /// Publish data to a GossipSub topic.
+    ///
+    /// Publishes on high-priority topics are handled before any queued
+    /// normal-priority command. A publish that finds no mesh peers returns
+    /// [`NetworkError::NoMeshPeers`]; if its topic category is retried (see
+    /// [`PublishRetryConfig`]) the host keeps re-attempting it in the
+    /// background.
     pub async fn publish(&self, topic: &str, data: Vec<u8>) -> Result<(), NetworkError> {
+        let data = match current_codec(&self.payload_codec) {
+            Some(codec) => codec.encode(topic, data),
+            None => data,
+        };
+        let lane = match self.topic_priority(topic) {
+            TopicPriority::High => &self.priority_tx,
+            TopicPriority::Normal => &self.command_tx,
+        };
         let (tx, rx) = oneshot::channel();
-        self.command_tx
+        lane
             .send(SwarmCommand::Publish {
                 topic: topic.to_string(),
                 data,
@@ -287,6 +473,58 @@ impl SwarmHandle {
         rx.await.map_err(|_| NetworkError::ChannelClosed)
     }
 
+    /// Get how the estimated swarm size has been moving recently.
+    pub async fn swarm_growth(&self) -> Result<SwarmGrowth, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(SwarmCommand::GetSwarmGrowth { reply: tx })
+            .await
+            .map_err(|_| NetworkError::ChannelClosed)?;
+        rx.await.map_err(|_| NetworkError::ChannelClosed)
+    }
+
+    /// Close all connections and stop the swarm host's event loop. Resolves
+    /// once the host has stopped.
+    pub async fn shutdown(&self) -> Result<(), NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(SwarmCommand::Shutdown { reply: tx })
+            .await
+            .map_err(|_| NetworkError::ChannelClosed)?;
+        rx.await.map_err(|_| NetworkError::ChannelClosed)
+    }
+
+    /// Snapshot the Kademlia routing table, ordered by bucket index.
+    pub async fn routing_table(&self) -> Result<Vec<RoutingTableEntry>, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(SwarmCommand::DumpRoutingTable { reply: tx })
+            .await
+            .map_err(|_| NetworkError::ChannelClosed)?;
+        rx.await.map_err(|_| NetworkError::ChannelClosed)
+    }
+
+    /// Messages published and received per GossipSub topic since startup.
+    pub async fn topic_stats(&self) -> Result<BTreeMap<String, TopicCounters>, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(SwarmCommand::GetTopicStats { reply: tx })
+            .await
+            .map_err(|_| NetworkError::ChannelClosed)?;
+        rx.await.map_err(|_| NetworkError::ChannelClosed)
+    }
+
+    /// Per-peer connection counts, open addresses and dial outcomes since
+    /// startup.
+    pub async fn connection_metrics(&self) -> Result<ConnectionMetrics, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(SwarmCommand::GetConnectionMetrics { reply: tx })
+            .await
+            .map_err(|_| NetworkError::ChannelClosed)?;
+        rx.await.map_err(|_| NetworkError::ChannelClosed)
+    }
+
     /// Store a key-value record in the Kademlia DHT.
     pub async fn put_dht_record(&self, key: Vec<u8>, value: Vec<u8>) -> Result<(), NetworkError> {
         let (tx, rx) = oneshot::channel();
@@ -311,6 +549,18 @@ impl SwarmHandle {
         rx.await.map_err(|_| NetworkError::ChannelClosed)?
     }
 
+    /// Run a Kademlia random walk now rather than on the next discovery
+    /// interval. Returns whether a walk was started; it is not when no
+    /// configured discovery source does random walks.
+    pub async fn trigger_random_walk(&self) -> Result<bool, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(SwarmCommand::TriggerRandomWalk { reply: tx })
+            .await
+            .map_err(|_| NetworkError::ChannelClosed)?;
+        rx.await.map_err(|_| NetworkError::ChannelClosed)
+    }
+
     /// Subscribe to the core protocol topics.
     pub async fn subscribe_core_topics(&self) -> Result<(), NetworkError> {
         let (tx, rx) = oneshot::channel();
@@ -321,12 +571,13 @@ impl SwarmHandle {
         rx.await.map_err(|_| NetworkError::ChannelClosed)?
     }
 
-    /// Subscribe to task-specific proposal/voting/result topics.
-    pub async fn subscribe_task_topics(&self, task_id: &str) -> Result<(), NetworkError> {
+    /// Subscribe to task-specific proposal/voting/result topics for the task's epoch.
+    pub async fn subscribe_task_topics(&self, task_id: &str, epoch: u64) -> Result<(), NetworkError> {
         let (tx, rx) = oneshot::channel();
         self.command_tx
             .send(SwarmCommand::SubscribeTaskTopics {
                 task_id: task_id.to_string(),
+                epoch,
                 reply: tx,
             })
             .await
@@ -335,11 +586,12 @@ impl SwarmHandle {
     }
 
     /// Unsubscribe from task-specific topics after task completion.
-    pub async fn unsubscribe_task_topics(&self, task_id: &str) -> Result<(), NetworkError> {
+    pub async fn unsubscribe_task_topics(&self, task_id: &str, epoch: u64) -> Result<(), NetworkError> {
         let (tx, rx) = oneshot::channel();
         self.command_tx
             .send(SwarmCommand::UnsubscribeTaskTopics {
                 task_id: task_id.to_string(),
+                epoch,
                 reply: tx,
             })
             .await
@@ -368,6 +620,105 @@ impl SwarmHandle {
         rx.await.map_err(|_| NetworkError::ChannelClosed)?
     }
 
+    /// Fetch chunked content by root CID from the DHT.
+    ///
+    /// Resolves the manifest stored under `root_cid`, retrieves every chunk,
+    /// and returns the assembled bytes. `NetworkEvent::ContentFetchProgress`
+    /// is emitted as chunks arrive. A fetch still incomplete after
+    /// [`CONTENT_FETCH_TIMEOUT`] is cancelled and fails.
+    pub async fn fetch_content(&self, root_cid: &str) -> Result<Vec<u8>, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(SwarmCommand::FetchContent {
+                root_cid: root_cid.to_string(),
+                reply: tx,
+            })
+            .await
+            .map_err(|_| NetworkError::ChannelClosed)?;
+        match tokio::time::timeout(CONTENT_FETCH_TIMEOUT, rx).await {
+            Ok(reply) => reply.map_err(|_| NetworkError::ChannelClosed)?,
+            Err(_) => {
+                let _ = self.cancel_fetch(root_cid).await;
+                Err(NetworkError::DhtError(format!(
+                    "fetch of {} timed out after {}s",
+                    root_cid,
+                    CONTENT_FETCH_TIMEOUT.as_secs()
+                )))
+            }
+        }
+    }
+
+    /// Abandon an in-flight [`Self::fetch_content`] for `root_cid`, finishing
+    /// its outstanding DHT queries. Its waiter, if any, sees
+    /// `NetworkError::ChannelClosed`.
+    pub async fn cancel_fetch(&self, root_cid: &str) -> Result<(), NetworkError> {
+        self.command_tx
+            .send(SwarmCommand::CancelFetch {
+                root_cid: root_cid.to_string(),
+            })
+            .await
+            .map_err(|_| NetworkError::ChannelClosed)
+    }
+
+    /// Ask `peer` directly for the content under `cid`. `Ok(None)` if the
+    /// peer answered that it does not hold it.
+    pub async fn request_content(
+        &self,
+        peer: PeerId,
+        cid: &str,
+    ) -> Result<Option<Vec<u8>>, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(SwarmCommand::RequestContent {
+                peer,
+                cid: cid.to_string(),
+                reply: tx,
+            })
+            .await
+            .map_err(|_| NetworkError::ChannelClosed)?;
+        rx.await.map_err(|_| NetworkError::ChannelClosed)?
+    }
+
+    /// Answer the `NetworkEvent::ContentRequested` with `request_id`;
+    /// `None` tells the requester the content is not held here.
+    pub async fn respond_content(
+        &self,
+        request_id: u64,
+        data: Option<Vec<u8>>,
+    ) -> Result<(), NetworkError> {
+        self.command_tx
+            .send(SwarmCommand::RespondContent { request_id, data })
+            .await
+            .map_err(|_| NetworkError::ChannelClosed)
+    }
+
+    /// Announce in the DHT that this node provides the content under `cid`.
+    /// Returns once the announcement is stored locally and under way.
+    pub async fn start_providing(&self, cid: &str) -> Result<(), NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(SwarmCommand::StartProviding {
+                cid: cid.to_string(),
+                reply: tx,
+            })
+            .await
+            .map_err(|_| NetworkError::ChannelClosed)?;
+        rx.await.map_err(|_| NetworkError::ChannelClosed)?
+    }
+
+    /// Look up the peers providing the content under `cid` in the DHT.
+    pub async fn get_providers(&self, cid: &str) -> Result<Vec<PeerId>, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(SwarmCommand::GetProviders {
+                cid: cid.to_string(),
+                reply: tx,
+            })
+            .await
+            .map_err(|_| NetworkError::ChannelClosed)?;
+        rx.await.map_err(|_| NetworkError::ChannelClosed)?
+    }
+
     /// Subscribe to all topics for a specific swarm (election, keepalive, hierarchy, discovery).
     pub async fn subscribe_swarm_topics(&self, swarm_id: &str) -> Result<(), NetworkError> {
         let (tx, rx) = oneshot::channel();
@@ -394,7 +745,11 @@ impl SwarmHandle {
 pub struct SwarmHost {
     swarm: Swarm<SwarmBehaviour>,
     command_rx: mpsc::Receiver<SwarmCommand>,
+    /// High-priority publishes, see [`SwarmHandle::publish`].
+    priority_rx: mpsc::Receiver<SwarmCommand>,
     event_tx: mpsc::Sender<NetworkEvent>,
+    /// Fan-out of every event to [`SwarmHandle::subscribe_events`] subscribers.
+    event_broadcast: broadcast::Sender<NetworkEvent>,
     topic_manager: TopicManager,
     discovery_manager: DiscoveryManager,
     size_estimator: SwarmSizeEstimator,
@@ -402,6 +757,42 @@ pub struct SwarmHost {
     peer_rtt: HashMap<PeerId, Duration>,
     /// Interval timer for Kademlia random walks.
     random_walk_interval: Duration,
+    /// Interval for restoring dropped GossipSub subscriptions.
+    resubscribe_interval: Option<Duration>,
+    /// In-flight content fetches keyed by root CID.
+    content_fetches: HashMap<String, PendingFetch>,
+    /// Outstanding DHT queries issued on behalf of content fetches.
+    fetch_queries: HashMap<kad::QueryId, FetchQuery>,
+    /// Dials requested through the handle, keyed by connection until they resolve.
+    pending_dials: HashMap<ConnectionId, Multiaddr>,
+    /// Codec applied to inbound GossipSub payloads.
+    payload_codec: SharedPayloadCodec,
+    /// Publishes waiting for their topic's mesh to form.
+    publish_retries: PublishRetryQueue,
+    /// Per-peer connection counters.
+    connections: ConnectionTracker,
+    /// Outbound content requests awaiting a response.
+    content_requests: HashMap<OutboundRequestId, ContentReply>,
+    /// Inbound content requests awaiting `SwarmHandle::respond_content`.
+    content_responses: HashMap<u64, (InboundRequestId, ResponseChannel<ContentResponse>)>,
+    next_content_request: u64,
+    /// Provider lookups in progress, with the providers found so far.
+    provider_queries: HashMap<kad::QueryId, (ProvidersReply, HashSet<PeerId>)>,
+}
+
+type ContentReply = oneshot::Sender<Result<Option<Vec<u8>>, NetworkError>>;
+type ProvidersReply = oneshot::Sender<Result<Vec<PeerId>, NetworkError>>;
+
+/// A content fetch awaiting its manifest and chunks.
+struct PendingFetch {
+    fetch: ContentFetch,
+    reply: oneshot::Sender<Result<Vec<u8>, NetworkError>>,
+}
+
+/// What a content-fetch DHT query is retrieving.
+enum FetchQuery {
+    Manifest { root_cid: String },
+    Chunk { root_cid: String, cid: String },
 }
 
 impl SwarmHost {
@@ -411,43 +802,86 @@ impl SwarmHost {
     /// with the network. The `mpsc::Receiver<NetworkEvent>` receives
     /// events from the network for upper-layer processing.
     pub fn new(
-        config: SwarmHostConfig,
+        mut config: SwarmHostConfig,
     ) -> Result<(Self, SwarmHandle, mpsc::Receiver<NetworkEvent>), NetworkError> {
+        // Only run the mDNS behaviour when a configured source asks for it,
+        // and build Kademlia with the discovery timers.
+        config
+            .discovery
+            .apply_to(&mut config.transport.behaviour_config);
+
         let mut swarm = if let Some(kp) = config.keypair {
             transport::build_swarm_with_keypair(kp, config.transport)?
         } else {
             transport::build_swarm(config.transport)?
         };
 
-        // Start listening.
-        swarm
-            .listen_on(config.listen_addr.clone())
-            .map_err(|e| NetworkError::ListenError(e.to_string()))?;
+        // Start listening on every configured address.
+        if config.listen_addrs.is_empty() {
+            return Err(NetworkError::ListenError(
+                "no listen addresses configured".into(),
+            ));
+        }
+        for addr in &config.listen_addrs {
+            swarm
+                .listen_on(addr.clone())
+                .map_err(|e| NetworkError::ListenError(format!("{addr}: {e}")))?;
+        }
 
         let local_peer_id = *swarm.local_peer_id();
         tracing::info!(peer_id = %local_peer_id, "Local peer ID");
 
+        if let Some(params) = config.topic_priorities.peer_score_params() {
+            swarm
+                .behaviour_mut()
+                .gossipsub
+                .with_peer_score(params, gossipsub::PeerScoreThresholds::default())
+                .map_err(|e| NetworkError::Behaviour(format!("GossipSub peer score error: {e}")))?;
+        }
+
         let (command_tx, command_rx) = mpsc::channel(config.command_buffer);
+        let (priority_tx, priority_rx) = mpsc::channel(config.command_buffer);
         let (event_tx, event_rx) = mpsc::channel(config.event_buffer);
+        let (event_broadcast, _) = broadcast::channel(config.event_broadcast_buffer.max(1));
 
         let discovery_manager = DiscoveryManager::new(config.discovery);
-        let topic_manager = TopicManager::new();
+        let topic_priorities = Arc::new(config.topic_priorities.clone());
+        let topic_manager =
+            TopicManager::with_priorities(config.topic_priorities).with_hashing(config.topic_hashing);
         let size_estimator = SwarmSizeEstimator::default();
+        let payload_codec: SharedPayloadCodec = Arc::new(RwLock::new(None));
 
         let host = Self {
             swarm,
             command_rx,
+            priority_rx,
             event_tx,
+            event_broadcast: event_broadcast.clone(),
             topic_manager,
             discovery_manager,
             size_estimator,
             peer_rtt: HashMap::new(),
             random_walk_interval: config.random_walk_interval,
+            resubscribe_interval: config.resubscribe_interval.filter(|d| !d.is_zero()),
+            content_fetches: HashMap::new(),
+            fetch_queries: HashMap::new(),
+            pending_dials: HashMap::new(),
+            payload_codec: payload_codec.clone(),
+            publish_retries: PublishRetryQueue::new(config.publish_retry),
+            connections: ConnectionTracker::new(),
+            content_requests: HashMap::new(),
+            content_responses: HashMap::new(),
+            next_content_request: 0,
+            provider_queries: HashMap::new(),
         };
 
         let handle = SwarmHandle {
             command_tx,
+            priority_tx,
+            topic_priorities,
             local_peer_id,
+            event_broadcast,
+            payload_codec,
         };
 
         Ok((host, handle, event_rx))
@@ -458,48 +892,153 @@ impl SwarmHost {
     /// This drives the libp2p Swarm and processes commands from the handle.
     /// It should be spawned as a long-running tokio task.
     pub async fn run(mut self) -> Result<(), NetworkError> {
-        // Initiate bootstrap with configured peers.
-        self.discovery_manager
-            .initiate_bootstrap(&mut self.swarm.behaviour_mut().kademlia)?;
+        // Start the configured discovery sources and dial what they return.
+        let dials = self
+            .discovery_manager
+            .start(&mut self.swarm.behaviour_mut().kademlia)?;
+        for addr in dials {
+            if let Err(e) = self.dial_tracked(addr.clone()) {
+                tracing::warn!(addr = %addr, error = %e, "Failed to dial discovered peer");
+            }
+        }
 
         let mut walk_interval = tokio::time::interval(self.random_walk_interval);
         walk_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        // The branch below is disabled when no interval is configured; the
+        // placeholder period only keeps the timer constructible.
+        let mut resubscribe_interval =
+            tokio::time::interval(self.resubscribe_interval.unwrap_or(Duration::from_secs(3600)));
+        resubscribe_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        resubscribe_interval.reset();
+        let mut publish_retry_interval =
+            tokio::time::interval(self.publish_retries.config().interval.max(Duration::from_millis(10)));
+        publish_retry_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
         loop {
+            // High-priority publishes go out before anything else that is
+            // ready, without starving swarm events or other commands.
+            while let Ok(cmd) = self.priority_rx.try_recv() {
+                self.handle_command(cmd).await;
+            }
             tokio::select! {
+                Some(cmd) = self.priority_rx.recv() => {
+                    self.handle_command(cmd).await;
+                }
                 event = self.swarm.select_next_some() => {
                     self.handle_swarm_event(event).await;
                 }
                 Some(cmd) = self.command_rx.recv() => {
+                    if let SwarmCommand::Shutdown { reply } = cmd {
+                        self.close_all_connections();
+                        let _ = reply.send(());
+                        return Ok(());
+                    }
                     self.handle_command(cmd).await;
                 }
                 _ = walk_interval.tick() => {
-                    self.discovery_manager.trigger_random_walk(
-                        &mut self.swarm.behaviour_mut().kademlia,
-                    );
+                    self.discovery_manager
+                        .tick(&mut self.swarm.behaviour_mut().kademlia);
                     // Update size estimate from connected peer count.
                     let peer_count = self.swarm.connected_peers().count();
                     self.size_estimator.update_from_peer_count(peer_count);
                 }
+                _ = resubscribe_interval.tick(), if self.resubscribe_interval.is_some() => {
+                    self.resubscribe_dropped_topics().await;
+                }
+                _ = publish_retry_interval.tick(), if !self.publish_retries.is_empty() => {
+                    self.retry_pending_publishes();
+                }
+            }
+        }
+    }
+
+    /// Disconnect every connected peer ahead of shutdown.
+    fn close_all_connections(&mut self) {
+        let peers: Vec<PeerId> = self.swarm.connected_peers().copied().collect();
+        tracing::info!(peers = peers.len(), "Swarm host shutting down");
+        for peer in peers {
+            let _ = self.swarm.disconnect_peer_id(peer);
+        }
+    }
+
+    /// Re-attempt publishes that found no mesh peers, dropping those that
+    /// have used up their retries.
+    fn retry_pending_publishes(&mut self) {
+        let now = std::time::Instant::now();
+        for pending in self.publish_retries.take_due(now) {
+            match self.publish_message(&pending.topic, pending.data.clone()) {
+                Ok(()) => {
+                    tracing::debug!(topic = %pending.topic, attempts = pending.attempts + 1, "Published after retry");
+                }
+                Err(NetworkError::NoMeshPeers(_)) => {
+                    let topic = pending.topic.clone();
+                    if !self.publish_retries.retry_later(pending, now) {
+                        tracing::warn!(topic = %topic, "Dropping message: still no mesh peers after retries");
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(topic = %pending.topic, error = %e, "Retried publish failed");
+                }
             }
         }
     }
 
+    /// Restore intended GossipSub subscriptions that have been dropped.
+    async fn resubscribe_dropped_topics(&mut self) {
+        let restored = self
+            .topic_manager
+            .resubscribe_missing(&mut self.swarm.behaviour_mut().gossipsub);
+        for topic in restored {
+            self.emit(NetworkEvent::TopicResubscribed { topic }).await;
+        }
+    }
+
+    /// Dial `addr`, remembering the connection so its outcome is reported
+    /// as [`NetworkEvent::DialSucceeded`] or [`NetworkEvent::DialFailed`].
+    fn dial_tracked(&mut self, addr: Multiaddr) -> Result<(), NetworkError> {
+        let opts = DialOpts::from(addr.clone());
+        let connection_id = opts.connection_id();
+        self.swarm
+            .dial(opts)
+            .map_err(|e| NetworkError::DialError(e.to_string()))?;
+        self.pending_dials.insert(connection_id, addr);
+        Ok(())
+    }
+
     // ---- Event Handling ----
 
+    /// Forward an event to the primary consumer and to all subscribers.
+    ///
+    /// Takes `&mut self` so the returned future stays `Send` without
+    /// requiring `SwarmHost: Sync`.
+    async fn emit(&mut self, event: NetworkEvent) {
+        // No subscribers is the common case and not an error.
+        let _ = self.event_broadcast.send(event.clone());
+        let _ = self.event_tx.send(event).await;
+    }
+
     async fn handle_swarm_event(&mut self, event: SwarmEvent<SwarmBehaviourEvent>) {
         match event {
             SwarmEvent::Behaviour(behaviour_event) => {
                 self.handle_behaviour_event(behaviour_event).await;
             }
             SwarmEvent::ConnectionEstablished {
-                peer_id, endpoint, ..
+                peer_id,
+                connection_id,
+                endpoint,
+                ..
             } => {
                 tracing::info!(
                     peer = %peer_id,
                     endpoint = ?endpoint,
                     "Connection established"
                 );
+                self.connections.record_established(
+                    peer_id,
+                    connection_id,
+                    endpoint.get_remote_address().clone(),
+                    endpoint.is_dialer(),
+                );
                 self.discovery_manager.add_peer(peer_id);
                 // Add the peer's address to the Kademlia routing table so DHT
                 // queries can reach them.  For outbound connections the remote
@@ -509,28 +1048,69 @@ impl SwarmHost {
                     .behaviour_mut()
                     .kademlia
                     .add_address(&peer_id, remote_addr);
-                let _ = self.event_tx.send(NetworkEvent::PeerConnected(peer_id)).await;
+                self.emit(NetworkEvent::PeerConnected(peer_id)).await;
+                if let Some(addr) = self.pending_dials.remove(&connection_id) {
+                    self.emit(NetworkEvent::DialSucceeded { addr, peer: peer_id }).await;
+                }
             }
-            SwarmEvent::ConnectionClosed { peer_id, cause, .. } => {
+            SwarmEvent::OutgoingConnectionError {
+                connection_id,
+                peer_id,
+                error,
+            } => {
+                self.connections.record_dial_failure(peer_id, error.to_string());
+                if let DialError::Denied { cause } = &error {
+                    self.report_rejection(cause).await;
+                }
+                if let Some(addr) = self.pending_dials.remove(&connection_id) {
+                    tracing::debug!(addr = %addr, error = %error, "Dial failed");
+                    self.emit(NetworkEvent::DialFailed {
+                            addr,
+                            error: error.to_string(),
+                        }).await;
+                }
+            }
+            SwarmEvent::IncomingConnectionError {
+                error: ListenError::Denied { cause },
+                ..
+            } => {
+                self.report_rejection(&cause).await;
+            }
+            SwarmEvent::ConnectionClosed {
+                peer_id,
+                connection_id,
+                cause,
+                ..
+            } => {
+                self.connections.record_closed(peer_id, connection_id);
                 tracing::info!(
                     peer = %peer_id,
                     cause = ?cause,
                     "Connection closed"
                 );
                 self.peer_rtt.remove(&peer_id);
-                let _ = self
-                    .event_tx
-                    .send(NetworkEvent::PeerDisconnected(peer_id))
-                    .await;
+                self.emit(NetworkEvent::PeerDisconnected(peer_id)).await;
             }
             SwarmEvent::NewListenAddr { address, .. } => {
                 tracing::info!(addr = %address, "Now listening");
-                let _ = self.event_tx.send(NetworkEvent::Listening(address)).await;
+                self.emit(NetworkEvent::Listening(address)).await;
             }
             _ => {}
         }
     }
 
+    /// Emit `ConnectionRejected` if `cause` came from the peer filter.
+    async fn report_rejection(&mut self, cause: &ConnectionDenied) {
+        if let Some(rejected) = cause.downcast_ref::<PeerRejected>() {
+            tracing::info!(peer = %rejected.peer, reason = %rejected.reason, "Connection rejected");
+            self.emit(NetworkEvent::ConnectionRejected {
+                peer: rejected.peer,
+                reason: rejected.reason,
+            })
+            .await;
+        }
+    }
+
     async fn handle_behaviour_event(&mut self, event: SwarmBehaviourEvent) {
         match event {
             SwarmBehaviourEvent::Gossipsub(gossipsub::Event::Message {
@@ -538,11 +1118,7 @@ impl SwarmHost {
                 message,
                 ..
             }) => {
-                let topic_str = self
-                    .topic_manager
-                    .resolve_topic(&message.topic)
-                    .map(|t| t.to_string())
-                    .unwrap_or_else(|| message.topic.to_string());
+                let topic_str = self.topic_manager.record_received(&message.topic);
 
                 tracing::debug!(
                     source = %propagation_source,
@@ -551,14 +1127,25 @@ impl SwarmHost {
                     "GossipSub message received"
                 );
 
-                let _ = self
-                    .event_tx
-                    .send(NetworkEvent::MessageReceived {
+                let data = match current_codec(&self.payload_codec) {
+                    Some(codec) => match codec.decode(&topic_str, message.data) {
+                        Some(data) => data,
+                        None => {
+                            tracing::debug!(
+                                topic = %topic_str,
+                                "Dropping GossipSub message rejected by payload codec"
+                            );
+                            return;
+                        }
+                    },
+                    None => message.data,
+                };
+
+                self.emit(NetworkEvent::MessageReceived {
                         source: propagation_source,
                         topic: topic_str,
-                        data: message.data,
-                    })
-                    .await;
+                        data,
+                    }).await;
             }
             SwarmBehaviourEvent::Mdns(mdns::Event::Discovered(list)) => {
                 let peers: Vec<_> = list.into_iter().collect();
@@ -579,20 +1166,38 @@ impl SwarmHost {
                 peer, is_new_peer, ..
             }) => {
                 tracing::debug!(peer = %peer, new = is_new_peer, "Kademlia routing updated");
-                let _ = self
-                    .event_tx
-                    .send(NetworkEvent::RoutingUpdated {
+                self.emit(NetworkEvent::RoutingUpdated {
                         peer,
                         is_new_peer,
-                    })
-                    .await;
+                    }).await;
             }
             SwarmBehaviourEvent::Kademlia(kad::Event::OutboundQueryProgressed {
-                result, ..
-            }) => {
-                if let kad::QueryResult::Bootstrap(Ok(_)) = result {
+                id, result, step, ..
+            }) => match result {
+                kad::QueryResult::Bootstrap(Ok(_)) => {
                     self.discovery_manager.on_bootstrap_complete();
                 }
+                kad::QueryResult::GetRecord(result) if self.fetch_queries.contains_key(&id) => {
+                    self.on_fetch_query_result(id, result).await;
+                }
+                kad::QueryResult::GetProviders(result) => {
+                    self.on_providers_result(id, result, step.last);
+                }
+                kad::QueryResult::PutRecord(Ok(kad::PutRecordOk { key })) => {
+                    self.emit(NetworkEvent::DhtPutSucceeded { key: key.to_vec() })
+                        .await;
+                }
+                kad::QueryResult::PutRecord(Err(e)) => {
+                    self.emit(NetworkEvent::DhtPutFailed {
+                        key: e.key().to_vec(),
+                        error: e.to_string(),
+                    })
+                    .await;
+                }
+                _ => {}
+            },
+            SwarmBehaviourEvent::ContentRequest(event) => {
+                self.on_content_request_event(event).await;
             }
             SwarmBehaviourEvent::Identify(identify::Event::Received {
                 peer_id, info, ..
@@ -609,14 +1214,11 @@ impl SwarmHost {
                         .kademlia
                         .add_address(&peer_id, addr.clone());
                 }
-                let _ = self
-                    .event_tx
-                    .send(NetworkEvent::PeerIdentified {
+                self.emit(NetworkEvent::PeerIdentified {
                         peer: peer_id,
                         agent_version: info.agent_version,
                         listen_addrs: info.listen_addrs,
-                    })
-                    .await;
+                    }).await;
             }
             SwarmBehaviourEvent::Ping(ping::Event {
                 peer,
@@ -624,10 +1226,7 @@ impl SwarmHost {
                 ..
             }) => {
                 self.peer_rtt.insert(peer, rtt);
-                let _ = self
-                    .event_tx
-                    .send(NetworkEvent::PingRtt { peer, rtt })
-                    .await;
+                self.emit(NetworkEvent::PingRtt { peer, rtt }).await;
             }
             _ => {}
         }
@@ -638,7 +1237,11 @@ impl SwarmHost {
     async fn handle_command(&mut self, cmd: SwarmCommand) {
         match cmd {
             SwarmCommand::Publish { topic, data, reply } => {
+                let retry = self.publish_retries.config().applies_to(&topic).then(|| data.clone());
                 let result = self.publish_message(&topic, data);
+                if let (Err(NetworkError::NoMeshPeers(_)), Some(data)) = (&result, retry) {
+                    self.publish_retries.push(&topic, data, std::time::Instant::now());
+                }
                 let _ = reply.send(result);
             }
             SwarmCommand::Subscribe { topic, reply } => {
@@ -655,11 +1258,7 @@ impl SwarmHost {
                 let _ = reply.send(result);
             }
             SwarmCommand::Dial { addr, reply } => {
-                let result = self
-                    .swarm
-                    .dial(addr)
-                    .map_err(|e| NetworkError::DialError(e.to_string()));
-                let _ = reply.send(result);
+                let _ = reply.send(self.dial_tracked(addr));
             }
             SwarmCommand::AddPeerAddress { peer_id, addr } => {
                 self.swarm
@@ -674,6 +1273,33 @@ impl SwarmHost {
             SwarmCommand::GetEstimatedSwarmSize { reply } => {
                 let _ = reply.send(self.size_estimator.estimated_size());
             }
+            SwarmCommand::GetSwarmGrowth { reply } => {
+                let _ = reply.send(self.size_estimator.growth());
+            }
+            // Stops the event loop, so it is handled in `run`.
+            SwarmCommand::Shutdown { reply } => {
+                let _ = reply.send(());
+            }
+            SwarmCommand::GetTopicStats { reply } => {
+                let _ = reply.send(self.topic_manager.topic_stats());
+            }
+            SwarmCommand::GetConnectionMetrics { reply } => {
+                let _ = reply.send(self.connections.snapshot());
+            }
+            SwarmCommand::DumpRoutingTable { reply } => {
+                let mut entries = Vec::new();
+                for bucket in self.swarm.behaviour_mut().kademlia.kbuckets() {
+                    let index = bucket.range().0.ilog2().unwrap_or(0);
+                    for entry in bucket.iter() {
+                        entries.push(RoutingTableEntry {
+                            peer_id: *entry.node.key.preimage(),
+                            addrs: entry.node.value.iter().cloned().collect(),
+                            bucket: index,
+                        });
+                    }
+                }
+                let _ = reply.send(entries);
+            }
             SwarmCommand::PutDhtRecord { key, value, reply } => {
                 let record = libp2p::kad::Record {
                     key: libp2p::kad::RecordKey::new(&key),
@@ -700,6 +1326,12 @@ impl SwarmHost {
                     .map_err(|e| NetworkError::DhtError(format!("Bootstrap failed: {e}")));
                 let _ = reply.send(result);
             }
+            SwarmCommand::TriggerRandomWalk { reply } => {
+                let started = self
+                    .discovery_manager
+                    .trigger_random_walk(&mut self.swarm.behaviour_mut().kademlia);
+                let _ = reply.send(started);
+            }
             SwarmCommand::GetLocalPeerId { reply } => {
                 let _ = reply.send(*self.swarm.local_peer_id());
             }
@@ -709,21 +1341,31 @@ impl SwarmHost {
                     .subscribe_core_topics(&mut self.swarm.behaviour_mut().gossipsub);
                 let _ = reply.send(result);
             }
-            SwarmCommand::SubscribeTaskTopics { task_id, reply } => {
+            SwarmCommand::SubscribeTaskTopics {
+                task_id,
+                epoch,
+                reply,
+            } => {
                 let result = self
                     .topic_manager
                     .subscribe_task_topics(
                         &mut self.swarm.behaviour_mut().gossipsub,
                         &task_id,
+                        epoch,
                     );
                 let _ = reply.send(result);
             }
-            SwarmCommand::UnsubscribeTaskTopics { task_id, reply } => {
+            SwarmCommand::UnsubscribeTaskTopics {
+                task_id,
+                epoch,
+                reply,
+            } => {
                 let result = self
                     .topic_manager
                     .unsubscribe_task_topics(
                         &mut self.swarm.behaviour_mut().gossipsub,
                         &task_id,
+                        epoch,
                     );
                 let _ = reply.send(result);
             }
@@ -753,6 +1395,72 @@ impl SwarmHost {
                     );
                 let _ = reply.send(result);
             }
+            SwarmCommand::FetchContent { root_cid, reply } => {
+                if self.content_fetches.contains_key(&root_cid) {
+                    let _ = reply.send(Err(NetworkError::DhtError(format!(
+                        "fetch already in progress for {}",
+                        root_cid
+                    ))));
+                    return;
+                }
+                let query_id = self
+                    .swarm
+                    .behaviour_mut()
+                    .kademlia
+                    .get_record(libp2p::kad::RecordKey::new(&root_cid));
+                self.fetch_queries.insert(
+                    query_id,
+                    FetchQuery::Manifest {
+                        root_cid: root_cid.clone(),
+                    },
+                );
+                self.content_fetches.insert(
+                    root_cid.clone(),
+                    PendingFetch {
+                        fetch: ContentFetch::new(root_cid),
+                        reply,
+                    },
+                );
+            }
+            SwarmCommand::CancelFetch { root_cid } => {
+                self.cancel_fetch(&root_cid);
+            }
+            SwarmCommand::RequestContent { peer, cid, reply } => {
+                let request_id = self
+                    .swarm
+                    .behaviour_mut()
+                    .content_request
+                    .send_request(&peer, ContentRequest { cid });
+                self.content_requests.insert(request_id, reply);
+            }
+            SwarmCommand::RespondContent { request_id, data } => {
+                if let Some((_, channel)) = self.content_responses.remove(&request_id) {
+                    // Fails only if the requester already gave up.
+                    let _ = self
+                        .swarm
+                        .behaviour_mut()
+                        .content_request
+                        .send_response(channel, ContentResponse { data });
+                }
+            }
+            SwarmCommand::StartProviding { cid, reply } => {
+                let result = self
+                    .swarm
+                    .behaviour_mut()
+                    .kademlia
+                    .start_providing(libp2p::kad::RecordKey::new(&cid))
+                    .map(|_| ())
+                    .map_err(|e| NetworkError::DhtError(e.to_string()));
+                let _ = reply.send(result);
+            }
+            SwarmCommand::GetProviders { cid, reply } => {
+                let query_id = self
+                    .swarm
+                    .behaviour_mut()
+                    .kademlia
+                    .get_providers(libp2p::kad::RecordKey::new(&cid));
+                self.provider_queries.insert(query_id, (reply, HashSet::new()));
+            }
             SwarmCommand::AddExplicitGossipPeer { peer_id } => {
                 self.swarm
                     .behaviour_mut()
@@ -763,15 +1471,228 @@ impl SwarmHost {
         }
     }
 
+    /// Route a content request, response or failure of the content protocol.
+    async fn on_content_request_event(
+        &mut self,
+        event: request_response::Event<ContentRequest, ContentResponse>,
+    ) {
+        match event {
+            request_response::Event::Message {
+                peer,
+                message:
+                    request_response::Message::Request {
+                        request_id,
+                        request,
+                        channel,
+                    },
+            } => {
+                let id = self.next_content_request;
+                self.next_content_request += 1;
+                self.content_responses.insert(id, (request_id, channel));
+                self.emit(NetworkEvent::ContentRequested {
+                    request_id: id,
+                    peer,
+                    cid: request.cid,
+                })
+                .await;
+            }
+            request_response::Event::Message {
+                message: request_response::Message::Response { request_id, response },
+                ..
+            } => {
+                if let Some(reply) = self.content_requests.remove(&request_id) {
+                    let _ = reply.send(Ok(response.data));
+                }
+            }
+            request_response::Event::OutboundFailure {
+                request_id, error, ..
+            } => {
+                if let Some(reply) = self.content_requests.remove(&request_id) {
+                    let _ = reply.send(Err(NetworkError::ContentRequest(error.to_string())));
+                }
+            }
+            request_response::Event::InboundFailure { request_id, .. } => {
+                self.content_responses.retain(|_, (inbound, _)| *inbound != request_id);
+            }
+            request_response::Event::ResponseSent { .. } => {}
+        }
+    }
+
+    /// Collect providers from a `get_providers` query, replying once it ends.
+    fn on_providers_result(
+        &mut self,
+        id: kad::QueryId,
+        result: Result<kad::GetProvidersOk, kad::GetProvidersError>,
+        last: bool,
+    ) {
+        let Some((_, found)) = self.provider_queries.get_mut(&id) else {
+            return;
+        };
+        let error = match result {
+            Ok(kad::GetProvidersOk::FoundProviders { providers, .. }) => {
+                found.extend(providers);
+                None
+            }
+            Ok(kad::GetProvidersOk::FinishedWithNoAdditionalRecord { .. }) => None,
+            Err(e) => Some(e),
+        };
+        if !last && error.is_none() {
+            return;
+        }
+        if let Some((reply, found)) = self.provider_queries.remove(&id) {
+            let result = match error {
+                // A timed-out lookup still reports what it found.
+                Some(e) if found.is_empty() => Err(NetworkError::DhtError(e.to_string())),
+                _ => Ok(found.into_iter().collect()),
+            };
+            let _ = reply.send(result);
+        }
+    }
+
+    /// Advance a content fetch with the result of one of its DHT queries.
+    async fn on_fetch_query_result(
+        &mut self,
+        id: kad::QueryId,
+        result: Result<kad::GetRecordOk, kad::GetRecordError>,
+    ) {
+        // A query may yield several records; only the first is used and the
+        // query is finished once it arrives.
+        let Some(query) = self.fetch_queries.remove(&id) else {
+            return;
+        };
+        let root_cid = match &query {
+            FetchQuery::Manifest { root_cid } | FetchQuery::Chunk { root_cid, .. } => {
+                root_cid.clone()
+            }
+        };
+        let record = match result {
+            Ok(kad::GetRecordOk::FoundRecord(peer_record)) => {
+                // The first record is all we need; stop asking further peers.
+                if let Some(mut query) = self.swarm.behaviour_mut().kademlia.query_mut(&id) {
+                    query.finish();
+                }
+                peer_record.record
+            }
+            Ok(kad::GetRecordOk::FinishedWithNoAdditionalRecord { .. }) => return,
+            Err(e) => {
+                self.fail_fetch(&root_cid, NetworkError::DhtError(e.to_string()));
+                return;
+            }
+        };
+
+        let Some(pending) = self.content_fetches.get_mut(&root_cid) else {
+            return;
+        };
+        let progress = match query {
+            FetchQuery::Manifest { .. } => {
+                let manifest = match serde_json::from_slice::<ContentManifest>(&record.value) {
+                    Ok(m) => m,
+                    Err(e) => {
+                        self.fail_fetch(&root_cid, NetworkError::Serialization(e.to_string()));
+                        return;
+                    }
+                };
+                let missing = match pending.fetch.on_manifest(manifest) {
+                    Ok(missing) => missing,
+                    Err(e) => {
+                        self.fail_fetch(&root_cid, e);
+                        return;
+                    }
+                };
+                for cid in missing {
+                    let query_id = self
+                        .swarm
+                        .behaviour_mut()
+                        .kademlia
+                        .get_record(libp2p::kad::RecordKey::new(&cid));
+                    self.fetch_queries.insert(
+                        query_id,
+                        FetchQuery::Chunk {
+                            root_cid: root_cid.clone(),
+                            cid,
+                        },
+                    );
+                }
+                self.content_fetches.get(&root_cid).map(|p| p.fetch.progress())
+            }
+            FetchQuery::Chunk { cid, .. } => {
+                if !crate::content_fetch::cid_matches(&cid, &record.value) {
+                    self.fail_fetch(
+                        &root_cid,
+                        NetworkError::DhtError(format!("chunk {} failed CID verification", cid)),
+                    );
+                    return;
+                }
+                pending.fetch.on_chunk(&cid, record.value)
+            }
+        };
+
+        if let Some(progress) = progress {
+            self.emit(NetworkEvent::ContentFetchProgress {
+                    cid: root_cid.clone(),
+                    received_chunks: progress.received_chunks,
+                    total_chunks: progress.total_chunks,
+                }).await;
+        }
+
+        if self
+            .content_fetches
+            .get(&root_cid)
+            .is_some_and(|p| p.fetch.is_complete())
+        {
+            if let Some(pending) = self.content_fetches.remove(&root_cid) {
+                let _ = pending.reply.send(pending.fetch.assemble());
+            }
+        }
+    }
+
+    /// Abort a content fetch, dropping its outstanding queries.
+    fn fail_fetch(&mut self, root_cid: &str, error: NetworkError) {
+        self.fetch_queries.retain(|_, q| match q {
+            FetchQuery::Manifest { root_cid: r } | FetchQuery::Chunk { root_cid: r, .. } => {
+                r != root_cid
+            }
+        });
+        if let Some(pending) = self.content_fetches.remove(root_cid) {
+            tracing::warn!(cid = %root_cid, error = %error, "Content fetch failed");
+            let _ = pending.reply.send(Err(error));
+        }
+    }
+
+    /// Drop a content fetch nobody waits for any more, finishing its DHT
+    /// queries so no further chunks are requested or assembled.
+    fn cancel_fetch(&mut self, root_cid: &str) {
+        let queries: Vec<kad::QueryId> = self
+            .fetch_queries
+            .iter()
+            .filter(|(_, q)| match q {
+                FetchQuery::Manifest { root_cid: r } | FetchQuery::Chunk { root_cid: r, .. } => {
+                    r == root_cid
+                }
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        for id in queries {
+            self.fetch_queries.remove(&id);
+            if let Some(mut query) = self.swarm.behaviour_mut().kademlia.query_mut(&id) {
+                query.finish();
+            }
+        }
+        if self.content_fetches.remove(root_cid).is_some() {
+            tracing::debug!(cid = %root_cid, "Content fetch cancelled");
+        }
+    }
+
     /// Internal helper to publish a message to a GossipSub topic.
     fn publish_message(&mut self, topic_str: &str, data: Vec<u8>) -> Result<(), NetworkError> {
-        let topic = IdentTopic::new(topic_str);
+        let topic = self.topic_manager.hash(topic_str);
         self.swarm
             .behaviour_mut()
             .gossipsub
             .publish(topic, data)
-            .map(|_| ())
-            .map_err(|e| NetworkError::PublishError(e.to_string()))
+            .map_err(|e| NetworkError::from_publish_error(topic_str, e))?;
+        self.topic_manager.record_sent(topic_str);
+        Ok(())
     }
 
     /// Get a reference to the peer RTT map for Vivaldi coordinate updates.
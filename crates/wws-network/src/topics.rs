@@ -3,30 +3,373 @@
 //! Manages subscription and lifecycle of GossipSub topics corresponding
 //! to the WWS protocol channels: elections, proposals, voting,
 //! tasks, results, keepalive, and hierarchy.
+//!
+//! Topics are grouped into [`TopicCategory`]s. Categories marked high
+//! priority in [`TopicPriorities`] (consensus by default) get dedicated
+//! GossipSub scoring parameters, so mesh maintenance favours peers that
+//! stay and deliver on them, and their publishes skip ahead of queued
+//! keepalive and announcement traffic in the swarm host.
+//!
+//! Topic names become GossipSub topic hashes according to
+//! [`TopicHashingConfig`]: as-is ([`TopicHashing::Ident`], the default) or
+//! as a SHA-256 digest ([`TopicHashing::Sha256`]), chosen per swarm. The
+//! [`TopicManager`] remembers the name behind every hash it subscribes or
+//! publishes to, so received messages are still reported by topic name.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::time::Duration;
 
-use libp2p::gossipsub::{self, IdentTopic, TopicHash};
+use libp2p::gossipsub::{self, Hasher, IdentTopic, Sha256Topic, Topic, TopicHash};
+use serde::{Deserialize, Serialize};
 
 use crate::NetworkError;
 
+/// Protocol function a topic serves, derived from its name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TopicCategory {
+    /// Elections, proposals and voting.
+    Consensus,
+    /// Task assignment per tier.
+    Tasks,
+    /// Task results.
+    Results,
+    /// Liveness keepalives.
+    Keepalive,
+    /// Hierarchy updates.
+    Hierarchy,
+    /// Swarm discovery and announcements.
+    Discovery,
+    /// Holon boards and anything not recognised.
+    Other,
+}
+
+impl TopicCategory {
+    /// Category of `topic`, e.g. `/wws/1.0.0/s/public/election/tier1` is
+    /// [`TopicCategory::Consensus`].
+    pub fn of(topic: &str) -> Self {
+        let rest = topic.strip_prefix(wws_protocol::constants::TOPIC_PREFIX).unwrap_or(topic);
+        if rest.starts_with("/swarm/") {
+            return Self::Discovery;
+        }
+        let function = rest
+            .strip_prefix("/s/")
+            .and_then(|scoped| scoped.split('/').nth(1))
+            .unwrap_or("");
+        match function {
+            "election" | "proposals" | "voting" => Self::Consensus,
+            "tasks" => Self::Tasks,
+            "results" => Self::Results,
+            "keepalive" => Self::Keepalive,
+            "hierarchy" => Self::Hierarchy,
+            _ => Self::Other,
+        }
+    }
+}
+
+impl std::str::FromStr for TopicCategory {
+    type Err = NetworkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "consensus" => Ok(Self::Consensus),
+            "tasks" => Ok(Self::Tasks),
+            "results" => Ok(Self::Results),
+            "keepalive" => Ok(Self::Keepalive),
+            "hierarchy" => Ok(Self::Hierarchy),
+            "discovery" => Ok(Self::Discovery),
+            "other" => Ok(Self::Other),
+            other => Err(NetworkError::InvalidConfig(format!(
+                "unknown topic category {other:?} (expected consensus, tasks, results, keepalive, hierarchy, discovery or other)"
+            ))),
+        }
+    }
+}
+
+/// Publish and mesh priority of a topic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TopicPriority {
+    /// Published ahead of normal traffic, with [`TopicMeshConfig`] scoring.
+    High,
+    /// GossipSub defaults.
+    Normal,
+}
+
+/// GossipSub scoring weights applied to high-priority topics.
+///
+/// Only rewarding components are set: peers gain score for time spent in
+/// the topic's mesh and for first deliveries, which GossipSub uses to keep
+/// them when pruning an oversized mesh and to graft better peers in. No
+/// component can push a peer towards the gossip or graylist thresholds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TopicMeshConfig {
+    /// Weight of the topic's score in a peer's overall score.
+    pub topic_weight: f64,
+    /// Score per second in the mesh.
+    pub time_in_mesh_weight: f64,
+    /// Score per message first delivered on the topic.
+    pub first_message_deliveries_weight: f64,
+}
+
+impl Default for TopicMeshConfig {
+    fn default() -> Self {
+        Self {
+            topic_weight: 1.0,
+            time_in_mesh_weight: 0.01,
+            first_message_deliveries_weight: 1.0,
+        }
+    }
+}
+
+impl TopicMeshConfig {
+    /// GossipSub topic scoring parameters for these weights.
+    pub fn score_params(&self) -> gossipsub::TopicScoreParams {
+        gossipsub::TopicScoreParams {
+            topic_weight: self.topic_weight,
+            time_in_mesh_weight: self.time_in_mesh_weight,
+            time_in_mesh_quantum: Duration::from_secs(1),
+            time_in_mesh_cap: 3600.0,
+            first_message_deliveries_weight: self.first_message_deliveries_weight,
+            first_message_deliveries_decay: 0.5,
+            first_message_deliveries_cap: 100.0,
+            mesh_message_deliveries_weight: 0.0,
+            mesh_failure_penalty_weight: 0.0,
+            invalid_message_deliveries_weight: 0.0,
+            ..Default::default()
+        }
+    }
+}
+
+/// Which topic categories are high priority, and the scoring they get.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TopicPriorities {
+    /// Categories published and meshed with high priority.
+    pub high: BTreeSet<TopicCategory>,
+    /// Scoring applied to high-priority topics.
+    pub mesh: TopicMeshConfig,
+}
+
+impl Default for TopicPriorities {
+    fn default() -> Self {
+        Self {
+            high: BTreeSet::from([TopicCategory::Consensus]),
+            mesh: TopicMeshConfig::default(),
+        }
+    }
+}
+
+impl TopicPriorities {
+    /// Every category at normal priority.
+    pub fn none() -> Self {
+        Self {
+            high: BTreeSet::new(),
+            mesh: TopicMeshConfig::default(),
+        }
+    }
+
+    /// Priority of `topic`.
+    pub fn priority(&self, topic: &str) -> TopicPriority {
+        if self.high.contains(&TopicCategory::of(topic)) {
+            TopicPriority::High
+        } else {
+            TopicPriority::Normal
+        }
+    }
+
+    /// Dedicated mesh configuration for `topic`, or `None` when it keeps
+    /// GossipSub's defaults.
+    pub fn mesh_config(&self, topic: &str) -> Option<&TopicMeshConfig> {
+        (self.priority(topic) == TopicPriority::High).then_some(&self.mesh)
+    }
+
+    /// Peer scoring parameters that enable per-topic scoring without any
+    /// penalties, or `None` when no category is high priority.
+    ///
+    /// IP colocation and behaviour penalties are off: local and NATed
+    /// swarms often share addresses, and a penalised peer could be
+    /// graylisted off non-consensus topics as well.
+    pub fn peer_score_params(&self) -> Option<gossipsub::PeerScoreParams> {
+        (!self.high.is_empty()).then(|| gossipsub::PeerScoreParams {
+            ip_colocation_factor_weight: 0.0,
+            behaviour_penalty_weight: 0.0,
+            ..Default::default()
+        })
+    }
+}
+
+/// How a topic name is turned into a GossipSub topic hash.
+///
+/// Peers only share a topic when they hash it the same way, so every
+/// member of a swarm must use the same setting for it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TopicHashing {
+    /// The name is the hash (`IdentTopic`).
+    #[default]
+    Ident,
+    /// Base64 SHA-256 digest of the name (`Sha256Topic`), so topic names
+    /// do not travel in subscriptions and message headers.
+    Sha256,
+}
+
+impl TopicHashing {
+    /// GossipSub hash of `topic`.
+    pub fn hash(self, topic: &str) -> TopicHash {
+        match self {
+            Self::Ident => IdentTopic::new(topic).hash(),
+            Self::Sha256 => Sha256Topic::new(topic).hash(),
+        }
+    }
+
+    fn subscribe(
+        self,
+        gossipsub: &mut gossipsub::Behaviour,
+        topic: &str,
+    ) -> Result<bool, gossipsub::SubscriptionError> {
+        match self {
+            Self::Ident => gossipsub.subscribe(&IdentTopic::new(topic)),
+            Self::Sha256 => gossipsub.subscribe(&Sha256Topic::new(topic)),
+        }
+    }
+
+    fn unsubscribe(
+        self,
+        gossipsub: &mut gossipsub::Behaviour,
+        topic: &str,
+    ) -> Result<bool, gossipsub::PublishError> {
+        match self {
+            Self::Ident => gossipsub.unsubscribe(&IdentTopic::new(topic)),
+            Self::Sha256 => gossipsub.unsubscribe(&Sha256Topic::new(topic)),
+        }
+    }
+
+    fn set_topic_params(
+        self,
+        gossipsub: &mut gossipsub::Behaviour,
+        topic: &str,
+        params: gossipsub::TopicScoreParams,
+    ) -> Result<(), &'static str> {
+        fn set<H: Hasher>(
+            gossipsub: &mut gossipsub::Behaviour,
+            topic: Topic<H>,
+            params: gossipsub::TopicScoreParams,
+        ) -> Result<(), &'static str> {
+            gossipsub.set_topic_params(topic, params)
+        }
+        match self {
+            Self::Ident => set(gossipsub, IdentTopic::new(topic), params),
+            Self::Sha256 => set(gossipsub, Sha256Topic::new(topic), params),
+        }
+    }
+}
+
+impl std::str::FromStr for TopicHashing {
+    type Err = NetworkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "ident" => Ok(Self::Ident),
+            "sha256" => Ok(Self::Sha256),
+            other => Err(NetworkError::InvalidConfig(format!(
+                "unknown topic hashing {other:?} (expected ident or sha256)"
+            ))),
+        }
+    }
+}
+
+/// Topic hashing for unscoped topics and for each swarm.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TopicHashingConfig {
+    /// Hashing of unscoped topics and of swarms not listed in `swarms`.
+    pub default: TopicHashing,
+    /// Hashing of swarm-scoped (`/s/<swarm_id>/...`) topics, by swarm ID.
+    pub swarms: BTreeMap<String, TopicHashing>,
+}
+
+impl TopicHashingConfig {
+    /// Hashing used for `topic`.
+    pub fn hashing_for(&self, topic: &str) -> TopicHashing {
+        wws_protocol::SwarmTopics::swarm_of(topic)
+            .and_then(|swarm_id| self.swarms.get(swarm_id))
+            .copied()
+            .unwrap_or(self.default)
+    }
+
+    /// GossipSub hash of `topic`.
+    pub fn hash(&self, topic: &str) -> TopicHash {
+        self.hashing_for(topic).hash(topic)
+    }
+}
+
 /// Manages GossipSub topic subscriptions for a WWS node.
 ///
 /// Topics are organized by protocol function. Each topic is tracked
-/// by its hash for efficient lookup during message routing.
+/// by its hash for efficient lookup during message routing. The tracked set
+/// is the node's *intended* subscriptions: if GossipSub loses one of them,
+/// [`TopicManager::resubscribe_missing`] restores it.
 pub struct TopicManager {
-    /// Map from topic hash to the topic itself for reverse lookup.
-    subscribed: HashMap<TopicHash, IdentTopic>,
+    /// Hashes of the intended subscriptions.
+    subscribed: HashSet<TopicHash>,
+    /// Topic name behind every subscribed hash. Entries are dropped on
+    /// unsubscribe, so the map stays as small as `subscribed`.
+    names: HashMap<TopicHash, String>,
+    /// Message counts keyed by resolved topic name.
+    counters: HashMap<String, TopicCounters>,
+    /// Priority and mesh scoring per topic category.
+    priorities: TopicPriorities,
+    /// How topic names are hashed.
+    hashing: TopicHashingConfig,
+}
+
+/// Messages published and received on one topic since startup.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopicCounters {
+    /// Messages this node published on the topic.
+    pub sent: u64,
+    /// Messages this node received on the topic.
+    pub received: u64,
 }
 
 impl TopicManager {
     /// Create a new empty topic manager.
     pub fn new() -> Self {
+        Self::with_priorities(TopicPriorities::none())
+    }
+
+    /// Create a topic manager that applies `priorities` to topics as they
+    /// are subscribed.
+    pub fn with_priorities(priorities: TopicPriorities) -> Self {
         Self {
-            subscribed: HashMap::new(),
+            subscribed: HashSet::new(),
+            names: HashMap::new(),
+            counters: HashMap::new(),
+            priorities,
+            hashing: TopicHashingConfig::default(),
         }
     }
 
+    /// Hash topic names according to `hashing`.
+    pub fn with_hashing(mut self, hashing: TopicHashingConfig) -> Self {
+        self.hashing = hashing;
+        self
+    }
+
+    /// How topic names are hashed.
+    pub fn hashing(&self) -> &TopicHashingConfig {
+        &self.hashing
+    }
+
+    /// GossipSub hash of `topic_str`.
+    pub fn hash(&self, topic_str: &str) -> TopicHash {
+        self.hashing.hash(topic_str)
+    }
+
+    /// Priority and mesh scoring per topic category.
+    pub fn priorities(&self) -> &TopicPriorities {
+        &self.priorities
+    }
+
     /// Subscribe to a topic on the given GossipSub behaviour.
     ///
     /// Returns the topic hash on success, or an error if subscription fails.
@@ -35,12 +378,20 @@ impl TopicManager {
         gossipsub: &mut gossipsub::Behaviour,
         topic_str: &str,
     ) -> Result<TopicHash, NetworkError> {
-        let topic = IdentTopic::new(topic_str);
-        let hash = topic.hash();
-        gossipsub
-            .subscribe(&topic)
+        let hashing = self.hashing.hashing_for(topic_str);
+        hashing
+            .subscribe(gossipsub, topic_str)
             .map_err(|e| NetworkError::SubscriptionError(format!("{e}")))?;
-        self.subscribed.insert(hash.clone(), topic);
+        if let Some(mesh) = self.priorities.mesh_config(topic_str) {
+            // Only fails when peer scoring is off, in which case the topic
+            // simply keeps GossipSub's defaults.
+            if let Err(e) = hashing.set_topic_params(gossipsub, topic_str, mesh.score_params()) {
+                tracing::debug!(topic = %topic_str, error = e, "High-priority topic scoring unavailable");
+            }
+        }
+        let hash = self.hash(topic_str);
+        self.names.insert(hash.clone(), topic_str.to_string());
+        self.subscribed.insert(hash.clone());
         tracing::info!(topic = %topic_str, "Subscribed to GossipSub topic");
         Ok(hash)
     }
@@ -51,29 +402,81 @@ impl TopicManager {
         gossipsub: &mut gossipsub::Behaviour,
         topic_str: &str,
     ) -> Result<(), NetworkError> {
-        let topic = IdentTopic::new(topic_str);
-        let hash = topic.hash();
-        gossipsub
-            .unsubscribe(&topic)
+        self.hashing
+            .hashing_for(topic_str)
+            .unsubscribe(gossipsub, topic_str)
             .map_err(|e| NetworkError::SubscriptionError(format!("{e}")))?;
+        let hash = self.hash(topic_str);
         self.subscribed.remove(&hash);
+        self.names.remove(&hash);
         tracing::info!(topic = %topic_str, "Unsubscribed from GossipSub topic");
         Ok(())
     }
 
-    /// Look up the topic string for a given topic hash.
-    pub fn resolve_topic(&self, hash: &TopicHash) -> Option<&IdentTopic> {
-        self.subscribed.get(hash)
+    /// Look up the topic name for a subscribed hash.
+    pub fn resolve_topic(&self, hash: &TopicHash) -> Option<&str> {
+        self.names.get(hash).map(String::as_str)
     }
 
     /// Check if we are subscribed to a given topic hash.
     pub fn is_subscribed(&self, hash: &TopicHash) -> bool {
-        self.subscribed.contains_key(hash)
+        self.subscribed.contains(hash)
+    }
+
+    /// Topic name for `hash`: the registered name when known, otherwise
+    /// the hash itself.
+    pub fn topic_name(&self, hash: &TopicHash) -> String {
+        self.resolve_topic(hash)
+            .map(str::to_string)
+            .unwrap_or_else(|| hash.to_string())
+    }
+
+    /// Count a message published on `topic_str`.
+    pub fn record_sent(&mut self, topic_str: &str) {
+        self.counters.entry(topic_str.to_string()).or_default().sent += 1;
+    }
+
+    /// Count a message received on the topic with `hash`, returning the
+    /// resolved topic name.
+    pub fn record_received(&mut self, hash: &TopicHash) -> String {
+        let name = self.topic_name(hash);
+        self.counters.entry(name.clone()).or_default().received += 1;
+        name
+    }
+
+    /// Sent/received message counts per topic name, including topics no
+    /// longer subscribed.
+    pub fn topic_stats(&self) -> BTreeMap<String, TopicCounters> {
+        self.counters.iter().map(|(name, c)| (name.clone(), *c)).collect()
     }
 
     /// Get all currently subscribed topic hashes.
     pub fn subscribed_topics(&self) -> Vec<TopicHash> {
-        self.subscribed.keys().cloned().collect()
+        self.subscribed.iter().cloned().collect()
+    }
+
+    /// Re-subscribe to every intended topic GossipSub is no longer
+    /// subscribed to, returning the topics that were restored.
+    pub fn resubscribe_missing(&mut self, gossipsub: &mut gossipsub::Behaviour) -> Vec<String> {
+        let active: HashSet<TopicHash> = gossipsub.topics().cloned().collect();
+        let mut restored = Vec::new();
+        for hash in &self.subscribed {
+            if active.contains(hash) {
+                continue;
+            }
+            let topic = self.topic_name(hash);
+            match self.hashing.hashing_for(&topic).subscribe(gossipsub, &topic) {
+                Ok(_) => {
+                    tracing::warn!(topic = %topic, "Re-subscribed to dropped GossipSub topic");
+                    restored.push(topic);
+                }
+                Err(e) => {
+                    tracing::warn!(topic = %topic, error = %e, "Failed to re-subscribe to GossipSub topic");
+                }
+            }
+        }
+        restored.sort();
+        restored
     }
 
     /// Subscribe to the core set of protocol topics that every node needs.
@@ -136,11 +539,12 @@ impl TopicManager {
         &mut self,
         gossipsub: &mut gossipsub::Behaviour,
         task_id: &str,
+        epoch: u64,
     ) -> Result<(), NetworkError> {
         use wws_protocol::SwarmTopics;
 
-        self.subscribe(gossipsub, &SwarmTopics::proposals(task_id))?;
-        self.subscribe(gossipsub, &SwarmTopics::voting(task_id))?;
+        self.subscribe(gossipsub, &SwarmTopics::proposals(task_id, epoch))?;
+        self.subscribe(gossipsub, &SwarmTopics::voting(task_id, epoch))?;
         self.subscribe(gossipsub, &SwarmTopics::results(task_id))?;
         tracing::info!(task_id, "Subscribed to task-specific topics");
         Ok(())
@@ -151,12 +555,13 @@ impl TopicManager {
         &mut self,
         gossipsub: &mut gossipsub::Behaviour,
         task_id: &str,
+        epoch: u64,
     ) -> Result<(), NetworkError> {
         use wws_protocol::SwarmTopics;
 
         // Best-effort unsubscribe; ignore errors for topics we may not be subscribed to.
-        let _ = self.unsubscribe(gossipsub, &SwarmTopics::proposals(task_id));
-        let _ = self.unsubscribe(gossipsub, &SwarmTopics::voting(task_id));
+        let _ = self.unsubscribe(gossipsub, &SwarmTopics::proposals(task_id, epoch));
+        let _ = self.unsubscribe(gossipsub, &SwarmTopics::voting(task_id, epoch));
         let _ = self.unsubscribe(gossipsub, &SwarmTopics::results(task_id));
         tracing::debug!(task_id, "Unsubscribed from task-specific topics");
         Ok(())
@@ -3,32 +3,78 @@
 //! Builds a libp2p Swarm using the SwarmBuilder API with:
 //! - TCP transport for reliable connections
 //! - QUIC transport for low-latency UDP connections
+//! - DNS resolution so peers can be dialed by `/dns4`, `/dns6` or `/dnsaddr` name
 //! - Noise protocol for authenticated encryption
 //! - Yamux for stream multiplexing
 //! - Circuit relay client for NAT traversal via relay nodes
 //! - DCUtR (hole-punching) for direct peer connections through NAT
-//! - Optional idle connection timeout
+//! - Configurable dial/handshake timeout and idle connection timeout
+//! - Optional private-network PSK wrapping every TCP connection (see [`crate::pnet`])
 
 use std::time::Duration;
 
-use libp2p::Swarm;
+use libp2p::core::muxing::StreamMuxerBox;
+use libp2p::core::transport::OptionalTransport;
+use libp2p::{Swarm, Transport};
+use serde::Serialize;
 
 use crate::behaviour::{BehaviourConfig, SwarmBehaviour};
+use crate::pnet::{PnetConfig, PreSharedKey, PskFingerprint};
 use crate::NetworkError;
 
 /// Configuration for the transport layer.
 #[derive(Debug, Clone)]
 pub struct TransportConfig {
+    /// Upper bound on establishing an outbound connection (TCP connect plus
+    /// security and multiplexer negotiation, or the QUIC handshake).
+    pub dial_timeout: Duration,
     /// How long an idle connection stays open before being closed.
     pub idle_connection_timeout: Duration,
+    /// Whether the QUIC transport is built alongside TCP.
+    pub enable_quic: bool,
+    /// Pre-shared key restricting connections to nodes holding the same
+    /// key. QUIC is not built when a key is set.
+    pub psk: Option<[u8; 32]>,
     /// Behaviour configuration.
     pub behaviour_config: BehaviourConfig,
 }
 
+/// Transports a swarm built from a [`TransportConfig`] can dial and listen on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct TransportFeatures {
+    pub tcp: bool,
+    pub quic: bool,
+    /// WebSocket is not compiled into this build.
+    pub ws: bool,
+    /// Connections are restricted to a private network by a PSK.
+    pub pnet: bool,
+}
+
+impl TransportConfig {
+    /// The transports [`build_swarm`] wires up for this configuration.
+    pub fn features(&self) -> TransportFeatures {
+        TransportFeatures {
+            tcp: true,
+            quic: self.enable_quic && self.psk.is_none(),
+            ws: false,
+            pnet: self.psk.is_some(),
+        }
+    }
+
+    /// Fingerprint of the configured PSK, if any. Nodes whose fingerprints
+    /// differ cannot connect to each other.
+    pub fn psk_fingerprint(&self) -> Option<PskFingerprint> {
+        self.psk.as_ref().map(PskFingerprint::of)
+    }
+}
+
 impl Default for TransportConfig {
     fn default() -> Self {
         Self {
+            dial_timeout: Duration::from_secs(10),
             idle_connection_timeout: Duration::from_secs(60),
+            enable_quic: true,
+            psk: None,
             behaviour_config: BehaviourConfig::default(),
         }
     }
@@ -57,31 +103,72 @@ pub fn build_swarm_with_keypair(
 /// Internal helper: build the swarm from a keypair + config.
 ///
 /// The builder chain is:
-/// 1. `.with_tcp()` — reliable byte-stream transport
-/// 2. `.with_quic()` — low-latency UDP transport (alongside TCP)
-/// 3. `.with_relay_client()` — circuit relay transport + behaviour
-/// 4. `.with_behaviour(|key, relay_client| ...)` — composite WWS behaviour
+/// 1. TCP + Noise + Yamux, wrapped in `dial_timeout` — reliable byte-stream transport.
+///    With a `psk`, the PSK handshake runs on the raw socket before Noise
+/// 2. QUIC with `dial_timeout` as handshake timeout — low-latency UDP transport,
+///    left out when `enable_quic` is false or a `psk` is set
+/// 3. `.with_dns()` — resolves `/dns4`, `/dns6` and `/dnsaddr` via the system resolver
+/// 4. `.with_relay_client()` — circuit relay transport + behaviour
+/// 5. `.with_behaviour(|key, relay_client| ...)` — composite WWS behaviour
+///
+/// TCP and QUIC are added via `with_other_transport` rather than the builder
+/// shortcuts so the dial timeout can be applied to each.
 ///
-/// The `relay_client` is produced by step 3 and threaded into the behaviour
+/// The `relay_client` is produced by step 4 and threaded into the behaviour
 /// constructor, where it is stored as a field in `SwarmBehaviour`.
 fn build_swarm_inner(
     keypair: libp2p::identity::Keypair,
     config: TransportConfig,
 ) -> Result<Swarm<SwarmBehaviour>, NetworkError> {
     let behaviour_config = config.behaviour_config.clone();
+    let dial_timeout = config.dial_timeout;
+    let enable_quic = config.features().quic;
+    let psk = config.psk;
 
     let swarm = libp2p::SwarmBuilder::with_existing_identity(keypair)
         .with_tokio()
-        // Step 1: TCP transport with Noise + Yamux.
-        .with_tcp(
-            libp2p::tcp::Config::default(),
-            libp2p::noise::Config::new,
-            libp2p::yamux::Config::default,
-        )
+        // Step 1: TCP transport with Noise + Yamux, bounded by the dial timeout.
+        .with_other_transport(|key| {
+            let noise = libp2p::noise::Config::new(key)?;
+            let tcp = libp2p::tcp::tokio::Transport::new(libp2p::tcp::Config::default());
+            let transport = match psk {
+                Some(psk) => tcp
+                    .and_then(move |socket, _| PnetConfig::new(PreSharedKey::new(psk)).handshake(socket))
+                    .upgrade(libp2p::core::upgrade::Version::V1Lazy)
+                    .authenticate(noise)
+                    .multiplex(libp2p::yamux::Config::default())
+                    .timeout(dial_timeout)
+                    .map(|(peer, muxer), _| (peer, StreamMuxerBox::new(muxer)))
+                    .boxed(),
+                None => tcp
+                    .upgrade(libp2p::core::upgrade::Version::V1Lazy)
+                    .authenticate(noise)
+                    .multiplex(libp2p::yamux::Config::default())
+                    .timeout(dial_timeout)
+                    .map(|(peer, muxer), _| (peer, StreamMuxerBox::new(muxer)))
+                    .boxed(),
+            };
+            Ok::<_, Box<dyn std::error::Error + Send + Sync>>(transport)
+        })
         .map_err(|e| NetworkError::Transport(format!("TCP transport error: {e}")))?
-        // Step 2: QUIC transport (UDP), added alongside TCP.
-        .with_quic()
-        // Step 3: Circuit relay client transport + behaviour.
+        // Step 2: QUIC transport (UDP), added alongside TCP unless disabled.
+        .with_other_transport(|key| {
+            if !enable_quic {
+                return OptionalTransport::none();
+            }
+            let mut quic_config = libp2p::quic::Config::new(key);
+            quic_config.handshake_timeout = dial_timeout;
+            OptionalTransport::some(
+                libp2p::quic::tokio::Transport::new(quic_config)
+                    .map(|(peer, conn), _| (peer, StreamMuxerBox::new(conn))),
+            )
+        })
+        .map_err(|e| NetworkError::Transport(format!("QUIC transport error: {e}")))?
+        // Step 3: DNS resolution for `/dns4`, `/dns6` and `/dnsaddr` addresses,
+        // wrapping both transports above.
+        .with_dns()
+        .map_err(|e| NetworkError::Transport(format!("DNS resolver error: {e}")))?
+        // Step 4: Circuit relay client transport + behaviour.
         // The relay client behaviour is returned from the builder and passed
         // into the with_behaviour closure as the second argument.
         .with_relay_client(
@@ -89,7 +176,7 @@ fn build_swarm_inner(
             libp2p::yamux::Config::default,
         )
         .map_err(|e| NetworkError::Transport(format!("Relay client transport error: {e}")))?
-        // Step 4: Composite WWS behaviour.
+        // Step 5: Composite WWS behaviour.
         .with_behaviour(|key, relay_client| {
             SwarmBehaviour::new(key, &behaviour_config, relay_client)
                 .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
@@ -0,0 +1,96 @@
+//! Tests for per-peer connection metrics.
+
+use std::time::Duration;
+
+use libp2p::multiaddr::Protocol;
+use libp2p::swarm::ConnectionId;
+use wws_network::{ConnectionTracker, DiscoveryConfig, NetworkEvent, PeerId, SwarmHost, SwarmHostConfig};
+
+#[test]
+fn established_and_closed_connections_update_peer_counters() {
+    let mut tracker = ConnectionTracker::new();
+    let peer = PeerId::random();
+    let tcp: libp2p::Multiaddr = "/ip4/10.0.0.1/tcp/9000".parse().unwrap();
+    let quic: libp2p::Multiaddr = "/ip4/10.0.0.1/udp/9000/quic-v1".parse().unwrap();
+    let (first, second) = (ConnectionId::new_unchecked(1), ConnectionId::new_unchecked(2));
+
+    tracker.record_established(peer, first, tcp.clone(), true);
+    tracker.record_established(peer, second, quic.clone(), false);
+    let metrics = tracker.peer(&peer).expect("peer tracked");
+    assert_eq!(metrics.open_connections, 2);
+    assert_eq!(metrics.established, 2);
+    assert_eq!(metrics.dials_succeeded, 1);
+    assert_eq!(metrics.addrs.len(), 2);
+
+    tracker.record_closed(peer, first);
+    let metrics = tracker.peer(&peer).unwrap();
+    assert_eq!(metrics.open_connections, 1);
+    assert_eq!(metrics.closed, 1);
+    assert_eq!(metrics.addrs, vec![quic]);
+
+    // Disconnected peers keep their history.
+    tracker.record_closed(peer, second);
+    let metrics = tracker.peer(&peer).unwrap();
+    assert_eq!(metrics.open_connections, 0);
+    assert!(metrics.addrs.is_empty());
+    assert_eq!((metrics.established, metrics.closed), (2, 2));
+}
+
+#[test]
+fn dial_failures_are_attributed_when_peer_is_known() {
+    let mut tracker = ConnectionTracker::new();
+    let peer = PeerId::random();
+    tracker.record_dial_failure(Some(peer), "connection refused".into());
+    tracker.record_dial_failure(None, "no route".into());
+
+    let snapshot = tracker.snapshot();
+    assert_eq!(snapshot.unattributed_dial_failures, 1);
+    let metrics = &snapshot.peers[&peer];
+    assert_eq!(metrics.dials_failed, 1);
+    assert_eq!(metrics.last_dial_error.as_deref(), Some("connection refused"));
+    assert_eq!(metrics.established, 0);
+}
+
+#[tokio::test]
+async fn host_reports_metrics_for_connected_peer() {
+    let config = || SwarmHostConfig {
+        listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+        discovery: DiscoveryConfig::static_only(Vec::new()),
+        ..Default::default()
+    };
+    let (host_a, handle_a, mut events_a) = SwarmHost::new(config()).expect("host A builds");
+    let (host_b, handle_b, mut events_b) = SwarmHost::new(config()).expect("host B builds");
+    let task_a = tokio::spawn(host_a.run());
+    let task_b = tokio::spawn(host_b.run());
+
+    let addr_b = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let NetworkEvent::Listening(addr) = events_b.recv().await.expect("event channel open") {
+                if addr.iter().any(|p| matches!(p, Protocol::Tcp(_))) {
+                    return addr;
+                }
+            }
+        }
+    })
+    .await
+    .expect("host B listens");
+    handle_a.dial(addr_b.clone()).await.expect("dial accepted");
+    tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            if let NetworkEvent::PeerConnected(_) = events_a.recv().await.expect("event channel open") {
+                return;
+            }
+        }
+    })
+    .await
+    .expect("hosts connect");
+
+    let metrics = handle_a.connection_metrics().await.expect("metrics available");
+    let peer = &metrics.peers[&handle_b.local_peer_id()];
+    assert_eq!(peer.open_connections, 1);
+    assert_eq!(peer.dials_succeeded, 1);
+    assert_eq!(peer.addrs, vec![addr_b]);
+
+    task_a.abort();
+    task_b.abort();
+}
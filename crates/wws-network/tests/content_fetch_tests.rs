@@ -0,0 +1,79 @@
+//! Tests for chunked content fetch bookkeeping and progress reporting.
+
+use wws_network::content_fetch::{cid_matches, compute_cid, ContentFetch, ContentManifest};
+
+fn sample_content(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+#[test]
+fn test_progress_reported_monotonically() {
+    let data = sample_content(10_000);
+    let (manifest, chunks) = ContentManifest::split(&data, 1_000);
+    assert_eq!(manifest.chunk_cids.len(), 10);
+
+    let mut fetch = ContentFetch::new(manifest.root_cid.clone());
+    let missing = fetch.on_manifest(manifest).expect("manifest accepted");
+    assert_eq!(missing.len(), 10);
+    assert_eq!(fetch.progress().received_chunks, 0);
+    assert_eq!(fetch.progress().total_chunks, 10);
+
+    // Deliver chunks out of order, with duplicates and a corrupted chunk mixed in.
+    let mut arrivals: Vec<(String, Vec<u8>)> = chunks.iter().rev().cloned().collect();
+    arrivals.insert(3, chunks[5].clone());
+    arrivals.insert(6, (chunks[0].0.clone(), b"corrupted".to_vec()));
+
+    let mut reported = Vec::new();
+    for (cid, bytes) in arrivals {
+        if let Some(progress) = fetch.on_chunk(&cid, bytes) {
+            assert_eq!(progress.total_chunks, 10);
+            reported.push(progress.received_chunks);
+        }
+    }
+
+    assert_eq!(reported, (1..=10).collect::<Vec<_>>());
+    assert!(fetch.is_complete());
+    assert_eq!(fetch.assemble().expect("assembles"), data);
+}
+
+#[test]
+fn test_manifest_root_mismatch_rejected() {
+    let (manifest, _) = ContentManifest::split(b"hello swarm", 4);
+    let mut fetch = ContentFetch::new(compute_cid(b"something else"));
+    assert!(fetch.on_manifest(manifest).is_err());
+    assert!(!fetch.is_complete());
+}
+
+#[test]
+fn test_cids_compare_by_digest() {
+    use sha2::{Digest, Sha256};
+    let data = b"hello swarm";
+    let legacy_root = hex::encode(Sha256::digest(data));
+    let (manifest, chunks) = ContentManifest::split(data, 4);
+    assert_eq!(wws_state::parse_cid(&manifest.root_cid).unwrap().digest.to_vec(), Sha256::digest(data).to_vec());
+
+    // A fetch requested by the legacy hex CID accepts the CIDv1 manifest.
+    let mut fetch = ContentFetch::new(legacy_root);
+    fetch.on_manifest(manifest).expect("same content");
+    for (cid, bytes) in chunks {
+        fetch.on_chunk(&cid, bytes).expect("chunk verifies");
+    }
+    assert_eq!(fetch.assemble().unwrap(), data);
+
+    // Content hashed with another algorithm verifies against its own CID.
+    let blake3_cid = wws_state::HashAlgo::Blake3.cid(data);
+    assert!(cid_matches(&blake3_cid, data));
+    assert!(!cid_matches(&blake3_cid, b"other"));
+    assert!(!cid_matches("not-a-cid", data));
+}
+
+#[test]
+fn test_chunk_not_in_manifest_ignored() {
+    let (manifest, _) = ContentManifest::split(b"abcdefgh", 4);
+    let mut fetch = ContentFetch::new(manifest.root_cid.clone());
+    fetch.on_manifest(manifest).unwrap();
+
+    let stray = b"zzzz".to_vec();
+    assert!(fetch.on_chunk(&compute_cid(&stray), stray).is_none());
+    assert_eq!(fetch.progress().received_chunks, 0);
+}
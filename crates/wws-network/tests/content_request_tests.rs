@@ -0,0 +1,74 @@
+//! Tests for direct content requests between peers.
+
+use std::time::Duration;
+
+use libp2p::multiaddr::Protocol;
+use wws_network::{ContentResponse, DiscoveryConfig, NetworkEvent, SwarmHost, SwarmHostConfig};
+
+#[test]
+fn response_content_is_hex_encoded() {
+    let response = ContentResponse {
+        data: Some(b"hi".to_vec()),
+    };
+    let json = serde_json::to_string(&response).unwrap();
+    assert_eq!(json, r#"{"data":"6869"}"#);
+    assert_eq!(serde_json::from_str::<ContentResponse>(&json).unwrap(), response);
+    let missing: ContentResponse = serde_json::from_str(r#"{"data":null}"#).unwrap();
+    assert_eq!(missing.data, None);
+}
+
+#[tokio::test]
+async fn peer_answers_content_request() {
+    let config = || SwarmHostConfig {
+        listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+        discovery: DiscoveryConfig::static_only(Vec::new()),
+        ..Default::default()
+    };
+    let (host_a, handle_a, mut events_a) = SwarmHost::new(config()).expect("host A builds");
+    let (host_b, handle_b, mut events_b) = SwarmHost::new(config()).expect("host B builds");
+    let task_a = tokio::spawn(host_a.run());
+    let task_b = tokio::spawn(host_b.run());
+
+    let addr_b = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let NetworkEvent::Listening(addr) = events_b.recv().await.expect("event channel open") {
+                if addr.iter().any(|p| matches!(p, Protocol::Tcp(_))) {
+                    return addr;
+                }
+            }
+        }
+    })
+    .await
+    .expect("host B listens");
+    handle_a.dial(addr_b).await.expect("dial accepted");
+    tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            if let NetworkEvent::PeerConnected(_) = events_a.recv().await.expect("event channel open") {
+                return;
+            }
+        }
+    })
+    .await
+    .expect("hosts connect");
+
+    // B serves "cid-1" and knows nothing else.
+    let responder = handle_b.clone();
+    let serve = tokio::spawn(async move {
+        loop {
+            if let Some(NetworkEvent::ContentRequested { request_id, cid, .. }) = events_b.recv().await {
+                let data = (cid == "cid-1").then(|| b"stored bytes".to_vec());
+                responder.respond_content(request_id, data).await.unwrap();
+            }
+        }
+    });
+
+    let peer_b = handle_b.local_peer_id();
+    let found = handle_a.request_content(peer_b, "cid-1").await.expect("request answered");
+    assert_eq!(found, Some(b"stored bytes".to_vec()));
+    let missing = handle_a.request_content(peer_b, "cid-2").await.expect("request answered");
+    assert_eq!(missing, None);
+
+    serve.abort();
+    task_a.abort();
+    task_b.abort();
+}
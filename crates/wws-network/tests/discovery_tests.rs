@@ -0,0 +1,233 @@
+//! Tests for pluggable discovery sources and the routing table dump.
+
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+use libp2p::kad;
+use libp2p::multiaddr::Protocol;
+use libp2p::{Multiaddr, PeerId};
+use wws_network::behaviour::BehaviourConfig;
+use wws_network::discovery::{DiscoveryConfig, DiscoveryManager, DiscoveryMode, KademliaBehaviour};
+use wws_network::NetworkError;
+use wws_network::{SwarmHost, SwarmHostConfig};
+
+fn kademlia() -> KademliaBehaviour {
+    let peer_id = PeerId::random();
+    kad::Behaviour::new(peer_id, kad::store::MemoryStore::new(peer_id))
+}
+
+#[test]
+fn static_only_discovery_dials_peers_without_mdns_or_random_walks() {
+    let seed = PeerId::random();
+    let peers: Vec<Multiaddr> = vec![
+        format!("/ip4/10.0.0.1/tcp/9000/p2p/{seed}").parse().unwrap(),
+        "/ip4/10.0.0.2/tcp/9000".parse().unwrap(),
+    ];
+    let config = DiscoveryConfig::static_only(peers.clone());
+    assert!(!config.mdns_enabled());
+    assert_eq!(config.source_names(), vec!["static"]);
+
+    let mut kad = kademlia();
+    let mut manager = DiscoveryManager::new(config);
+    let dials = manager.start(&mut kad).expect("static source starts");
+    assert_eq!(dials, peers);
+    assert!(manager.known_peers().contains(&seed));
+
+    // Neither a bootstrap query nor a random walk is issued.
+    manager.tick(&mut kad);
+    assert_eq!(kad.iter_queries().count(), 0);
+}
+
+#[test]
+fn standard_discovery_runs_mdns_and_random_walks() {
+    let config = DiscoveryConfig::default();
+    assert!(config.mdns_enabled());
+    assert_eq!(config.source_names(), vec!["mdns", "kademlia"]);
+
+    let mut kad = kademlia();
+    let mut manager = DiscoveryManager::new(config);
+    assert!(manager.start(&mut kad).unwrap().is_empty());
+    manager.tick(&mut kad);
+    assert_eq!(kad.iter_queries().count(), 1);
+
+    assert!(!DiscoveryConfig::standard(Vec::new(), false).mdns_enabled());
+}
+
+#[test]
+fn kademlia_bootstrap_peers_become_known_on_start() {
+    let seed = PeerId::random();
+    let addr: Multiaddr = format!("/ip4/10.0.0.1/tcp/9000/p2p/{seed}").parse().unwrap();
+    let config = DiscoveryConfig::standard(vec![(seed, addr)], false);
+
+    let mut kad = kademlia();
+    let mut manager = DiscoveryManager::new(config);
+    assert!(manager.start(&mut kad).unwrap().is_empty());
+    assert!(manager.known_peers().contains(&seed));
+    assert_eq!(manager.known_peer_count(), 1);
+}
+
+#[test]
+fn triggered_random_walk_queries_kademlia_immediately() {
+    let mut kad = kademlia();
+    let manager = DiscoveryManager::new(DiscoveryConfig::standard(Vec::new(), false));
+    assert!(manager.trigger_random_walk(&mut kad));
+    assert_eq!(kad.iter_queries().count(), 1);
+
+    // Static discovery has no walk to trigger.
+    let mut kad = kademlia();
+    let manager = DiscoveryManager::new(DiscoveryConfig::static_only(Vec::new()));
+    assert!(!manager.trigger_random_walk(&mut kad));
+    assert_eq!(kad.iter_queries().count(), 0);
+}
+
+#[tokio::test]
+async fn handle_triggers_random_walk_without_waiting_for_interval() {
+    let config = SwarmHostConfig {
+        listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+        discovery: DiscoveryConfig::standard(Vec::new(), false),
+        random_walk_interval: Duration::from_secs(3600),
+        ..Default::default()
+    };
+    let (host, handle, _events) = SwarmHost::new(config).expect("swarm host builds");
+    let host_task = tokio::spawn(host.run());
+
+    let started = tokio::time::timeout(Duration::from_secs(5), handle.trigger_random_walk())
+        .await
+        .expect("walk dispatched promptly")
+        .unwrap();
+    assert!(started);
+
+    host_task.abort();
+}
+
+#[test]
+fn discovery_modes_select_sources() {
+    let seed = PeerId::random();
+    let bootstrap = vec![(seed, format!("/ip4/10.0.0.1/tcp/9000/p2p/{seed}").parse().unwrap())];
+
+    let auto = DiscoveryConfig::for_mode(DiscoveryMode::Auto, bootstrap.clone(), &[], true).unwrap();
+    assert!(auto.mdns_enabled());
+    assert_eq!(auto.source_names(), vec!["mdns", "kademlia"]);
+    let auto_no_mdns = DiscoveryConfig::for_mode(DiscoveryMode::Auto, Vec::new(), &[], false).unwrap();
+    assert_eq!(auto_no_mdns.source_names(), vec!["kademlia"]);
+
+    let mdns_only = DiscoveryConfig::for_mode(DiscoveryMode::MdnsOnly, bootstrap.clone(), &[], false).unwrap();
+    assert!(mdns_only.mdns_enabled());
+    assert_eq!(mdns_only.source_names(), vec!["mdns"]);
+    let mut kad = kademlia();
+    let mut manager = DiscoveryManager::new(mdns_only);
+    assert!(manager.start(&mut kad).unwrap().is_empty());
+    manager.tick(&mut kad);
+    assert_eq!(kad.iter_queries().count(), 0, "no bootstrap or random walk");
+
+    let bootstrap_only =
+        DiscoveryConfig::for_mode(DiscoveryMode::BootstrapOnly, bootstrap.clone(), &[], true).unwrap();
+    assert!(!bootstrap_only.mdns_enabled());
+    assert_eq!(bootstrap_only.source_names(), vec!["kademlia"]);
+    let mut behaviour = BehaviourConfig::default();
+    bootstrap_only.apply_to(&mut behaviour);
+    assert!(!behaviour.mdns_enabled);
+
+    let dns: Multiaddr = "/dns4/bootstrap.example.net/tcp/9000".parse().unwrap();
+    let static_only = DiscoveryConfig::for_mode(
+        DiscoveryMode::StaticOnly,
+        bootstrap.clone(),
+        std::slice::from_ref(&dns),
+        true,
+    )
+    .unwrap();
+    assert!(!static_only.mdns_enabled());
+    assert_eq!(static_only.source_names(), vec!["static"]);
+    let mut kad = kademlia();
+    let mut manager = DiscoveryManager::new(static_only);
+    assert_eq!(manager.start(&mut kad).unwrap(), vec![bootstrap[0].1.clone(), dns]);
+    manager.tick(&mut kad);
+    assert_eq!(kad.iter_queries().count(), 0, "no bootstrap or random walk");
+}
+
+#[test]
+fn bootstrap_only_discovery_requires_bootstrap_peers() {
+    let result = DiscoveryConfig::for_mode(DiscoveryMode::BootstrapOnly, Vec::new(), &[], false);
+    assert!(matches!(result, Err(NetworkError::InvalidConfig(_))));
+
+    // A DNS-resolved bootstrap address without a peer ID is enough.
+    let dns: Multiaddr = "/dns4/bootstrap.example.net/tcp/9000".parse().unwrap();
+    let config = DiscoveryConfig::for_mode(DiscoveryMode::BootstrapOnly, Vec::new(), &[dns], true)
+        .unwrap();
+    assert!(!config.mdns_enabled());
+    assert_eq!(config.source_names(), vec!["kademlia"]);
+}
+
+#[test]
+fn discovery_mode_parses_config_spellings() {
+    assert_eq!("bootstrap_only".parse::<DiscoveryMode>().unwrap(), DiscoveryMode::BootstrapOnly);
+    assert_eq!("MDNS-ONLY".parse::<DiscoveryMode>().unwrap(), DiscoveryMode::MdnsOnly);
+    assert_eq!("static-only".parse::<DiscoveryMode>().unwrap(), DiscoveryMode::StaticOnly);
+    assert!("multicast".parse::<DiscoveryMode>().is_err());
+}
+
+#[test]
+fn custom_kademlia_timers_are_threaded_into_kad_config() {
+    let mut config = DiscoveryConfig::static_only(Vec::new());
+    config.kademlia_query_timeout = Duration::from_secs(7);
+    config.kademlia_replication_interval = Some(Duration::from_secs(123));
+    config.kademlia_record_ttl = None;
+
+    let mut behaviour = BehaviourConfig::default();
+    config.apply_to(&mut behaviour);
+    assert!(!behaviour.mdns_enabled);
+    assert_eq!(behaviour.kad_query_timeout, Duration::from_secs(7));
+    assert_eq!(behaviour.kad_replication_interval, Some(Duration::from_secs(123)));
+    assert_eq!(behaviour.kad_record_ttl, None);
+
+    // kad::Config exposes no getters, so inspect its Debug output.
+    let kad_config = format!("{:?}", behaviour.kademlia_config().expect("valid protocol"));
+    assert!(kad_config.contains("timeout: 7s"), "{kad_config}");
+    assert!(kad_config.contains("record_replication_interval: Some(123s)"), "{kad_config}");
+    assert!(kad_config.contains("record_ttl: None"), "{kad_config}");
+}
+
+#[test]
+fn custom_replication_factor_is_applied_to_kad_config() {
+    let defaults = BehaviourConfig::default();
+    assert_eq!(defaults.kad_replication_factor, kad::K_VALUE);
+    let kad_config = format!("{:?}", defaults.kademlia_config().expect("valid protocol"));
+    assert!(kad_config.contains("replication_factor: 20"), "{kad_config}");
+
+    let mut config = DiscoveryConfig::standard(Vec::new(), false);
+    config.kademlia_replication_factor = NonZeroUsize::new(32).unwrap();
+    let mut behaviour = BehaviourConfig::default();
+    config.apply_to(&mut behaviour);
+    assert_eq!(behaviour.kad_replication_factor.get(), 32);
+
+    let kad_config = format!("{:?}", behaviour.kademlia_config().expect("valid protocol"));
+    assert!(kad_config.contains("replication_factor: 32"), "{kad_config}");
+}
+
+#[tokio::test]
+async fn routing_table_dump_includes_added_peer() {
+    let config = SwarmHostConfig {
+        listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+        discovery: DiscoveryConfig::static_only(Vec::new()),
+        ..Default::default()
+    };
+    let (host, handle, _events) = SwarmHost::new(config).expect("swarm host builds");
+    let host_task = tokio::spawn(host.run());
+
+    assert!(handle.routing_table().await.unwrap().is_empty());
+
+    let peer = PeerId::random();
+    let addr: Multiaddr = "/ip4/10.0.0.7/tcp/9000".parse().unwrap();
+    handle.add_peer_address(peer, addr.clone()).await.unwrap();
+
+    let table = handle.routing_table().await.unwrap();
+    let entry = table
+        .iter()
+        .find(|e| e.peer_id == peer)
+        .expect("added peer is in the routing table");
+    // Kademlia stores the address with the peer ID appended.
+    assert_eq!(entry.addrs, vec![addr.with(Protocol::P2p(peer))]);
+    assert!(entry.bucket < 256);
+
+    host_task.abort();
+}
@@ -0,0 +1,57 @@
+//! Tests for fan-out of network events to `SwarmHandle::subscribe_events`.
+
+use std::time::Duration;
+
+use wws_network::{DiscoveryConfig, NetworkEvent, SwarmHost, SwarmHostConfig};
+
+#[tokio::test]
+async fn every_subscriber_receives_forwarded_events() {
+    let config = SwarmHostConfig {
+        listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+        discovery: DiscoveryConfig::static_only(Vec::new()),
+        ..Default::default()
+    };
+    let (host, handle, mut primary) = SwarmHost::new(config).expect("swarm host builds");
+    let mut first = handle.subscribe_events();
+    let mut second = handle.subscribe_events();
+    let host_task = tokio::spawn(host.run());
+
+    let listening = |event: NetworkEvent| match event {
+        NetworkEvent::Listening(addr) => Some(addr),
+        _ => None,
+    };
+    let from_first = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let Some(addr) = listening(first.recv().await.expect("first subscriber open")) {
+                break addr;
+            }
+        }
+    })
+    .await
+    .expect("first subscriber sees the listen address");
+    let from_second = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let Some(addr) = listening(second.recv().await.expect("second subscriber open")) {
+                break addr;
+            }
+        }
+    })
+    .await
+    .expect("second subscriber sees the listen address");
+    assert_eq!(from_first, from_second);
+
+    // The primary receiver still gets the same event.
+    let from_primary = tokio::time::timeout(Duration::from_secs(5), async {
+        while let Some(event) = primary.recv().await {
+            if let Some(addr) = listening(event) {
+                return addr;
+            }
+        }
+        panic!("primary receiver closed");
+    })
+    .await
+    .expect("primary receiver sees the listen address");
+    assert_eq!(from_primary, from_first);
+
+    host_task.abort();
+}
@@ -0,0 +1,132 @@
+//! Tests for listening on several addresses at once and dialing by DNS name.
+
+use std::time::Duration;
+
+use libp2p::multiaddr::Protocol;
+use wws_network::{DiscoveryConfig, NetworkEvent, SwarmHost, SwarmHostConfig};
+
+#[tokio::test]
+async fn host_listens_on_every_configured_address() {
+    let config = SwarmHostConfig {
+        listen_addrs: vec![
+            "/ip4/127.0.0.1/tcp/0".parse().unwrap(),
+            "/ip4/127.0.0.1/udp/0/quic-v1".parse().unwrap(),
+        ],
+        discovery: DiscoveryConfig::static_only(Vec::new()),
+        ..Default::default()
+    };
+    let (host, _handle, mut events) = SwarmHost::new(config).expect("swarm host builds");
+    let host_task = tokio::spawn(host.run());
+
+    let mut listening = Vec::new();
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while listening.len() < 2 {
+            match events.recv().await.expect("event channel open") {
+                NetworkEvent::Listening(addr) => listening.push(addr),
+                _ => continue,
+            }
+        }
+    })
+    .await
+    .expect("both listen addresses are reported");
+
+    assert!(listening
+        .iter()
+        .any(|addr| addr.iter().any(|p| matches!(p, Protocol::Tcp(_)))));
+    assert!(listening
+        .iter()
+        .any(|addr| addr.iter().any(|p| matches!(p, Protocol::QuicV1))));
+
+    host_task.abort();
+}
+
+#[test]
+fn single_address_constructor_keeps_defaults() {
+    let addr: libp2p::Multiaddr = "/ip4/127.0.0.1/tcp/9000".parse().unwrap();
+    let config = SwarmHostConfig::with_listen_addr(addr.clone());
+    assert_eq!(config.listen_addrs, vec![addr]);
+    assert_eq!(config.command_buffer, SwarmHostConfig::default().command_buffer);
+}
+
+#[test]
+fn host_without_listen_addresses_is_rejected() {
+    let config = SwarmHostConfig {
+        listen_addrs: Vec::new(),
+        discovery: DiscoveryConfig::static_only(Vec::new()),
+        ..Default::default()
+    };
+    assert!(SwarmHost::new(config).is_err());
+}
+
+#[test]
+fn host_without_quic_cannot_listen_on_quic() {
+    let mut config =
+        SwarmHostConfig::with_listen_addr("/ip4/127.0.0.1/udp/0/quic-v1".parse().unwrap());
+    config.transport.enable_quic = false;
+    config.discovery = DiscoveryConfig::static_only(Vec::new());
+    assert!(!config.transport.features().quic);
+    assert!(SwarmHost::new(config).is_err());
+}
+
+#[tokio::test]
+async fn host_dials_peer_by_dns_name() {
+    let config = || SwarmHostConfig {
+        listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+        discovery: DiscoveryConfig::static_only(Vec::new()),
+        ..Default::default()
+    };
+    let (host_a, handle_a, mut events_a) = SwarmHost::new(config()).expect("host A builds");
+    let (host_b, _handle_b, mut events_b) = SwarmHost::new(config()).expect("host B builds");
+    let task_a = tokio::spawn(host_a.run());
+    let task_b = tokio::spawn(host_b.run());
+
+    let port = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let NetworkEvent::Listening(addr) = events_b.recv().await.expect("event channel open") {
+                if let Some(port) = addr.iter().find_map(|p| match p {
+                    Protocol::Tcp(port) => Some(port),
+                    _ => None,
+                }) {
+                    return port;
+                }
+            }
+        }
+    })
+    .await
+    .expect("host B listens");
+
+    let dns_addr: libp2p::Multiaddr = format!("/dns4/localhost/tcp/{port}").parse().unwrap();
+    handle_a.dial(dns_addr).await.expect("dial by DNS name is accepted");
+    tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            if let NetworkEvent::PeerConnected(_) = events_a.recv().await.expect("event channel open") {
+                return;
+            }
+        }
+    })
+    .await
+    .expect("connected through the resolved address");
+
+    task_a.abort();
+    task_b.abort();
+}
+
+#[tokio::test]
+async fn shutdown_stops_host_event_loop() {
+    let config = SwarmHostConfig {
+        listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+        discovery: DiscoveryConfig::static_only(Vec::new()),
+        ..Default::default()
+    };
+    let (host, handle, _events) = SwarmHost::new(config).expect("swarm host builds");
+    let host_task = tokio::spawn(host.run());
+
+    handle.shutdown().await.expect("host acknowledges shutdown");
+    let result = tokio::time::timeout(Duration::from_secs(5), host_task)
+        .await
+        .expect("host stops promptly")
+        .expect("host task does not panic");
+    assert!(result.is_ok());
+    // Later commands find the host gone.
+    assert!(handle.connected_peers().await.is_err());
+}
@@ -0,0 +1,140 @@
+//! Tests for peer allow-list and deny-list enforcement.
+
+use std::time::Duration;
+
+use libp2p::identity::Keypair;
+use libp2p::multiaddr::Protocol;
+use tokio::sync::mpsc;
+use wws_network::{
+    DiscoveryConfig, NetworkError, NetworkEvent, PeerFilter, PeerId, RejectReason, SwarmHandle, SwarmHost,
+    SwarmHostConfig,
+};
+
+struct Node {
+    handle: SwarmHandle,
+    events: mpsc::Receiver<NetworkEvent>,
+    task: tokio::task::JoinHandle<Result<(), NetworkError>>,
+}
+
+fn start(keypair: Keypair, filter: PeerFilter) -> Node {
+    let mut config = SwarmHostConfig {
+        listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+        discovery: DiscoveryConfig::static_only(Vec::new()),
+        keypair: Some(keypair),
+        ..Default::default()
+    };
+    config.transport.behaviour_config.peer_filter = filter;
+    let (host, handle, events) = SwarmHost::new(config).expect("host builds");
+    Node {
+        handle,
+        events,
+        task: tokio::spawn(host.run()),
+    }
+}
+
+/// Dial `b` from `a` and return the first connect or rejection event seen on
+/// `watch`, which is either `a` or `b`.
+async fn dial_and_watch(a: &mut Node, b: &mut Node, watch_b: bool) -> NetworkEvent {
+    let addr_b = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let NetworkEvent::Listening(addr) = b.events.recv().await.expect("event channel open") {
+                if addr.iter().any(|p| matches!(p, Protocol::Tcp(_))) {
+                    return addr;
+                }
+            }
+        }
+    })
+    .await
+    .expect("host B listens");
+    a.handle.dial(addr_b).await.expect("dial accepted");
+    let events = if watch_b { &mut b.events } else { &mut a.events };
+    tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let event = events.recv().await.expect("event channel open");
+            if matches!(
+                event,
+                NetworkEvent::PeerConnected(_) | NetworkEvent::ConnectionRejected { .. }
+            ) {
+                return event;
+            }
+        }
+    })
+    .await
+    .expect("connection settles")
+}
+
+fn allowing(peers: &[PeerId]) -> PeerFilter {
+    PeerFilter {
+        allowed: peers.iter().copied().collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn deny_list_overrides_allow_list() {
+    let (friend, foe, stranger) = (PeerId::random(), PeerId::random(), PeerId::random());
+    let mut filter = allowing(&[friend, foe]);
+    filter.denied.insert(foe);
+
+    assert_eq!(filter.check(&friend), Ok(()));
+    assert_eq!(filter.check(&foe), Err(RejectReason::Denied));
+    assert_eq!(filter.check(&stranger), Err(RejectReason::NotAllowed));
+    assert!(PeerFilter::default().is_open());
+    assert_eq!(PeerFilter::default().check(&stranger), Ok(()));
+}
+
+#[tokio::test]
+async fn allow_listed_peer_connects() {
+    let (key_a, key_b) = (Keypair::generate_ed25519(), Keypair::generate_ed25519());
+    let peer_b = key_b.public().to_peer_id();
+    let mut a = start(key_a, allowing(&[peer_b]));
+    let mut b = start(key_b, PeerFilter::default());
+
+    let event = dial_and_watch(&mut a, &mut b, false).await;
+    assert!(matches!(event, NetworkEvent::PeerConnected(peer) if peer == peer_b));
+
+    a.task.abort();
+    b.task.abort();
+}
+
+#[tokio::test]
+async fn peer_missing_from_allow_list_is_rejected() {
+    let (key_a, key_b) = (Keypair::generate_ed25519(), Keypair::generate_ed25519());
+    let peer_b = key_b.public().to_peer_id();
+    let mut a = start(key_a, allowing(&[PeerId::random()]));
+    let mut b = start(key_b, PeerFilter::default());
+
+    let event = dial_and_watch(&mut a, &mut b, false).await;
+    assert!(matches!(
+        event,
+        NetworkEvent::ConnectionRejected { peer, reason: RejectReason::NotAllowed } if peer == peer_b
+    ));
+    assert!(a.handle.connected_peers().await.unwrap().is_empty());
+
+    a.task.abort();
+    b.task.abort();
+}
+
+#[tokio::test]
+async fn denied_peer_is_rejected_on_inbound_connection() {
+    let (key_a, key_b) = (Keypair::generate_ed25519(), Keypair::generate_ed25519());
+    let peer_a = key_a.public().to_peer_id();
+    let mut a = start(key_a, PeerFilter::default());
+    let mut b = start(
+        key_b,
+        PeerFilter {
+            denied: [peer_a].into(),
+            ..Default::default()
+        },
+    );
+
+    let event = dial_and_watch(&mut a, &mut b, true).await;
+    assert!(matches!(
+        event,
+        NetworkEvent::ConnectionRejected { peer, reason: RejectReason::Denied } if peer == peer_a
+    ));
+    assert!(b.handle.connected_peers().await.unwrap().is_empty());
+
+    a.task.abort();
+    b.task.abort();
+}
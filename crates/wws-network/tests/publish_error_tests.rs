@@ -0,0 +1,35 @@
+//! Tests for mapping GossipSub publish failures to `NetworkError`.
+
+use libp2p::gossipsub::PublishError;
+use wws_network::NetworkError;
+
+const TOPIC: &str = "/wws/1.0.0/s/public/tasks/tier1";
+
+#[test]
+fn insufficient_peers_maps_to_no_mesh_peers() {
+    let err = NetworkError::from_publish_error(TOPIC, PublishError::InsufficientPeers);
+    assert!(matches!(&err, NetworkError::NoMeshPeers(t) if t == TOPIC));
+    assert!(err.is_retryable());
+}
+
+#[test]
+fn duplicate_maps_to_publish_error() {
+    let err = NetworkError::from_publish_error(TOPIC, PublishError::Duplicate);
+    assert!(matches!(err, NetworkError::PublishError(_)));
+    assert!(!err.is_retryable());
+}
+
+#[test]
+fn message_too_large_maps_to_message_too_large() {
+    let err = NetworkError::from_publish_error(TOPIC, PublishError::MessageTooLarge);
+    assert!(matches!(&err, NetworkError::MessageTooLarge(t) if t == TOPIC));
+    assert!(!err.is_retryable());
+}
+
+#[test]
+fn transform_failed_maps_to_publish_error() {
+    let io = std::io::Error::other("compression failed");
+    let err = NetworkError::from_publish_error(TOPIC, PublishError::TransformFailed(io));
+    assert!(matches!(err, NetworkError::PublishError(_)));
+    assert!(!err.is_retryable());
+}
@@ -0,0 +1,132 @@
+//! Tests for retrying publishes made before a topic's mesh formed.
+
+use std::time::{Duration, Instant};
+
+use libp2p::multiaddr::Protocol;
+use wws_network::{
+    DiscoveryConfig, NetworkError, NetworkEvent, PublishRetryConfig, PublishRetryQueue, SwarmHost,
+    SwarmHostConfig,
+};
+
+const RESULTS_TOPIC: &str = "/wws/1.0.0/s/public/results/task-1";
+const KEEPALIVE_TOPIC: &str = "/wws/1.0.0/s/public/keepalive";
+
+fn retry_config(max_attempts: u32) -> PublishRetryConfig {
+    PublishRetryConfig {
+        max_attempts,
+        interval: Duration::from_millis(100),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn keepalives_are_not_retried() {
+    let config = PublishRetryConfig::default();
+    assert!(config.applies_to(RESULTS_TOPIC));
+    assert!(config.applies_to("/wws/1.0.0/s/public/election/tier1"));
+    assert!(!config.applies_to(KEEPALIVE_TOPIC));
+    assert!(!PublishRetryConfig::disabled().applies_to(RESULTS_TOPIC));
+
+    let mut queue = PublishRetryQueue::new(config);
+    assert!(!queue.push(KEEPALIVE_TOPIC, b"ping".to_vec(), Instant::now()));
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn message_is_dropped_after_retry_cap() {
+    let mut queue = PublishRetryQueue::new(retry_config(2));
+    let t0 = Instant::now();
+    assert!(queue.push(RESULTS_TOPIC, b"result".to_vec(), t0));
+    assert!(queue.take_due(t0).is_empty(), "not due before the interval");
+
+    let t1 = t0 + Duration::from_millis(100);
+    let first = queue.take_due(t1).pop().expect("due after the interval");
+    assert!(queue.retry_later(first, t1));
+
+    let t2 = t1 + Duration::from_millis(100);
+    let second = queue.take_due(t2).pop().expect("due again");
+    assert_eq!(second.attempts, 1);
+    assert!(!queue.retry_later(second, t2), "dropped once retries are used up");
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn full_queue_drops_oldest_message() {
+    let mut queue = PublishRetryQueue::new(PublishRetryConfig {
+        capacity: 2,
+        ..retry_config(5)
+    });
+    let now = Instant::now();
+    for data in [b"a", b"b", b"c"] {
+        assert!(queue.push(RESULTS_TOPIC, data.to_vec(), now));
+    }
+    assert_eq!(queue.len(), 2);
+    let kept: Vec<Vec<u8>> = queue
+        .take_due(now + Duration::from_secs(1))
+        .into_iter()
+        .map(|p| p.data)
+        .collect();
+    assert_eq!(kept, vec![b"b".to_vec(), b"c".to_vec()]);
+}
+
+#[tokio::test]
+async fn publish_without_mesh_is_delivered_once_peer_subscribes() {
+    let config = || SwarmHostConfig {
+        listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+        discovery: DiscoveryConfig::static_only(Vec::new()),
+        publish_retry: retry_config(50),
+        ..Default::default()
+    };
+    let (host_a, handle_a, mut events_a) = SwarmHost::new(config()).expect("host A builds");
+    let (host_b, handle_b, mut events_b) = SwarmHost::new(config()).expect("host B builds");
+    let task_a = tokio::spawn(host_a.run());
+    let task_b = tokio::spawn(host_b.run());
+
+    let addr_b = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let NetworkEvent::Listening(addr) = events_b.recv().await.expect("event channel open") {
+                if addr.iter().any(|p| matches!(p, Protocol::Tcp(_))) {
+                    return addr;
+                }
+            }
+        }
+    })
+    .await
+    .expect("host B listens");
+    handle_a.dial(addr_b).await.expect("dial accepted");
+    tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            if let NetworkEvent::PeerConnected(_) = events_a.recv().await.expect("event channel open") {
+                return;
+            }
+        }
+    })
+    .await
+    .expect("hosts connect");
+
+    // Nobody subscribes to the topic yet, so the first attempt finds no peers.
+    let err = handle_a
+        .publish(RESULTS_TOPIC, b"late result".to_vec())
+        .await
+        .expect_err("no peer is subscribed yet");
+    assert!(matches!(err, NetworkError::NoMeshPeers(_)));
+
+    handle_b.subscribe(RESULTS_TOPIC).await.expect("B subscribes");
+    let data = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            if let NetworkEvent::MessageReceived { topic, data, .. } =
+                events_b.recv().await.expect("event channel open")
+            {
+                if topic == RESULTS_TOPIC {
+                    return data;
+                }
+            }
+        }
+    })
+    .await
+    .expect("retried publish reaches the new subscriber");
+    assert_eq!(data, b"late result".to_vec());
+
+    task_a.abort();
+    task_b.abort();
+}
@@ -0,0 +1,242 @@
+//! Tests for GossipSub subscription tracking, per-topic counters, topic
+//! priorities and topic hashing.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use libp2p::gossipsub::{self, IdentTopic, MessageAuthenticity, Sha256Topic};
+use libp2p::identity::Keypair;
+use libp2p::multiaddr::Protocol;
+use wws_network::{
+    DiscoveryConfig, NetworkEvent, SwarmHost, SwarmHostConfig, TopicCategory, TopicCounters,
+    TopicHashing, TopicHashingConfig, TopicManager, TopicPriorities, TopicPriority,
+};
+use wws_protocol::SwarmTopics;
+
+fn gossipsub() -> gossipsub::Behaviour {
+    gossipsub::Behaviour::new(
+        MessageAuthenticity::Signed(Keypair::generate_ed25519()),
+        gossipsub::Config::default(),
+    )
+    .expect("gossipsub builds")
+}
+
+#[test]
+fn reconciliation_restores_subscription_dropped_out_of_band() {
+    let mut gossipsub = gossipsub();
+    let mut topics = TopicManager::new();
+    topics.subscribe(&mut gossipsub, "/wws/1.0.0/keepalive").unwrap();
+    topics.subscribe(&mut gossipsub, "/wws/1.0.0/hierarchy").unwrap();
+
+    // Nothing to do while every intended topic is active.
+    assert!(topics.resubscribe_missing(&mut gossipsub).is_empty());
+
+    // Drop one subscription behind the manager's back.
+    let dropped = IdentTopic::new("/wws/1.0.0/keepalive");
+    assert!(gossipsub.unsubscribe(&dropped).unwrap());
+    assert!(!gossipsub.topics().any(|h| *h == dropped.hash()));
+
+    let restored = topics.resubscribe_missing(&mut gossipsub);
+    assert_eq!(restored, vec!["/wws/1.0.0/keepalive".to_string()]);
+    assert!(gossipsub.topics().any(|h| *h == dropped.hash()));
+    assert!(topics.resubscribe_missing(&mut gossipsub).is_empty());
+}
+
+#[test]
+fn intentional_unsubscribe_is_not_restored() {
+    let mut gossipsub = gossipsub();
+    let mut topics = TopicManager::new();
+    topics.subscribe(&mut gossipsub, "/wws/1.0.0/keepalive").unwrap();
+    topics.unsubscribe(&mut gossipsub, "/wws/1.0.0/keepalive").unwrap();
+
+    assert!(topics.resubscribe_missing(&mut gossipsub).is_empty());
+    assert_eq!(gossipsub.topics().count(), 0);
+}
+
+#[test]
+fn publish_and_receive_counters_are_kept_per_topic() {
+    let mut gossipsub = gossipsub();
+    let mut topics = TopicManager::new();
+    topics.subscribe(&mut gossipsub, "/wws/1.0.0/keepalive").unwrap();
+    let hash = IdentTopic::new("/wws/1.0.0/keepalive").hash();
+
+    topics.record_sent("/wws/1.0.0/keepalive");
+    topics.record_sent("/wws/1.0.0/hierarchy");
+    assert_eq!(topics.record_received(&hash), "/wws/1.0.0/keepalive");
+    topics.record_received(&hash);
+
+    let stats = topics.topic_stats();
+    assert_eq!(stats["/wws/1.0.0/keepalive"], TopicCounters { sent: 1, received: 2 });
+    assert_eq!(stats["/wws/1.0.0/hierarchy"], TopicCounters { sent: 1, received: 0 });
+
+    // Counts survive unsubscribing so past traffic stays visible.
+    topics.unsubscribe(&mut gossipsub, "/wws/1.0.0/keepalive").unwrap();
+    assert_eq!(topics.topic_stats()["/wws/1.0.0/keepalive"].received, 2);
+}
+
+#[test]
+fn consensus_topics_get_high_priority_mesh_config() {
+    let priorities = TopicPriorities::default();
+    let mut gossipsub = gossipsub();
+    gossipsub
+        .with_peer_score(
+            priorities.peer_score_params().expect("consensus is high priority by default"),
+            gossipsub::PeerScoreThresholds::default(),
+        )
+        .unwrap();
+    let mut topics = TopicManager::with_priorities(priorities.clone());
+
+    let election = SwarmTopics::election_tier1();
+    let proposals = SwarmTopics::proposals("task-1", 3);
+    let voting = SwarmTopics::voting("task-1", 3);
+    let keepalive = SwarmTopics::keepalive();
+    for topic in [&election, &proposals, &voting, &keepalive] {
+        topics.subscribe(&mut gossipsub, topic).unwrap();
+    }
+
+    let expected = priorities.mesh.score_params();
+    for topic in [&election, &proposals, &voting] {
+        let params = gossipsub
+            .get_topic_params(&IdentTopic::new(topic.as_str()))
+            .unwrap_or_else(|| panic!("{topic} has no dedicated mesh config"));
+        assert_eq!(params.topic_weight, expected.topic_weight);
+        assert_eq!(params.time_in_mesh_weight, expected.time_in_mesh_weight);
+        assert_eq!(params.first_message_deliveries_weight, expected.first_message_deliveries_weight);
+        assert_eq!(params.mesh_message_deliveries_weight, 0.0);
+    }
+    assert!(gossipsub.get_topic_params(&IdentTopic::new(keepalive.as_str())).is_none());
+    assert!(priorities.mesh_config(&keepalive).is_none());
+}
+
+#[test]
+fn topic_categories_follow_topic_names() {
+    assert_eq!(TopicCategory::of(&SwarmTopics::election_tier1_for("s1")), TopicCategory::Consensus);
+    assert_eq!(TopicCategory::of(&SwarmTopics::voting_for("s1", "t", 0)), TopicCategory::Consensus);
+    assert_eq!(TopicCategory::of(&SwarmTopics::tasks(2)), TopicCategory::Tasks);
+    assert_eq!(TopicCategory::of(&SwarmTopics::results("t")), TopicCategory::Results);
+    assert_eq!(TopicCategory::of(&SwarmTopics::keepalive()), TopicCategory::Keepalive);
+    assert_eq!(TopicCategory::of(&SwarmTopics::hierarchy()), TopicCategory::Hierarchy);
+    assert_eq!(TopicCategory::of(&SwarmTopics::swarm_discovery()), TopicCategory::Discovery);
+    assert_eq!(TopicCategory::of(&SwarmTopics::swarm_announce("s1")), TopicCategory::Discovery);
+    assert_eq!(TopicCategory::of(&SwarmTopics::board("t")), TopicCategory::Other);
+    assert_eq!("Keepalive".parse::<TopicCategory>().unwrap(), TopicCategory::Keepalive);
+    assert!("gossip".parse::<TopicCategory>().is_err());
+}
+
+#[tokio::test]
+async fn consensus_publishes_use_the_priority_lane() {
+    let config = SwarmHostConfig::with_listen_addr("/ip4/127.0.0.1/tcp/0".parse().unwrap());
+    let (_host, handle, _events) = SwarmHost::new(config).unwrap();
+    assert_eq!(handle.topic_priority(&SwarmTopics::election_tier1()), TopicPriority::High);
+    assert_eq!(handle.topic_priority(&SwarmTopics::proposals("t", 1)), TopicPriority::High);
+    assert_eq!(handle.topic_priority(&SwarmTopics::keepalive()), TopicPriority::Normal);
+    assert_eq!(handle.topic_priority(&SwarmTopics::swarm_announce("public")), TopicPriority::Normal);
+
+    let config = SwarmHostConfig {
+        topic_priorities: TopicPriorities::none(),
+        ..SwarmHostConfig::with_listen_addr("/ip4/127.0.0.1/tcp/0".parse().unwrap())
+    };
+    let (_host, handle, _events) = SwarmHost::new(config).unwrap();
+    assert_eq!(handle.topic_priority(&SwarmTopics::election_tier1()), TopicPriority::Normal);
+}
+
+fn sha256_for(swarm_id: &str) -> TopicHashingConfig {
+    TopicHashingConfig {
+        default: TopicHashing::Ident,
+        swarms: BTreeMap::from([(swarm_id.to_string(), TopicHashing::Sha256)]),
+    }
+}
+
+#[test]
+fn hashed_topics_round_trip_through_the_name_registry() {
+    let mut gossipsub = gossipsub();
+    let mut topics = TopicManager::new().with_hashing(sha256_for("private"));
+    let keepalive = SwarmTopics::keepalive_for("private");
+    let results = SwarmTopics::results_for("private", "task-1");
+
+    let hash = topics.subscribe(&mut gossipsub, &keepalive).unwrap();
+    assert_eq!(hash, Sha256Topic::new(keepalive.as_str()).hash());
+    assert_ne!(hash, IdentTopic::new(keepalive.as_str()).hash());
+    assert!(gossipsub.topics().any(|h| *h == hash));
+    assert_eq!(topics.resolve_topic(&hash), Some(keepalive.as_str()));
+    assert_eq!(topics.record_received(&hash), keepalive);
+
+    // Other swarms keep plain topic names.
+    let public = topics.subscribe(&mut gossipsub, &SwarmTopics::keepalive()).unwrap();
+    assert_eq!(public, IdentTopic::new(SwarmTopics::keepalive()).hash());
+
+    // Publishing only needs the hash; the name registry holds subscriptions.
+    let published = topics.hash(&results);
+    assert_eq!(published, Sha256Topic::new(results.as_str()).hash());
+    assert_eq!(topics.resolve_topic(&published), None);
+
+    // Reconciliation re-subscribes with the same hashing.
+    assert!(gossipsub.unsubscribe(&Sha256Topic::new(keepalive.as_str())).unwrap());
+    assert_eq!(topics.resubscribe_missing(&mut gossipsub), vec![keepalive.clone()]);
+    assert!(gossipsub.topics().any(|h| *h == hash));
+
+    topics.unsubscribe(&mut gossipsub, &keepalive).unwrap();
+    assert!(!gossipsub.topics().any(|h| *h == hash));
+    assert_eq!(topics.resolve_topic(&hash), None);
+    assert_eq!("SHA256".parse::<TopicHashing>().unwrap(), TopicHashing::Sha256);
+    assert!("md5".parse::<TopicHashing>().is_err());
+}
+
+#[tokio::test]
+async fn hashed_topic_messages_are_reported_by_name() {
+    let topic = SwarmTopics::results_for("private", "task-1");
+    let config = || SwarmHostConfig {
+        listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+        discovery: DiscoveryConfig::static_only(Vec::new()),
+        topic_hashing: sha256_for("private"),
+        ..Default::default()
+    };
+    let (host_a, handle_a, mut events_a) = SwarmHost::new(config()).expect("host A builds");
+    let (host_b, handle_b, mut events_b) = SwarmHost::new(config()).expect("host B builds");
+    let task_a = tokio::spawn(host_a.run());
+    let task_b = tokio::spawn(host_b.run());
+
+    let addr_b = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let NetworkEvent::Listening(addr) = events_b.recv().await.expect("event channel open") {
+                if addr.iter().any(|p| matches!(p, Protocol::Tcp(_))) {
+                    return addr;
+                }
+            }
+        }
+    })
+    .await
+    .expect("host B listens");
+    handle_b.subscribe(&topic).await.expect("B subscribes");
+    handle_a.dial(addr_b).await.expect("dial accepted");
+    tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            if let NetworkEvent::PeerConnected(_) = events_a.recv().await.expect("event channel open") {
+                return;
+            }
+        }
+    })
+    .await
+    .expect("hosts connect");
+
+    // The publish is retried until B's subscription reaches A.
+    let _ = handle_a.publish(&topic, b"result".to_vec()).await;
+    let received = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            if let NetworkEvent::MessageReceived { topic, data, .. } =
+                events_b.recv().await.expect("event channel open")
+            {
+                return (topic, data);
+            }
+        }
+    })
+    .await
+    .expect("message reaches B");
+    assert_eq!(received, (topic.clone(), b"result".to_vec()));
+
+    let stats = handle_a.topic_stats().await.expect("stats available");
+    assert_eq!(stats[&topic].sent, 1);
+
+    task_a.abort();
+    task_b.abort();
+}
@@ -0,0 +1,184 @@
+//! Tests for transport construction with custom timeouts, GossipSub
+//! deduplication settings and private-network PSKs.
+
+use std::time::Duration;
+
+use futures::StreamExt;
+use libp2p::swarm::SwarmEvent;
+use wws_network::behaviour::{BehaviourConfig, GossipConfig};
+use wws_network::pnet::parse_psk_hex;
+use wws_network::transport::{build_swarm, TransportConfig};
+use wws_network::{DiscoveryConfig, NetworkEvent, SwarmHost, SwarmHostConfig};
+
+#[test]
+fn test_transport_config_default_timeouts() {
+    let config = TransportConfig::default();
+    assert_eq!(config.dial_timeout, Duration::from_secs(10));
+    assert_eq!(config.idle_connection_timeout, Duration::from_secs(60));
+}
+
+#[tokio::test]
+async fn test_build_swarm_with_custom_timeouts() {
+    let config = TransportConfig {
+        dial_timeout: Duration::from_millis(500),
+        idle_connection_timeout: Duration::from_secs(5),
+        ..Default::default()
+    };
+    let mut swarm = build_swarm(config).expect("swarm builds with custom timeouts");
+
+    // A non-routable address must fail within the dial timeout rather than
+    // hanging for the OS TCP connect timeout.
+    swarm
+        .dial("/ip4/10.255.255.1/tcp/9".parse::<libp2p::Multiaddr>().unwrap())
+        .expect("dial is initiated");
+    let outcome = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let SwarmEvent::OutgoingConnectionError { .. } = swarm.select_next_some().await {
+                break;
+            }
+        }
+    })
+    .await;
+    assert!(outcome.is_ok(), "dial should fail within the configured timeout");
+}
+
+fn psk_host(
+    psk: Option<[u8; 32]>,
+) -> (
+    wws_network::SwarmHost,
+    wws_network::SwarmHandle,
+    tokio::sync::mpsc::Receiver<NetworkEvent>,
+) {
+    let mut config = SwarmHostConfig::with_listen_addr("/ip4/127.0.0.1/tcp/0".parse().unwrap());
+    config.discovery = DiscoveryConfig::static_only(Vec::new());
+    config.transport.psk = psk;
+    // A PSK mismatch stalls the handshake until the dial timeout, which has
+    // to fire well within the test's own timeout.
+    config.transport.dial_timeout = Duration::from_secs(2);
+    SwarmHost::new(config).expect("swarm host builds")
+}
+
+async fn listen_addr(events: &mut tokio::sync::mpsc::Receiver<NetworkEvent>) -> libp2p::Multiaddr {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let NetworkEvent::Listening(addr) = events.recv().await.expect("event channel open") {
+                return addr;
+            }
+        }
+    })
+    .await
+    .expect("host listens")
+}
+
+/// Dial `b` from `a` and report whether the connection was established.
+async fn psk_hosts_connect(psk_a: Option<[u8; 32]>, psk_b: Option<[u8; 32]>) -> bool {
+    let (host_a, handle_a, mut events_a) = psk_host(psk_a);
+    let (host_b, _handle_b, mut events_b) = psk_host(psk_b);
+    let task_a = tokio::spawn(host_a.run());
+    let task_b = tokio::spawn(host_b.run());
+
+    let addr = listen_addr(&mut events_b).await;
+    handle_a.dial(addr).await.expect("dial is accepted");
+    let connected = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            match events_a.recv().await.expect("event channel open") {
+                NetworkEvent::PeerConnected(_) => return true,
+                NetworkEvent::DialFailed { .. } => return false,
+                _ => continue,
+            }
+        }
+    })
+    .await
+    .expect("dial resolves");
+
+    task_a.abort();
+    task_b.abort();
+    connected
+}
+
+#[test]
+fn psk_config_disables_quic_and_fingerprints_key() {
+    let psk = parse_psk_hex(&"11".repeat(32)).expect("valid PSK hex");
+    let config = TransportConfig {
+        psk: Some(psk),
+        ..Default::default()
+    };
+    let features = config.features();
+    assert!(features.pnet);
+    assert!(!features.quic);
+
+    let same = TransportConfig {
+        psk: Some([0x11; 32]),
+        ..Default::default()
+    };
+    let other = TransportConfig {
+        psk: Some([0x22; 32]),
+        ..Default::default()
+    };
+    assert_eq!(config.psk_fingerprint(), same.psk_fingerprint());
+    assert_ne!(config.psk_fingerprint(), other.psk_fingerprint());
+    assert_eq!(TransportConfig::default().psk_fingerprint(), None);
+
+    assert!(parse_psk_hex("1234").is_err());
+    assert!(parse_psk_hex(&"zz".repeat(32)).is_err());
+}
+
+#[tokio::test]
+async fn hosts_with_matching_psk_connect() {
+    assert!(psk_hosts_connect(Some([7; 32]), Some([7; 32])).await);
+}
+
+#[tokio::test]
+async fn hosts_with_mismatched_psk_fail_handshake() {
+    assert!(!psk_hosts_connect(Some([7; 32]), Some([8; 32])).await);
+    assert!(!psk_hosts_connect(None, Some([8; 32])).await);
+}
+
+#[tokio::test]
+async fn test_gossip_dedup_settings_are_applied() {
+    let defaults = BehaviourConfig::default().gossipsub_config().expect("default gossip config");
+    assert_eq!(defaults.duplicate_cache_time(), Duration::from_secs(60));
+    assert_eq!(defaults.history_length(), 5);
+    assert_eq!(defaults.history_gossip(), 3);
+
+    let behaviour_config = BehaviourConfig {
+        gossip: GossipConfig {
+            duplicate_cache_time: Duration::from_secs(300),
+            history_length: 2,
+        },
+        ..Default::default()
+    };
+    let gossipsub = behaviour_config.gossipsub_config().expect("custom gossip config");
+    assert_eq!(gossipsub.duplicate_cache_time(), Duration::from_secs(300));
+    assert_eq!(gossipsub.history_length(), 2);
+    assert_eq!(gossipsub.history_gossip(), 2, "advertised history fits the kept history");
+
+    let config = TransportConfig {
+        behaviour_config,
+        ..Default::default()
+    };
+    build_swarm(config).expect("swarm builds with custom dedup settings");
+}
+
+#[tokio::test]
+async fn test_ping_interval_and_timeout_are_applied() {
+    // ping::Config exposes no getters, so inspect its Debug output.
+    let defaults = format!("{:?}", BehaviourConfig::default().ping_config());
+    assert!(defaults.contains("timeout: 20s"), "{defaults}");
+    assert!(defaults.contains("interval: 1s"), "{defaults}");
+
+    let behaviour_config = BehaviourConfig {
+        ping_interval: Duration::from_millis(250),
+        ping_timeout: Duration::from_secs(5),
+        ..Default::default()
+    };
+    let ping = format!("{:?}", behaviour_config.ping_config());
+    assert!(ping.contains("timeout: 5s"), "{ping}");
+    assert!(ping.contains("interval: 250ms"), "{ping}");
+
+    let config = TransportConfig {
+        behaviour_config,
+        ..Default::default()
+    };
+    build_swarm(config).expect("swarm builds with custom ping settings");
+}
@@ -15,12 +15,24 @@ pub const LEADER_TIMEOUT_SECS: u64 = 30;
 /// Commit-Reveal timeout: how long to wait for all proposal hashes.
 pub const COMMIT_REVEAL_TIMEOUT_SECS: u64 = 60;
 
+/// Default window for holding a proposal reveal that arrived before its commit.
+pub const DEFAULT_REVEAL_GRACE_SECS: u64 = 5;
+
 /// Voting phase timeout in seconds.
 pub const VOTING_TIMEOUT_SECS: u64 = 120;
 
 /// Maximum hierarchy depth to prevent infinite recursion.
 pub const MAX_HIERARCHY_DEPTH: u32 = 10;
 
+/// Default upper bound on the number of subtasks in a single plan.
+pub const DEFAULT_MAX_SUBTASKS_PER_PLAN: usize = 100;
+
+/// Default upper bound on a plan subtask description, in bytes.
+pub const DEFAULT_MAX_SUBTASK_DESCRIPTION_LEN: usize = 4096;
+
+/// Default upper bound on an injected task description, in bytes.
+pub const DEFAULT_MAX_TASK_DESCRIPTION_LEN: usize = 4096;
+
 /// GossipSub topic prefix.
 pub const TOPIC_PREFIX: &str = "/wws/1.0.0";
 
@@ -48,6 +60,13 @@ pub const SWARM_MEMBERSHIP_PREFIX: &str = "/wws/membership/";
 /// Swarm announcement interval in seconds.
 pub const SWARM_ANNOUNCE_INTERVAL_SECS: u64 = 30;
 
+/// Swarms up to this many agents announce at the base interval; larger
+/// swarms stretch it proportionally so discovery traffic stays flat.
+pub const SWARM_ANNOUNCE_BACKOFF_THRESHOLD: u64 = 20;
+
+/// Upper bound on the adaptive swarm announcement interval, in seconds.
+pub const MAX_SWARM_ANNOUNCE_INTERVAL_SECS: u64 = 600;
+
 /// Default well-known bootstrap peers.
 /// These are entry points only — not required after joining the mesh.
 pub const DEFAULT_BOOTSTRAP_PEERS: &[&str] = &[
@@ -1,7 +1,10 @@
 use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use hkdf::Hkdf;
 use sha2::{Digest, Sha256};
 
-use crate::ProtocolError;
+use crate::{ProtocolError, SwarmId, SwarmToken};
 
 /// Generate a new Ed25519 keypair.
 pub fn generate_keypair() -> SigningKey {
@@ -189,6 +192,94 @@ pub fn hex_decode(s: &str) -> Result<Vec<u8>, ProtocolError> {
         .collect()
 }
 
+/// Magic prefix marking a payload sealed with [`SwarmCipher`].
+const SEALED_PAYLOAD_MAGIC: &[u8; 4] = b"WWE1";
+const SEALED_NONCE_LEN: usize = 12;
+/// HKDF context string for private swarm payload keys.
+const SWARM_PAYLOAD_KEY_INFO: &[u8] = b"wws-swarm-payload-key";
+
+/// Symmetric AEAD cipher for a private swarm's GossipSub payloads.
+///
+/// The key is derived from the swarm ID and its join token, so every member
+/// that was admitted with the token can read the traffic while relays and
+/// other swarms only see ciphertext. Sealed payloads are laid out as
+/// `magic || nonce || ciphertext`, with the swarm ID bound as associated data.
+#[derive(Clone)]
+pub struct SwarmCipher {
+    cipher: ChaCha20Poly1305,
+    swarm_id: String,
+}
+
+impl SwarmCipher {
+    /// Derive the payload key for `swarm_id` from its join token with
+    /// HKDF-SHA256, salted with the swarm ID.
+    pub fn from_token(swarm_id: &SwarmId, token: &SwarmToken) -> Self {
+        let hkdf = Hkdf::<Sha256>::new(Some(swarm_id.as_str().as_bytes()), token.as_str().as_bytes());
+        let mut key = Key::default();
+        hkdf.expand(SWARM_PAYLOAD_KEY_INFO, &mut key)
+            .expect("32 bytes is a valid HKDF-SHA256 output length");
+        Self {
+            cipher: ChaCha20Poly1305::new(&key),
+            swarm_id: swarm_id.as_str().to_string(),
+        }
+    }
+
+    /// Whether `payload` carries the sealed-payload prefix.
+    pub fn is_sealed(payload: &[u8]) -> bool {
+        payload.starts_with(SEALED_PAYLOAD_MAGIC)
+    }
+
+    /// Encrypt `plaintext` under a fresh random nonce.
+    pub fn seal(&self, plaintext: &[u8]) -> Vec<u8> {
+        let nonce_bytes: [u8; SEALED_NONCE_LEN] = rand::random();
+        let nonce = Nonce::from_slice(&nonce_bytes);
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                nonce,
+                Payload {
+                    msg: plaintext,
+                    aad: self.swarm_id.as_bytes(),
+                },
+            )
+            .expect("ChaCha20-Poly1305 encryption of an in-memory buffer cannot fail");
+        let mut sealed =
+            Vec::with_capacity(SEALED_PAYLOAD_MAGIC.len() + SEALED_NONCE_LEN + ciphertext.len());
+        sealed.extend_from_slice(SEALED_PAYLOAD_MAGIC);
+        sealed.extend_from_slice(&nonce_bytes);
+        sealed.extend_from_slice(&ciphertext);
+        sealed
+    }
+
+    /// Decrypt a payload produced by [`SwarmCipher::seal`] with the same key.
+    pub fn open(&self, payload: &[u8]) -> Result<Vec<u8>, ProtocolError> {
+        let body = payload
+            .strip_prefix(SEALED_PAYLOAD_MAGIC.as_slice())
+            .ok_or_else(|| ProtocolError::Crypto("payload is not sealed".into()))?;
+        if body.len() < SEALED_NONCE_LEN {
+            return Err(ProtocolError::Crypto("sealed payload truncated".into()));
+        }
+        let (nonce_bytes, ciphertext) = body.split_at(SEALED_NONCE_LEN);
+        self.cipher
+            .decrypt(
+                Nonce::from_slice(nonce_bytes),
+                Payload {
+                    msg: ciphertext,
+                    aad: self.swarm_id.as_bytes(),
+                },
+            )
+            .map_err(|_| ProtocolError::Crypto("failed to decrypt swarm payload".into()))
+    }
+}
+
+impl std::fmt::Debug for SwarmCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SwarmCipher")
+            .field("swarm_id", &self.swarm_id)
+            .finish_non_exhaustive()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -106,15 +106,42 @@ pub struct NodeScore {
     pub stake: Option<f64>,
 }
 
+/// Weights of the [`NodeScore`] components in its composite score.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoreWeights {
+    pub proof_of_compute: f64,
+    pub reputation: f64,
+    pub uptime: f64,
+    pub stake: f64,
+}
+
+impl Default for ScoreWeights {
+    /// PoC=0.25, Reputation=0.40, Uptime=0.20, Stake=0.15
+    fn default() -> Self {
+        Self {
+            proof_of_compute: 0.25,
+            reputation: 0.40,
+            uptime: 0.20,
+            stake: 0.15,
+        }
+    }
+}
+
 impl NodeScore {
-    /// Compute the composite weighted score used for elections.
-    /// Weights: PoC=0.25, Reputation=0.40, Uptime=0.20, Stake=0.15
+    /// Compute the composite weighted score used for elections, with the
+    /// default [`ScoreWeights`].
     pub fn composite_score(&self) -> f64 {
+        self.composite_score_with(&ScoreWeights::default())
+    }
+
+    /// Compute the composite score with the given component weights.
+    pub fn composite_score_with(&self, weights: &ScoreWeights) -> f64 {
         let stake_val = self.stake.unwrap_or(0.0).min(1.0);
-        0.25 * self.proof_of_compute
-            + 0.40 * self.reputation
-            + 0.20 * self.uptime
-            + 0.15 * stake_val
+        weights.proof_of_compute * self.proof_of_compute
+            + weights.reputation * self.reputation
+            + weights.uptime * self.uptime
+            + weights.stake * stake_val
     }
 }
 
@@ -15,6 +15,13 @@ pub struct SwarmMessage {
     pub params: serde_json::Value,
     /// Ed25519 signature over the canonical JSON of (method + params)
     pub signature: String,
+    /// Application-level hop budget. Each message a node publishes in
+    /// reaction to this one (e.g. subtask assignments for an injected task)
+    /// carries one hop less, and receivers ignore messages that arrive with
+    /// no hops left. `None` means unlimited. Not covered by the signature,
+    /// since forwarders rewrite it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl_hops: Option<u32>,
 }
 
 impl SwarmMessage {
@@ -25,9 +32,26 @@ impl SwarmMessage {
             id: Some(uuid::Uuid::new_v4().to_string()),
             params,
             signature,
+            ttl_hops: None,
         }
     }
 
+    /// Set the hop budget; `None` leaves propagation unlimited.
+    pub fn with_ttl_hops(mut self, ttl_hops: Option<u32>) -> Self {
+        self.ttl_hops = ttl_hops;
+        self
+    }
+
+    /// Whether the message arrived with no hops left and must not be acted on.
+    pub fn hops_exhausted(&self) -> bool {
+        self.ttl_hops == Some(0)
+    }
+
+    /// Hop budget for a message forwarded from one that carried `ttl_hops`.
+    pub fn forwarded_ttl(ttl_hops: Option<u32>) -> Option<u32> {
+        ttl_hops.map(|hops| hops.saturating_sub(1))
+    }
+
     /// Get the canonical bytes for signing: JSON(method + params).
     pub fn signing_payload(method: &str, params: &serde_json::Value) -> Vec<u8> {
         let canonical = serde_json::json!({
@@ -71,6 +95,145 @@ impl SwarmResponse {
             }),
         }
     }
+
+    /// Error response carrying a named [`RpcErrorCode`].
+    pub fn rpc_error(id: Option<String>, code: RpcErrorCode, message: impl Into<String>) -> Self {
+        Self::error(id, code.code(), message.into())
+    }
+
+    /// `InvalidParams` error, for missing or malformed request parameters.
+    pub fn invalid_params(id: Option<String>, message: impl Into<String>) -> Self {
+        Self::rpc_error(id, RpcErrorCode::InvalidParams, message)
+    }
+
+    /// `NotFound` error, for a referenced entity that does not exist.
+    pub fn not_found(id: Option<String>, message: impl Into<String>) -> Self {
+        Self::rpc_error(id, RpcErrorCode::NotFound, message)
+    }
+
+    /// `OperationFailed` error, for a valid request that could not be carried out.
+    pub fn operation_failed(id: Option<String>, message: impl Into<String>) -> Self {
+        Self::rpc_error(id, RpcErrorCode::OperationFailed, message)
+    }
+
+    /// The named code of this response's error, if any and if recognised.
+    pub fn error_code(&self) -> Option<RpcErrorCode> {
+        self.error.as_ref().and_then(|e| RpcErrorCode::from_code(e.code))
+    }
+}
+
+/// Error codes returned by the connector's JSON-RPC API.
+///
+/// Codes from -32700 to -32600 are the JSON-RPC 2.0 standard errors; the
+/// -32000 to -32099 range is application-defined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RpcErrorCode {
+    /// -32700: the request body is not valid JSON.
+    ParseError,
+    /// -32600: the request is not a valid JSON-RPC request, or a receipt or
+    /// clarification is in the wrong state for the operation.
+    InvalidRequest,
+    /// -32601: unknown method name.
+    MethodNotFound,
+    /// -32602: a parameter is missing or malformed, or names a receipt or
+    /// clarification that does not exist.
+    InvalidParams,
+    /// -32603: unexpected failure inside the connector.
+    InternalError,
+    /// -32000: a well-formed request failed (dial error, serialization, consensus rejection).
+    OperationFailed,
+    /// -32001: the RPC token is missing or invalid. Also returned for an
+    /// unknown swarm or an unregistered name, as before these codes were named.
+    Unauthorized,
+    /// -32002: the caller is authenticated but not allowed to act (swarm token, ownership, guardian).
+    Forbidden,
+    /// -32003: the agent's reputation is below the required tier.
+    InsufficientReputation,
+    /// -32004: a referenced task, content or voting result does not exist.
+    NotFound,
+    /// -32005: the connector is still starting up and cannot reach the swarm yet.
+    NotReady,
+    /// -32006: a plan exceeds the configured size limits or lacks a required rationale.
+    PlanTooLarge,
+    /// -32007: too many concurrently active injected tasks.
+    BudgetExceeded,
+    /// -32008: outstanding receipts exceed the blast-radius budget.
+    BlastRadiusExceeded,
+    /// -32009: the target exists but is not in a state that allows the operation.
+    InvalidState,
+    /// -32010: an aggregated result was submitted before all subtasks completed.
+    SubtasksIncomplete,
+    /// -32011: a root task result was submitted without any decomposed subtasks.
+    NoSubtasks,
+    /// -32012: the submitting agent is no longer the task's assignee.
+    StaleAssignee,
+    /// -32013: a plan was proposed without subtasks.
+    EmptyPlan,
+    /// -32029: the caller exceeded a rate limit.
+    RateLimited,
+}
+
+impl RpcErrorCode {
+    /// Every code, in numeric order from the standard range down.
+    pub const ALL: [RpcErrorCode; 20] = [
+        Self::ParseError,
+        Self::InvalidRequest,
+        Self::MethodNotFound,
+        Self::InvalidParams,
+        Self::InternalError,
+        Self::OperationFailed,
+        Self::Unauthorized,
+        Self::Forbidden,
+        Self::InsufficientReputation,
+        Self::NotFound,
+        Self::NotReady,
+        Self::PlanTooLarge,
+        Self::BudgetExceeded,
+        Self::BlastRadiusExceeded,
+        Self::InvalidState,
+        Self::SubtasksIncomplete,
+        Self::NoSubtasks,
+        Self::StaleAssignee,
+        Self::EmptyPlan,
+        Self::RateLimited,
+    ];
+
+    /// The integer sent in `RpcError::code`.
+    pub fn code(self) -> i32 {
+        match self {
+            Self::ParseError => -32700,
+            Self::InvalidRequest => -32600,
+            Self::MethodNotFound => -32601,
+            Self::InvalidParams => -32602,
+            Self::InternalError => -32603,
+            Self::OperationFailed => -32000,
+            Self::Unauthorized => -32001,
+            Self::Forbidden => -32002,
+            Self::InsufficientReputation => -32003,
+            Self::NotFound => -32004,
+            Self::NotReady => -32005,
+            Self::PlanTooLarge => -32006,
+            Self::BudgetExceeded => -32007,
+            Self::BlastRadiusExceeded => -32008,
+            Self::InvalidState => -32009,
+            Self::SubtasksIncomplete => -32010,
+            Self::NoSubtasks => -32011,
+            Self::StaleAssignee => -32012,
+            Self::EmptyPlan => -32013,
+            Self::RateLimited => -32029,
+        }
+    }
+
+    /// Look up the named code for an integer, if it is one of ours.
+    pub fn from_code(code: i32) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.code() == code)
+    }
+}
+
+impl From<RpcErrorCode> for i32 {
+    fn from(code: RpcErrorCode) -> Self {
+        code.code()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -113,6 +276,9 @@ pub struct ElectionVoteParams {
 }
 
 /// Tier assignment notification from parent to subordinate.
+///
+/// Signed by the issuing leader so recipients can check it came from a
+/// leader of the current epoch rather than an arbitrary peer.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TierAssignmentParams {
     pub assigned_agent: AgentId,
@@ -120,6 +286,25 @@ pub struct TierAssignmentParams {
     pub parent_id: AgentId,
     pub epoch: u64,
     pub branch_size: u64,
+    /// Leader that computed this assignment.
+    pub issuer: AgentId,
+    /// Hex-encoded signature by `issuer` over [`Self::signing_payload`].
+    pub signature: String,
+}
+
+impl TierAssignmentParams {
+    /// Canonical bytes the issuer signs: every field except the signature.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        let canonical = serde_json::json!({
+            "assigned_agent": self.assigned_agent,
+            "tier": self.tier,
+            "parent_id": self.parent_id,
+            "epoch": self.epoch,
+            "branch_size": self.branch_size,
+            "issuer": self.issuer,
+        });
+        serde_json::to_vec(&canonical).unwrap_or_default()
+    }
 }
 
 /// Task injection from external source or parent agent.
@@ -165,6 +350,87 @@ pub struct TaskAssignmentParams {
     pub winning_plan_id: String,
 }
 
+/// Assignee's reply to a [`TaskAssignmentParams`], published on the
+/// subtask's results topic. The assigning node reassigns subtasks that are
+/// declined or not acknowledged in time.
+///
+/// Signed by `assignee`, since only the assignee may answer for itself.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskAckParams {
+    pub task_id: String,
+    pub assignee: AgentId,
+    pub accepted: bool,
+    /// Hex-encoded signature by `assignee` over [`Self::signing_payload`].
+    pub signature: String,
+}
+
+impl TaskAckParams {
+    /// Canonical bytes covered by `signature`.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        let canonical = serde_json::json!({
+            "task_id": self.task_id,
+            "assignee": self.assignee,
+            "accepted": self.accepted,
+        });
+        serde_json::to_vec(&canonical).unwrap_or_default()
+    }
+}
+
+/// Hand-off of an in-progress subtask from its current assignee to a peer.
+///
+/// Signed by `from`, since only the current assignee may give its task away.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskDelegationParams {
+    pub task_id: String,
+    pub from: AgentId,
+    pub to: AgentId,
+    /// Hex-encoded signature by `from` over [`Self::signing_payload`].
+    pub signature: String,
+}
+
+impl TaskDelegationParams {
+    /// Canonical bytes the delegating agent signs: every field except the
+    /// signature.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        let canonical = serde_json::json!({
+            "task_id": self.task_id,
+            "from": self.from,
+            "to": self.to,
+        });
+        serde_json::to_vec(&canonical).unwrap_or_default()
+    }
+}
+
+/// Progress update from a task's assignee. `percent` is clamped to
+/// `[0, 100]` when applied.
+///
+/// Signed by `reporter`, since only the current assignee may report on its
+/// task.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskProgressParams {
+    pub task_id: String,
+    pub reporter: AgentId,
+    pub percent: f64,
+    #[serde(default)]
+    pub message: String,
+    /// Hex-encoded signature by `reporter` over [`Self::signing_payload`].
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl TaskProgressParams {
+    /// Canonical bytes the reporter signs: every field except the signature.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        let canonical = serde_json::json!({
+            "task_id": self.task_id,
+            "reporter": self.reporter,
+            "percent": self.percent,
+            "message": self.message,
+        });
+        serde_json::to_vec(&canonical).unwrap_or_default()
+    }
+}
+
 /// Result submission from executor to coordinator.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResultSubmissionParams {
@@ -179,6 +445,19 @@ pub struct ResultSubmissionParams {
     pub is_synthesis: bool,
 }
 
+impl ResultSubmissionParams {
+    /// Canonical bytes the producer signs to vouch for the artifact:
+    /// the task, the content CID and the submitting agent.
+    pub fn artifact_signing_payload(&self) -> Vec<u8> {
+        let canonical = serde_json::json!({
+            "task_id": self.task_id,
+            "content_cid": self.artifact.content_cid,
+            "agent_id": self.agent_id,
+        });
+        serde_json::to_vec(&canonical).unwrap_or_default()
+    }
+}
+
 /// Verification result from coordinator back to subordinate.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VerificationResultParams {
@@ -225,23 +504,84 @@ pub struct SwarmAnnounceParams {
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
-/// Request to join a swarm. For private swarms, includes token.
+/// Request to join a swarm.
+///
+/// For private swarms the token itself never goes on the wire; `proof` is
+/// [`SwarmToken::proof`] over [`Self::proof_payload`], which members
+/// holding the swarm secret can check.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SwarmJoinParams {
     pub swarm_id: SwarmId,
     pub agent_id: AgentId,
-    /// Token for private swarm authentication (None for public swarms).
-    pub token: Option<SwarmToken>,
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Random value making each request's proof unique; echoed by a
+    /// rejecting response.
+    #[serde(default)]
+    pub nonce: String,
+    /// Proof of possession of the swarm token (None for public swarms).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<String>,
+}
+
+impl SwarmJoinParams {
+    /// A join request for `swarm_id` with a fresh nonce, proving possession
+    /// of `token` when one is given.
+    pub fn new(swarm_id: SwarmId, agent_id: AgentId, token: Option<&SwarmToken>) -> Self {
+        let mut params = Self {
+            swarm_id,
+            agent_id,
+            timestamp: chrono::Utc::now(),
+            nonce: uuid::Uuid::new_v4().to_string(),
+            proof: None,
+        };
+        params.proof = token.map(|token| token.proof(&params.proof_payload()));
+        params
+    }
+
+    /// Canonical bytes covered by `proof`: every field except the proof.
+    pub fn proof_payload(&self) -> Vec<u8> {
+        let canonical = serde_json::json!({
+            "swarm_id": self.swarm_id,
+            "agent_id": self.agent_id,
+            "timestamp": self.timestamp,
+            "nonce": self.nonce,
+        });
+        serde_json::to_vec(&canonical).unwrap_or_default()
+    }
 }
 
 /// Response to a join request.
+///
+/// Signed by `responder`, a member holding the swarm secret, and bound to
+/// the request through its `nonce`, so no other peer can make the joiner
+/// leave.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SwarmJoinResponseParams {
     pub swarm_id: SwarmId,
     pub agent_id: AgentId,
     pub accepted: bool,
     pub reason: Option<String>,
+    /// Nonce of the join request being answered.
+    #[serde(default)]
+    pub nonce: String,
+    pub responder: AgentId,
+    /// Hex-encoded signature by `responder` over [`Self::signing_payload`].
+    pub signature: String,
+}
+
+impl SwarmJoinResponseParams {
+    /// Canonical bytes covered by `signature`.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        let canonical = serde_json::json!({
+            "swarm_id": self.swarm_id,
+            "agent_id": self.agent_id,
+            "accepted": self.accepted,
+            "reason": self.reason,
+            "nonce": self.nonce,
+            "responder": self.responder,
+        });
+        serde_json::to_vec(&canonical).unwrap_or_default()
+    }
 }
 
 /// Leave a swarm notification.
@@ -252,6 +592,14 @@ pub struct SwarmLeaveParams {
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
+/// Several state updates published to the same topic within one batching
+/// window, delivered as a single message. Receivers handle each update in
+/// order as if it had arrived on its own; nested batches are ignored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateBatchParams {
+    pub updates: Vec<SwarmMessage>,
+}
+
 // ── Holonic Board Messages ──
 
 /// Board invitation from chair to local cluster.
@@ -320,6 +668,9 @@ pub enum ProtocolMethod {
     ProposalReveal,
     ConsensusVote,
     TaskAssignment,
+    TaskAck,
+    TaskDelegation,
+    TaskProgress,
     ResultSubmission,
     VerificationResult,
     KeepAlive,
@@ -329,6 +680,8 @@ pub enum ProtocolMethod {
     SwarmJoin,
     SwarmJoinResponse,
     SwarmLeave,
+    /// Batch of state updates coalesced by the sender (see [`StateBatchParams`]).
+    StateBatch,
     BoardInvite,
     BoardAccept,
     BoardDecline,
@@ -351,6 +704,9 @@ impl ProtocolMethod {
             Self::ProposalReveal => "consensus.proposal_reveal",
             Self::ConsensusVote => "consensus.vote",
             Self::TaskAssignment => "task.assign",
+            Self::TaskAck => "task.ack",
+            Self::TaskDelegation => "task.delegate",
+            Self::TaskProgress => "task.progress",
             Self::ResultSubmission => "task.submit_result",
             Self::VerificationResult => "task.verification",
             Self::KeepAlive => "swarm.keepalive",
@@ -360,6 +716,7 @@ impl ProtocolMethod {
             Self::SwarmJoin => "swarm.join",
             Self::SwarmJoinResponse => "swarm.join_response",
             Self::SwarmLeave => "swarm.leave",
+            Self::StateBatch => "swarm.state_batch",
             Self::BoardInvite => "board.invite",
             Self::BoardAccept => "board.accept",
             Self::BoardDecline => "board.decline",
@@ -381,6 +738,9 @@ impl ProtocolMethod {
             "consensus.proposal_reveal" => Some(Self::ProposalReveal),
             "consensus.vote" => Some(Self::ConsensusVote),
             "task.assign" => Some(Self::TaskAssignment),
+            "task.ack" => Some(Self::TaskAck),
+            "task.delegate" => Some(Self::TaskDelegation),
+            "task.progress" => Some(Self::TaskProgress),
             "task.submit_result" => Some(Self::ResultSubmission),
             "task.verification" => Some(Self::VerificationResult),
             "swarm.keepalive" => Some(Self::KeepAlive),
@@ -390,6 +750,7 @@ impl ProtocolMethod {
             "swarm.join" => Some(Self::SwarmJoin),
             "swarm.join_response" => Some(Self::SwarmJoinResponse),
             "swarm.leave" => Some(Self::SwarmLeave),
+            "swarm.state_batch" => Some(Self::StateBatch),
             "board.invite" => Some(Self::BoardInvite),
             "board.accept" => Some(Self::BoardAccept),
             "board.decline" => Some(Self::BoardDecline),
@@ -407,6 +768,10 @@ impl ProtocolMethod {
 /// All topics are namespaced by swarm_id to isolate communication between
 /// different swarms on the same network. The default public swarm uses
 /// "public" as its swarm_id.
+///
+/// Consensus topics (proposals and voting) are additionally scoped by the
+/// task's epoch, so a stale message for a reused task ID from an earlier
+/// epoch never lands on the current epoch's topic.
 pub struct SwarmTopics;
 
 impl SwarmTopics {
@@ -428,20 +793,56 @@ impl SwarmTopics {
         format!("{}/s/{}/election/tier1", crate::constants::TOPIC_PREFIX, swarm_id)
     }
 
-    pub fn proposals(task_id: &str) -> String {
-        Self::proposals_for(crate::constants::DEFAULT_SWARM_ID, task_id)
+    pub fn proposals(task_id: &str, epoch: u64) -> String {
+        Self::proposals_for(crate::constants::DEFAULT_SWARM_ID, task_id, epoch)
+    }
+
+    pub fn proposals_for(swarm_id: &str, task_id: &str, epoch: u64) -> String {
+        format!(
+            "{}/s/{}/proposals/e{}/{}",
+            crate::constants::TOPIC_PREFIX,
+            swarm_id,
+            epoch,
+            task_id
+        )
+    }
+
+    pub fn voting(task_id: &str, epoch: u64) -> String {
+        Self::voting_for(crate::constants::DEFAULT_SWARM_ID, task_id, epoch)
     }
 
-    pub fn proposals_for(swarm_id: &str, task_id: &str) -> String {
-        format!("{}/s/{}/proposals/{}", crate::constants::TOPIC_PREFIX, swarm_id, task_id)
+    pub fn voting_for(swarm_id: &str, task_id: &str, epoch: u64) -> String {
+        format!(
+            "{}/s/{}/voting/e{}/{}",
+            crate::constants::TOPIC_PREFIX,
+            swarm_id,
+            epoch,
+            task_id
+        )
     }
 
-    pub fn voting(task_id: &str) -> String {
-        Self::voting_for(crate::constants::DEFAULT_SWARM_ID, task_id)
+    /// Epoch of a proposals or voting topic; `None` for topics not scoped by epoch.
+    pub fn epoch_of(topic: &str) -> Option<u64> {
+        let rest = topic
+            .strip_prefix(crate::constants::TOPIC_PREFIX)?
+            .strip_prefix("/s/")?;
+        let mut segments = rest.splitn(4, '/');
+        let _swarm_id = segments.next()?;
+        match segments.next()? {
+            "proposals" | "voting" => {}
+            _ => return None,
+        }
+        segments.next()?.strip_prefix('e')?.parse().ok()
     }
 
-    pub fn voting_for(swarm_id: &str, task_id: &str) -> String {
-        format!("{}/s/{}/voting/{}", crate::constants::TOPIC_PREFIX, swarm_id, task_id)
+    /// Swarm ID of a swarm-scoped (`/s/<swarm_id>/...`) topic.
+    pub fn swarm_of(topic: &str) -> Option<&str> {
+        topic
+            .strip_prefix(crate::constants::TOPIC_PREFIX)?
+            .strip_prefix("/s/")?
+            .split('/')
+            .next()
+            .filter(|id| !id.is_empty())
     }
 
     pub fn tasks(tier: u32) -> String {
@@ -509,6 +910,24 @@ mod tests {
         assert_eq!(parsed.method, "swarm.handshake");
     }
 
+    #[test]
+    fn test_ttl_hops_run_out_on_forward() {
+        let msg = SwarmMessage::new("task.inject", serde_json::json!({}), String::new());
+        assert!(!serde_json::to_string(&msg).unwrap().contains("ttl_hops"));
+        assert_eq!(SwarmMessage::forwarded_ttl(None), None);
+
+        let msg = msg.with_ttl_hops(Some(1));
+        let parsed: SwarmMessage =
+            serde_json::from_str(&serde_json::to_string(&msg).unwrap()).unwrap();
+        assert_eq!(parsed.ttl_hops, Some(1));
+        assert!(!parsed.hops_exhausted());
+
+        let forwarded = SwarmMessage::new("task.assign", serde_json::json!({}), String::new())
+            .with_ttl_hops(SwarmMessage::forwarded_ttl(parsed.ttl_hops));
+        assert!(forwarded.hops_exhausted());
+        assert_eq!(SwarmMessage::forwarded_ttl(forwarded.ttl_hops), Some(0));
+    }
+
     #[test]
     fn test_protocol_method_roundtrip() {
         let methods = vec![
@@ -517,6 +936,7 @@ mod tests {
             ProtocolMethod::ConsensusVote,
             ProtocolMethod::ResultSubmission,
             ProtocolMethod::AgentKeepAlive,
+            ProtocolMethod::TaskAck,
         ];
         for method in methods {
             let s = method.as_str();
@@ -546,6 +966,7 @@ mod tests {
             ProtocolMethod::SwarmJoin,
             ProtocolMethod::SwarmJoinResponse,
             ProtocolMethod::SwarmLeave,
+            ProtocolMethod::StateBatch,
         ];
         for method in methods {
             let s = method.as_str();
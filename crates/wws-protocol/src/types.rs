@@ -1,7 +1,9 @@
+use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
+use crate::error::ProtocolError;
 use crate::identity::AgentId;
 
 /// Tier in the dynamic pyramid hierarchy.
@@ -51,6 +53,39 @@ pub enum TaskStatus {
     Rejected,
     /// Task result submitted but confidence delta exceeded review threshold.
     PendingReview,
+    /// Subtask waiting for sibling subtasks it depends on to complete.
+    Blocked,
+}
+
+/// How urgently a task should be picked up. Agents polling
+/// `swarm.receive_task` get higher-priority tasks first, and subtasks
+/// inherit their parent's priority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl TaskPriority {
+    pub fn is_normal(&self) -> bool {
+        *self == TaskPriority::Normal
+    }
+}
+
+impl std::str::FromStr for TaskPriority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "low" => Ok(TaskPriority::Low),
+            "normal" => Ok(TaskPriority::Normal),
+            "high" => Ok(TaskPriority::High),
+            other => Err(format!("unknown task priority '{}' (expected low, normal or high)", other)),
+        }
+    }
 }
 
 /// Tri-state of a spec-anchored deliverable (Moltbook insight #13).
@@ -69,6 +104,28 @@ pub struct Deliverable {
     pub state: DeliverableState,
 }
 
+/// Intermediate progress reported by a task's executor while it runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskProgress {
+    /// Completion estimate, 0 to 100.
+    pub percent: u8,
+    #[serde(default)]
+    pub message: String,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TaskProgress {
+    /// Progress of `percent`, clamped to `[0, 100]` and rounded. NaN counts as 0.
+    pub fn new(percent: f64, message: impl Into<String>) -> Self {
+        let percent = if percent.is_nan() { 0.0 } else { percent.clamp(0.0, 100.0) };
+        Self {
+            percent: percent.round() as u8,
+            message: message.into(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+}
+
 /// A clarification request from an agent to a task principal (Moltbook insight #20).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClarificationRequest {
@@ -119,6 +176,22 @@ pub struct Task {
     /// Confidence delta gate: if pre−post > threshold, task moves to PendingReview.
     #[serde(default = "default_confidence_review_threshold")]
     pub confidence_review_threshold: f32,
+    /// Sibling subtask IDs that must be `Completed` before this one is assigned.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// Latest progress reported by the assignee.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress: Option<TaskProgress>,
+    /// Cause of the failure while `status` is `Failed`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<FailureReason>,
+    /// Every executor running this task under redundant execution, starting
+    /// with `assigned_to`. Empty when a single executor runs it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redundant_assignees: Vec<AgentId>,
+    /// Scheduling priority; omitted on the wire when `Normal`.
+    #[serde(default, skip_serializing_if = "TaskPriority::is_normal")]
+    pub priority: TaskPriority,
 }
 
 impl Task {
@@ -144,8 +217,91 @@ impl Task {
             deliverables: Vec::new(),
             coverage_threshold: 0.0,
             confidence_review_threshold: 1.0,
+            depends_on: Vec::new(),
+            progress: None,
+            failure_reason: None,
+            redundant_assignees: Vec::new(),
+            priority: TaskPriority::Normal,
+        }
+    }
+
+    /// Whether `agent` is an executor of this task: its assignee or, under
+    /// redundant execution, one of the redundant assignees.
+    pub fn is_assignee(&self, agent: &AgentId) -> bool {
+        self.assigned_to.as_ref() == Some(agent) || self.redundant_assignees.contains(agent)
+    }
+
+    /// Mark the task `Failed` for `reason`.
+    pub fn fail(&mut self, reason: FailureReason) {
+        self.status = TaskStatus::Failed;
+        self.failure_reason = Some(reason);
+    }
+}
+
+/// Clean up an operator- or agent-supplied task description before it is
+/// stored and broadcast.
+///
+/// ANSI escape sequences (CSI, OSC and two-character escapes) are removed,
+/// line breaks and tabs become spaces and any other control characters are
+/// dropped, so a description cannot redraw the TUI or forge extra log
+/// lines. Fails if nothing printable is left or the cleaned description is
+/// longer than `max_len` bytes.
+pub fn sanitize_task_description(raw: &str, max_len: usize) -> Result<String, ProtocolError> {
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\u{1b}' => match chars.next() {
+                // CSI: parameters and intermediates up to a final byte in '@'..='~'.
+                Some('[') => {
+                    for c in chars.by_ref() {
+                        if ('@'..='~').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+                // OSC: terminated by BEL or ST (ESC \).
+                Some(']') => {
+                    while let Some(c) = chars.next() {
+                        if c == '\u{7}' {
+                            break;
+                        }
+                        if c == '\u{1b}' && chars.peek() == Some(&'\\') {
+                            chars.next();
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            },
+            // C1 CSI introducer.
+            '\u{9b}' => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            '\n' | '\r' | '\t' => out.push(' '),
+            c if c.is_control() => {}
+            c => out.push(c),
         }
     }
+
+    let cleaned = out.trim();
+    if cleaned.is_empty() {
+        return Err(ProtocolError::InvalidMessage(
+            "Task description is empty".into(),
+        ));
+    }
+    if cleaned.len() > max_len {
+        return Err(ProtocolError::InvalidMessage(format!(
+            "Task description too long ({} bytes, max {})",
+            cleaned.len(),
+            max_len
+        )));
+    }
+    Ok(cleaned.to_string())
 }
 
 impl Default for Task {
@@ -205,6 +361,9 @@ pub struct PlanSubtask {
     #[serde(default)]
     pub required_capabilities: Vec<String>,
     pub estimated_complexity: f64,
+    /// `index` values of subtasks in the same plan that must complete first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<usize>,
 }
 
 /// Result artifact from task execution.
@@ -225,6 +384,7 @@ pub struct Artifact {
     /// Merkle hash for verification chain; computed server-side if empty.
     #[serde(default)]
     pub merkle_hash: String,
+    /// MIME type of the content, e.g. `application/json`.
     #[serde(default = "default_content_type")]
     pub content_type: String,
     #[serde(default)]
@@ -234,6 +394,13 @@ pub struct Artifact {
     /// Human-readable content / result text.
     #[serde(default)]
     pub content: String,
+    /// Free-form producer metadata (encoding, schema, tool versions, ...).
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub metadata: serde_json::Value,
+    /// Hex-encoded producer signature over `(task_id, content_cid, agent_id)`;
+    /// see [`crate::ResultSubmissionParams::artifact_signing_payload`].
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub signature: String,
 }
 
 fn default_content_type() -> String {
@@ -295,6 +462,21 @@ pub struct NetworkStats {
     pub subordinate_count: u32,
     /// Parent agent ID (None if Tier-1)
     pub parent_id: Option<AgentId>,
+    /// Change in estimated swarm size, in agents per minute
+    #[serde(default)]
+    pub growth_rate_per_min: f64,
+    /// Direction the swarm size is moving in
+    #[serde(default)]
+    pub trend: SwarmTrend,
+}
+
+/// Direction of recent swarm size change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum SwarmTrend {
+    Growing,
+    #[default]
+    Stable,
+    Shrinking,
 }
 
 /// Proof of Work entry proof submitted during handshake.
@@ -363,9 +545,34 @@ impl SwarmToken {
     }
 
     /// Verify that a token matches a swarm ID and secret.
+    ///
+    /// The comparison runs in constant time over the token bytes.
     pub fn verify(&self, swarm_id: &SwarmId, secret: &str) -> bool {
         let expected = Self::generate(swarm_id, secret);
-        self.0 == expected.0
+        let (a, b) = (self.0.as_bytes(), expected.0.as_bytes());
+        a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+    }
+
+    /// Hex-encoded HMAC-SHA256 of `payload` keyed by this token, proving
+    /// possession of the token without putting it on the wire.
+    pub fn proof(&self, payload: &[u8]) -> String {
+        hex::encode(self.mac(payload).finalize().into_bytes())
+    }
+
+    /// Check a [`Self::proof`] of `payload` against the token a swarm ID and
+    /// secret generate. The comparison runs in constant time.
+    pub fn verify_proof(swarm_id: &SwarmId, secret: &str, payload: &[u8], proof: &str) -> bool {
+        let Ok(proof) = hex::decode(proof) else {
+            return false;
+        };
+        Self::generate(swarm_id, secret).mac(payload).verify_slice(&proof).is_ok()
+    }
+
+    fn mac(&self, payload: &[u8]) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.0.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(payload);
+        mac
     }
 
     pub fn as_str(&self) -> &str {
@@ -458,6 +665,15 @@ pub enum FailureReason {
     ResourceExhausted { resource: String },
     ExternalDependencyFailed { dependency: String },
     TaskAmbiguous { ambiguity_description: String, proposed_resolution: Option<String> },
+    /// The assignee missed its deadline and no replacement was available.
+    DeadlineExceeded,
+    /// The assignee stopped sending keepalives while the task was in progress.
+    ExecutorLost,
+    /// The assignee declined the assignment or did not acknowledge it in
+    /// time.
+    AssignmentNotAcknowledged,
+    /// Redundant executors returned results and none reached the majority.
+    VerificationMismatch,
 }
 
 /// Commitment receipt with rich reversibility info (Moltbook insight #1).
@@ -562,7 +778,7 @@ pub struct BallotRecord {
 pub struct IrvRound {
     pub task_id: String,
     pub round_number: u32,
-    pub tallies: std::collections::HashMap<String, usize>,
+    pub tallies: std::collections::BTreeMap<String, usize>,
     pub eliminated: Option<String>,
     pub continuing_candidates: Vec<String>,
 }
@@ -579,6 +795,22 @@ mod tests {
         assert!(task.subtasks.is_empty());
     }
 
+    #[test]
+    fn test_sanitize_task_description_strips_escapes() {
+        let raw = "\u{1b}[2J\u{1b}[31mDeploy\u{1b}[0m the\tfix\n[INFO] fake log\u{7}\u{1b}]0;title\u{7}!";
+        let cleaned = sanitize_task_description(raw, 4096).unwrap();
+        assert_eq!(cleaned, "Deploy the fix [INFO] fake log!");
+        assert!(!cleaned.chars().any(char::is_control));
+    }
+
+    #[test]
+    fn test_sanitize_task_description_rejects_empty_and_overlong() {
+        assert!(sanitize_task_description("", 4096).is_err());
+        assert!(sanitize_task_description(" \u{1b}[1m\u{1b}[0m\r\n\u{0}", 4096).is_err());
+        assert!(sanitize_task_description("abcdef", 5).is_err());
+        assert_eq!(sanitize_task_description("abcde", 5).unwrap(), "abcde");
+    }
+
     #[test]
     fn test_critic_score_aggregate() {
         let score = CriticScore {
@@ -865,9 +1097,9 @@ mod tests {
 
     #[test]
     fn test_irv_round_serialization_roundtrip() {
-        use std::collections::HashMap;
+        use std::collections::BTreeMap;
 
-        let mut tallies = HashMap::new();
+        let mut tallies = BTreeMap::new();
         tallies.insert("plan-A".to_string(), 3usize);
         tallies.insert("plan-B".to_string(), 2usize);
         tallies.insert("plan-C".to_string(), 1usize);
@@ -941,8 +1173,8 @@ mod tests {
 
     #[test]
     fn test_irv_round_final_no_elimination() {
-        use std::collections::HashMap;
-        let mut tallies = HashMap::new();
+        use std::collections::BTreeMap;
+        let mut tallies = BTreeMap::new();
         tallies.insert("plan-A".to_string(), 5usize);
 
         let round = IrvRound {
@@ -347,3 +347,62 @@ fn test_registration_pow_difficulty() {
     assert_eq!(registration_pow_difficulty(1000), 16);
     assert_eq!(registration_pow_difficulty(10000), 18);
 }
+
+// ═══════════════════════════════════════════════════════════════
+// § Private swarm payload encryption
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_swarm_cipher_round_trip() {
+    use wws_protocol::{SwarmId, SwarmToken};
+    let swarm_id = SwarmId::new("private-1".into());
+    let cipher = SwarmCipher::from_token(&swarm_id, &SwarmToken::generate(&swarm_id, "secret"));
+    let sealed = cipher.seal(b"hello swarm");
+    assert!(SwarmCipher::is_sealed(&sealed));
+    assert_ne!(cipher.seal(b"hello swarm"), sealed, "nonces must differ");
+    assert_eq!(cipher.open(&sealed).unwrap(), b"hello swarm");
+}
+
+#[test]
+fn test_swarm_cipher_rejects_wrong_key_and_tampering() {
+    use wws_protocol::{SwarmId, SwarmToken};
+    let swarm_id = SwarmId::new("private-1".into());
+    let cipher = SwarmCipher::from_token(&swarm_id, &SwarmToken::generate(&swarm_id, "secret"));
+    let sealed = cipher.seal(b"payload");
+
+    let wrong = SwarmCipher::from_token(&swarm_id, &SwarmToken::generate(&swarm_id, "other"));
+    assert!(wrong.open(&sealed).is_err());
+
+    let other_swarm = SwarmId::new("private-2".into());
+    let token = SwarmToken::generate(&swarm_id, "secret");
+    assert!(SwarmCipher::from_token(&other_swarm, &token).open(&sealed).is_err());
+
+    let mut tampered = sealed.clone();
+    *tampered.last_mut().unwrap() ^= 1;
+    assert!(cipher.open(&tampered).is_err());
+    assert!(cipher.open(b"plaintext").is_err());
+}
+
+#[test]
+fn test_discovery_observer_cannot_open_private_swarm_payloads() {
+    use wws_protocol::{AgentId, SwarmId, SwarmJoinParams, SwarmToken};
+    let swarm_id = SwarmId::new("private-1".into());
+    let token = SwarmToken::generate(&swarm_id, "secret");
+    let sealed = SwarmCipher::from_token(&swarm_id, &token).seal(b"/s/private-1/tasks payload");
+
+    // Everything a peer sees of a member joining on the discovery topic.
+    let join = SwarmJoinParams::new(swarm_id.clone(), AgentId::new("did:swarm:joiner".into()), Some(&token));
+    let observed = serde_json::to_value(&join).unwrap();
+    let strings: Vec<String> = observed
+        .as_object()
+        .unwrap()
+        .values()
+        .filter_map(|v| v.as_str().map(String::from))
+        .collect();
+    assert!(!strings.contains(&token.as_str().to_string()));
+
+    for guess in &strings {
+        let cipher = SwarmCipher::from_token(&swarm_id, &SwarmToken::new(guess.clone()));
+        assert!(cipher.open(&sealed).is_err(), "key derived from {guess:?} opened the payload");
+    }
+}
@@ -259,6 +259,50 @@ fn composite_score_stake_clamped_to_one() {
     );
 }
 
+#[test]
+fn composite_score_with_default_weights_matches_composite_score() {
+    let score = NodeScore {
+        agent_id: AgentId::new("did:swarm:test".into()),
+        proof_of_compute: 0.3,
+        reputation: 0.6,
+        uptime: 0.9,
+        stake: Some(0.5),
+    };
+    assert!(
+        (score.composite_score_with(&ScoreWeights::default()) - score.composite_score()).abs() < 1e-10
+    );
+}
+
+#[test]
+fn composite_score_weights_change_ranking() {
+    let reputable = NodeScore {
+        agent_id: AgentId::new("did:swarm:a".into()),
+        proof_of_compute: 0.2,
+        reputation: 1.0,
+        uptime: 0.5,
+        stake: None,
+    };
+    let powerful = NodeScore {
+        agent_id: AgentId::new("did:swarm:b".into()),
+        proof_of_compute: 1.0,
+        reputation: 0.4,
+        uptime: 0.5,
+        stake: None,
+    };
+    assert!(reputable.composite_score() > powerful.composite_score());
+
+    let compute_heavy = ScoreWeights {
+        proof_of_compute: 0.70,
+        reputation: 0.10,
+        uptime: 0.10,
+        stake: 0.10,
+    };
+    assert!(
+        powerful.composite_score_with(&compute_heavy) > reputable.composite_score_with(&compute_heavy),
+        "Weighting PoC above reputation must favour the stronger benchmark"
+    );
+}
+
 // ═══════════════════════════════════════════════════════════════
 // Serialization
 // ═══════════════════════════════════════════════════════════════
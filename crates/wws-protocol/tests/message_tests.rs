@@ -132,6 +132,57 @@ fn response_serialization_roundtrip() {
     assert_eq!(parsed.error.as_ref().unwrap().code, -31000);
 }
 
+#[test]
+fn rpc_error_codes_map_to_documented_integers() {
+    let expected = [
+        (RpcErrorCode::ParseError, -32700),
+        (RpcErrorCode::InvalidRequest, -32600),
+        (RpcErrorCode::MethodNotFound, -32601),
+        (RpcErrorCode::InvalidParams, -32602),
+        (RpcErrorCode::InternalError, -32603),
+        (RpcErrorCode::OperationFailed, -32000),
+        (RpcErrorCode::Unauthorized, -32001),
+        (RpcErrorCode::Forbidden, -32002),
+        (RpcErrorCode::InsufficientReputation, -32003),
+        (RpcErrorCode::NotFound, -32004),
+        (RpcErrorCode::NotReady, -32005),
+        (RpcErrorCode::PlanTooLarge, -32006),
+        (RpcErrorCode::BudgetExceeded, -32007),
+        (RpcErrorCode::BlastRadiusExceeded, -32008),
+        (RpcErrorCode::InvalidState, -32009),
+        (RpcErrorCode::SubtasksIncomplete, -32010),
+        (RpcErrorCode::NoSubtasks, -32011),
+        (RpcErrorCode::StaleAssignee, -32012),
+        (RpcErrorCode::EmptyPlan, -32013),
+        (RpcErrorCode::RateLimited, -32029),
+    ];
+    assert_eq!(expected.len(), RpcErrorCode::ALL.len());
+    for (variant, code) in expected {
+        assert_eq!(variant.code(), code, "{:?}", variant);
+        assert_eq!(i32::from(variant), code);
+        assert_eq!(RpcErrorCode::from_code(code), Some(variant));
+    }
+    assert_eq!(RpcErrorCode::from_code(-31000), None);
+}
+
+#[test]
+fn rpc_error_code_survives_response_roundtrip() {
+    for variant in RpcErrorCode::ALL {
+        let resp = SwarmResponse::rpc_error(Some("id-4".into()), variant, "failed");
+        let json = serde_json::to_string(&resp).unwrap();
+        let parsed: SwarmResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.error_code(), Some(variant));
+        assert_eq!(parsed.error.unwrap().message, "failed");
+    }
+
+    let resp = SwarmResponse::not_found(Some("id-5".into()), "Task not found: t1");
+    assert_eq!(resp.error_code(), Some(RpcErrorCode::NotFound));
+    assert_eq!(
+        SwarmResponse::success(None, serde_json::json!({})).error_code(),
+        None
+    );
+}
+
 // ═══════════════════════════════════════════════════════════════
 // § 13.2 Protocol Method Registry
 // ═══════════════════════════════════════════════════════════════
@@ -148,6 +199,8 @@ fn all_protocol_methods_have_string_representation() {
         ProtocolMethod::ProposalReveal,
         ProtocolMethod::ConsensusVote,
         ProtocolMethod::TaskAssignment,
+        ProtocolMethod::TaskDelegation,
+        ProtocolMethod::TaskProgress,
         ProtocolMethod::ResultSubmission,
         ProtocolMethod::VerificationResult,
         ProtocolMethod::KeepAlive,
@@ -176,6 +229,8 @@ fn protocol_method_roundtrip_all() {
         "consensus.proposal_reveal",
         "consensus.vote",
         "task.assign",
+        "task.delegate",
+        "task.progress",
         "task.submit_result",
         "task.verification",
         "swarm.keepalive",
@@ -209,8 +264,8 @@ fn topics_have_correct_prefix() {
     assert!(SwarmTopics::election_tier1().starts_with(prefix));
     assert!(SwarmTopics::keepalive().starts_with(prefix));
     assert!(SwarmTopics::hierarchy().starts_with(prefix));
-    assert!(SwarmTopics::proposals("task-1").starts_with(prefix));
-    assert!(SwarmTopics::voting("task-1").starts_with(prefix));
+    assert!(SwarmTopics::proposals("task-1", 1).starts_with(prefix));
+    assert!(SwarmTopics::voting("task-1", 1).starts_with(prefix));
     assert!(SwarmTopics::tasks(1).starts_with(prefix));
     assert!(SwarmTopics::results("task-1").starts_with(prefix));
 }
@@ -218,11 +273,37 @@ fn topics_have_correct_prefix() {
 #[test]
 fn topics_contain_task_id() {
     let task_id = "unique-task-id-12345";
-    assert!(SwarmTopics::proposals(task_id).contains(task_id));
-    assert!(SwarmTopics::voting(task_id).contains(task_id));
+    assert!(SwarmTopics::proposals(task_id, 1).contains(task_id));
+    assert!(SwarmTopics::voting(task_id, 1).contains(task_id));
     assert!(SwarmTopics::results(task_id).contains(task_id));
 }
 
+#[test]
+fn consensus_topics_are_scoped_by_epoch() {
+    let task_id = "task-reused";
+    assert_ne!(
+        SwarmTopics::proposals_for("public", task_id, 1),
+        SwarmTopics::proposals_for("public", task_id, 2)
+    );
+    assert_ne!(
+        SwarmTopics::voting_for("public", task_id, 1),
+        SwarmTopics::voting_for("public", task_id, 2)
+    );
+    assert_eq!(SwarmTopics::epoch_of(&SwarmTopics::proposals_for("public", task_id, 7)), Some(7));
+    assert_eq!(SwarmTopics::epoch_of(&SwarmTopics::voting_for("voting", task_id, 3)), Some(3));
+    assert_eq!(SwarmTopics::epoch_of(&SwarmTopics::results_for("public", task_id)), None);
+    assert_eq!(SwarmTopics::epoch_of(&SwarmTopics::tasks_for("public", 1)), None);
+}
+
+#[test]
+fn swarm_of_extracts_swarm_id_from_scoped_topics() {
+    assert_eq!(SwarmTopics::swarm_of(&SwarmTopics::tasks_for("work", 2)), Some("work"));
+    assert_eq!(SwarmTopics::swarm_of(&SwarmTopics::proposals_for("mon", "t1", 4)), Some("mon"));
+    assert_eq!(SwarmTopics::swarm_of(&SwarmTopics::tasks(1)), Some("public"));
+    assert_eq!(SwarmTopics::swarm_of(&SwarmTopics::swarm_discovery()), None);
+    assert_eq!(SwarmTopics::swarm_of("unrelated/topic"), None);
+}
+
 #[test]
 fn topics_contain_tier_number() {
     let topic = SwarmTopics::tasks(3);
@@ -334,6 +415,8 @@ fn result_submission_params_serialization() {
             size_bytes: 4096,
             created_at: chrono::Utc::now(),
             content: "result content".into(),
+            metadata: serde_json::json!({"schema": "report-v1"}),
+            signature: String::new(),
         },
         merkle_proof: vec!["hash1".into(), "hash2".into()],
         is_synthesis: false,
@@ -342,6 +425,25 @@ fn result_submission_params_serialization() {
     let parsed: ResultSubmissionParams = serde_json::from_str(&json).unwrap();
     assert_eq!(parsed.artifact.size_bytes, 4096);
     assert_eq!(parsed.merkle_proof.len(), 2);
+    assert_eq!(parsed.artifact.metadata["schema"], "report-v1");
+    // Unsigned artifacts omit the signature on the wire.
+    assert!(!json.contains("signature"));
+}
+
+#[test]
+fn artifact_signing_payload_covers_task_cid_and_agent() {
+    let mut params: ResultSubmissionParams = serde_json::from_value(serde_json::json!({
+        "task_id": "task-1",
+        "agent_id": "did:swarm:exec1",
+        "artifact": {"content_cid": "cid-a", "content": "result"},
+    }))
+    .unwrap();
+    let payload = params.artifact_signing_payload();
+    params.artifact.signature = "ff".into();
+    params.artifact.content = "edited".into();
+    assert_eq!(params.artifact_signing_payload(), payload);
+    params.artifact.content_cid = "cid-b".into();
+    assert_ne!(params.artifact_signing_payload(), payload);
 }
 
 #[test]
@@ -383,11 +485,32 @@ fn tier_assignment_params_serialization() {
         parent_id: AgentId::new("did:swarm:leader".into()),
         epoch: 106,
         branch_size: 85,
+        issuer: AgentId::new("did:swarm:leader".into()),
+        signature: "ab01".into(),
     };
     let json = serde_json::to_string(&params).unwrap();
     let parsed: TierAssignmentParams = serde_json::from_str(&json).unwrap();
     assert_eq!(parsed.tier, Tier::Tier1);
     assert_eq!(parsed.branch_size, 85);
+    assert_eq!(parsed.issuer.as_str(), "did:swarm:leader");
+}
+
+#[test]
+fn tier_assignment_signing_payload_covers_fields_but_not_signature() {
+    let mut params = TierAssignmentParams {
+        assigned_agent: AgentId::new("did:swarm:agent".into()),
+        tier: Tier::Executor,
+        parent_id: AgentId::new("did:swarm:leader".into()),
+        epoch: 3,
+        branch_size: 4,
+        issuer: AgentId::new("did:swarm:leader".into()),
+        signature: String::new(),
+    };
+    let payload = params.signing_payload();
+    params.signature = "ff".into();
+    assert_eq!(params.signing_payload(), payload);
+    params.tier = Tier::Tier1;
+    assert_ne!(params.signing_payload(), payload);
 }
 
 // ═══════════════════════════════════════════════════════════════
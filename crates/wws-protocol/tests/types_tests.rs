@@ -143,6 +143,7 @@ fn plan_subtask_ordering() {
             description: format!("Subtask {}", i),
             required_capabilities: vec![],
             estimated_complexity: 0.5,
+            depends_on: Vec::new(),
         });
     }
     assert_eq!(plan.subtasks.len(), 10);
@@ -165,6 +166,7 @@ fn plan_serialization_roundtrip() {
         description: "Do thing".into(),
         required_capabilities: vec!["web-search".into()],
         estimated_complexity: 0.7,
+        depends_on: Vec::new(),
     });
 
     let json = serde_json::to_string(&plan).unwrap();
@@ -331,12 +333,37 @@ fn artifact_serialization() {
         size_bytes: 1024,
         created_at: chrono::Utc::now(),
         content: "test artifact content".into(),
+        metadata: serde_json::Value::Null,
+        signature: String::new(),
     };
     let json = serde_json::to_string(&artifact).unwrap();
     let parsed: Artifact = serde_json::from_str(&json).unwrap();
     assert_eq!(parsed.artifact_id, "art-001");
     assert_eq!(parsed.size_bytes, 1024);
     assert_eq!(parsed.content_type, "text/plain");
+    // Null metadata is omitted on the wire.
+    assert!(!json.contains("metadata"));
+}
+
+#[test]
+fn artifact_content_type_and_metadata_round_trip() {
+    let json = serde_json::json!({
+        "content": "{\"rows\": 3}",
+        "content_type": "application/json",
+        "metadata": {"schema": "table-v2", "encoding": "utf-8", "rows": 3}
+    });
+    let parsed: Artifact = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed.content_type, "application/json");
+    assert_eq!(parsed.metadata["schema"], "table-v2");
+
+    let reparsed: Artifact =
+        serde_json::from_str(&serde_json::to_string(&parsed).unwrap()).unwrap();
+    assert_eq!(reparsed.metadata, parsed.metadata);
+
+    // Older producers send neither field.
+    let legacy: Artifact = serde_json::from_value(serde_json::json!({})).unwrap();
+    assert_eq!(legacy.content_type, "text/plain");
+    assert!(legacy.metadata.is_null());
 }
 
 // ═══════════════════════════════════════════════════════════════
@@ -353,6 +380,8 @@ fn network_stats_serialization() {
         my_tier: Tier::Tier2,
         subordinate_count: 8,
         parent_id: Some(AgentId::new("did:swarm:leader".into())),
+        growth_rate_per_min: 0.0,
+        trend: SwarmTrend::Stable,
     };
     let json = serde_json::to_string(&stats).unwrap();
     let parsed: NetworkStats = serde_json::from_str(&json).unwrap();
@@ -361,6 +390,16 @@ fn network_stats_serialization() {
     assert_eq!(parsed.my_tier, Tier::Tier2);
 }
 
+#[test]
+fn network_stats_without_growth_fields_defaults_to_stable() {
+    let json = r#"{"total_agents":5,"hierarchy_depth":1,"branching_factor":10,
+        "current_epoch":1,"my_tier":"Tier1","subordinate_count":0,"parent_id":null}"#;
+    let parsed: NetworkStats = serde_json::from_str(json).unwrap();
+    assert_eq!(parsed.trend, SwarmTrend::Stable);
+    assert_eq!(parsed.growth_rate_per_min, 0.0);
+    assert_eq!(serde_json::to_value(SwarmTrend::Shrinking).unwrap(), "Shrinking");
+}
+
 #[test]
 fn network_stats_tier0_has_no_parent() {
     let stats = NetworkStats {
@@ -371,6 +410,8 @@ fn network_stats_tier0_has_no_parent() {
         my_tier: Tier::Tier0,
         subordinate_count: 0,
         parent_id: None,
+        growth_rate_per_min: 0.0,
+        trend: SwarmTrend::Stable,
     };
     assert!(stats.parent_id.is_none());
 }
@@ -427,6 +468,28 @@ fn ranked_vote_serialization() {
     assert!(parsed.critic_scores.contains_key("plan-a"));
 }
 
+// ═══════════════════════════════════════════════════════════════
+// SwarmToken
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn join_proof_verifies_against_the_swarm_secret_only() {
+    let swarm_id = SwarmId::new("team".into());
+    let join = wws_protocol::SwarmJoinParams::new(
+        swarm_id.clone(),
+        AgentId::new("did:swarm:joiner".into()),
+        Some(&SwarmToken::generate(&swarm_id, "s3cret")),
+    );
+    let payload = join.proof_payload();
+    let proof = join.proof.as_deref().unwrap();
+
+    assert!(SwarmToken::verify_proof(&swarm_id, "s3cret", &payload, proof));
+    assert!(!SwarmToken::verify_proof(&swarm_id, "guess", &payload, proof));
+    let mut replayed = join.clone();
+    replayed.agent_id = AgentId::new("did:swarm:other".into());
+    assert!(!SwarmToken::verify_proof(&swarm_id, "s3cret", &replayed.proof_payload(), proof));
+}
+
 // ═══════════════════════════════════════════════════════════════
 // Constants
 // ═══════════════════════════════════════════════════════════════
@@ -1,7 +1,7 @@
 //! Content-addressed storage: CID generation, local storage, DHT publishing.
 //!
 //! All artifacts produced by agents are stored content-addressed:
-//! - The Content ID (CID) is the SHA-256 hex digest of the content
+//! - The Content ID (CID) is the hex-encoded multihash of the content
 //! - Content is stored locally and its CID is published to the DHT
 //! - Other agents can retrieve content by CID from the DHT
 //!
@@ -9,53 +9,418 @@
 //! - Deduplication: identical content has the same CID
 //! - Integrity: any bit flip changes the CID
 //! - Location-independence: content is found by hash, not by location
+//!
+//! CIDs are CIDv1 strings, as used by IPFS tooling: the multibase prefix
+//! `b` followed by unpadded lowercase base32 of `<version 1><codec raw
+//! 0x55><multihash>`. A multihash is `<code><digest length><digest>`, so
+//! each CID declares the hash function that produced it (`bafkrei...` for
+//! SHA-256, `bafkr4i...` for BLAKE3) and stores configured with different
+//! algorithms can still verify each other's content. [`parse_cid`] and
+//! [`format_cid`] convert between the string and its parts.
+//!
+//! Earlier releases used the hex-encoded multihash (`1220...`), and before
+//! that the bare 64-character hex SHA-256 digest. Both are still parsed so
+//! content addressed by older peers can be verified.
+//!
+//! A store may be given a byte capacity; once full, the oldest content is
+//! evicted to make room for new content.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
+use crate::StateError;
+
+/// Length in bytes of the digests produced by every supported algorithm.
+const DIGEST_LEN: usize = 32;
+
+/// CID version byte.
+const CID_VERSION: u8 = 0x01;
+
+/// Multicodec code for raw bytes.
+const RAW_CODEC: u8 = 0x55;
+
+/// Multibase prefix for unpadded lowercase RFC 4648 base32.
+const BASE32_PREFIX: char = 'b';
+
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Hash function used to derive content IDs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgo {
+    /// SHA-256 (multihash code `0x12`).
+    #[default]
+    Sha256,
+    /// BLAKE3 with a 256-bit output (multihash code `0x1e`).
+    Blake3,
+}
+
+impl HashAlgo {
+    /// Multihash code identifying this algorithm.
+    pub fn multihash_code(self) -> u8 {
+        match self {
+            HashAlgo::Sha256 => 0x12,
+            HashAlgo::Blake3 => 0x1e,
+        }
+    }
+
+    /// Look up an algorithm by its multihash code.
+    pub fn from_multihash_code(code: u8) -> Option<Self> {
+        match code {
+            0x12 => Some(HashAlgo::Sha256),
+            0x1e => Some(HashAlgo::Blake3),
+            _ => None,
+        }
+    }
+
+    /// Raw digest of `data`.
+    pub fn digest(self, data: &[u8]) -> [u8; DIGEST_LEN] {
+        match self {
+            HashAlgo::Sha256 => Sha256::digest(data).into(),
+            HashAlgo::Blake3 => *blake3::hash(data).as_bytes(),
+        }
+    }
+
+    /// CIDv1 of `data`.
+    pub fn cid(self, data: &[u8]) -> String {
+        format_cid(&Cid {
+            hash_algo: self,
+            digest: self.digest(data),
+        })
+    }
+
+    /// The algorithm a CID declares.
+    ///
+    /// Bare 64-character hex CIDs are treated as SHA-256.
+    pub fn of_cid(cid: &str) -> Result<Self, StateError> {
+        parse_cid(cid).map(|c| c.hash_algo)
+    }
+}
+
+/// The parts of a content ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cid {
+    /// Hash function the digest was produced with.
+    pub hash_algo: HashAlgo,
+    /// Digest of the content.
+    pub digest: [u8; DIGEST_LEN],
+}
+
+/// Encode `cid` as a CIDv1 string: multibase base32, raw codec, multihash.
+pub fn format_cid(cid: &Cid) -> String {
+    let mut bytes = Vec::with_capacity(DIGEST_LEN + 4);
+    bytes.extend_from_slice(&[
+        CID_VERSION,
+        RAW_CODEC,
+        cid.hash_algo.multihash_code(),
+        DIGEST_LEN as u8,
+    ]);
+    bytes.extend_from_slice(&cid.digest);
+    let mut out = String::with_capacity(1 + (bytes.len() * 8).div_ceil(5));
+    out.push(BASE32_PREFIX);
+    out.push_str(&base32_encode(&bytes));
+    out
+}
+
+/// Parse a content ID.
+///
+/// Accepts the CIDv1 strings produced by [`format_cid`] exactly, plus the
+/// legacy hex multihash and bare hex SHA-256 forms. Anything else, including
+/// a CIDv1 with another multibase, codec or non-canonical padding bits, is
+/// `InvalidCid`.
+pub fn parse_cid(cid: &str) -> Result<Cid, StateError> {
+    let invalid = || StateError::InvalidCid(cid.to_string());
+    let multihash = if let Some(encoded) = cid.strip_prefix(BASE32_PREFIX) {
+        let bytes = base32_decode(encoded).ok_or_else(invalid)?;
+        match bytes.as_slice() {
+            [CID_VERSION, RAW_CODEC, multihash @ ..] => multihash.to_vec(),
+            _ => return Err(invalid()),
+        }
+    } else {
+        let bytes = hex_decode(cid).ok_or_else(invalid)?;
+        if bytes.len() == DIGEST_LEN {
+            let mut digest = [0u8; DIGEST_LEN];
+            digest.copy_from_slice(&bytes);
+            return Ok(Cid {
+                hash_algo: HashAlgo::Sha256,
+                digest,
+            });
+        }
+        bytes
+    };
+    match multihash.as_slice() {
+        [code, len, digest @ ..] if *len as usize == DIGEST_LEN && digest.len() == DIGEST_LEN => {
+            let hash_algo = HashAlgo::from_multihash_code(*code).ok_or_else(invalid)?;
+            let mut bytes = [0u8; DIGEST_LEN];
+            bytes.copy_from_slice(digest);
+            Ok(Cid {
+                hash_algo,
+                digest: bytes,
+            })
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// The [`format_cid`] form of `cid`, so legacy hex CIDs and CIDv1 strings
+/// of the same content compare equal. Unparseable CIDs are returned as is.
+pub fn canonical_cid(cid: &str) -> String {
+    parse_cid(cid).map_or_else(|_| cid.to_string(), |parsed| format_cid(&parsed))
+}
+
+impl std::str::FromStr for HashAlgo {
+    type Err = StateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "sha256" | "sha2-256" => Ok(HashAlgo::Sha256),
+            "blake3" => Ok(HashAlgo::Blake3),
+            other => Err(StateError::InvalidCid(format!(
+                "unknown hash algorithm: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Descriptive information stored alongside a piece of content.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContentInfo {
+    /// MIME type, e.g. `text/plain` or `application/json`.
+    pub content_type: String,
+    /// Free-form producer metadata.
+    #[serde(default)]
+    pub metadata: serde_json::Value,
+    /// Producer signature kept for later audit, when the content was
+    /// submitted as a signed artifact.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<ContentProvenance>,
+}
+
+/// Who vouched for a piece of content, and their signature.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentProvenance {
+    /// Agent that signed the content.
+    pub signer: String,
+    /// Hex-encoded signature over the artifact signing payload.
+    pub signature: String,
+}
+
+/// Size and utilization of a [`ContentStore`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentStats {
+    /// Number of stored items.
+    pub item_count: usize,
+    /// Total size of stored content in bytes.
+    pub total_bytes: u64,
+    /// CID of the largest stored item, if any.
+    pub largest_cid: Option<String>,
+    /// Byte capacity of the store; `None` when unlimited.
+    pub capacity_bytes: Option<u64>,
+    /// Items evicted to stay within capacity.
+    pub evicted_items: u64,
+    /// Bytes evicted to stay within capacity.
+    pub evicted_bytes: u64,
+}
+
 /// Content-addressed storage for artifacts.
 ///
 /// Stores content locally with optional DHT publishing for
-/// distributed retrieval. All content is identified by its
-/// SHA-256 hash (CID).
+/// distributed retrieval. All content is identified by the
+/// multihash of its bytes (CID), using the store's `HashAlgo`.
 pub struct ContentStore {
     /// Local content storage: CID -> content bytes.
     data: HashMap<String, Vec<u8>>,
     /// Provider records: CID -> set of agent IDs that have the content.
     providers: HashMap<String, HashSet<String>>,
+    /// Content type and metadata: CID -> info, for content stored with one.
+    info: HashMap<String, ContentInfo>,
+    /// Algorithm used for CIDs of newly stored content.
+    hash_algo: HashAlgo,
+    /// CIDs in insertion order, oldest first, for capacity eviction.
+    order: VecDeque<String>,
+    /// Total size of `data` in bytes.
+    total_bytes: u64,
+    /// Maximum total size before the oldest content is evicted.
+    capacity_bytes: Option<u64>,
+    /// Items and bytes evicted to stay within `capacity_bytes`.
+    evicted_items: u64,
+    evicted_bytes: u64,
 }
 
 impl ContentStore {
-    /// Create a new empty content store.
+    /// Create a new empty content store using SHA-256 CIDs.
     pub fn new() -> Self {
+        Self::with_hash_algo(HashAlgo::default())
+    }
+
+    /// Create a new empty content store that derives CIDs with `hash_algo`.
+    pub fn with_hash_algo(hash_algo: HashAlgo) -> Self {
         Self {
             data: HashMap::new(),
             providers: HashMap::new(),
+            info: HashMap::new(),
+            hash_algo,
+            order: VecDeque::new(),
+            total_bytes: 0,
+            capacity_bytes: None,
+            evicted_items: 0,
+            evicted_bytes: 0,
+        }
+    }
+
+    /// Limit the total size of stored content, evicting the oldest items
+    /// immediately if the store is already over the new limit. `None`
+    /// removes the limit.
+    pub fn set_capacity_bytes(&mut self, capacity_bytes: Option<u64>) {
+        self.capacity_bytes = capacity_bytes;
+        self.evict_to_capacity(None);
+    }
+
+    /// Size and utilization of the store.
+    pub fn stats(&self) -> ContentStats {
+        let largest_cid = self
+            .data
+            .iter()
+            .max_by(|(a_cid, a), (b_cid, b)| a.len().cmp(&b.len()).then_with(|| b_cid.cmp(a_cid)))
+            .map(|(cid, _)| cid.clone());
+        ContentStats {
+            item_count: self.data.len(),
+            total_bytes: self.total_bytes,
+            largest_cid,
+            capacity_bytes: self.capacity_bytes,
+            evicted_items: self.evicted_items,
+            evicted_bytes: self.evicted_bytes,
+        }
+    }
+
+    /// Insert content under `cid` unless already present, then evict older
+    /// content if the store is over capacity. Content larger than the whole
+    /// capacity is still kept, at the cost of everything else.
+    fn insert(&mut self, cid: &str, data: &[u8]) {
+        if self.data.contains_key(cid) {
+            return;
+        }
+        self.data.insert(cid.to_string(), data.to_vec());
+        self.order.push_back(cid.to_string());
+        self.total_bytes += data.len() as u64;
+        self.evict_to_capacity(Some(cid));
+    }
+
+    /// Evict the oldest content, except `keep`, until within capacity.
+    fn evict_to_capacity(&mut self, keep: Option<&str>) {
+        let Some(capacity) = self.capacity_bytes else {
+            return;
+        };
+        let mut skipped = None;
+        while self.total_bytes > capacity {
+            let Some(oldest) = self.order.pop_front() else {
+                break;
+            };
+            if Some(oldest.as_str()) == keep {
+                skipped = Some(oldest);
+                continue;
+            }
+            let size = self.data.get(&oldest).map_or(0, |d| d.len() as u64);
+            self.remove(&oldest);
+            self.evicted_items += 1;
+            self.evicted_bytes += size;
+        }
+        if let Some(kept) = skipped {
+            self.order.push_front(kept);
         }
     }
 
-    /// Store content and return its CID (SHA-256 hex).
+    /// Algorithm used for CIDs of newly stored content.
+    pub fn hash_algo(&self) -> HashAlgo {
+        self.hash_algo
+    }
+
+    /// Store content and return its CID.
     ///
     /// If content with the same CID already exists, this is a no-op
     /// (deduplication). Returns the CID in either case.
     pub fn store(&mut self, data: &[u8]) -> String {
-        let cid = Self::compute_cid(data);
+        let cid = self.hash_algo.cid(data);
         // Deduplicate: only insert if not already present.
-        self.data.entry(cid.clone()).or_insert_with(|| data.to_vec());
+        self.insert(&cid, data);
+        cid
+    }
+
+    /// Store content received under a claimed CID.
+    ///
+    /// The CID is recomputed with the algorithm it declares, regardless of
+    /// this store's own algorithm, and the content is rejected if it does
+    /// not match. Content is keyed by its canonical CID, so the same bytes
+    /// claimed under the hex and CIDv1 forms are stored once.
+    pub fn put_verified(&mut self, cid: &str, data: &[u8]) -> Result<(), StateError> {
+        let parsed = parse_cid(cid)?;
+        if parsed.hash_algo.digest(data) != parsed.digest {
+            return Err(StateError::InvalidCid(format!(
+                "{} does not match content",
+                cid
+            )));
+        }
+        self.insert(&format_cid(&parsed), data);
+        Ok(())
+    }
+
+    /// Store content together with its content type and metadata.
+    ///
+    /// The bytes are deduplicated as in [`ContentStore::store`]; the info
+    /// is replaced by the latest description of the same content.
+    pub fn store_with_info(&mut self, data: &[u8], info: ContentInfo) -> String {
+        let cid = self.store(data);
+        self.info.insert(cid.clone(), info);
         cid
     }
 
+    /// Attach a content type and metadata to content already in the store.
+    ///
+    /// Returns `false` if the CID is not stored locally.
+    pub fn set_info(&mut self, cid: &str, info: ContentInfo) -> bool {
+        let cid = canonical_cid(cid);
+        if !self.data.contains_key(&cid) {
+            return false;
+        }
+        self.info.insert(cid, info);
+        true
+    }
+
+    /// Content type and metadata recorded for a CID, if any.
+    pub fn get_info(&self, cid: &str) -> Option<&ContentInfo> {
+        self.info.get(&canonical_cid(cid))
+    }
+
     /// Retrieve content by CID.
     ///
     /// Returns `None` if the CID is not found in local storage.
     pub fn get(&self, cid: &str) -> Option<Vec<u8>> {
-        self.data.get(cid).cloned()
+        self.data.get(&canonical_cid(cid)).cloned()
     }
 
     /// Check if content exists locally.
     pub fn exists(&self, cid: &str) -> bool {
-        self.data.contains_key(cid)
+        self.data.contains_key(&canonical_cid(cid))
+    }
+
+    /// Remove content and everything recorded about it.
+    ///
+    /// Returns `false` if the CID was not stored locally.
+    pub fn remove(&mut self, cid: &str) -> bool {
+        let cid = canonical_cid(cid);
+        self.info.remove(&cid);
+        self.providers.remove(&cid);
+        let Some(data) = self.data.remove(&cid) else {
+            return false;
+        };
+        self.total_bytes -= data.len() as u64;
+        if let Some(pos) = self.order.iter().position(|c| *c == cid) {
+            self.order.remove(pos);
+        }
+        true
     }
 
     /// Publish a provider record for a CID.
@@ -64,7 +429,7 @@ impl ContentStore {
     /// by `cid`. Multiple agents can provide the same content.
     pub fn publish_provider(&mut self, cid: &str, agent_id: String) {
         self.providers
-            .entry(cid.to_string())
+            .entry(canonical_cid(cid))
             .or_insert_with(HashSet::new)
             .insert(agent_id);
     }
@@ -74,15 +439,14 @@ impl ContentStore {
     /// Returns an empty list if no providers are known.
     pub fn get_providers(&self, cid: &str) -> Vec<String> {
         self.providers
-            .get(cid)
+            .get(&canonical_cid(cid))
             .map(|set| set.iter().cloned().collect())
             .unwrap_or_default()
     }
 
-    /// Compute the CID for content without storing it.
+    /// Compute the SHA-256 CID for content without storing it.
     pub fn compute_cid(content: &[u8]) -> String {
-        let hash = Sha256::digest(content);
-        hex_encode(&hash)
+        HashAlgo::Sha256.cid(content)
     }
 
     /// Get the total number of stored items.
@@ -102,9 +466,54 @@ impl Default for ContentStore {
     }
 }
 
-/// Hex-encode a byte slice into a lowercase hex string.
-fn hex_encode(bytes: &[u8]) -> String {
-    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+/// Decode a hex string of either case; `None` if malformed.
+fn hex_decode(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) || !s.is_ascii() {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// Unpadded lowercase RFC 4648 base32.
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity((bytes.len() * 8).div_ceil(5));
+    let mut buffer: u16 = 0;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u16;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    out
+}
+
+/// Inverse of [`base32_encode`]. `None` on characters outside the lowercase
+/// alphabet, impossible lengths or non-zero trailing bits, so every byte
+/// string has exactly one accepted encoding.
+fn base32_decode(s: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(s.len() * 5 / 8);
+    let mut buffer: u16 = 0;
+    let mut bits = 0;
+    for c in s.bytes() {
+        let value = BASE32_ALPHABET.iter().position(|&a| a == c)? as u16;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+        buffer &= (1 << bits) - 1;
+    }
+    (bits < 5 && buffer == 0).then_some(out)
 }
 
 #[cfg(test)]
@@ -145,4 +554,12 @@ mod tests {
         let providers = store.get_providers(&cid);
         assert_eq!(providers.len(), 2);
     }
+
+    #[test]
+    fn test_cid_declares_algorithm() {
+        assert!(HashAlgo::Sha256.cid(b"x").starts_with("bafkrei"));
+        assert!(HashAlgo::Blake3.cid(b"x").starts_with("bafkr4i"));
+        assert_eq!(HashAlgo::of_cid(&HashAlgo::Blake3.cid(b"x")).unwrap(), HashAlgo::Blake3);
+        assert!(HashAlgo::of_cid("zz").is_err());
+    }
 }
@@ -24,7 +24,7 @@ use std::hash::Hash;
 /// Each tag is a pair of (node_id, counter) which is guaranteed
 /// to be unique across the entire swarm as long as node IDs are unique
 /// and counters are monotonically increasing per node.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct UniqueTag {
     pub node_id: String,
     pub counter: u64,
@@ -47,7 +47,14 @@ pub struct UniqueTag {
 /// - On merge, both entries and tombstones are unioned. Because a
 ///   concurrent add creates a tag the remote side has never seen,
 ///   that tag survives the merge (add wins).
-#[derive(Debug)]
+///
+/// Serializing an OR-Set keeps every tag and tombstone, so a replica
+/// restored from disk merges exactly like the one it was saved from.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(bound(
+    serialize = "T: serde::Serialize",
+    deserialize = "T: serde::de::DeserializeOwned"
+))]
 pub struct OrSet<T: Clone + Eq + Hash + Display> {
     /// This node's unique identifier.
     node_id: String,
@@ -179,6 +186,19 @@ impl<T: Clone + Eq + Hash + Display> OrSet<T> {
         for tag in &other.tombstones {
             self.tombstones.insert(tag.clone());
         }
+
+        // Never reissue a tag this node already handed out, e.g. when
+        // merging a restored copy of our own earlier state.
+        let own_max = other
+            .entries
+            .values()
+            .flatten()
+            .chain(other.tombstones.iter())
+            .filter(|tag| tag.node_id == self.node_id)
+            .map(|tag| tag.counter)
+            .max()
+            .unwrap_or(0);
+        self.counter = self.counter.max(own_max);
     }
 
     /// Get the node ID of this replica.
@@ -13,7 +13,10 @@ pub mod merkle_dag;
 pub mod pn_counter;
 pub mod reputation;
 
-pub use content_store::ContentStore;
+pub use content_store::{
+    canonical_cid, format_cid, parse_cid, Cid, ContentInfo, ContentProvenance, ContentStats,
+    ContentStore, HashAlgo,
+};
 pub use crdt::OrSet;
 pub use crdt::PnCounter;
 pub use granularity::{GranularityAlgorithm, GranularityEngine};
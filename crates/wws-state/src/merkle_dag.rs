@@ -12,7 +12,7 @@
 //! - Tamper detection: any modification invalidates the root hash
 //! - Incremental assembly: results can be added as they arrive
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use sha2::{Digest, Sha256};
 
@@ -120,6 +120,40 @@ impl MerkleDag {
     pub fn node_count(&self) -> usize {
         self.nodes.len()
     }
+
+    /// Nodes that are not a child of any other node.
+    pub fn roots(&self) -> Vec<&MerkleNode> {
+        let children: HashSet<&str> = self
+            .nodes
+            .values()
+            .flat_map(|n| n.children.iter().map(String::as_str))
+            .collect();
+        self.nodes
+            .values()
+            .filter(|n| !children.contains(n.hash.as_str()))
+            .collect()
+    }
+
+    /// Hashes of every node reachable from a root accepted by `live`,
+    /// including the roots themselves.
+    pub fn reachable_from_roots(&self, live: impl Fn(&MerkleNode) -> bool) -> HashSet<String> {
+        let mut reached = HashSet::new();
+        let mut stack: Vec<&str> = self
+            .roots()
+            .into_iter()
+            .filter(|root| live(root))
+            .map(|root| root.hash.as_str())
+            .collect();
+        while let Some(hash) = stack.pop() {
+            if !reached.insert(hash.to_string()) {
+                continue;
+            }
+            if let Some(node) = self.nodes.get(hash) {
+                stack.extend(node.children.iter().map(String::as_str));
+            }
+        }
+        reached
+    }
 }
 
 impl Default for MerkleDag {
@@ -5,7 +5,7 @@
 //! - Content retrieval by CID
 //! - Provider record publishing
 
-use wws_state::content_store::ContentStore;
+use wws_state::content_store::{format_cid, parse_cid, ContentInfo, ContentStore, HashAlgo};
 
 // ═══════════════════════════════════════════════════════════════
 // § 8.2 Content-Addressed Storage
@@ -86,3 +86,210 @@ fn content_exists_check() {
     assert!(store.exists(&cid));
     assert!(!store.exists("nope"));
 }
+
+// ═══════════════════════════════════════════════════════════════
+// Configurable hash algorithm
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn same_bytes_yield_distinct_cids_per_algorithm() {
+    let mut sha = ContentStore::with_hash_algo(HashAlgo::Sha256);
+    let mut blake = ContentStore::with_hash_algo(HashAlgo::Blake3);
+    let sha_cid = sha.store(b"shared artifact");
+    let blake_cid = blake.store(b"shared artifact");
+    assert_ne!(sha_cid, blake_cid);
+    assert_eq!(HashAlgo::of_cid(&sha_cid).unwrap(), HashAlgo::Sha256);
+    assert_eq!(HashAlgo::of_cid(&blake_cid).unwrap(), HashAlgo::Blake3);
+}
+
+#[test]
+fn put_verified_uses_the_cids_declared_algorithm() {
+    // A SHA-256 store still accepts BLAKE3-addressed content, and vice versa.
+    let data = b"mixed-algorithm content";
+    for store_algo in [HashAlgo::Sha256, HashAlgo::Blake3] {
+        let mut store = ContentStore::with_hash_algo(store_algo);
+        for cid_algo in [HashAlgo::Sha256, HashAlgo::Blake3] {
+            let cid = cid_algo.cid(data);
+            store.put_verified(&cid, data).unwrap();
+            assert_eq!(store.get(&cid), Some(data.to_vec()));
+        }
+    }
+}
+
+#[test]
+fn put_verified_rejects_mismatched_content() {
+    let mut store = ContentStore::new();
+    for algo in [HashAlgo::Sha256, HashAlgo::Blake3] {
+        let cid = algo.cid(b"original");
+        assert!(store.put_verified(&cid, b"tampered").is_err());
+        assert!(!store.exists(&cid));
+    }
+    assert!(store.put_verified("not-a-cid", b"original").is_err());
+}
+
+// ═══════════════════════════════════════════════════════════════
+// CIDv1 encoding
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn cid_round_trips_through_parse_and_format() {
+    // The well-known CIDv1 (raw, sha2-256) of the empty byte string.
+    let empty = "bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku";
+    assert_eq!(ContentStore::compute_cid(b""), empty);
+
+    for algo in [HashAlgo::Sha256, HashAlgo::Blake3] {
+        let cid = algo.cid(b"round trip");
+        let parsed = parse_cid(&cid).unwrap();
+        assert_eq!(parsed.hash_algo, algo);
+        assert_eq!(parsed.digest, algo.digest(b"round trip"));
+        assert_eq!(format_cid(&parsed), cid);
+    }
+}
+
+#[test]
+fn parse_cid_rejects_malformed_strings() {
+    let cid = ContentStore::compute_cid(b"data");
+    let malformed = [
+        String::new(),
+        "b".to_string(),
+        cid.to_uppercase(),
+        format!("z{}", &cid[1..]),
+        cid[..cid.len() - 1].to_string(),
+        format!("{}a", cid),
+        // Same length, but the trailing padding bits are not zero.
+        format!("{}{}", &cid[..cid.len() - 1], "z"),
+        "bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyk1".to_string(),
+    ];
+    for bad in &malformed {
+        assert!(
+            matches!(parse_cid(bad), Err(wws_state::StateError::InvalidCid(_))),
+            "{bad:?} should be rejected"
+        );
+    }
+}
+
+#[test]
+fn put_verified_accepts_legacy_sha256_cids() {
+    use sha2::{Digest, Sha256};
+    let data = b"legacy";
+    let legacy_cid: String = Sha256::digest(data).iter().map(|b| format!("{:02x}", b)).collect();
+    let mut store = ContentStore::with_hash_algo(HashAlgo::Blake3);
+    store.put_verified(&legacy_cid, data).unwrap();
+    assert!(store.exists(&legacy_cid));
+
+    // The CIDv1 form names the same content, so it is found and not stored twice.
+    let cid_v1 = HashAlgo::Sha256.cid(data);
+    assert_eq!(store.get(&cid_v1), Some(data.to_vec()));
+    store.put_verified(&cid_v1, data).unwrap();
+    assert_eq!(store.item_count(), 1);
+}
+
+#[test]
+fn content_info_is_stored_alongside_content() {
+    let mut store = ContentStore::new();
+    let info = ContentInfo {
+        content_type: "application/json".into(),
+        metadata: serde_json::json!({"schema": "report-v1"}),
+        provenance: None,
+    };
+    let cid = store.store_with_info(br#"{"ok":true}"#, info.clone());
+    assert_eq!(store.get_info(&cid), Some(&info));
+
+    let plain = store.store(b"no info");
+    assert_eq!(store.get_info(&plain), None);
+    let text = ContentInfo {
+        content_type: "text/plain".into(),
+        metadata: serde_json::Value::Null,
+        provenance: None,
+    };
+    assert!(store.set_info(&plain, text.clone()));
+    assert_eq!(store.get_info(&plain), Some(&text));
+    assert!(!store.set_info("missing", text));
+}
+
+#[test]
+fn remove_drops_content_info_and_providers() {
+    let mut store = ContentStore::new();
+    let cid = store.store_with_info(
+        b"old result",
+        ContentInfo {
+            content_type: "text/plain".into(),
+            metadata: serde_json::Value::Null,
+            provenance: None,
+        },
+    );
+    store.publish_provider(&cid, "did:swarm:exec".into());
+
+    assert!(store.remove(&cid));
+    assert!(!store.exists(&cid));
+    assert!(store.get_info(&cid).is_none());
+    assert!(store.get_providers(&cid).is_empty());
+    assert!(!store.remove(&cid));
+}
+
+#[test]
+fn stats_track_puts_and_removals() {
+    let mut store = ContentStore::new();
+    assert_eq!(store.stats().item_count, 0);
+    assert_eq!(store.stats().largest_cid, None);
+
+    let small = store.store(b"abc");
+    let large = store.store(b"a larger payload");
+    store.store(b"abc");
+    let stats = store.stats();
+    assert_eq!(stats.item_count, 2);
+    assert_eq!(stats.total_bytes, 19);
+    assert_eq!(stats.largest_cid.as_deref(), Some(large.as_str()));
+    assert_eq!(stats.capacity_bytes, None);
+
+    store.remove(&large);
+    let stats = store.stats();
+    assert_eq!(stats.total_bytes, 3);
+    assert_eq!(stats.largest_cid, Some(small));
+    assert_eq!(stats.evicted_items, 0, "explicit removal is not an eviction");
+}
+
+#[test]
+fn capacity_evicts_oldest_content() {
+    let mut store = ContentStore::new();
+    store.set_capacity_bytes(Some(10));
+    let first = store.store(b"1111");
+    let second = store.store(b"2222");
+    let third = store.store(b"3333");
+
+    assert!(!store.exists(&first));
+    assert!(store.exists(&second));
+    assert!(store.exists(&third));
+    let stats = store.stats();
+    assert_eq!(stats.item_count, 2);
+    assert_eq!(stats.total_bytes, 8);
+    assert_eq!(stats.capacity_bytes, Some(10));
+    assert_eq!(stats.evicted_items, 1);
+    assert_eq!(stats.evicted_bytes, 4);
+
+    // Content bigger than the whole capacity is kept on its own.
+    let huge = store.store(b"0123456789abcdef");
+    assert!(store.exists(&huge));
+    assert_eq!(store.stats().item_count, 1);
+    assert_eq!(store.stats().evicted_items, 3);
+
+    // Shrinking the capacity evicts immediately.
+    let tiny = store.store(b"x");
+    store.set_capacity_bytes(Some(1));
+    assert_eq!(store.all_cids(), vec![tiny]);
+    assert_eq!(store.stats().evicted_bytes, 28);
+}
+
+#[test]
+fn put_verified_accepts_legacy_hex_multihash_cids() {
+    let data = b"hex multihash";
+    let digest = HashAlgo::Blake3.digest(data);
+    let legacy_cid: String = [0x1e, 0x20]
+        .iter()
+        .chain(digest.iter())
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    let mut store = ContentStore::new();
+    store.put_verified(&legacy_cid, data).unwrap();
+    assert_eq!(HashAlgo::of_cid(&legacy_cid).unwrap(), HashAlgo::Blake3);
+}
@@ -216,3 +216,37 @@ fn orset_merge_empty_sets() {
     set_a.merge(&set_b);
     assert!(set_a.elements().is_empty());
 }
+
+// ═══════════════════════════════════════════════════════════════
+// Serialization
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn orset_serde_round_trip_keeps_tombstones() {
+    let mut set = OrSet::<String>::new("a".into());
+    set.add("x".into());
+    let stale = set.clone();
+    set.remove("x");
+    set.add("y".into());
+
+    let json = serde_json::to_string(&set).unwrap();
+    let mut restored: OrSet<String> = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.node_id(), "a");
+    assert!(restored.contains("y"));
+
+    restored.merge(&stale);
+    assert!(!restored.contains("x"));
+}
+
+#[test]
+fn orset_merge_with_own_history_does_not_reuse_tags() {
+    let mut original = OrSet::<String>::new("a".into());
+    original.add("x".into());
+    original.remove("x");
+
+    // A fresh replica with the same node ID catches up from the old one.
+    let mut rebuilt = OrSet::<String>::new("a".into());
+    rebuilt.merge(&original);
+    rebuilt.add("x".into());
+    assert!(rebuilt.contains("x"));
+}
@@ -165,3 +165,21 @@ fn three_level_dag_integrity() {
     let expected_root = MerkleDag::branch_hash(&[b1.hash, b2.hash]);
     assert_eq!(root.hash, expected_root);
 }
+
+#[test]
+fn reachability_follows_live_roots_only() {
+    let mut dag = MerkleDag::new();
+    let a = dag.add_leaf("sub-a".into(), b"cid-a");
+    let b = dag.add_leaf("sub-b".into(), b"cid-b");
+    let root = dag.add_branch("root".into(), vec![a.hash.clone(), b.hash.clone()]);
+    let orphan = dag.add_leaf("old".into(), b"cid-old");
+
+    let roots: Vec<String> = dag.roots().into_iter().map(|n| n.task_id.clone()).collect();
+    assert_eq!(roots.len(), 2);
+    assert!(roots.contains(&"root".to_string()) && roots.contains(&"old".to_string()));
+
+    let reached = dag.reachable_from_roots(|n| n.task_id != "old");
+    assert!(reached.contains(&root.hash));
+    assert!(reached.contains(&a.hash) && reached.contains(&b.hash));
+    assert!(!reached.contains(&orphan.hash));
+}